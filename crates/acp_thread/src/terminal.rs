@@ -5,7 +5,9 @@ use gpui::{App, AppContext, AsyncApp, Context, Entity, Task};
 use language::LanguageRegistry;
 use markdown::Markdown;
 use project::Project;
+use serde::{Deserialize, Serialize};
 use std::{
+    ops::Range,
     path::PathBuf,
     process::ExitStatus,
     sync::{
@@ -32,9 +34,36 @@ pub struct Terminal {
     user_stopped: Arc<AtomicBool>,
 }
 
+/// The key of the [`acp::TerminalOutputResponse`] meta listing the [`TerminalCommand`]s the shell
+/// reported running.
+pub const TERMINAL_COMMANDS_META_KEY: &str = "commands";
+
+/// A command the shell reported through its integration marks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerminalCommand {
+    pub command: Option<String>,
+    /// The lines of the terminal output the command printed, counted from the start of the
+    /// untruncated output.
+    pub output_lines: Option<Range<usize>>,
+    pub exit_code: Option<i32>,
+}
+
+impl TerminalCommand {
+    /// Reads the commands from the meta of a [`acp::TerminalOutputResponse`].
+    pub fn from_meta(meta: &Option<acp::Meta>) -> Vec<Self> {
+        meta.as_ref()
+            .and_then(|meta| meta.get(TERMINAL_COMMANDS_META_KEY))
+            .and_then(|commands| serde_json::from_value(commands.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
 pub struct TerminalOutput {
     pub ended_at: Instant,
     pub exit_status: Option<ExitStatus>,
+    /// The exit code of the last command the shell reported through its integration marks,
+    /// used when the terminal process itself didn't report one.
+    pub shell_exit_code: Option<i32>,
     pub content: String,
     pub original_content_len: usize,
     pub content_line_count: usize,
@@ -71,25 +100,37 @@ impl Terminal {
                 .spawn(async move |this, cx| {
                     let exit_status = command_task.await;
 
-                    this.update(cx, |this, cx| {
-                        let (content, original_content_len) = this.truncated_output(cx);
-                        let content_line_count = this.terminal.read(cx).total_lines();
-
-                        this.output = Some(TerminalOutput {
-                            ended_at: Instant::now(),
-                            exit_status,
-                            content,
-                            original_content_len,
-                            content_line_count,
-                        });
-                        cx.notify();
-                    })
-                    .ok();
+                    let shell_exit_code = this
+                        .update(cx, |this, cx| {
+                            let (content, original_content_len) = this.truncated_output(cx);
+                            let terminal = this.terminal.read(cx);
+                            let content_line_count = terminal.total_lines();
+                            let shell_exit_code = terminal
+                                .last_finished_command()
+                                .and_then(|block| block.exit_code());
+
+                            this.output = Some(TerminalOutput {
+                                ended_at: Instant::now(),
+                                exit_status,
+                                shell_exit_code,
+                                content,
+                                original_content_len,
+                                content_line_count,
+                            });
+                            cx.notify();
+                            shell_exit_code
+                        })
+                        .ok()
+                        .flatten();
 
                     let exit_status = exit_status.map(portable_pty::ExitStatus::from);
 
                     acp::TerminalExitStatus::new()
-                        .exit_code(exit_status.as_ref().map(|e| e.exit_code()))
+                        .exit_code(
+                            exit_status.as_ref().map(|e| e.exit_code()).or_else(|| {
+                                shell_exit_code.and_then(|code| u32::try_from(code).ok())
+                            }),
+                        )
                         .signal(exit_status.and_then(|e| e.signal().map(ToOwned::to_owned)))
                 })
                 .shared(),
@@ -123,8 +164,46 @@ impl Terminal {
     }
 
     pub fn current_output(&self, cx: &App) -> acp::TerminalOutputResponse {
+        let response = self.current_output_without_commands(cx);
+        let commands = self.commands(cx);
+        if commands.is_empty() {
+            return response;
+        }
+        match serde_json::to_value(commands) {
+            Ok(commands) => response.meta(acp::Meta::from_iter([(
+                TERMINAL_COMMANDS_META_KEY.into(),
+                commands,
+            )])),
+            Err(_) => response,
+        }
+    }
+
+    /// The commands the shell reported running in this terminal, oldest first.
+    pub fn commands(&self, cx: &App) -> Vec<TerminalCommand> {
+        let terminal = self.terminal.read(cx);
+        terminal
+            .command_blocks()
+            .iter()
+            .filter(|block| block.output_start.is_some())
+            .map(|block| TerminalCommand {
+                command: terminal.command_text(block),
+                output_lines: block
+                    .output_start
+                    .zip(block.output_end)
+                    .map(|(start, end)| start..end),
+                exit_code: block.exit_code(),
+            })
+            .collect()
+    }
+
+    fn current_output_without_commands(&self, cx: &App) -> acp::TerminalOutputResponse {
         if let Some(output) = self.output.as_ref() {
             let exit_status = output.exit_status.map(portable_pty::ExitStatus::from);
+            let exit_code = exit_status.as_ref().map(|e| e.exit_code()).or_else(|| {
+                output
+                    .shell_exit_code
+                    .and_then(|code| u32::try_from(code).ok())
+            });
 
             acp::TerminalOutputResponse::new(
                 output.content.clone(),
@@ -132,7 +211,7 @@ impl Terminal {
             )
             .exit_status(
                 acp::TerminalExitStatus::new()
-                    .exit_code(exit_status.as_ref().map(|e| e.exit_code()))
+                    .exit_code(exit_code)
                     .signal(exit_status.and_then(|e| e.signal().map(ToOwned::to_owned))),
             )
         } else {
@@ -731,6 +731,7 @@ impl EditAgent {
             tools,
             stop: Vec::new(),
             temperature: None,
+            max_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
         };
@@ -2397,6 +2397,7 @@ impl Thread {
             tool_choice: None,
             stop: Vec::new(),
             temperature: AgentSettings::temperature_for_model(model, cx),
+            max_tokens: None,
             thinking_allowed: self.thinking_enabled,
             thinking_effort: self.thinking_effort.clone(),
         };
@@ -198,6 +198,7 @@ fn process_content(
     timed_out: bool,
     user_stopped: bool,
 ) -> String {
+    let commands = acp_thread::TerminalCommand::from_meta(&output.meta);
     let content = output.output.trim();
     let is_empty = content.is_empty();
 
@@ -265,7 +266,33 @@ fn process_content(
             }
         }
     };
-    content
+    if commands.len() > 1 {
+        format!("{content}\n\n{}", describe_commands(&commands))
+    } else {
+        content
+    }
+}
+
+/// Lists the commands the shell reported running, so the exit code of each one is known and not
+/// only the last one's.
+fn describe_commands(commands: &[acp_thread::TerminalCommand]) -> String {
+    let mut description = String::from("The shell ran these commands:");
+    for command in commands {
+        let name = command.command.as_deref().unwrap_or("unknown command");
+        let exit_code = match command.exit_code {
+            Some(exit_code) => format!("exited with code {exit_code}"),
+            None => "exit code unknown".to_string(),
+        };
+        description.push_str(&format!("\n- `{name}`: {exit_code}"));
+        if let Some(lines) = &command.output_lines {
+            description.push_str(&format!(
+                ", output on lines {}-{}",
+                lines.start + 1,
+                lines.end
+            ));
+        }
+    }
+    description
 }
 
 fn working_dir(
@@ -583,6 +610,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_content_lists_shell_commands() {
+        let commands = [
+            acp_thread::TerminalCommand {
+                command: Some("cargo build".to_string()),
+                output_lines: Some(1..3),
+                exit_code: Some(0),
+            },
+            acp_thread::TerminalCommand {
+                command: Some("cargo test".to_string()),
+                output_lines: Some(4..5),
+                exit_code: Some(101),
+            },
+        ];
+        let output = acp::TerminalOutputResponse::new("test output".to_string(), false)
+            .exit_status(acp::TerminalExitStatus::new().exit_code(101))
+            .meta(acp::Meta::from_iter([(
+                acp_thread::TERMINAL_COMMANDS_META_KEY.into(),
+                serde_json::to_value(commands).unwrap(),
+            )]));
+
+        let result = process_content(output, "cargo build; cargo test", false, false);
+
+        assert!(
+            result.contains("- `cargo build`: exited with code 0, output on lines 2-3"),
+            "Expected the first command, got: {}",
+            result
+        );
+        assert!(
+            result.contains("- `cargo test`: exited with code 101, output on lines 5-5"),
+            "Expected the second command, got: {}",
+            result
+        );
+    }
+
     #[test]
     fn test_process_content_unexpected_termination() {
         let output = acp::TerminalOutputResponse::new("some output".to_string(), false);
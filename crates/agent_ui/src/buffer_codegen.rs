@@ -542,6 +542,7 @@ impl CodegenAlternative {
                 tool_choice,
                 stop: Vec::new(),
                 temperature,
+                max_tokens: None,
                 messages,
                 thinking_allowed: false,
                 thinking_effort: None,
@@ -621,6 +622,7 @@ impl CodegenAlternative {
                 tool_choice: None,
                 stop: Vec::new(),
                 temperature,
+                max_tokens: None,
                 messages: vec![request_message],
                 thinking_allowed: false,
                 thinking_effort: None,
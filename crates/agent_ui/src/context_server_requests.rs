@@ -0,0 +1,762 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use context_server::{
+    ContextServer, ContextServerId,
+    types::{
+        self, ElicitationAction, ElicitationParams, ElicitationResult, PrimitiveSchemaDefinition,
+    },
+};
+use futures::{StreamExt as _, channel::oneshot, lock::Mutex};
+use gpui::{
+    AnyWindowHandle, AsyncApp, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Image,
+    ImageFormat, PromptLevel, ScrollHandle, WeakEntity,
+};
+use language_model::{
+    LanguageModel, LanguageModelImage, LanguageModelRegistry, LanguageModelRequest,
+    LanguageModelRequestMessage,
+};
+use project::context_server_store::{ContextServerStatus, ServerStatusChangedEvent};
+use ui::{
+    Banner, Checkbox, KeyBinding, Modal, ModalFooter, ModalHeader, Section, ToggleState,
+    WithScrollbar, prelude::*,
+};
+use ui_input::InputField;
+use workspace::{ModalView, Workspace};
+
+const MAX_SAMPLING_PREVIEW_CHARS: usize = 1000;
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        let Some(window) = window else {
+            return;
+        };
+        let window_handle = window.window_handle();
+        let context_server_store = workspace.project().read(cx).context_server_store();
+        // Only show one elicitation form at a time per workspace, answering the rest in order.
+        let elicitation_lock = Arc::new(Mutex::new(()));
+
+        let servers = context_server_store
+            .read(cx)
+            .server_ids()
+            .iter()
+            .filter_map(|id| context_server_store.read(cx).get_server(id))
+            .collect::<Vec<_>>();
+        for server in servers {
+            register_request_handlers(&server, window_handle, elicitation_lock.clone(), cx);
+        }
+
+        // Handlers are registered as the server starts, so that they're advertised when it's
+        // initialized.
+        cx.subscribe(
+            &context_server_store,
+            move |_, store, event: &ServerStatusChangedEvent, cx| {
+                if event.status != ContextServerStatus::Starting {
+                    return;
+                }
+                if let Some(server) = store.read(cx).get_server(&event.server_id) {
+                    register_request_handlers(&server, window_handle, elicitation_lock.clone(), cx);
+                }
+            },
+        )
+        .detach();
+    })
+    .detach();
+}
+
+fn register_request_handlers(
+    server: &ContextServer,
+    window_handle: AnyWindowHandle,
+    elicitation_lock: Arc<Mutex<()>>,
+    cx: &mut Context<Workspace>,
+) {
+    let server_id = server.id();
+    let workspace = cx.weak_entity();
+
+    server.on_request::<types::requests::CreateMessage>({
+        let server_id = server_id.clone();
+        move |params, cx| {
+            let server_id = server_id.clone();
+            cx.spawn(async move |cx| create_message(server_id, params, window_handle, cx).await)
+        }
+    });
+
+    server.on_request::<types::requests::Elicitation>(move |params, cx| {
+        let server_id = server_id.clone();
+        let workspace = workspace.clone();
+        let elicitation_lock = elicitation_lock.clone();
+        cx.spawn(async move |cx| {
+            let _guard = elicitation_lock.lock().await;
+            elicit(server_id, params, window_handle, workspace, cx).await
+        })
+    });
+}
+
+/// Answers a `sampling/createMessage` request with the configured language model,
+/// after the user has approved sending the server's messages to it.
+async fn create_message(
+    server_id: ContextServerId,
+    params: types::CreateMessageRequest,
+    window_handle: AnyWindowHandle,
+    cx: &mut AsyncApp,
+) -> Result<types::CreateMessageResult> {
+    let model = cx
+        .update(|cx| select_model(params.model_preferences.as_ref(), cx))
+        .context("no language model is configured")?;
+
+    let message = format!(
+        "The MCP server \"{server_id}\" wants to send a request to {}.",
+        model.name().0
+    );
+    let detail = sampling_preview(&params);
+    // Check the request can be sent before asking the user about it.
+    let request = sampling_request(params, model.supports_images(), cx).await?;
+    let answer = window_handle.update(cx, |_, window, cx| {
+        window.prompt(
+            PromptLevel::Info,
+            &message,
+            Some(&detail),
+            &["Allow", "Deny"],
+            cx,
+        )
+    })?;
+    anyhow::ensure!(answer.await? == 0, "the user rejected the sampling request");
+
+    let mut stream = model.stream_completion_text(request, cx).await?.stream;
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        text.push_str(&chunk?);
+    }
+
+    Ok(types::CreateMessageResult {
+        role: types::Role::Assistant,
+        content: types::MessageContent::Text {
+            text,
+            annotations: None,
+        },
+        model: model.id().0.to_string(),
+        stop_reason: Some("endTurn".to_string()),
+    })
+}
+
+/// Picks the first available model matching one of the server's hints, falling back to the default model.
+fn select_model(
+    preferences: Option<&types::ModelPreferences>,
+    cx: &App,
+) -> Option<Arc<dyn LanguageModel>> {
+    let registry = LanguageModelRegistry::read_global(cx);
+    let hints = preferences
+        .and_then(|preferences| preferences.hints.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|hint| hint.name.as_deref());
+    for hint in hints {
+        if let Some(model) = registry
+            .available_models(cx)
+            .find(|model| model.id().0.contains(hint) || model.name().0.contains(hint))
+        {
+            return Some(model);
+        }
+    }
+    registry
+        .default_model()
+        .map(|configured_model| configured_model.model)
+}
+
+fn sampling_preview(params: &types::CreateMessageRequest) -> String {
+    let mut preview = String::new();
+    if let Some(system_prompt) = &params.system_prompt {
+        preview.push_str(system_prompt);
+        preview.push_str("\n\n");
+    }
+    for message in &params.messages {
+        if let types::MessageContent::Text { text, .. } = &message.content {
+            preview.push_str(text);
+            preview.push('\n');
+        }
+    }
+    util::truncate_and_trailoff(preview.trim(), MAX_SAMPLING_PREVIEW_CHARS)
+}
+
+async fn sampling_request(
+    params: types::CreateMessageRequest,
+    supports_images: bool,
+    cx: &mut AsyncApp,
+) -> Result<LanguageModelRequest> {
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = params.system_prompt {
+        messages.push(LanguageModelRequestMessage {
+            role: language_model::Role::System,
+            content: vec![system_prompt.into()],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+
+    for message in params.messages {
+        let role = match message.role {
+            types::Role::User => language_model::Role::User,
+            types::Role::Assistant => language_model::Role::Assistant,
+        };
+        let content = match message.content {
+            types::MessageContent::Text { text, .. } => text.into(),
+            types::MessageContent::Image {
+                data, mime_type, ..
+            } => {
+                anyhow::ensure!(supports_images, "the language model doesn't support images");
+                language_model::MessageContent::Image(sampling_image(&data, &mime_type, cx).await?)
+            }
+            types::MessageContent::Audio { mime_type, .. } => {
+                anyhow::bail!("unsupported {mime_type} content in sampling request");
+            }
+            types::MessageContent::Resource { resource, .. } => {
+                format!("[resource: {}]", resource.uri).into()
+            }
+        };
+        messages.push(LanguageModelRequestMessage {
+            role,
+            content: vec![content],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+
+    Ok(LanguageModelRequest {
+        messages,
+        stop: params.stop_sequences.unwrap_or_default(),
+        temperature: params.temperature.map(|temperature| temperature as f32),
+        max_tokens: Some(params.max_tokens.into()),
+        ..Default::default()
+    })
+}
+
+/// Decodes an image sent by the server and converts it to the PNG that language models expect.
+async fn sampling_image(
+    data: &str,
+    mime_type: &str,
+    cx: &mut AsyncApp,
+) -> Result<LanguageModelImage> {
+    let format = ImageFormat::from_mime_type(mime_type)
+        .with_context(|| format!("unsupported {mime_type} content in sampling request"))?;
+    let bytes = base64::Engine::decode(&base64::prelude::BASE64_STANDARD, data)
+        .with_context(|| format!("invalid {mime_type} data in sampling request"))?;
+    let image = Arc::new(Image::from_bytes(format, bytes));
+    cx.update(|cx| LanguageModelImage::from_image(image, cx))
+        .await
+        .with_context(|| format!("failed to convert {mime_type} image in sampling request"))
+}
+
+async fn elicit(
+    server_id: ContextServerId,
+    params: ElicitationParams,
+    window_handle: AnyWindowHandle,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut AsyncApp,
+) -> Result<ElicitationResult> {
+    let (tx, rx) = oneshot::channel();
+    window_handle.update(cx, |_, window, cx| {
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                ElicitationModal::new(server_id, params, tx, window, cx)
+            });
+        })
+    })??;
+
+    // Dismissing the modal without answering drops the sender, which counts as cancelling.
+    Ok(rx.await.unwrap_or(ElicitationResult {
+        action: ElicitationAction::Cancel,
+        content: None,
+        meta: None,
+    }))
+}
+
+enum ElicitationInput {
+    Text(Entity<InputField>),
+    Boolean(ToggleState),
+    Choice {
+        options: Vec<(String, SharedString)>,
+        selected: Option<usize>,
+    },
+}
+
+struct ElicitationField {
+    name: String,
+    label: SharedString,
+    required: bool,
+    schema: PrimitiveSchemaDefinition,
+    input: ElicitationInput,
+}
+
+impl ElicitationField {
+    fn new(
+        name: String,
+        schema: PrimitiveSchemaDefinition,
+        required: bool,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let label: SharedString = schema.title().unwrap_or(&name).to_string().into();
+        let input = match &schema {
+            PrimitiveSchemaDefinition::String {
+                enum_values: Some(values),
+                enum_names,
+                default,
+                ..
+            } => {
+                let options = values
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, value)| {
+                        let name = enum_names
+                            .as_ref()
+                            .and_then(|names| names.get(ix))
+                            .unwrap_or(value);
+                        (value.clone(), name.clone().into())
+                    })
+                    .collect();
+                let selected = default
+                    .as_ref()
+                    .and_then(|default| values.iter().position(|value| value == default));
+                ElicitationInput::Choice { options, selected }
+            }
+            PrimitiveSchemaDefinition::Boolean { default, .. } => {
+                ElicitationInput::Boolean(default.unwrap_or(false).into())
+            }
+            PrimitiveSchemaDefinition::String { default, .. } => {
+                Self::text_input(&label, default.clone(), window, cx)
+            }
+            PrimitiveSchemaDefinition::Number { default, .. } => {
+                Self::text_input(&label, default.map(|n| n.to_string()), window, cx)
+            }
+            PrimitiveSchemaDefinition::Integer { default, .. } => {
+                Self::text_input(&label, default.map(|n| n.to_string()), window, cx)
+            }
+        };
+
+        Self {
+            name,
+            label,
+            required,
+            schema,
+            input,
+        }
+    }
+
+    fn text_input(
+        label: &SharedString,
+        default: Option<String>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> ElicitationInput {
+        ElicitationInput::Text(cx.new(|cx| {
+            let input = InputField::new(window, cx, "").label(label.clone());
+            if let Some(default) = default {
+                input.set_text(&default, window, cx);
+            }
+            input
+        }))
+    }
+
+    /// Returns the value entered for this field, `None` if it was left empty,
+    /// or an error describing why the entered value doesn't match the schema.
+    fn value(&self, cx: &App) -> Result<Option<serde_json::Value>, String> {
+        let text = match &self.input {
+            ElicitationInput::Boolean(state) => return Ok(Some(state.selected().into())),
+            ElicitationInput::Choice { options, selected } => {
+                return Ok(selected.map(|ix| options[ix].0.clone().into()));
+            }
+            ElicitationInput::Text(input) => input.read(cx).text(cx),
+        };
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        let label = &self.label;
+        match &self.schema {
+            PrimitiveSchemaDefinition::String {
+                min_length,
+                max_length,
+                ..
+            } => {
+                let length = text.chars().count();
+                if min_length.is_some_and(|min| length < min) {
+                    return Err(format!("{label} is too short"));
+                }
+                if max_length.is_some_and(|max| length > max) {
+                    return Err(format!("{label} is too long"));
+                }
+                Ok(Some(text.into()))
+            }
+            PrimitiveSchemaDefinition::Number {
+                minimum, maximum, ..
+            } => {
+                let number = text
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| format!("{label} must be a number"))?;
+                if minimum.is_some_and(|min| number < min)
+                    || maximum.is_some_and(|max| number > max)
+                {
+                    return Err(format!("{label} is out of range"));
+                }
+                Ok(Some(number.into()))
+            }
+            PrimitiveSchemaDefinition::Integer {
+                minimum, maximum, ..
+            } => {
+                let number = text
+                    .trim()
+                    .parse::<i64>()
+                    .map_err(|_| format!("{label} must be a whole number"))?;
+                if minimum.is_some_and(|min| number < min)
+                    || maximum.is_some_and(|max| number > max)
+                {
+                    return Err(format!("{label} is out of range"));
+                }
+                Ok(Some(number.into()))
+            }
+            PrimitiveSchemaDefinition::Boolean { .. } => Ok(None),
+        }
+    }
+}
+
+/// A form asking the user for the information an MCP server requested through `elicitation/create`.
+pub struct ElicitationModal {
+    server_id: ContextServerId,
+    message: SharedString,
+    fields: Vec<ElicitationField>,
+    tx: Option<oneshot::Sender<ElicitationResult>>,
+    last_error: Option<SharedString>,
+    scroll_handle: ScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl ElicitationModal {
+    fn new(
+        server_id: ContextServerId,
+        params: ElicitationParams,
+        tx: oneshot::Sender<ElicitationResult>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let required = params.requested_schema.required;
+        let fields = params
+            .requested_schema
+            .properties
+            .into_iter()
+            .map(|(name, schema)| {
+                let is_required = required.contains(&name);
+                ElicitationField::new(name, schema, is_required, window, cx)
+            })
+            .collect();
+
+        Self {
+            server_id,
+            message: params.message.into(),
+            fields,
+            tx: Some(tx),
+            last_error: None,
+            scroll_handle: ScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn respond(
+        &mut self,
+        action: ElicitationAction,
+        content: Option<serde_json::Map<String, serde_json::Value>>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(tx) = self.tx.take() {
+            tx.send(ElicitationResult {
+                action,
+                content,
+                meta: None,
+            })
+            .ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let mut content = serde_json::Map::new();
+        for field in &self.fields {
+            match field.value(cx) {
+                Ok(Some(value)) => {
+                    content.insert(field.name.clone(), value);
+                }
+                Ok(None) if field.required => {
+                    self.last_error = Some(format!("{} is required", field.label).into());
+                    cx.notify();
+                    return;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    self.last_error = Some(error.into());
+                    cx.notify();
+                    return;
+                }
+            }
+        }
+        self.respond(ElicitationAction::Accept, Some(content), cx);
+    }
+
+    fn decline(&mut self, cx: &mut Context<Self>) {
+        self.respond(ElicitationAction::Decline, None, cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        self.respond(ElicitationAction::Cancel, None, cx);
+    }
+
+    fn on_tab(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus_next(cx);
+    }
+
+    fn on_tab_prev(
+        &mut self,
+        _: &menu::SelectPrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus_prev(cx);
+    }
+
+    fn render_field(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement + use<> {
+        let field = &self.fields[ix];
+        let input = match &field.input {
+            ElicitationInput::Text(input) => input.clone().into_any_element(),
+            ElicitationInput::Boolean(state) => Checkbox::new(("field-checkbox", ix), *state)
+                .label(field.label.clone())
+                .on_click(cx.listener(move |this, checked, _window, cx| {
+                    if let ElicitationInput::Boolean(state) = &mut this.fields[ix].input {
+                        *state = *checked;
+                    }
+                    cx.notify();
+                }))
+                .into_any_element(),
+            ElicitationInput::Choice { options, selected } => v_flex()
+                .gap_1()
+                .child(Label::new(field.label.clone()).size(LabelSize::Small))
+                .child(
+                    h_flex()
+                        .id(("field-options", ix))
+                        .flex_wrap()
+                        .gap_1()
+                        .children(options.iter().enumerate().map(|(option_ix, (_, name))| {
+                            Button::new(option_ix, name.clone())
+                                .style(ButtonStyle::Outlined)
+                                .label_size(LabelSize::Small)
+                                .toggle_state(*selected == Some(option_ix))
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    if let ElicitationInput::Choice { selected, .. } =
+                                        &mut this.fields[ix].input
+                                    {
+                                        *selected = Some(option_ix);
+                                    }
+                                    cx.notify();
+                                }))
+                        })),
+                )
+                .into_any_element(),
+        };
+
+        v_flex().gap_0p5().child(input).when_some(
+            field.schema.description(),
+            |this, description| {
+                this.child(
+                    Label::new(description.to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            },
+        )
+    }
+}
+
+impl EventEmitter<DismissEvent> for ElicitationModal {}
+
+impl Focusable for ElicitationModal {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for ElicitationModal {}
+
+impl Render for ElicitationModal {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle(cx);
+
+        v_flex()
+            .id("elicitation-modal")
+            .key_context("ElicitationModal")
+            .w(rems(34.))
+            .elevation_3(cx)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::on_tab))
+            .on_action(cx.listener(Self::on_tab_prev))
+            .capture_any_mouse_down(cx.listener(|this, _, window, cx| {
+                this.focus_handle(cx).focus(window, cx);
+            }))
+            .child(
+                Modal::new("elicitation", None)
+                    .header(
+                        ModalHeader::new()
+                            .headline(format!("Request from {}", self.server_id))
+                            .description(self.message.clone()),
+                    )
+                    .when_some(self.last_error.clone(), |this, error| {
+                        this.section(
+                            Section::new().child(
+                                Banner::new()
+                                    .severity(Severity::Warning)
+                                    .child(div().text_xs().child(error)),
+                            ),
+                        )
+                    })
+                    .child(
+                        div()
+                            .size_full()
+                            .vertical_scrollbar_for(&self.scroll_handle, window, cx)
+                            .child(
+                                v_flex()
+                                    .id("elicitation-fields")
+                                    .size_full()
+                                    .tab_group()
+                                    .max_h(rems_from_px(450.))
+                                    .pl_3()
+                                    .pr_4()
+                                    .pb_2()
+                                    .gap_2()
+                                    .overflow_y_scroll()
+                                    .track_scroll(&self.scroll_handle)
+                                    .children(
+                                        (0..self.fields.len()).map(|ix| self.render_field(ix, cx)),
+                                    ),
+                            ),
+                    )
+                    .footer(
+                        ModalFooter::new().end_slot(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Button::new("cancel", "Cancel")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Cancel,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(cx.listener(|this, _event, window, cx| {
+                                            this.cancel(&menu::Cancel, window, cx)
+                                        })),
+                                )
+                                .child(Button::new("decline", "Decline").on_click(
+                                    cx.listener(|this, _event, _window, cx| this.decline(cx)),
+                                ))
+                                .child(
+                                    Button::new("submit", "Submit")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Confirm,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(cx.listener(|this, _event, window, cx| {
+                                            this.confirm(&menu::Confirm, window, cx)
+                                        })),
+                                ),
+                        ),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{DevicePixels, TestAppContext, size};
+    use std::io::Cursor;
+
+    fn sampling_params(content: types::MessageContent) -> types::CreateMessageRequest {
+        types::CreateMessageRequest {
+            messages: vec![types::SamplingMessage {
+                role: types::Role::User,
+                content,
+            }],
+            model_preferences: None,
+            system_prompt: None,
+            include_context: None,
+            temperature: None,
+            max_tokens: 100,
+            stop_sequences: None,
+            metadata: None,
+        }
+    }
+
+    #[gpui::test]
+    async fn test_sampling_request_images(cx: &mut TestAppContext) {
+        let mut jpeg = Vec::new();
+        image::DynamicImage::new_rgb8(2, 3)
+            .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+            .unwrap();
+        let data = base64::Engine::encode(&base64::prelude::BASE64_STANDARD, &jpeg);
+        let image_content = |mime_type: &str| types::MessageContent::Image {
+            data: data.clone(),
+            mime_type: mime_type.to_string(),
+            annotations: None,
+        };
+        let mut cx = cx.to_async();
+
+        let request = sampling_request(sampling_params(image_content("image/jpeg")), true, &mut cx)
+            .await
+            .unwrap();
+        let language_model::MessageContent::Image(image) = &request.messages[0].content[0] else {
+            panic!("expected an image, got {:?}", request.messages[0].content);
+        };
+        assert_eq!(image.size, Some(size(DevicePixels(2), DevicePixels(3))));
+        assert_eq!(request.max_tokens, Some(100));
+
+        let error = sampling_request(sampling_params(image_content("image/jpeg")), false, &mut cx)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the language model doesn't support images"
+        );
+
+        let error = sampling_request(
+            sampling_params(image_content("image/x-unknown")),
+            true,
+            &mut cx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unsupported image/x-unknown content in sampling request"
+        );
+
+        let error = sampling_request(
+            sampling_params(types::MessageContent::Audio {
+                data: String::new(),
+                mime_type: "audio/wav".to_string(),
+                annotations: None,
+            }),
+            true,
+            &mut cx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unsupported audio/wav content in sampling request"
+        );
+    }
+}
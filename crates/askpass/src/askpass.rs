@@ -286,6 +286,94 @@ impl PasswordProxy {
         }
     }
 }
+
+/// Serves as `GIT_EDITOR` or `GIT_SEQUENCE_EDITOR`, so that files git wants
+/// edited are handed back to this process instead of a terminal editor.
+///
+/// The script forwards the path it was invoked with over the same socket
+/// protocol as [`PasswordProxy`], and exits once `edit_file` resolves.
+pub struct EditorProxy {
+    _task: Task<()>,
+    editor_command: String,
+}
+
+impl EditorProxy {
+    pub async fn new(
+        mut edit_file: impl FnMut(std::path::PathBuf) -> Task<()> + 'static + Send + Sync,
+        executor: BackgroundExecutor,
+    ) -> Result<Self> {
+        let temp_dir = tempfile::Builder::new().prefix("zed-editor").tempdir()?;
+        let editor_socket = temp_dir.path().join("editor.sock");
+        let editor_script_path = temp_dir.path().join(ASKPASS_SCRIPT_NAME);
+        let current_exec =
+            std::env::current_exe().context("Failed to determine current zed executable path.")?;
+
+        let shell_kind = if cfg!(windows) {
+            ShellKind::PowerShell
+        } else {
+            ShellKind::Posix
+        };
+        let editor_program = ASKPASS_PROGRAM.get_or_init(|| current_exec);
+        let editor_script = generate_askpass_script(shell_kind, editor_program, &editor_socket)?;
+        let _task = executor.spawn(async move {
+            maybe!(async move {
+                let listener =
+                    UnixListener::bind(&editor_socket).context("creating editor socket")?;
+
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let mut buffer = Vec::new();
+                    let mut reader = BufReader::new(&mut stream);
+                    if reader.read_until(b'\0', &mut buffer).await.is_err() {
+                        continue;
+                    }
+                    if buffer.last() == Some(&b'\0') {
+                        buffer.pop();
+                    }
+                    let path = String::from_utf8_lossy(&buffer).into_owned();
+                    edit_file(path.into()).await;
+                    // Closing the stream without a response lets the script exit,
+                    // which git takes as the editor being done with the file.
+                    drop(stream);
+                }
+                drop(temp_dir);
+                Result::<_, anyhow::Error>::Ok(())
+            })
+            .await
+            .log_err();
+        });
+
+        fs::write(&editor_script_path, editor_script)
+            .await
+            .with_context(|| format!("creating editor script at {editor_script_path:?}"))?;
+        make_file_executable(&editor_script_path)
+            .await
+            .with_context(|| {
+                format!("marking editor script executable at {editor_script_path:?}")
+            })?;
+
+        // Git runs the editor through a shell, so the path has to be quoted.
+        #[cfg(not(target_os = "windows"))]
+        let editor_command = editor_script_path
+            .try_shell_safe(ShellKind::Posix)
+            .context("Failed to shell-escape editor script path")?;
+        #[cfg(target_os = "windows")]
+        let editor_command = format!(
+            "powershell.exe -ExecutionPolicy Bypass -File \"{}\"",
+            editor_script_path.display()
+        );
+
+        Ok(Self {
+            _task,
+            editor_command,
+        })
+    }
+
+    /// The command to put in `GIT_EDITOR` or `GIT_SEQUENCE_EDITOR`.
+    pub fn editor_command(&self) -> &str {
+        &self.editor_command
+    }
+}
+
 /// The main function for when Zed is running in netcat mode for use in askpass.
 /// Called from both the remote server binary and the zed binary in their respective main functions.
 pub fn main(socket: &str) {
@@ -0,0 +1,406 @@
+//! A project-wide picker for the bookmarks set in editors, and a modal to label them.
+
+use std::sync::Arc;
+
+use editor::{Anchor, Editor, SelectionEffects, actions::SelectAll, scroll::Autoscroll};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    Task, WeakEntity, Window, actions,
+};
+use language::Buffer;
+use picker::{Picker, PickerDelegate};
+use project::bookmark_store::{BookmarkEditAction, BookmarkStore};
+use text::Point;
+use theme::ActiveTheme;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+actions!(
+    bookmarks,
+    [
+        /// Toggles a picker listing the bookmarks in the project.
+        Toggle,
+        /// Edits the label of the bookmark on the current line, adding a bookmark if there's none.
+        EditLabel,
+        /// Removes all bookmarks in the project.
+        ClearAll,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &Toggle, window, cx| {
+            BookmarksModal::toggle(workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ClearAll, _, cx| {
+            workspace
+                .project()
+                .read(cx)
+                .bookmark_store()
+                .update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+        });
+    })
+    .detach();
+    cx.observe_new(BookmarkLabelModal::register).detach();
+}
+
+pub struct BookmarksModal {
+    picker: Entity<Picker<BookmarksDelegate>>,
+}
+
+impl BookmarksModal {
+    fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let bookmark_store = workspace.project().read(cx).bookmark_store();
+        let entries = bookmark_entries(bookmark_store.read(cx), cx);
+        let weak_workspace = cx.entity().downgrade();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            let delegate = BookmarksDelegate {
+                modal: cx.entity().downgrade(),
+                workspace: weak_workspace,
+                entries,
+                matches: Vec::new(),
+                selected_index: 0,
+            };
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            Self { picker }
+        });
+    }
+}
+
+impl Render for BookmarksModal {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("BookmarksModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for BookmarksModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for BookmarksModal {}
+impl ModalView for BookmarksModal {}
+
+/// A bookmark as it's listed in the picker.
+struct BookmarkEntry {
+    buffer: Entity<Buffer>,
+    row: u32,
+    /// The bookmark's label followed by its location, which the query is matched against.
+    title: String,
+    /// The text of the bookmarked line.
+    line: String,
+}
+
+/// The bookmarks in the project, ordered by path and then by row.
+fn bookmark_entries(bookmark_store: &BookmarkStore, cx: &App) -> Vec<BookmarkEntry> {
+    let mut entries = Vec::new();
+    for (abs_path, buffer, bookmarks) in bookmark_store.all_bookmarks() {
+        let buffer_ref = buffer.read(cx);
+        let path = buffer_ref
+            .file()
+            .map(|file| file.full_path(cx))
+            .unwrap_or_else(|| abs_path.to_path_buf());
+        let snapshot = buffer_ref.snapshot();
+        for bookmark in bookmarks {
+            let row = bookmark.position.summary::<Point>(&snapshot).row;
+            let location = format!("{}:{}", path.display(), row + 1);
+            let title = match &bookmark.label {
+                Some(label) => format!("{label} {location}"),
+                None => location,
+            };
+            let line = snapshot
+                .text_for_range(Point::new(row, 0)..Point::new(row, snapshot.line_len(row)))
+                .collect::<String>()
+                .trim()
+                .to_string();
+            entries.push(BookmarkEntry {
+                buffer: buffer.clone(),
+                row,
+                title,
+                line,
+            });
+        }
+    }
+    entries
+}
+
+pub struct BookmarksDelegate {
+    modal: WeakEntity<BookmarksModal>,
+    workspace: WeakEntity<Workspace>,
+    entries: Vec<BookmarkEntry>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for BookmarksDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Go to a bookmark…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No bookmarks. Toggle one in the gutter or with editor::ToggleBookmark.".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| StringMatchCandidate::new(id, &entry.title))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self
+            .matches
+            .get(self.selected_index)
+            .map(|mat| &self.entries[mat.candidate_id])
+        else {
+            return;
+        };
+        let buffer = entry.buffer.clone();
+        let position = Point::new(entry.row, 0);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = if secondary {
+                    workspace.adjacent_pane(window, cx)
+                } else {
+                    workspace.active_pane().clone()
+                };
+                let editor = workspace
+                    .open_project_item::<Editor>(pane, buffer, true, true, true, true, window, cx);
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |s| s.select_ranges([position..position]),
+                    );
+                });
+            })
+            .log_err();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let entry = &self.entries[mat.candidate_id];
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::Bookmark).color(Color::Accent))
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            mat.string.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(entry.line.clone())
+                                .color(Color::Muted)
+                                .size(LabelSize::Small)
+                                .truncate(),
+                        ),
+                ),
+        )
+    }
+}
+
+/// Edits the label of the bookmarks on the lines with a cursor.
+pub struct BookmarkLabelModal {
+    label_editor: Entity<Editor>,
+    active_editor: Entity<Editor>,
+    positions: Vec<Anchor>,
+    _subscription: Subscription,
+}
+
+impl ModalView for BookmarkLabelModal {}
+
+impl Focusable for BookmarkLabelModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.label_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for BookmarkLabelModal {}
+
+impl BookmarkLabelModal {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        let handle = cx.entity().downgrade();
+        editor
+            .register_action(move |_: &EditLabel, window, cx| {
+                let Some(editor_handle) = handle.upgrade() else {
+                    return;
+                };
+                let Some(workspace) = editor_handle.read(cx).workspace() else {
+                    return;
+                };
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, move |window, cx| {
+                        BookmarkLabelModal::new(editor_handle, window, cx)
+                    });
+                })
+            })
+            .detach();
+    }
+
+    fn new(active_editor: Entity<Editor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let bookmarks =
+            active_editor.update(cx, |editor, cx| editor.bookmarks_at_cursors(window, cx));
+        let current_label = bookmarks
+            .iter()
+            .find_map(|(_, bookmark)| bookmark.as_ref()?.label.clone());
+        let positions = bookmarks
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect();
+
+        let label_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bookmark label", window, cx);
+            if let Some(current_label) = current_label {
+                editor.set_text(current_label, window, cx);
+                editor.select_all(&SelectAll, window, cx);
+            }
+            editor
+        });
+        let subscription = cx.subscribe_in(&label_editor, window, |_, _, event, _, cx| {
+            if let editor::EditorEvent::Blurred = event {
+                cx.emit(DismissEvent);
+            }
+        });
+
+        Self {
+            label_editor,
+            active_editor,
+            positions,
+            _subscription: subscription,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let label: Arc<str> = self.label_editor.read(cx).text(cx).into();
+        self.active_editor.update(cx, |editor, cx| {
+            for position in &self.positions {
+                editor.edit_bookmark_at_anchor(
+                    *position,
+                    BookmarkEditAction::SetLabel(label.clone()),
+                    cx,
+                );
+            }
+            editor.focus_handle(cx).focus(window, cx);
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for BookmarkLabelModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("BookmarkLabelModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.label_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .child(Label::new("Leave the label empty to remove it").color(Color::Muted)),
+            )
+    }
+}
@@ -1,3 +1,4 @@
+use collections::HashSet;
 use futures::channel::oneshot;
 use git2::{DiffLineType as GitDiffLineType, DiffOptions as GitOptions, Patch as GitPatch};
 use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Task};
@@ -1151,6 +1152,195 @@ fn compute_hunks(
     tree
 }
 
+/// A hunk of a line-based diff, with zero lines of context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineHunk {
+    old_rows: Range<u32>,
+    new_rows: Range<u32>,
+}
+
+fn line_hunks(old_text: &str, new_text: &str) -> Vec<LineHunk> {
+    let mut options = GitOptions::default();
+    options.context_lines(0);
+    let Some(patch) = GitPatch::from_buffers(
+        old_text.as_bytes(),
+        None,
+        new_text.as_bytes(),
+        None,
+        Some(&mut options),
+    )
+    .log_err() else {
+        return Vec::new();
+    };
+
+    // Hunk starts are one-based, except when the hunk has no lines on that side, in which case
+    // they refer to the line after which the hunk applies.
+    let start_row = |start: u32, lines: u32| if lines == 0 { start } else { start - 1 };
+    (0..patch.num_hunks())
+        .filter_map(|hunk_index| {
+            let (hunk, _) = patch.hunk(hunk_index).log_err()?;
+            let old_start = start_row(hunk.old_start(), hunk.old_lines());
+            let new_start = start_row(hunk.new_start(), hunk.new_lines());
+            Some(LineHunk {
+                old_rows: old_start..old_start + hunk.old_lines(),
+                new_rows: new_start..new_start + hunk.new_lines(),
+            })
+        })
+        .collect()
+}
+
+/// Maps a row on one side of a line diff to the other side, returning `None` for rows that are
+/// part of a hunk and so have no counterpart.
+fn map_unchanged_row(hunks: &[LineHunk], row: u32, from_new: bool) -> Option<u32> {
+    let mut mapped_row = row as i64;
+    for hunk in hunks {
+        let (from_rows, to_rows) = if from_new {
+            (&hunk.new_rows, &hunk.old_rows)
+        } else {
+            (&hunk.old_rows, &hunk.new_rows)
+        };
+        if from_rows.contains(&row) {
+            return None;
+        }
+        if from_rows.end > row {
+            break;
+        }
+        mapped_row = row as i64 + to_rows.end as i64 - from_rows.end as i64;
+    }
+    u32::try_from(mapped_row).ok()
+}
+
+/// Applies the selected lines of a diff from `old_text` to `new_text` onto `old_text`.
+///
+/// Within each hunk, unselected removals are kept and selected additions follow them, which is
+/// what `git add --patch` produces when lines are dropped from a hunk. Returns `None` when no
+/// selected line is part of a hunk.
+fn apply_selected_lines(
+    old_text: &str,
+    new_text: &str,
+    hunks: &[LineHunk],
+    old_row_selected: impl Fn(u32) -> bool,
+    new_row_selected: impl Fn(u32) -> bool,
+) -> Option<String> {
+    fn push_line(text: &mut String, line: &str) {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(line);
+    }
+
+    fn line<'a>(lines: &[&'a str], row: u32) -> &'a str {
+        lines.get(row as usize).copied().unwrap_or_default()
+    }
+
+    let old_lines = old_text.split_inclusive('\n').collect::<Vec<_>>();
+    let new_lines = new_text.split_inclusive('\n').collect::<Vec<_>>();
+
+    let mut changed = false;
+    let mut result = String::with_capacity(old_text.len());
+    let mut old_row = 0;
+    for hunk in hunks {
+        for row in old_row..hunk.old_rows.start {
+            push_line(&mut result, line(&old_lines, row));
+        }
+        for row in hunk.old_rows.clone() {
+            if old_row_selected(row) {
+                changed = true;
+            } else {
+                push_line(&mut result, line(&old_lines, row));
+            }
+        }
+        for row in hunk.new_rows.clone() {
+            if new_row_selected(row) {
+                changed = true;
+                push_line(&mut result, line(&new_lines, row));
+            }
+        }
+        old_row = hunk.old_rows.end;
+    }
+    for row in old_row..old_lines.len() as u32 {
+        push_line(&mut result, line(&old_lines, row));
+    }
+
+    changed.then_some(result)
+}
+
+/// The rows that a range of whole lines spans.
+fn rows_for_point_range(range: Range<Point>) -> Range<u32> {
+    range.start.row..range.end.row + (range.end.column > 0) as u32
+}
+
+/// Computes the index text after staging or unstaging the selected lines.
+///
+/// Deleted lines are selected by their row in HEAD, and added lines by their row in the buffer.
+/// Staging applies the selected part of the index-to-buffer diff, and unstaging applies the
+/// selected part of the index-to-HEAD diff. Returns `None` when the index is unaffected, and
+/// `Some(None)` when the file should be removed from the index.
+fn stage_or_unstage_lines_impl(
+    stage: bool,
+    head_text: Option<&str>,
+    index_text: Option<&str>,
+    buffer_text: Option<&str>,
+    buffer_rows: &[Range<u32>],
+    head_rows: &[Range<u32>],
+) -> Option<Option<String>> {
+    let head = head_text.unwrap_or_default();
+    let index = index_text.unwrap_or_default();
+    let buffer = buffer_text.unwrap_or_default();
+    let head_to_index = line_hunks(head, index);
+    let index_to_buffer = line_hunks(index, buffer);
+    let is_selected = |rows: &[Range<u32>], row: u32| rows.iter().any(|range| range.contains(&row));
+
+    let new_index_text = if stage {
+        // Deleted lines that are already gone from the index have nothing left to stage.
+        let index_rows = head_rows
+            .iter()
+            .flat_map(|range| range.clone())
+            .filter_map(|row| map_unchanged_row(&head_to_index, row, false))
+            .collect::<HashSet<_>>();
+        apply_selected_lines(
+            index,
+            buffer,
+            &index_to_buffer,
+            |row| index_rows.contains(&row),
+            |row| is_selected(buffer_rows, row),
+        )?
+    } else {
+        // Added lines that aren't in the index yet have nothing to unstage.
+        let index_rows = buffer_rows
+            .iter()
+            .flat_map(|range| range.clone())
+            .filter_map(|row| map_unchanged_row(&index_to_buffer, row, true))
+            .collect::<HashSet<_>>();
+        let index_to_head = head_to_index
+            .iter()
+            .map(|hunk| LineHunk {
+                old_rows: hunk.new_rows.clone(),
+                new_rows: hunk.old_rows.clone(),
+            })
+            .collect::<Vec<_>>();
+        apply_selected_lines(
+            index,
+            head,
+            &index_to_head,
+            |row| index_rows.contains(&row),
+            |row| is_selected(head_rows, row),
+        )?
+    };
+
+    // Removing every line of a file that only exists on the other side removes it from the index.
+    let removes_file = if stage {
+        buffer_text.is_none()
+    } else {
+        head_text.is_none()
+    };
+    if new_index_text.is_empty() && removes_file {
+        Some(None)
+    } else {
+        Some(Some(new_index_text))
+    }
+}
+
 fn compare_hunks(
     new_hunks: &SumTree<InternalDiffHunk>,
     old_hunks: &SumTree<InternalDiffHunk>,
@@ -1637,6 +1827,108 @@ impl BufferDiff {
         }
     }
 
+    /// Stages or unstages individual lines instead of whole hunks, returning the new index text.
+    ///
+    /// `buffer_rows` selects added lines in the buffer and `base_text_rows` selects deleted lines
+    /// in this diff's base text. Hunks whose lines are all selected become pending hunks, like
+    /// with [`Self::stage_or_unstage_hunks`], and the remaining lines are applied on top of them.
+    /// Selected lines with nothing to stage or unstage are ignored, and when nothing is left the
+    /// current index text is returned without requesting a write.
+    pub fn stage_or_unstage_lines(
+        &mut self,
+        stage: bool,
+        buffer_rows: &[Range<u32>],
+        base_text_rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+        file_exists: bool,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let secondary_diff = self.secondary_diff.clone()?;
+        let base_text = self.base_text(cx);
+        let is_selected =
+            |selected: &[Range<u32>], row: u32| selected.iter().any(|range| range.contains(&row));
+        let mut touched_hunks = Vec::new();
+        let mut whole_hunks = Vec::new();
+        for hunk in self
+            .snapshot(cx)
+            .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, buffer)
+        {
+            let hunk_base_text_rows =
+                rows_for_point_range(hunk.diff_base_byte_range.to_point(&base_text));
+            let selected = rows_for_point_range(hunk.range.clone())
+                .map(|row| is_selected(buffer_rows, row))
+                .chain(hunk_base_text_rows.map(|row| is_selected(base_text_rows, row)))
+                .collect::<Vec<_>>();
+            if !selected.contains(&true) {
+                continue;
+            }
+            if !selected.contains(&false) {
+                whole_hunks.push(hunk.clone());
+            }
+            touched_hunks.push(hunk);
+        }
+
+        let head_text = self
+            .inner
+            .base_text_exists
+            .then(|| base_text.as_rope().clone());
+        let index_text = {
+            let secondary_diff = secondary_diff.read(cx);
+            secondary_diff
+                .inner
+                .base_text_exists
+                .then(|| secondary_diff.inner.base_text.read(cx).as_rope().clone())
+        };
+        // Without whole hunks to stage, only fold in the pending hunks when that can't turn into
+        // creating or deleting the whole file in the index.
+        let is_whole_file = head_text.is_none() || index_text.is_none() || (stage && !file_exists);
+        let pending_index_text = if whole_hunks.is_empty() && is_whole_file {
+            index_text
+        } else {
+            secondary_diff.update(cx, |secondary_diff, cx| {
+                self.inner.stage_or_unstage_hunks_impl(
+                    &secondary_diff.inner,
+                    stage,
+                    &whole_hunks,
+                    buffer,
+                    file_exists,
+                    cx,
+                )
+            })
+        };
+
+        let pending_index_string = pending_index_text.as_ref().map(Rope::to_string);
+        let head_string = head_text.as_ref().map(Rope::to_string);
+        let buffer_string = file_exists.then(|| buffer.text());
+        let new_index_text = match stage_or_unstage_lines_impl(
+            stage,
+            head_string.as_deref(),
+            pending_index_string.as_deref(),
+            buffer_string.as_deref(),
+            buffer_rows,
+            base_text_rows,
+        ) {
+            Some(new_index_text) => new_index_text.map(|text| Rope::from(text.as_str())),
+            None if whole_hunks.is_empty() => return pending_index_text,
+            None => pending_index_text,
+        };
+
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(
+            new_index_text.clone(),
+        ));
+        if let Some((first, last)) = touched_hunks.first().zip(touched_hunks.last()) {
+            let changed_range = Some(first.buffer_range.start..last.buffer_range.end);
+            let base_text_changed_range =
+                Some(first.diff_base_byte_range.start..last.diff_base_byte_range.end);
+            cx.emit(BufferDiffEvent::DiffChanged(DiffChanged {
+                changed_range: changed_range.clone(),
+                base_text_changed_range,
+                extended_range: changed_range,
+            }));
+        }
+        new_index_text
+    }
+
     pub fn update_diff(
         &self,
         buffer: text::BufferSnapshot,
@@ -2613,6 +2905,264 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stage_and_unstage_lines() {
+        struct Example {
+            name: &'static str,
+            stage: bool,
+            head_text: Option<&'static str>,
+            index_text: Option<&'static str>,
+            buffer_text: Option<&'static str>,
+            buffer_rows: Vec<Range<u32>>,
+            head_rows: Vec<Range<u32>>,
+            final_index_text: Option<Option<&'static str>>,
+        }
+
+        let table = [
+            Example {
+                name: "stage the addition of a modified line",
+                stage: true,
+                head_text: Some("one\ntwo\nthree\n"),
+                index_text: Some("one\ntwo\nthree\n"),
+                buffer_text: Some("one\nTWO\nthree\n"),
+                buffer_rows: vec![1..2],
+                head_rows: vec![],
+                final_index_text: Some(Some("one\ntwo\nTWO\nthree\n")),
+            },
+            Example {
+                name: "stage the deletion of a modified line",
+                stage: true,
+                head_text: Some("one\ntwo\nthree\n"),
+                index_text: Some("one\ntwo\nthree\n"),
+                buffer_text: Some("one\nTWO\nthree\n"),
+                buffer_rows: vec![],
+                head_rows: vec![1..2],
+                final_index_text: Some(Some("one\nthree\n")),
+            },
+            Example {
+                name: "stage both sides of a modified line",
+                stage: true,
+                head_text: Some("one\ntwo\nthree\n"),
+                index_text: Some("one\ntwo\nthree\n"),
+                buffer_text: Some("one\nTWO\nthree\n"),
+                buffer_rows: vec![1..2],
+                head_rows: vec![1..2],
+                final_index_text: Some(Some("one\nTWO\nthree\n")),
+            },
+            Example {
+                name: "stage some lines of an added block",
+                stage: true,
+                head_text: Some("a\nb\n"),
+                index_text: Some("a\nb\n"),
+                buffer_text: Some("a\nX\nY\nZ\nb\n"),
+                buffer_rows: vec![1..2, 3..4],
+                head_rows: vec![],
+                final_index_text: Some(Some("a\nX\nZ\nb\n")),
+            },
+            Example {
+                name: "stage on top of already staged changes",
+                stage: true,
+                head_text: Some("one\ntwo\nthree\n"),
+                index_text: Some("one\nTWO\nthree\n"),
+                buffer_text: Some("one\nTWO\nthree\nfour\nfive\n"),
+                buffer_rows: vec![1..5],
+                head_rows: vec![1..2],
+                final_index_text: Some(Some("one\nTWO\nthree\nfour\nfive\n")),
+            },
+            Example {
+                name: "staging lines that are already staged does nothing",
+                stage: true,
+                head_text: Some("one\ntwo\nthree\n"),
+                index_text: Some("one\nTWO\nthree\n"),
+                buffer_text: Some("one\nTWO\nthree\n"),
+                buffer_rows: vec![1..2],
+                head_rows: vec![1..2],
+                final_index_text: None,
+            },
+            Example {
+                name: "unstage part of a staged addition",
+                stage: false,
+                head_text: Some("a\nb\n"),
+                index_text: Some("a\nX\nY\nb\n"),
+                buffer_text: Some("a\nX\nY\nb\n"),
+                buffer_rows: vec![1..2],
+                head_rows: vec![],
+                final_index_text: Some(Some("a\nY\nb\n")),
+            },
+            Example {
+                name: "unstage a staged deletion",
+                stage: false,
+                head_text: Some("a\nb\nc\n"),
+                index_text: Some("a\nc\n"),
+                buffer_text: Some("a\nc\n"),
+                buffer_rows: vec![],
+                head_rows: vec![1..2],
+                final_index_text: Some(Some("a\nb\nc\n")),
+            },
+            Example {
+                name: "unstaging lines that aren't staged does nothing",
+                stage: false,
+                head_text: Some("a\nb\n"),
+                index_text: Some("a\nb\n"),
+                buffer_text: Some("a\nX\nb\n"),
+                buffer_rows: vec![1..2],
+                head_rows: vec![],
+                final_index_text: None,
+            },
+            Example {
+                name: "partially stage an untracked file",
+                stage: true,
+                head_text: None,
+                index_text: None,
+                buffer_text: Some("a\nb\nc\n"),
+                buffer_rows: vec![0..1, 2..3],
+                head_rows: vec![],
+                final_index_text: Some(Some("a\nc\n")),
+            },
+            Example {
+                name: "partially stage an intent-to-add file",
+                stage: true,
+                head_text: None,
+                index_text: Some(""),
+                buffer_text: Some("a\nb\nc\n"),
+                buffer_rows: vec![1..2],
+                head_rows: vec![],
+                final_index_text: Some(Some("b\n")),
+            },
+            Example {
+                name: "partially unstage a new file",
+                stage: false,
+                head_text: None,
+                index_text: Some("a\nc\n"),
+                buffer_text: Some("a\nb\nc\n"),
+                buffer_rows: vec![0..1],
+                head_rows: vec![],
+                final_index_text: Some(Some("c\n")),
+            },
+            Example {
+                name: "unstage every line of a new file",
+                stage: false,
+                head_text: None,
+                index_text: Some("a\nc\n"),
+                buffer_text: Some("a\nb\nc\n"),
+                buffer_rows: vec![0..3],
+                head_rows: vec![],
+                final_index_text: Some(None),
+            },
+            Example {
+                name: "partially stage a deleted file",
+                stage: true,
+                head_text: Some("a\nb\n"),
+                index_text: Some("a\nb\n"),
+                buffer_text: None,
+                buffer_rows: vec![],
+                head_rows: vec![0..1],
+                final_index_text: Some(Some("b\n")),
+            },
+            Example {
+                name: "stage every line of a deleted file",
+                stage: true,
+                head_text: Some("a\nb\n"),
+                index_text: Some("a\nb\n"),
+                buffer_text: None,
+                buffer_rows: vec![],
+                head_rows: vec![0..2],
+                final_index_text: Some(None),
+            },
+        ];
+
+        for example in table {
+            let new_index_text = stage_or_unstage_lines_impl(
+                example.stage,
+                example.head_text,
+                example.index_text,
+                example.buffer_text,
+                &example.buffer_rows,
+                &example.head_rows,
+            );
+            assert_eq!(
+                new_index_text,
+                example
+                    .final_index_text
+                    .map(|text| text.map(ToOwned::to_owned)),
+                "example: {}",
+                example.name
+            );
+        }
+    }
+
+    #[gpui::test]
+    async fn test_stage_lines_emits_new_index_text(cx: &mut TestAppContext) {
+        let head_text = "one\ntwo\nthree\n";
+        let index_text = "one\ntwo\nthree\n";
+        let buffer = Buffer::new(
+            ReplicaId::LOCAL,
+            BufferId::new(1).unwrap(),
+            "one\nTWO\nthree\nfour\n".to_string(),
+        );
+
+        let unstaged_diff = cx.new(|cx| BufferDiff::new_with_base_text(index_text, &buffer, cx));
+        let uncommitted_diff = cx.new(|cx| {
+            let mut diff = BufferDiff::new_with_base_text(head_text, &buffer, cx);
+            diff.set_secondary_diff(unstaged_diff);
+            diff
+        });
+
+        let (tx, rx) = mpsc::channel();
+        let (changed_tx, changed_rx) = mpsc::channel();
+        let _subscription = cx.update(|cx| {
+            cx.subscribe(&uncommitted_diff, move |_, event, _| match event {
+                BufferDiffEvent::HunksStagedOrUnstaged(text) => {
+                    tx.send(text.as_ref().map(|text| text.to_string())).unwrap();
+                }
+                BufferDiffEvent::DiffChanged(_) => changed_tx.send(()).unwrap(),
+                _ => {}
+            })
+        });
+
+        uncommitted_diff.update(cx, |diff, cx| {
+            // "TWO" isn't in the index yet, so unstaging it requests no write.
+            let unchanged_index_text = diff
+                .stage_or_unstage_lines(false, &[1..2], &[], &buffer, true, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(unchanged_index_text, index_text);
+
+            // Both hunks are selected in full, so they become pending.
+            let new_index_text = diff
+                .stage_or_unstage_lines(true, &[1..2, 3..4], &[1..2], &buffer, true, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(new_index_text, "one\nTWO\nthree\nfour\n");
+            assert_eq!(
+                diff.snapshot(cx)
+                    .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &buffer)
+                    .map(|hunk| hunk.secondary_status)
+                    .collect::<Vec<_>>(),
+                [
+                    DiffHunkSecondaryStatus::SecondaryHunkRemovalPending,
+                    DiffHunkSecondaryStatus::SecondaryHunkRemovalPending,
+                ]
+            );
+
+            // Unstaging a line builds on the pending hunks rather than the stale index.
+            let new_index_text = diff
+                .stage_or_unstage_lines(false, &[3..4], &[], &buffer, true, cx)
+                .unwrap()
+                .to_string();
+            assert_eq!(new_index_text, "one\nTWO\nthree\n");
+        });
+
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            [
+                Some("one\nTWO\nthree\nfour\n".to_string()),
+                Some("one\nTWO\nthree\n".to_string()),
+            ]
+        );
+        assert_eq!(changed_rx.try_iter().count(), 2);
+    }
+
     #[gpui::test]
     async fn test_stage_all_with_nested_hunks(cx: &mut TestAppContext) {
         // This test reproduces a crash where staging all hunks would cause an underflow
@@ -7205,3 +7205,67 @@ async fn test_remote_git_branches(
 
     assert_eq!(host_branch.name(), "totally-new-branch");
 }
+
+#[gpui::test]
+async fn test_remote_git_cherry_pick_and_revert(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/project", serde_json::json!({ ".git":{} }))
+        .await;
+
+    let (project_a, _) = client_a.build_local_project("/project", cx_a).await;
+
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    executor.run_until_parked();
+
+    let repo_b = cx_b.update(|cx| project_b.read(cx).active_repository(cx).unwrap());
+
+    cx_b.update(|cx| {
+        repo_b.update(cx, |repository, cx| {
+            repository.cherry_pick(vec!["abc123".to_string(), "def456".to_string()], cx)
+        })
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    cx_b.update(|cx| {
+        repo_b.update(cx, |repository, cx| {
+            repository.revert(vec!["fed321".to_string()], cx)
+        })
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    executor.run_until_parked();
+
+    let (cherry_picked, reverted) = client_a
+        .fs()
+        .with_git_state(Path::new("/project/.git"), false, |state| {
+            (
+                state.cherry_picked_commits.clone(),
+                state.reverted_commits.clone(),
+            )
+        })
+        .unwrap();
+    assert_eq!(cherry_picked, ["abc123", "def456"]);
+    assert_eq!(reverted, ["fed321"]);
+}
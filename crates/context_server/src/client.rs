@@ -52,6 +52,7 @@ pub(crate) struct Client {
     outbound_tx: channel::Sender<String>,
     name: Arc<str>,
     subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
     response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
     #[allow(clippy::type_complexity)]
     #[allow(dead_code)]
@@ -101,7 +102,6 @@ struct AnyResponse<'a> {
 }
 
 #[derive(Serialize, Deserialize)]
-#[allow(dead_code)]
 pub(crate) struct Response<T> {
     pub jsonrpc: &'static str,
     pub id: RequestId,
@@ -114,7 +114,6 @@ pub(crate) struct Response<T> {
 pub(crate) enum CspResult<T> {
     #[serde(rename = "result")]
     Ok(Option<T>),
-    #[allow(dead_code)]
     Error(Option<Error>),
 }
 
@@ -201,6 +200,7 @@ impl Client {
             let subscription_set = subscription_set.clone();
             let response_handlers = response_handlers.clone();
             let request_handlers = request_handlers.clone();
+            let outbound_tx = outbound_tx.clone();
             let transport = transport.clone();
             async move |cx| {
                 Self::handle_input(
@@ -208,6 +208,7 @@ impl Client {
                     subscription_set,
                     request_handlers,
                     response_handlers,
+                    outbound_tx,
                     cx,
                 )
                 .log_err()
@@ -237,6 +238,7 @@ impl Client {
         Ok(Self {
             server_id,
             subscription_set,
+            request_handlers,
             response_handlers,
             name: server_name,
             next_id: Default::default(),
@@ -255,11 +257,14 @@ impl Client {
     /// parses them as JSON-RPC responses or notifications, and dispatches them
     /// to the appropriate handlers. It processes both responses (which are matched
     /// to pending requests) and notifications (which trigger registered handlers).
+    /// Requests initiated by the server are answered with an error unless a handler
+    /// has been registered for their method.
     async fn handle_input(
         transport: Arc<dyn Transport>,
         subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
         request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
         response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+        outbound_tx: channel::Sender<String>,
         cx: &mut AsyncApp,
     ) -> anyhow::Result<()> {
         let mut receiver = transport.receive();
@@ -274,6 +279,16 @@ impl Client {
                         request.params.unwrap_or(RawValue::NULL),
                         cx.clone(),
                     );
+                } else {
+                    log::debug!("unhandled context server request: {}", request.method);
+                    send_response::<()>(
+                        &outbound_tx,
+                        request.id,
+                        CspResult::Error(Some(Error {
+                            message: format!("method not found: {}", request.method),
+                            code: METHOD_NOT_FOUND,
+                        })),
+                    );
                 }
             } else if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
                 if let Some(handlers) = response_handlers.lock().as_mut()
@@ -452,6 +467,57 @@ impl Client {
         Ok(())
     }
 
+    /// Registers a handler for requests initiated by the context server.
+    ///
+    /// The handler's result is sent back to the server once the returned task
+    /// completes. Registering a handler for a method replaces any previous one.
+    pub fn on_request<Params, Res, F>(&self, method: &'static str, mut f: F)
+    where
+        Params: DeserializeOwned + 'static,
+        Res: Serialize + 'static,
+        F: 'static + Send + FnMut(Params, AsyncApp) -> Task<Result<Res>>,
+    {
+        let outbound_tx = self.outbound_tx.clone();
+        self.request_handlers.lock().insert(
+            method,
+            Box::new(move |id, params, cx| {
+                let params = match serde_json::from_str::<Params>(params.get()) {
+                    Ok(params) => params,
+                    Err(error) => {
+                        send_response::<()>(
+                            &outbound_tx,
+                            id,
+                            CspResult::Error(Some(Error {
+                                message: error.to_string(),
+                                code: INVALID_PARAMS,
+                            })),
+                        );
+                        return;
+                    }
+                };
+
+                let task = f(params, cx.clone());
+                let outbound_tx = outbound_tx.clone();
+                cx.spawn(async move |_| {
+                    let value = match task.await {
+                        Ok(result) => CspResult::Ok(Some(result)),
+                        Err(error) => CspResult::Error(Some(Error {
+                            message: format!("{error:#}"),
+                            code: INTERNAL_ERROR,
+                        })),
+                    };
+                    send_response(&outbound_tx, id, value);
+                })
+                .detach();
+            }),
+        );
+    }
+
+    /// Returns whether a handler is registered for requests of the given method.
+    pub fn handles_request(&self, method: &str) -> bool {
+        self.request_handlers.lock().contains_key(method)
+    }
+
     #[must_use]
     pub fn on_notification(
         &self,
@@ -467,6 +533,23 @@ impl Client {
     }
 }
 
+fn send_response<T: Serialize>(
+    outbound_tx: &channel::Sender<String>,
+    id: RequestId,
+    value: CspResult<T>,
+) {
+    let response = serde_json::to_string(&Response {
+        jsonrpc: JSON_RPC_VERSION,
+        id,
+        value,
+    })
+    .unwrap();
+    outbound_tx
+        .try_send(response)
+        .context("failed to write to context server's stdin")
+        .log_err();
+}
+
 #[derive(Debug)]
 pub struct RequestCanceled;
 
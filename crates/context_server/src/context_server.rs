@@ -15,12 +15,13 @@ use std::{fmt::Display, path::PathBuf};
 
 use anyhow::Result;
 use client::Client;
-use gpui::AsyncApp;
-use parking_lot::RwLock;
+use gpui::{AsyncApp, Task};
+use parking_lot::{Mutex, RwLock};
 pub use settings::ContextServerCommand;
 use url::Url;
 
 use crate::transport::HttpTransport;
+use crate::types::Request;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ContextServerId(pub Arc<str>);
@@ -31,6 +32,9 @@ impl Display for ContextServerId {
     }
 }
 
+/// Registers a request handler on a newly created client.
+type RequestHandlerInstaller = Box<dyn Send + Sync + Fn(&Client)>;
+
 enum ContextServerTransport {
     Stdio(ContextServerCommand, Option<PathBuf>),
     Custom(Arc<dyn crate::transport::Transport>),
@@ -41,6 +45,7 @@ pub struct ContextServer {
     client: RwLock<Option<Arc<crate::protocol::InitializedContextServerProtocol>>>,
     configuration: ContextServerTransport,
     request_timeout: Option<Duration>,
+    request_handlers: Mutex<HashMap<&'static str, RequestHandlerInstaller>>,
 }
 
 impl ContextServer {
@@ -57,6 +62,7 @@ impl ContextServer {
                 working_directory.map(|directory| directory.to_path_buf()),
             ),
             request_timeout: None,
+            request_handlers: Mutex::default(),
         }
     }
 
@@ -94,6 +100,7 @@ impl ContextServer {
             client: RwLock::new(None),
             configuration: ContextServerTransport::Custom(transport),
             request_timeout,
+            request_handlers: Mutex::default(),
         }
     }
 
@@ -105,6 +112,28 @@ impl ContextServer {
         self.client.read().clone()
     }
 
+    /// Handles requests of type `T` sent by the server, such as sampling or elicitation.
+    ///
+    /// Handlers registered before the server starts are advertised in the client
+    /// capabilities it's initialized with, and are kept across restarts.
+    pub fn on_request<T: Request>(
+        &self,
+        f: impl 'static + Send + Sync + Fn(T::Params, AsyncApp) -> Task<Result<T::Response>>,
+    ) {
+        let f = Arc::new(f);
+        if let Some(protocol) = self.client() {
+            let f = f.clone();
+            protocol.on_request::<T>(move |params, cx| f(params, cx));
+        }
+        self.request_handlers.lock().insert(
+            T::METHOD,
+            Box::new(move |client| {
+                let f = f.clone();
+                client.on_request(T::METHOD, move |params, cx| f(params, cx));
+            }),
+        );
+    }
+
     pub async fn start(&self, cx: &AsyncApp) -> Result<()> {
         self.initialize(self.new_client(cx)?).await
     }
@@ -134,6 +163,9 @@ impl ContextServer {
 
     async fn initialize(&self, client: Client) -> Result<()> {
         log::debug!("starting context server {}", self.id);
+        for install_handler in self.request_handlers.lock().values() {
+            install_handler(&client);
+        }
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
             name: "Zed".to_string(),
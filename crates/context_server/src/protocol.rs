@@ -9,7 +9,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use futures::channel::oneshot;
-use gpui::AsyncApp;
+use gpui::{AsyncApp, Task};
 use serde_json::Value;
 
 use crate::client::{Client, NotificationSubscription};
@@ -37,9 +37,18 @@ impl ModelContextProtocol {
     ) -> Result<InitializedContextServerProtocol> {
         let params = types::InitializeParams {
             protocol_version: types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
+            // Only advertise what a handler was registered for, as the server may otherwise send
+            // requests nobody answers.
             capabilities: types::ClientCapabilities {
                 experimental: None,
-                sampling: None,
+                sampling: self
+                    .inner
+                    .handles_request(types::requests::CreateMessage::METHOD)
+                    .then(|| serde_json::json!({})),
+                elicitation: self
+                    .inner
+                    .handles_request(types::requests::Elicitation::METHOD)
+                    .then(|| serde_json::json!({})),
                 roots: None,
             },
             meta: None,
@@ -115,6 +124,14 @@ impl InitializedContextServerProtocol {
         self.inner.notify(T::METHOD, params)
     }
 
+    /// Handles requests of type `T` sent by the server, such as sampling or elicitation.
+    pub fn on_request<T: Request>(
+        &self,
+        f: impl 'static + Send + FnMut(T::Params, AsyncApp) -> Task<Result<T::Response>>,
+    ) {
+        self.inner.on_request(T::METHOD, f)
+    }
+
     pub fn on_notification(
         &self,
         method: &'static str,
@@ -41,20 +41,46 @@ pub struct FakeTransport {
     >,
     tx: futures::channel::mpsc::UnboundedSender<String>,
     rx: Arc<Mutex<futures::channel::mpsc::UnboundedReceiver<String>>>,
+    client_responses_tx: futures::channel::mpsc::UnboundedSender<serde_json::Value>,
+    client_responses_rx: Mutex<futures::channel::mpsc::UnboundedReceiver<serde_json::Value>>,
     executor: BackgroundExecutor,
 }
 
 impl FakeTransport {
     pub fn new(executor: BackgroundExecutor) -> Self {
         let (tx, rx) = futures::channel::mpsc::unbounded();
+        let (client_responses_tx, client_responses_rx) = futures::channel::mpsc::unbounded();
         Self {
             request_handlers: Default::default(),
             tx,
             rx: Arc::new(Mutex::new(rx)),
+            client_responses_tx,
+            client_responses_rx: Mutex::new(client_responses_rx),
             executor,
         }
     }
 
+    /// Sends a request to the client as if the server initiated it.
+    pub fn request_client<T: crate::types::Request>(&self, id: u64, params: T::Params) {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": T::METHOD,
+            "params": params,
+        });
+        self.tx.unbounded_send(request.to_string()).unwrap();
+    }
+
+    /// Waits for the client to answer a request sent with [`Self::request_client`].
+    pub async fn next_client_response(&self) -> serde_json::Value {
+        self.client_responses_rx
+            .lock()
+            .await
+            .next()
+            .await
+            .expect("The transport was dropped")
+    }
+
     pub fn on_request<T, Fut>(
         mut self,
         handler: impl 'static + Send + Sync + Fn(T::Params) -> Fut,
@@ -101,6 +127,10 @@ impl Transport for FakeTransport {
                 } else {
                     log::debug!("No handler registered for MCP request '{method}'");
                 }
+            } else {
+                self.client_responses_tx
+                    .unbounded_send(msg)
+                    .context("sending a client response")?;
             }
         }
         Ok(())
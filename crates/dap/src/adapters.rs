@@ -395,6 +395,11 @@ pub trait DebugAdapter: 'static + Send + Sync {
     fn prefer_thread_name(&self) -> bool {
         false
     }
+
+    /// Whether stack frames without a source should be shown in the disassembly view.
+    fn show_disassembly_without_source(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(any(test, feature = "test-support"))]
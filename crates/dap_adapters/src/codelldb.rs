@@ -260,6 +260,12 @@ impl DebugAdapter for CodeLldbDebugAdapter {
                         "type": "string"
                     }
                 },
+                "showDisassembly": {
+                    "type": "string",
+                    "enum": ["always", "auto", "never"],
+                    "description": "When to open CodeLLDB's own disassembly documents. Defaults to 'never', as frames without source are shown in the debugger's disassembly view",
+                    "default": "never"
+                },
                 "reverseDebugging": {
                     "type": "boolean",
                     "description": "Enable reverse debugging",
@@ -324,6 +330,10 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         })
     }
 
+    fn show_disassembly_without_source(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
@@ -399,21 +409,26 @@ impl DebugAdapter for CodeLldbDebugAdapter {
             command: Some(command.unwrap()),
             cwd: Some(delegate.worktree_root_path().to_path_buf()),
             arguments: user_args.unwrap_or_else(|| {
-                if let Some(config) = json_config.as_object_mut()
-                    && let Some(source_languages) = config.get("sourceLanguages").filter(|value| {
+                let mut settings = serde_json::Map::default();
+                if let Some(config) = json_config.as_object_mut() {
+                    if let Some(source_languages) = config.get("sourceLanguages").filter(|value| {
                         value
                             .as_array()
                             .is_some_and(|array| array.iter().all(Value::is_string))
-                    })
-                {
-                    let ret = vec![
-                        "--settings".into(),
-                        json!({"sourceLanguages": source_languages}).to_string(),
-                    ];
-                    config.remove("sourceLanguages");
-                    ret
-                } else {
+                    }) {
+                        settings.insert("sourceLanguages".into(), source_languages.clone());
+                        config.remove("sourceLanguages");
+                    }
+                    let show_disassembly = config
+                        .remove("showDisassembly")
+                        .filter(Value::is_string)
+                        .unwrap_or_else(|| "never".into());
+                    settings.insert("showDisassembly".into(), show_disassembly);
+                }
+                if settings.is_empty() {
                     vec![]
+                } else {
+                    vec!["--settings".into(), Value::Object(settings).to_string()]
                 }
             }),
             request_args: self
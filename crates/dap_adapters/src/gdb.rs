@@ -174,6 +174,10 @@ impl DebugAdapter for GdbDebugAdapter {
         })
     }
 
+    fn show_disassembly_without_source(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
@@ -30,7 +30,7 @@ use project::{Project, debugger::session::ThreadStatus};
 use rpc::proto::{self};
 use settings::Settings;
 use std::sync::{Arc, LazyLock};
-use task::{DebugScenario, SharedTaskContext};
+use task::{DebugCompound, DebugScenario, SharedTaskContext};
 use tree_sitter::{Query, StreamingIterator as _};
 use ui::{
     ContextMenu, Divider, PopoverMenu, PopoverMenuHandle, SplitButton, Tab, Tooltip, prelude::*,
@@ -53,6 +53,22 @@ impl FeatureFlag for DebuggerHistoryFeatureFlag {
 
 const DEBUG_PANEL_KEY: &str = "DebugPanel";
 
+/// Sessions that were started together from a compound debug scenario.
+pub(crate) struct CompoundSessions {
+    pub(crate) label: SharedString,
+    stop_all: bool,
+    sessions: Vec<WeakEntity<Session>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CompoundSessions {
+    fn contains(&self, session: &Entity<Session>) -> bool {
+        self.sessions
+            .iter()
+            .any(|other| other.entity_id() == session.entity_id())
+    }
+}
+
 pub struct DebugPanel {
     size: Pixels,
     active_session: Option<Entity<DebugSession>>,
@@ -63,6 +79,7 @@ pub struct DebugPanel {
     debug_scenario_scheduled_last: bool,
     pub(crate) sessions_with_children:
         IndexMap<Entity<DebugSession>, Vec<WeakEntity<DebugSession>>>,
+    pub(crate) compounds: Vec<CompoundSessions>,
     pub(crate) thread_picker_menu_handle: PopoverMenuHandle<ContextMenu>,
     pub(crate) session_picker_menu_handle: PopoverMenuHandle<ContextMenu>,
     fs: Arc<dyn Fs>,
@@ -94,6 +111,7 @@ impl DebugPanel {
             Self {
                 size: px(300.),
                 sessions_with_children: Default::default(),
+                compounds: Vec::new(),
                 active_session: None,
                 focus_handle,
                 breakpoint_list: BreakpointList::new(
@@ -182,13 +200,109 @@ impl DebugPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let dap_store = self.project.read(cx).dap_store();
-        let Some(adapter) = DapRegistry::global(cx).adapter(&scenario.adapter) else {
+        self.start_session_inner(
+            scenario,
+            task_context,
+            active_buffer,
+            worktree_id,
+            window,
+            cx,
+        );
+    }
+
+    /// Starts a session for each scenario of a compound, grouping them under the compound's label
+    /// in the session list.
+    pub fn start_compound(
+        &mut self,
+        compound: DebugCompound,
+        scenarios: Vec<DebugScenario>,
+        task_context: SharedTaskContext,
+        worktree_id: Option<WorktreeId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let sessions = scenarios
+            .into_iter()
+            .filter_map(|scenario| {
+                self.start_session_inner(
+                    scenario,
+                    task_context.clone(),
+                    None,
+                    worktree_id,
+                    window,
+                    cx,
+                )
+            })
+            .collect::<Vec<_>>();
+        if sessions.is_empty() {
+            return;
+        }
+
+        let _subscriptions = sessions
+            .iter()
+            .map(|session| {
+                cx.subscribe(session, |this, session, event: &SessionStateEvent, cx| {
+                    if matches!(event, SessionStateEvent::Shutdown) {
+                        this.handle_compound_session_shutdown(&session, cx);
+                    }
+                })
+            })
+            .collect();
+
+        self.compounds.push(CompoundSessions {
+            label: compound.label,
+            stop_all: compound.stop_all,
+            sessions: sessions.iter().map(|session| session.downgrade()).collect(),
+            _subscriptions,
+        });
+        cx.notify();
+    }
+
+    fn handle_compound_session_shutdown(&mut self, session: &Entity<Session>, cx: &mut App) {
+        let Some(compound) = self
+            .compounds
+            .iter()
+            .find(|compound| compound.contains(session))
+        else {
             return;
         };
+        if !compound.stop_all {
+            return;
+        }
+
+        for sibling in compound
+            .sessions
+            .iter()
+            .filter_map(|sibling| sibling.upgrade())
+        {
+            sibling
+                .update(cx, |sibling, cx| sibling.shutdown(cx))
+                .detach();
+        }
+    }
+
+    pub(crate) fn compound_label(&self, session: &Entity<Session>) -> Option<SharedString> {
+        self.compounds
+            .iter()
+            .find(|compound| compound.contains(session))
+            .map(|compound| compound.label.clone())
+    }
+
+    fn start_session_inner(
+        &mut self,
+        scenario: DebugScenario,
+        task_context: SharedTaskContext,
+        active_buffer: Option<Entity<Buffer>>,
+        worktree_id: Option<WorktreeId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Session>> {
+        let dap_store = self.project.read(cx).dap_store();
+        let adapter = DapRegistry::global(cx).adapter(&scenario.adapter)?;
         let quirks = SessionQuirks {
             compact: adapter.compact_child_session(),
             prefer_thread_name: adapter.prefer_thread_name(),
+            disassembly_without_source: adapter.show_disassembly_without_source(),
         };
         let session = dap_store.update(cx, |dap_store, cx| {
             dap_store.new_session(
@@ -212,7 +326,7 @@ impl DebugPanel {
             .or_else(|| self.project.read(cx).visible_worktrees(cx).next())
         else {
             log::debug!("Could not find a worktree to spawn the debug session in");
-            return;
+            return None;
         };
 
         self.debug_scenario_scheduled_last = true;
@@ -298,6 +412,8 @@ impl DebugPanel {
                 debug_panic!("Session state should be in building because we are just starting it");
             }
         });
+
+        Some(session)
     }
 
     pub(crate) fn rerun_last_session(
@@ -1340,6 +1456,12 @@ impl DebugPanel {
                 keep(&child)
             });
         }
+        self.compounds.retain_mut(|compound| {
+            compound
+                .sessions
+                .retain(|session| session.upgrade().is_some());
+            !compound.sessions.is_empty()
+        });
     }
 
     fn render_history_button(
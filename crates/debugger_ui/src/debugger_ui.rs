@@ -10,6 +10,7 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use session::DebugSession;
 use stack_trace_view::StackTraceView;
+use step_in_target_modal::StepInTargetModal;
 use tasks_ui::{Spawn, TaskOverrides};
 use ui::{FluentBuilder, InteractiveElement};
 use util::maybe;
@@ -25,6 +26,7 @@ mod onboarding_modal;
 mod persistence;
 pub(crate) mod session;
 mod stack_trace_view;
+mod step_in_target_modal;
 
 #[cfg(any(test, feature = "test-support"))]
 pub mod tests;
@@ -53,6 +55,10 @@ actions!(
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Steps over the current machine instruction.
+        StepOverInstruction,
+        /// Steps into the current machine instruction.
+        StepIntoInstruction,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
@@ -194,6 +200,22 @@ pub fn init(cx: &mut App) {
                             active_item.update(cx, |item, cx| item.step_out(cx)).ok();
                         }
                     })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &StepOverInstruction, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.step_over_instruction(cx))
+                                .ok();
+                        }
+                    })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &StepIntoInstruction, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.step_into_instruction(cx))
+                                .ok();
+                        }
+                    })
                     .when(supports_step_back, |div| {
                         let active_item = active_item.clone();
                         div.on_action(move |_: &StepBack, _, cx| {
@@ -384,6 +406,102 @@ pub fn init(cx: &mut App) {
                         },
                     );
 
+                    let capabilities = session.capabilities();
+                    let supports_goto_targets = capabilities
+                        .supports_goto_targets_request
+                        .unwrap_or_default();
+                    let supports_step_in_targets = capabilities
+                        .supports_step_in_targets_request
+                        .unwrap_or_default();
+
+                    window.on_action_when(
+                        session.any_stopped_thread() && supports_goto_targets,
+                        TypeId::of::<editor::actions::JumpToCursor>(),
+                        {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, _, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (buffer, position, _) = editor
+                                        .update(cx, |editor, cx| {
+                                            let cursor_point: language::Point = editor
+                                                .selections
+                                                .newest(&editor.display_snapshot(cx))
+                                                .head();
+
+                                            editor
+                                                .buffer()
+                                                .read(cx)
+                                                .point_to_buffer_point(cursor_point, cx)
+                                        })
+                                        .ok()??;
+
+                                    let path =
+                                debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
+                                    &buffer, cx,
+                                )?;
+
+                                    active_session.update(cx, |session, cx| {
+                                        session.running_state().update(cx, |state, cx| {
+                                            state.jump_to_position(path, position.row, cx);
+                                        });
+                                    });
+
+                                    Some(())
+                                });
+                            }
+                        },
+                    );
+
+                    window.on_action_when(
+                        session.any_stopped_thread() && supports_step_in_targets,
+                        TypeId::of::<editor::actions::StepIntoTarget>(),
+                        {
+                            let active_session = active_session.clone();
+                            move |_, phase, window, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                let running_state = active_session.read(cx).running_state().clone();
+                                let Some(stack_frame_id) =
+                                    running_state.read(cx).selected_stack_frame_id(cx)
+                                else {
+                                    return;
+                                };
+
+                                let session = running_state.read(cx).session().clone();
+                                let targets = session.update(cx, |session, cx| {
+                                    session.step_in_targets(stack_frame_id, cx)
+                                });
+                                let running_state = running_state.downgrade();
+                                let workspace = workspace.downgrade();
+                                window
+                                    .spawn(cx, async move |cx| {
+                                        let targets = targets.await.unwrap_or_default();
+                                        if targets.is_empty() {
+                                            return;
+                                        }
+                                        workspace
+                                            .update_in(cx, |workspace, window, cx| {
+                                                workspace.toggle_modal(window, cx, |window, cx| {
+                                                    StepInTargetModal::new(
+                                                        running_state,
+                                                        targets,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                });
+                                            })
+                                            .ok();
+                                    })
+                                    .detach();
+                            }
+                        },
+                    );
+
                     window.on_action(
                         TypeId::of::<editor::actions::EvaluateSelectedText>(),
                         move |_, _, window, cx| {
@@ -114,6 +114,18 @@ impl DebugPanel {
             };
             session_entries.push(root_entry);
         }
+        let compound_labels = session_entries
+            .iter()
+            .map(|entry| {
+                let session = entry
+                    .ancestors
+                    .first()
+                    .unwrap_or(&entry.leaf)
+                    .read(cx)
+                    .session(cx);
+                self.compound_label(&session)
+            })
+            .collect::<Vec<_>>();
 
         let weak = cx.weak_entity();
         let trigger_label = if let Some(active_session) = active_session.clone() {
@@ -160,7 +172,18 @@ impl DebugPanel {
             ContextMenu::build(window, cx, move |mut this, _, cx| {
                 let context_menu = cx.weak_entity();
                 let mut session_depths = HashMap::default();
-                for session_entry in session_entries {
+                let mut current_compound = None;
+                for (session_entry, compound_label) in
+                    session_entries.into_iter().zip(compound_labels)
+                {
+                    if let Some(label) = &compound_label
+                        && compound_label != current_compound
+                    {
+                        this = this.header(label.clone());
+                    }
+                    let compound_depth = compound_label.is_some().then_some(1);
+                    current_compound = compound_label;
+
                     let session_id = session_entry.leaf.read(cx).session_id(cx);
                     let parent_depth = session_entry
                         .ancestors
@@ -171,9 +194,12 @@ impl DebugPanel {
                         .read(cx)
                         .parent_id(cx)
                         .and_then(|parent_id| session_depths.get(&parent_id).cloned());
-                    let self_depth = *session_depths
-                        .entry(session_id)
-                        .or_insert_with(|| parent_depth.map(|depth| depth + 1).unwrap_or(0usize));
+                    let self_depth = *session_depths.entry(session_id).or_insert_with(|| {
+                        parent_depth
+                            .map(|depth| depth + 1)
+                            .or(compound_depth)
+                            .unwrap_or(0usize)
+                    });
                     this = this.custom_entry(
                         {
                             let weak = weak.clone();
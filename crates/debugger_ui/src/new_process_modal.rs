@@ -21,7 +21,9 @@ use gpui::{
 use itertools::Itertools as _;
 use picker::{Picker, PickerDelegate, highlighted_match_with_paths::HighlightedMatch};
 use project::{DebugScenarioContext, Project, TaskContexts, TaskSourceKind, task_store::TaskStore};
-use task::{DebugScenario, RevealTarget, SharedTaskContext, VariableName, ZedDebugConfig};
+use task::{
+    DebugCompound, DebugScenario, RevealTarget, SharedTaskContext, VariableName, ZedDebugConfig,
+};
 use ui::{
     ContextMenu, DropdownMenu, IconWithIndicator, Indicator, KeyBinding, ListItem, ListItemSpacing,
     Switch, SwitchLabelPosition, ToggleButtonGroup, ToggleButtonSimple, ToggleState, Tooltip,
@@ -1014,6 +1016,8 @@ pub(super) struct DebugDelegate {
         DebugScenario,
         Option<DebugScenarioContext>,
     )>,
+    /// Compound scenarios, matched after `candidates` using ids offset by `candidates.len()`.
+    compounds: Vec<(TaskSourceKind, DebugCompound)>,
     selected_index: usize,
     matches: Vec<StringMatch>,
     prompt: String,
@@ -1028,6 +1032,7 @@ impl DebugDelegate {
         Self {
             task_store,
             candidates: Vec::default(),
+            compounds: Vec::default(),
             selected_index: 0,
             matches: Vec::new(),
             prompt: String::new(),
@@ -1145,6 +1150,17 @@ impl DebugDelegate {
             })
         });
 
+        let compounds = self
+            .task_store
+            .read(cx)
+            .task_inventory()
+            .map(|inventory| {
+                inventory
+                    .read(cx)
+                    .list_debug_compounds(task_contexts.worktree())
+            })
+            .unwrap_or_default();
+
         let valid_adapters: HashSet<_> = cx.global::<DapRegistry>().enumerate_adapters();
 
         cx.spawn(async move |this, cx| {
@@ -1198,10 +1214,70 @@ impl DebugDelegate {
                             }),
                     )
                     .collect();
+                this.delegate.compounds = compounds;
             })
             .ok();
         })
     }
+
+    fn compound_for_candidate(
+        &self,
+        candidate_id: usize,
+    ) -> Option<&(TaskSourceKind, DebugCompound)> {
+        candidate_id
+            .checked_sub(self.candidates.len())
+            .and_then(|ix| self.compounds.get(ix))
+    }
+
+    fn confirm_compound(
+        &mut self,
+        kind: TaskSourceKind,
+        compound: DebugCompound,
+        window: &mut Window,
+        cx: &mut Context<picker::Picker<Self>>,
+    ) {
+        let (task_context, worktree_id) = self
+            .task_contexts
+            .as_ref()
+            .map(|task_contexts| {
+                (
+                    task_contexts
+                        .active_context()
+                        .cloned()
+                        .map(SharedTaskContext::from)
+                        .unwrap_or_default(),
+                    task_contexts.worktree(),
+                )
+            })
+            .unwrap_or_default();
+        let worktree_id = match kind {
+            TaskSourceKind::Worktree { id, .. } => Some(id),
+            _ => worktree_id,
+        };
+
+        let Some(scenarios) = self
+            .task_store
+            .read(cx)
+            .task_inventory()
+            .and_then(|inventory| {
+                inventory
+                    .read(cx)
+                    .compound_scenarios(&compound, worktree_id)
+                    .log_err()
+            })
+        else {
+            return;
+        };
+
+        for scenario in &scenarios {
+            send_telemetry(scenario, TelemetrySpawnLocation::ScenarioList, cx);
+        }
+        self.debug_panel
+            .update(cx, |panel, cx| {
+                panel.start_compound(compound, scenarios, task_context, worktree_id, window, cx);
+            })
+            .ok();
+    }
 }
 
 impl PickerDelegate for DebugDelegate {
@@ -1235,14 +1311,19 @@ impl PickerDelegate for DebugDelegate {
         cx: &mut Context<picker::Picker<Self>>,
     ) -> gpui::Task<()> {
         let candidates = self.candidates.clone();
+        let compound_labels = self
+            .compounds
+            .iter()
+            .map(|(_, compound)| compound.label.clone())
+            .collect::<Vec<_>>();
 
         cx.spawn_in(window, async move |picker, cx| {
             let candidates: Vec<_> = candidates
                 .into_iter()
+                .map(|(_, _, candidate, _)| candidate.label)
+                .chain(compound_labels)
                 .enumerate()
-                .map(|(index, (_, _, candidate, _))| {
-                    StringMatchCandidate::new(index, candidate.label.as_ref())
-                })
+                .map(|(index, label)| StringMatchCandidate::new(index, label.as_ref()))
                 .collect();
 
             let matches = fuzzy::match_strings(
@@ -1408,10 +1489,24 @@ impl PickerDelegate for DebugDelegate {
         window: &mut Window,
         cx: &mut Context<picker::Picker<Self>>,
     ) {
-        let debug_scenario = self
+        let Some(candidate_id) = self
             .matches
             .get(self.selected_index())
-            .and_then(|match_candidate| self.candidates.get(match_candidate.candidate_id).cloned());
+            .map(|match_candidate| match_candidate.candidate_id)
+        else {
+            return;
+        };
+
+        if let Some((kind, compound)) = self.compound_for_candidate(candidate_id).cloned() {
+            if secondary {
+                return;
+            }
+            self.confirm_compound(kind, compound, window, cx);
+            cx.emit(DismissEvent);
+            return;
+        }
+
+        let debug_scenario = self.candidates.get(candidate_id).cloned();
 
         let Some((kind, _, debug_scenario, context)) = debug_scenario else {
             return;
@@ -1537,7 +1632,6 @@ impl PickerDelegate for DebugDelegate {
         cx: &mut Context<picker::Picker<Self>>,
     ) -> Option<Self::ListItem> {
         let hit = &self.matches.get(ix)?;
-        let (task_kind, language_name, _scenario, context) = &self.candidates[hit.candidate_id];
 
         let highlighted_location = HighlightedMatch {
             text: hit.string.clone(),
@@ -1545,6 +1639,39 @@ impl PickerDelegate for DebugDelegate {
             color: Color::Default,
         };
 
+        if let Some((task_kind, compound)) = self.compound_for_candidate(hit.candidate_id) {
+            let source = self.get_task_subtitle(&Some(task_kind.clone()), &None, cx);
+            let subtitle = compound.compound.iter().join(", ");
+            let subtitle = match source {
+                Some(source) => format!("{subtitle} ({source})"),
+                None => subtitle,
+            };
+
+            return Some(
+                ListItem::new(format!("debug-scenario-selection-{ix}"))
+                    .inset(true)
+                    .start_slot(
+                        Icon::new(IconName::ListTree)
+                            .color(Color::Muted)
+                            .size(IconSize::Small),
+                    )
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(selected)
+                    .child(
+                        v_flex()
+                            .items_start()
+                            .child(highlighted_location.render(window, cx))
+                            .child(
+                                Label::new(subtitle)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    ),
+            );
+        }
+
+        let (task_kind, language_name, _scenario, context) = &self.candidates[hit.candidate_id];
+
         let subtitle = self.get_task_subtitle(task_kind, context, cx);
 
         let language_icon = language_name.as_ref().and_then(|lang| {
@@ -3,14 +3,16 @@ use collections::HashMap;
 use dap::{Capabilities, adapters::DebugAdapterName};
 use db::kvp::KEY_VALUE_STORE;
 use gpui::{Axis, Context, Entity, EntityId, Focusable, Subscription, WeakEntity, Window};
-use project::Project;
+use project::{Project, debugger::dap_store::PersistedAdapterOptions};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use ui::{App, SharedString};
 use util::ResultExt;
 use workspace::{Member, Pane, PaneAxis, Workspace};
 
 use crate::session::running::{
     self, DebugTerminal, RunningState, SubView, breakpoint_list::BreakpointList, console::Console,
+    disassembly_view::DisassemblyView, exception_info_view::ExceptionInfoView,
     loaded_source_list::LoadedSourceList, memory_view::MemoryView, module_list::ModuleList,
     stack_frame_list::StackFrameList, variable_list::VariableList,
 };
@@ -25,6 +27,8 @@ pub(crate) enum DebuggerPaneItem {
     LoadedSources,
     Terminal,
     MemoryView,
+    Disassembly,
+    ExceptionInfo,
 }
 
 impl DebuggerPaneItem {
@@ -38,6 +42,8 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources,
             DebuggerPaneItem::Terminal,
             DebuggerPaneItem::MemoryView,
+            DebuggerPaneItem::Disassembly,
+            DebuggerPaneItem::ExceptionInfo,
         ];
         VARIANTS
     }
@@ -48,6 +54,12 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::MemoryView => capabilities
                 .supports_read_memory_request
                 .unwrap_or_default(),
+            DebuggerPaneItem::Disassembly => capabilities
+                .supports_disassemble_request
+                .unwrap_or_default(),
+            DebuggerPaneItem::ExceptionInfo => capabilities
+                .supports_exception_info_request
+                .unwrap_or_default(),
             DebuggerPaneItem::LoadedSources => capabilities
                 .supports_loaded_sources_request
                 .unwrap_or_default(),
@@ -65,6 +77,8 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources => SharedString::new_static("Sources"),
             DebuggerPaneItem::Terminal => SharedString::new_static("Terminal"),
             DebuggerPaneItem::MemoryView => SharedString::new_static("Memory View"),
+            DebuggerPaneItem::Disassembly => SharedString::new_static("Disassembly"),
+            DebuggerPaneItem::ExceptionInfo => SharedString::new_static("Exception"),
         }
     }
     pub(crate) fn tab_tooltip(self) -> SharedString {
@@ -87,6 +101,12 @@ impl DebuggerPaneItem {
                 "Provides an interactive terminal session within the debugging environment."
             }
             DebuggerPaneItem::MemoryView => "Allows inspection of memory contents.",
+            DebuggerPaneItem::Disassembly => {
+                "Shows the machine instructions around the selected stack frame."
+            }
+            DebuggerPaneItem::ExceptionInfo => {
+                "Shows the type, message and stack trace of the exception the thread stopped on."
+            }
         };
         SharedString::new_static(tooltip)
     }
@@ -199,6 +219,32 @@ pub(crate) async fn get_serialized_layout(
         .and_then(|value| serde_json::from_str::<SerializedLayout>(&value).ok())
 }
 
+fn adapter_options_key(adapter_name: &str) -> String {
+    format!("debug_adapter_`{adapter_name}`_persistence")
+}
+
+/// Persists the exception and function breakpoints of an adapter, so that they're restored in
+/// the next session using it.
+pub(crate) async fn serialize_adapter_options(
+    adapter_name: DebugAdapterName,
+    options: Arc<PersistedAdapterOptions>,
+) -> anyhow::Result<()> {
+    let serialized_options = serde_json::to_string(&options)
+        .context("Serializing adapter options with serde_json as a string")?;
+    KEY_VALUE_STORE
+        .write_kvp(adapter_options_key(&adapter_name), serialized_options)
+        .await
+}
+
+pub(crate) fn get_serialized_adapter_options(
+    adapter_name: &DebugAdapterName,
+) -> anyhow::Result<Option<PersistedAdapterOptions>> {
+    let Some(value) = KEY_VALUE_STORE.read_kvp(&adapter_options_key(adapter_name))? else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_str(&value)?))
+}
+
 pub(crate) fn deserialize_pane_layout(
     serialized: SerializedPaneLayout,
     should_invert: bool,
@@ -212,6 +258,8 @@ pub(crate) fn deserialize_pane_layout(
     loaded_sources: &Entity<LoadedSourceList>,
     terminal: &Entity<DebugTerminal>,
     memory_view: &Entity<MemoryView>,
+    disassembly_view: &Entity<DisassemblyView>,
+    exception_info_view: &Entity<ExceptionInfoView>,
     subscriptions: &mut HashMap<EntityId, Subscription>,
     window: &mut Window,
     cx: &mut Context<RunningState>,
@@ -237,6 +285,8 @@ pub(crate) fn deserialize_pane_layout(
                     loaded_sources,
                     terminal,
                     memory_view,
+                    disassembly_view,
+                    exception_info_view,
                     subscriptions,
                     window,
                     cx,
@@ -310,6 +360,18 @@ pub(crate) fn deserialize_pane_layout(
                         DebuggerPaneItem::MemoryView,
                         cx,
                     )),
+                    DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                        disassembly_view.focus_handle(cx),
+                        disassembly_view.clone().into(),
+                        DebuggerPaneItem::Disassembly,
+                        cx,
+                    )),
+                    DebuggerPaneItem::ExceptionInfo => Box::new(SubView::new(
+                        exception_info_view.focus_handle(cx),
+                        exception_info_view.clone().into(),
+                        DebuggerPaneItem::ExceptionInfo,
+                        cx,
+                    )),
                 })
                 .collect();
 
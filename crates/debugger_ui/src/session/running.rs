@@ -1,5 +1,7 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
+pub(crate) mod exception_info_view;
 pub(crate) mod loaded_source_list;
 pub(crate) mod memory_view;
 pub(crate) mod module_list;
@@ -8,7 +10,7 @@ pub mod variable_list;
 use std::{
     any::Any,
     ops::ControlFlow,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, LazyLock},
     time::Duration,
 };
@@ -18,7 +20,10 @@ use crate::{
     attach_modal::{AttachModal, ModalIntent},
     new_process_modal::resolve_path,
     persistence::{self, DebuggerPaneItem, SerializedLayout},
-    session::running::memory_view::MemoryView,
+    session::running::{
+        disassembly_view::DisassemblyView, exception_info_view::ExceptionInfoView,
+        memory_view::MemoryView,
+    },
 };
 
 use anyhow::{Context as _, Result, anyhow, bail};
@@ -26,7 +31,7 @@ use breakpoint_list::BreakpointList;
 use collections::{HashMap, IndexMap};
 use console::Console;
 use dap::{
-    Capabilities, DapRegistry, RunInTerminalRequestArguments, Thread,
+    Capabilities, DapRegistry, RunInTerminalRequestArguments, SteppingGranularity, Thread,
     adapters::{DebugAdapterName, DebugTaskDefinition},
     client::SessionId,
     debugger_settings::DebuggerSettings,
@@ -89,6 +94,8 @@ pub struct RunningState {
     pub(crate) scenario: Option<DebugScenario>,
     pub(crate) scenario_context: Option<DebugScenarioContext>,
     memory_view: Entity<MemoryView>,
+    disassembly_view: Entity<DisassemblyView>,
+    exception_info_view: Entity<ExceptionInfoView>,
 }
 
 impl RunningState {
@@ -751,6 +758,10 @@ impl RunningState {
                 cx,
             )
         });
+        let disassembly_view =
+            cx.new(|cx| DisassemblyView::new(session.clone(), &stack_frame_list, cx));
+        let exception_info_view =
+            cx.new(|cx| ExceptionInfoView::new(session.clone(), weak_state.clone(), cx));
         let variable_list = cx.new(|cx| {
             VariableList::new(
                 session.clone(),
@@ -810,6 +821,13 @@ impl RunningState {
 
                         if let Some(thread_id) = thread_id {
                             this.select_thread(*thread_id, window, cx);
+
+                            if this.session.read(cx).stopped_on_exception(*thread_id)
+                                && DebuggerPaneItem::ExceptionInfo
+                                    .is_supported(this.session.read(cx).capabilities())
+                            {
+                                this.activate_item(DebuggerPaneItem::ExceptionInfo, window, cx);
+                            }
                         }
                         if let Some(panel) = panel {
                             let id = this.session_id;
@@ -835,6 +853,15 @@ impl RunningState {
                         {
                             this.remove_pane_item(DebuggerPaneItem::LoadedSources, window, cx);
                         }
+                        if !capabilities.supports_disassemble_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                        }
+                        if !capabilities
+                            .supports_exception_info_request
+                            .unwrap_or(false)
+                        {
+                            this.remove_pane_item(DebuggerPaneItem::ExceptionInfo, window, cx);
+                        }
                     }
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
@@ -873,6 +900,8 @@ impl RunningState {
                 &loaded_source_list,
                 &debug_terminal,
                 &memory_view,
+                &disassembly_view,
+                &exception_info_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -902,6 +931,8 @@ impl RunningState {
 
         Self {
             memory_view,
+            disassembly_view,
+            exception_info_view,
             session,
             workspace,
             focus_handle,
@@ -1268,6 +1299,7 @@ impl RunningState {
             show_summary: false,
             show_command: false,
             show_rerun: false,
+            problem_matchers: Vec::new(),
         };
 
         let workspace = self.workspace.clone();
@@ -1348,6 +1380,18 @@ impl RunningState {
                 item_kind,
                 cx,
             )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                cx,
+            )),
+            DebuggerPaneItem::ExceptionInfo => Box::new(SubView::new(
+                self.exception_info_view.focus_handle(cx),
+                self.exception_info_view.clone().into(),
+                item_kind,
+                cx,
+            )),
         }
     }
 
@@ -1532,6 +1576,11 @@ impl RunningState {
         &self.module_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
     pub(crate) fn activate_item(
         &mut self,
         item: DebuggerPaneItem,
@@ -1650,6 +1699,63 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn step_in_target(&mut self, target_id: u64, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+        if !self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_step_in_targets_request
+            .unwrap_or_default()
+        {
+            return;
+        }
+
+        let granularity = DebuggerSettings::get_global(cx).stepping_granularity;
+
+        self.session().update(cx, |state, cx| {
+            state.step_in_target(thread_id, target_id, granularity, cx);
+        });
+    }
+
+    /// Moves the next statement of the selected thread to the given row, using the first
+    /// target the adapter reports for it.
+    pub(crate) fn jump_to_position(&mut self, path: Arc<Path>, row: u32, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+        if !self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_goto_targets_request
+            .unwrap_or_default()
+        {
+            return;
+        }
+
+        let targets = self
+            .session()
+            .update(cx, |session, cx| session.goto_targets(path, row, cx));
+        let session = self.session().downgrade();
+        cx.spawn(async move |_, cx| {
+            let Some(target) = targets.await.and_then(|targets| targets.into_iter().next()) else {
+                log::warn!(
+                    "Debug adapter reported no goto targets for line {}",
+                    row + 1
+                );
+                return;
+            };
+
+            session
+                .update(cx, |session, cx| session.goto(thread_id, target.id, cx))
+                .ok();
+        })
+        .detach();
+    }
+
     pub(crate) fn step_out(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
@@ -1662,6 +1768,26 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn step_over_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_over(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    pub(crate) fn step_into_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_in(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
     pub(crate) fn step_back(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
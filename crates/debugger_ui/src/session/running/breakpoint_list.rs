@@ -6,7 +6,6 @@ use std::{
 };
 
 use dap::{Capabilities, ExceptionBreakpointsFilter, adapters::DebugAdapterName};
-use db::kvp::KEY_VALUE_STORE;
 use editor::Editor;
 use gpui::{
     Action, AppContext, ClickEvent, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy,
@@ -18,8 +17,8 @@ use project::{
     Project,
     debugger::{
         breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
-        dap_store::{DapStore, PersistedAdapterOptions},
-        session::Session,
+        dap_store::DapStore,
+        session::{FunctionBreakpointState, Session},
     },
     worktree_store::WorktreeStore,
 };
@@ -31,13 +30,17 @@ use util::rel_path::RelPath;
 use workspace::Workspace;
 use zed_actions::{ToggleEnableBreakpoint, UnsetBreakpoint};
 
+use crate::persistence;
+
 actions!(
     debugger,
     [
         /// Navigates to the previous breakpoint property in the list.
         PreviousBreakpointProperty,
         /// Navigates to the next breakpoint property in the list.
-        NextBreakpointProperty
+        NextBreakpointProperty,
+        /// Adds a breakpoint on a function, given by its name.
+        AddFunctionBreakpoint
     ]
 );
 #[derive(Clone, Copy, PartialEq)]
@@ -45,6 +48,7 @@ pub(crate) enum SelectedBreakpointKind {
     Source,
     Exception,
     Data,
+    Function,
 }
 pub(crate) struct BreakpointList {
     workspace: WeakEntity<Workspace>,
@@ -59,7 +63,8 @@ pub(crate) struct BreakpointList {
     max_width_index: Option<usize>,
     input: Entity<Editor>,
     strip_mode: Option<ActiveBreakpointStripMode>,
-    serialize_exception_breakpoints_task: Option<Task<anyhow::Result<()>>>,
+    serialize_adapter_options_task: Option<Task<anyhow::Result<()>>>,
+    supports_function_breakpoints: bool,
 }
 
 impl Focusable for BreakpointList {
@@ -73,6 +78,8 @@ enum ActiveBreakpointStripMode {
     Log,
     Condition,
     HitCondition,
+    /// Entering the name of a new function breakpoint.
+    FunctionName,
 }
 
 impl BreakpointList {
@@ -105,10 +112,11 @@ impl BreakpointList {
                 selected_ix: None,
                 input: cx.new(|cx| Editor::single_line(window, cx)),
                 strip_mode: None,
-                serialize_exception_breakpoints_task: None,
+                serialize_adapter_options_task: None,
+                supports_function_breakpoints: false,
             };
             if let Some(name) = adapter_name {
-                _ = this.deserialize_exception_breakpoints(name, cx);
+                _ = this.deserialize_adapter_options(name, cx);
             }
             this
         })
@@ -185,6 +193,9 @@ impl BreakpointList {
                 BreakpointEntryKind::DataBreakpoint(bp) => {
                     (SelectedBreakpointKind::Data, bp.0.is_enabled)
                 }
+                BreakpointEntryKind::FunctionBreakpoint(bp) => {
+                    (SelectedBreakpointKind::Function, bp.0.is_enabled)
+                }
             })
         })
     }
@@ -200,28 +211,44 @@ impl BreakpointList {
             ActiveBreakpointStripMode::Log => "Set Log Message",
             ActiveBreakpointStripMode::Condition => "Set Condition",
             ActiveBreakpointStripMode::HitCondition => "Set Hit Condition",
+            ActiveBreakpointStripMode::FunctionName => "Function Name",
         };
-        let mut is_exception_breakpoint = true;
-        let active_value = self.selected_ix.and_then(|ix| {
-            self.breakpoints.get(ix).and_then(|bp| {
-                if let BreakpointEntryKind::LineBreakpoint(bp) = &bp.kind {
-                    is_exception_breakpoint = false;
-                    match prop {
-                        ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
-                        ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
-                        ActiveBreakpointStripMode::HitCondition => {
-                            bp.breakpoint.hit_condition.clone()
+        let mut is_read_only = prop != ActiveBreakpointStripMode::FunctionName;
+        let active_value = self
+            .selected_ix
+            .filter(|_| prop != ActiveBreakpointStripMode::FunctionName)
+            .and_then(|ix| {
+                self.breakpoints.get(ix).and_then(|bp| match &bp.kind {
+                    BreakpointEntryKind::LineBreakpoint(bp) => {
+                        is_read_only = false;
+                        match prop {
+                            ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
+                            ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
+                            ActiveBreakpointStripMode::HitCondition => {
+                                bp.breakpoint.hit_condition.clone()
+                            }
+                            ActiveBreakpointStripMode::FunctionName => None,
                         }
                     }
-                } else {
-                    None
-                }
-            })
-        });
+                    BreakpointEntryKind::FunctionBreakpoint(bp) => {
+                        is_read_only = prop == ActiveBreakpointStripMode::Log;
+                        match prop {
+                            ActiveBreakpointStripMode::Condition => {
+                                bp.0.dap.condition.clone().map(Arc::from)
+                            }
+                            ActiveBreakpointStripMode::HitCondition => {
+                                bp.0.dap.hit_condition.clone().map(Arc::from)
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+            });
 
         self.input.update(cx, |this, cx| {
             this.set_placeholder_text(placeholder, window, cx);
-            this.set_read_only(is_exception_breakpoint);
+            this.set_read_only(is_read_only);
             this.set_text(active_value.as_deref().unwrap_or(""), window, cx);
         });
     }
@@ -319,6 +346,23 @@ impl BreakpointList {
         }
     }
     fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.strip_mode == Some(ActiveBreakpointStripMode::FunctionName) {
+            let handle = self.input.focus_handle(cx);
+            if handle.is_focused(window) {
+                let name = self.input.read(cx).text(cx);
+                let name = name.trim();
+                if !name.is_empty() {
+                    self.add_function_breakpoint(name.to_owned(), cx);
+                }
+                self.strip_mode.take();
+                self.focus_handle.focus(window, cx);
+            } else {
+                handle.focus(window, cx);
+            }
+            cx.notify();
+            return;
+        }
+
         let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
             return;
         };
@@ -329,6 +373,14 @@ impl BreakpointList {
                 // Go back to the main strip. Save the result as well.
                 let text = self.input.read(cx).text(cx);
 
+                if let BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) = &entry.kind {
+                    let name = function_breakpoint.0.dap.name.clone();
+                    let value = (!text.is_empty()).then_some(text);
+                    self.edit_function_breakpoint(&name, mode, value, cx);
+                    self.focus_handle.focus(window, cx);
+                    return;
+                }
+
                 match mode {
                     ActiveBreakpointStripMode::Log => {
                         if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
@@ -363,6 +415,7 @@ impl BreakpointList {
                             );
                         }
                     }
+                    ActiveBreakpointStripMode::FunctionName => {}
                 }
                 self.focus_handle.focus(window, cx);
             } else {
@@ -378,7 +431,8 @@ impl BreakpointList {
                 self.go_to_line_breakpoint(path, row, window, cx);
             }
             BreakpointEntryKind::DataBreakpoint(_)
-            | BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+            | BreakpointEntryKind::ExceptionBreakpoint(_)
+            | BreakpointEntryKind::FunctionBreakpoint(_) => {}
         }
     }
 
@@ -410,6 +464,10 @@ impl BreakpointList {
                 let id = data_breakpoint.0.dap.data_id.clone();
                 self.toggle_data_breakpoint(&id, cx);
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.0.dap.name.clone();
+                self.toggle_function_breakpoint(&name, cx);
+            }
         }
         cx.notify();
     }
@@ -424,10 +482,17 @@ impl BreakpointList {
             return;
         };
 
-        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &mut entry.kind {
-            let path = line_breakpoint.breakpoint.path.clone();
-            let row = line_breakpoint.breakpoint.row;
-            self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+        match &mut entry.kind {
+            BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
+                let path = line_breakpoint.breakpoint.path.clone();
+                let row = line_breakpoint.breakpoint.row;
+                self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                let name = function_breakpoint.0.dap.name.clone();
+                self.remove_function_breakpoint(&name, cx);
+            }
+            _ => {}
         }
         cx.notify();
     }
@@ -439,7 +504,7 @@ impl BreakpointList {
         cx: &mut Context<Self>,
     ) {
         let next_mode = match self.strip_mode {
-            Some(ActiveBreakpointStripMode::Log) => None,
+            Some(ActiveBreakpointStripMode::Log | ActiveBreakpointStripMode::FunctionName) => None,
             Some(ActiveBreakpointStripMode::Condition) => Some(ActiveBreakpointStripMode::Log),
             Some(ActiveBreakpointStripMode::HitCondition) => {
                 Some(ActiveBreakpointStripMode::Condition)
@@ -465,7 +530,9 @@ impl BreakpointList {
             Some(ActiveBreakpointStripMode::Condition) => {
                 Some(ActiveBreakpointStripMode::HitCondition)
             }
-            Some(ActiveBreakpointStripMode::HitCondition) => None,
+            Some(
+                ActiveBreakpointStripMode::HitCondition | ActiveBreakpointStripMode::FunctionName,
+            ) => None,
             None => Some(ActiveBreakpointStripMode::Log),
         };
         if let Some(mode) = next_mode {
@@ -490,54 +557,115 @@ impl BreakpointList {
                 this.toggle_exception_breakpoint(id, cx);
             });
             cx.notify();
-            const EXCEPTION_SERIALIZATION_INTERVAL: Duration = Duration::from_secs(1);
-            self.serialize_exception_breakpoints_task = Some(cx.spawn(async move |this, cx| {
-                cx.background_executor()
-                    .timer(EXCEPTION_SERIALIZATION_INTERVAL)
-                    .await;
-                this.update(cx, |this, cx| this.serialize_exception_breakpoints(cx))?
-                    .await?;
-                Ok(())
-            }));
+            self.schedule_adapter_options_serialization(cx);
+        }
+    }
+
+    fn add_function_breakpoint(&mut self, name: String, cx: &mut Context<Self>) {
+        if let Some(session) = &self.session {
+            session.update(cx, |this, cx| {
+                this.add_function_breakpoint(name, cx);
+            });
+            cx.notify();
+            self.schedule_adapter_options_serialization(cx);
         }
     }
 
-    fn kvp_key(adapter_name: &str) -> String {
-        format!("debug_adapter_`{adapter_name}`_persistence")
+    fn toggle_function_breakpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        if let Some(session) = &self.session {
+            session.update(cx, |this, cx| {
+                this.toggle_function_breakpoint(name, cx);
+            });
+            cx.notify();
+            self.schedule_adapter_options_serialization(cx);
+        }
+    }
+
+    fn remove_function_breakpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        if let Some(session) = &self.session {
+            session.update(cx, |this, cx| {
+                this.remove_function_breakpoint(name, cx);
+            });
+            cx.notify();
+            self.schedule_adapter_options_serialization(cx);
+        }
     }
-    fn serialize_exception_breakpoints(
+
+    fn edit_function_breakpoint(
         &mut self,
+        name: &str,
+        mode: ActiveBreakpointStripMode,
+        value: Option<String>,
         cx: &mut Context<Self>,
-    ) -> Task<anyhow::Result<()>> {
-        if let Some(session) = self.session.as_ref() {
-            let key = {
-                let session = session.read(cx);
-                let name = session.adapter().0;
-                Self::kvp_key(&name)
-            };
-            let settings = self.dap_store.update(cx, |this, cx| {
-                this.sync_adapter_options(session, cx);
-            });
-            let value = serde_json::to_string(&settings);
+    ) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        session.update(cx, |session, cx| match mode {
+            ActiveBreakpointStripMode::Condition => {
+                session.set_function_breakpoint_condition(name, value, cx)
+            }
+            ActiveBreakpointStripMode::HitCondition => {
+                session.set_function_breakpoint_hit_condition(name, value, cx)
+            }
+            ActiveBreakpointStripMode::Log | ActiveBreakpointStripMode::FunctionName => {}
+        });
+        self.schedule_adapter_options_serialization(cx);
+    }
+
+    fn start_adding_function_breakpoint(
+        &mut self,
+        _: &AddFunctionBreakpoint,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.supports_function_breakpoints {
+            return;
+        }
+        self.set_active_breakpoint_property(ActiveBreakpointStripMode::FunctionName, window, cx);
+        self.input.focus_handle(cx).focus(window, cx);
+        cx.notify();
+    }
 
+    fn schedule_adapter_options_serialization(&mut self, cx: &mut Context<Self>) {
+        const ADAPTER_OPTIONS_SERIALIZATION_INTERVAL: Duration = Duration::from_secs(1);
+        self.serialize_adapter_options_task = Some(cx.spawn(async move |this, cx| {
             cx.background_executor()
-                .spawn(async move { KEY_VALUE_STORE.write_kvp(key, value?).await })
+                .timer(ADAPTER_OPTIONS_SERIALIZATION_INTERVAL)
+                .await;
+            this.update(cx, |this, cx| this.serialize_adapter_options(cx))?
+                .await?;
+            Ok(())
+        }));
+    }
+
+    fn serialize_adapter_options(&mut self, cx: &mut Context<Self>) -> Task<anyhow::Result<()>> {
+        if let Some(session) = self.session.as_ref() {
+            let adapter_name = session.read(cx).adapter();
+            let options = self
+                .dap_store
+                .update(cx, |this, cx| this.sync_adapter_options(session, cx));
+
+            cx.background_spawn(persistence::serialize_adapter_options(
+                adapter_name,
+                options,
+            ))
         } else {
             Task::ready(Result::Ok(()))
         }
     }
 
-    fn deserialize_exception_breakpoints(
+    fn deserialize_adapter_options(
         &self,
         adapter_name: DebugAdapterName,
         cx: &mut Context<Self>,
     ) -> anyhow::Result<()> {
-        let Some(val) = KEY_VALUE_STORE.read_kvp(&Self::kvp_key(&adapter_name))? else {
+        let Some(options) = persistence::get_serialized_adapter_options(&adapter_name)? else {
             return Ok(());
         };
-        let value: PersistedAdapterOptions = serde_json::from_str(&val)?;
-        self.dap_store
-            .update(cx, |this, _| this.set_adapter_options(adapter_name, value));
+        self.dap_store.update(cx, |this, _| {
+            this.set_adapter_options(adapter_name, options)
+        });
 
         Ok(())
     }
@@ -589,6 +717,7 @@ impl BreakpointList {
                 "Exception Breakpoints cannot be removed from the breakpoint list"
             }
             SelectedBreakpointKind::Data => "Remove data breakpoint from a breakpoint list",
+            SelectedBreakpointKind::Function => "Remove function breakpoint from a breakpoint list",
         });
 
         let toggle_label = selection_kind.map(|(_, is_enabled)| {
@@ -649,16 +778,40 @@ impl BreakpointList {
                             }
                         })
                     })
-                    .disabled(
-                        selection_kind.map(|kind| kind.0) != Some(SelectedBreakpointKind::Source),
-                    )
+                    .disabled(!matches!(
+                        selection_kind.map(|kind| kind.0),
+                        Some(SelectedBreakpointKind::Source | SelectedBreakpointKind::Function)
+                    ))
                     .on_click({
+                        let focus_handle = focus_handle.clone();
                         move |_, window, cx| {
                             focus_handle.focus(window, cx);
                             window.dispatch_action(UnsetBreakpoint.boxed_clone(), cx)
                         }
                     }),
             )
+            .when(self.supports_function_breakpoints, |this| {
+                this.child(
+                    IconButton::new("add-function-breakpoint-breakpoint-list", IconName::Plus)
+                        .icon_size(IconSize::Small)
+                        .tooltip({
+                            let focus_handle = focus_handle.clone();
+                            move |_window, cx| {
+                                Tooltip::with_meta_in(
+                                    "Add Function Breakpoint",
+                                    Some(&AddFunctionBreakpoint),
+                                    "Break whenever a function with the given name is called",
+                                    &focus_handle,
+                                    cx,
+                                )
+                            }
+                        })
+                        .on_click(move |_, window, cx| {
+                            focus_handle.focus(window, cx);
+                            window.dispatch_action(AddFunctionBreakpoint.boxed_clone(), cx)
+                        }),
+                )
+            })
             .into_any_element()
     }
 }
@@ -722,6 +875,24 @@ impl Render for BreakpointList {
                     weak: weak.clone(),
                 })
         });
+        self.supports_function_breakpoints = self.session.as_ref().is_some_and(|session| {
+            session
+                .read(cx)
+                .capabilities()
+                .supports_function_breakpoints
+                .unwrap_or_default()
+        });
+        let function_breakpoints = self.session.as_ref().into_iter().flat_map(|session| {
+            session
+                .read(cx)
+                .function_breakpoints()
+                .map(|state| BreakpointEntry {
+                    kind: BreakpointEntryKind::FunctionBreakpoint(FunctionBreakpoint(
+                        state.clone(),
+                    )),
+                    weak: weak.clone(),
+                })
+        });
         let data_breakpoints = self.session.as_ref().into_iter().flat_map(|session| {
             session
                 .read(cx)
@@ -733,6 +904,7 @@ impl Render for BreakpointList {
         });
         self.breakpoints.extend(
             breakpoints
+                .chain(function_breakpoints)
                 .chain(data_breakpoints)
                 .chain(exception_breakpoints),
         );
@@ -754,6 +926,9 @@ impl Render for BreakpointList {
                 BreakpointEntryKind::DataBreakpoint(data_bp) => {
                     data_bp.0.context.human_readable_label().len() as f32 * text_pixels
                 }
+                BreakpointEntryKind::FunctionBreakpoint(function_bp) => {
+                    function_bp.0.dap.name.len() as f32 * text_pixels
+                }
             })
             .position_max_by(|left, right| left.total_cmp(right));
 
@@ -771,6 +946,7 @@ impl Render for BreakpointList {
             .on_action(cx.listener(Self::unset_breakpoint))
             .on_action(cx.listener(Self::next_breakpoint_property))
             .on_action(cx.listener(Self::previous_breakpoint_property))
+            .on_action(cx.listener(Self::start_adding_function_breakpoint))
             .size_full()
             .pt_1()
             .child(self.render_list(cx))
@@ -972,6 +1148,112 @@ struct ExceptionBreakpoint {
 #[derive(Clone, Debug)]
 struct DataBreakpoint(project::debugger::session::DataBreakpointState);
 
+#[derive(Clone, Debug)]
+struct FunctionBreakpoint(FunctionBreakpointState);
+
+impl FunctionBreakpoint {
+    fn render(
+        &self,
+        props: SupportedBreakpointProperties,
+        strip_mode: Option<ActiveBreakpointStripMode>,
+        ix: usize,
+        is_selected: bool,
+        focus_handle: FocusHandle,
+        list: WeakEntity<BreakpointList>,
+    ) -> ListItem {
+        let icon_name = if self.0.is_enabled {
+            IconName::DebugBreakpoint
+        } else {
+            IconName::DebugDisabledBreakpoint
+        };
+        let is_enabled = self.0.is_enabled;
+        let name = self.0.dap.name.clone();
+
+        ListItem::new(SharedString::from(format!(
+            "function-breakpoint-ui-item-{}",
+            self.0.dap.name
+        )))
+        .toggle_state(is_selected)
+        .inset(true)
+        .on_click({
+            let list = list.clone();
+            move |_, window, cx| {
+                list.update(cx, |list, cx| list.select_ix(Some(ix), window, cx))
+                    .ok();
+            }
+        })
+        .on_secondary_mouse_down(|_, _, cx| {
+            cx.stop_propagation();
+        })
+        .start_slot(
+            div()
+                .id(SharedString::from(format!(
+                    "function-breakpoint-ui-item-{}-click-handler",
+                    self.0.dap.name
+                )))
+                .child(
+                    Icon::new(icon_name)
+                        .color(Color::Debugger)
+                        .size(IconSize::XSmall),
+                )
+                .tooltip({
+                    let focus_handle = focus_handle.clone();
+                    move |_window, cx| {
+                        Tooltip::for_action_in(
+                            if is_enabled {
+                                "Disable Function Breakpoint"
+                            } else {
+                                "Enable Function Breakpoint"
+                            },
+                            &ToggleEnableBreakpoint,
+                            &focus_handle,
+                            cx,
+                        )
+                    }
+                })
+                .on_click({
+                    let list = list.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            this.toggle_function_breakpoint(&name, cx);
+                        })
+                        .ok();
+                    }
+                }),
+        )
+        .child(
+            h_flex()
+                .w_full()
+                .gap_1()
+                .min_h(rems_from_px(26.))
+                .justify_between()
+                .child(
+                    v_flex()
+                        .py_1()
+                        .gap_1()
+                        .justify_center()
+                        .id(("function-breakpoint-label", ix))
+                        .child(
+                            Label::new(self.0.dap.name.clone())
+                                .size(LabelSize::Small)
+                                .line_height_style(ui::LineHeightStyle::UiLabel),
+                        ),
+                )
+                .child(BreakpointOptionsStrip {
+                    props,
+                    breakpoint: BreakpointEntry {
+                        kind: BreakpointEntryKind::FunctionBreakpoint(self.clone()),
+                        weak: list,
+                    },
+                    is_selected,
+                    focus_handle,
+                    strip_mode,
+                    index: ix,
+                }),
+        )
+    }
+}
+
 impl DataBreakpoint {
     fn render(
         &self,
@@ -1175,6 +1457,7 @@ enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
     ExceptionBreakpoint(ExceptionBreakpoint),
     DataBreakpoint(DataBreakpoint),
+    FunctionBreakpoint(FunctionBreakpoint),
 }
 
 #[derive(Clone, Debug)]
@@ -1218,6 +1501,15 @@ impl BreakpointEntry {
                 focus_handle,
                 self.weak.clone(),
             ),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => function_breakpoint
+                .render(
+                    props.for_function_breakpoints(),
+                    strip_mode,
+                    ix,
+                    is_selected,
+                    focus_handle,
+                    self.weak.clone(),
+                ),
         }
     }
 
@@ -1238,6 +1530,11 @@ impl BreakpointEntry {
                 data_breakpoint.0.dap.data_id
             )
             .into(),
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => format!(
+                "function-breakpoint-control-strip--{}",
+                function_breakpoint.0.dap.name
+            )
+            .into(),
         }
     }
 
@@ -1255,6 +1552,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.0.dap.condition.is_some()
+            }
             // We don't support conditions on exception/data breakpoints
             _ => false,
         }
@@ -1265,6 +1565,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.hit_condition.is_some()
             }
+            BreakpointEntryKind::FunctionBreakpoint(function_breakpoint) => {
+                function_breakpoint.0.dap.hit_condition.is_some()
+            }
             _ => false,
         }
     }
@@ -1313,6 +1616,10 @@ impl SupportedBreakpointProperties {
         // TODO: we don't yet support conditions for data breakpoints at the data layer, hence all props are disabled here.
         Self::empty()
     }
+    fn for_function_breakpoints(self) -> Self {
+        // Function breakpoints can't be turned into logpoints.
+        self & (Self::CONDITION | Self::HIT_CONDITION)
+    }
 }
 #[derive(IntoElement)]
 struct BreakpointOptionsStrip {
@@ -0,0 +1,312 @@
+use std::{ops::Range, sync::Arc};
+
+use collections::HashSet;
+use dap::DisassembledInstruction;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ListHorizontalSizingBehavior, ScrollStrategy,
+    Subscription, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent};
+use ui::{Tooltip, WithScrollbar, prelude::*};
+
+use crate::session::running::stack_frame_list::{StackFrameList, StackFrameListEvent};
+
+/// How many instructions are disassembled on either side of the instruction pointer at first, and
+/// how many more are added each time earlier or later ones are requested.
+const INSTRUCTIONS_PAGE: u64 = 64;
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: WeakEntity<StackFrameList>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    /// Memory reference of the instruction the selected stack frame is executing.
+    instruction_pointer: Option<String>,
+    instructions: Arc<[DisassembledInstruction]>,
+    instructions_before_pointer: u64,
+    instructions_after_pointer: u64,
+    /// The address to scroll to once it was disassembled.
+    scroll_to: Option<(u64, ScrollStrategy)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: &Entity<StackFrameList>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_)
+                | StackFrameListEvent::BuiltEntries => this.update_instruction_pointer(cx),
+            }),
+            cx.subscribe(&session, |_, _, event, cx| match event {
+                SessionEvent::Stopped(_)
+                | SessionEvent::HistoricSnapshotSelected
+                | SessionEvent::Disassembly => cx.notify(),
+                _ => {}
+            }),
+        ];
+
+        let mut this = Self {
+            session,
+            stack_frame_list: stack_frame_list.downgrade(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            instruction_pointer: None,
+            instructions: Arc::default(),
+            instructions_before_pointer: INSTRUCTIONS_PAGE,
+            instructions_after_pointer: INSTRUCTIONS_PAGE,
+            scroll_to: None,
+            _subscriptions,
+        };
+        this.update_instruction_pointer(cx);
+        this
+    }
+
+    fn update_instruction_pointer(&mut self, cx: &mut Context<Self>) {
+        let Ok(instruction_pointer) = self.stack_frame_list.update(cx, |list, cx| {
+            let stack_frame_id = list.opened_stack_frame_id()?;
+            list.dap_stack_frames(cx)
+                .into_iter()
+                .find(|stack_frame| stack_frame.id == stack_frame_id)?
+                .instruction_pointer_reference
+        }) else {
+            return;
+        };
+
+        if instruction_pointer != self.instruction_pointer {
+            self.scroll_to = instruction_pointer
+                .as_deref()
+                .and_then(parse_address)
+                .map(|address| (address, ScrollStrategy::Center));
+            self.instruction_pointer = instruction_pointer;
+            self.instructions_before_pointer = INSTRUCTIONS_PAGE;
+            self.instructions_after_pointer = INSTRUCTIONS_PAGE;
+            cx.notify();
+        }
+    }
+
+    /// Disassembles another page of instructions before the first one shown, keeping the
+    /// instructions currently shown in place.
+    pub(crate) fn show_earlier_instructions(&mut self, cx: &mut Context<Self>) {
+        self.scroll_to = self
+            .instructions
+            .first()
+            .and_then(|instruction| parse_address(&instruction.address))
+            .map(|address| (address, ScrollStrategy::Top));
+        self.instructions_before_pointer += INSTRUCTIONS_PAGE;
+        cx.notify();
+    }
+
+    /// Disassembles another page of instructions after the last one shown.
+    pub(crate) fn show_later_instructions(&mut self, cx: &mut Context<Self>) {
+        self.instructions_after_pointer += INSTRUCTIONS_PAGE;
+        cx.notify();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn instructions(&self) -> &[DisassembledInstruction] {
+        &self.instructions
+    }
+
+    fn toggle_breakpoint(&mut self, address: String, cx: &mut Context<Self>) {
+        self.session.update(cx, |session, cx| {
+            session.toggle_instruction_breakpoint(address, cx);
+        });
+        cx.notify();
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        instruction_pointer: Option<u64>,
+        breakpoints: &HashSet<u64>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let instruction = &self.instructions[ix];
+        let address = parse_address(&instruction.address);
+        let is_current = address.is_some() && address == instruction_pointer;
+        let has_breakpoint = address.is_some_and(|address| breakpoints.contains(&address));
+        let supports_breakpoints = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_instruction_breakpoints
+            .unwrap_or_default();
+
+        h_flex()
+            .id(("disassembly-entry", ix))
+            .w_full()
+            .gap_2()
+            .px_1()
+            .when(is_current, |this| {
+                this.bg(cx.theme().colors().editor_debugger_active_line_background)
+            })
+            .child(
+                div()
+                    .id(("disassembly-breakpoint", ix))
+                    .flex_none()
+                    .w_4()
+                    .child(
+                        Icon::new(IconName::DebugBreakpoint)
+                            .size(IconSize::XSmall)
+                            .color(if has_breakpoint {
+                                Color::Debugger
+                            } else {
+                                Color::Hidden
+                            }),
+                    )
+                    .when(supports_breakpoints, |this| {
+                        let address = instruction.address.clone();
+                        this.cursor_pointer()
+                            .hover(|style| style.opacity(0.7))
+                            .tooltip(Tooltip::text("Toggle Instruction Breakpoint"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_breakpoint(address.clone(), cx);
+                            }))
+                    }),
+            )
+            .child(
+                Label::new(instruction.address.clone())
+                    .buffer_font(cx)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                this.child(
+                    Label::new(bytes)
+                        .buffer_font(cx)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .child(
+                Label::new(instruction.instruction.clone())
+                    .buffer_font(cx)
+                    .size(LabelSize::Small),
+            )
+            .when_some(instruction.symbol.clone(), |this, symbol| {
+                this.child(
+                    Label::new(format!("<{symbol}>"))
+                        .buffer_font(cx)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .into_any()
+    }
+
+    fn render_list(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        let instruction_pointer = self.instruction_pointer.as_deref().and_then(parse_address);
+        let breakpoints = self
+            .session
+            .read(cx)
+            .instruction_breakpoints()
+            .filter_map(|breakpoint| parse_address(&breakpoint.dap.instruction_reference))
+            .collect::<HashSet<_>>();
+
+        uniform_list(
+            "disassembly-view",
+            self.instructions.len(),
+            cx.processor(move |this, range: Range<usize>, _window, cx| {
+                range
+                    .map(|ix| this.render_entry(ix, instruction_pointer, &breakpoints, cx))
+                    .collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+        .size_full()
+    }
+}
+
+/// Adapters report addresses as hexadecimal with a `0x` prefix, but the casing and padding
+/// differs between them, so we compare them numerically.
+fn parse_address(address: &str) -> Option<u64> {
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse().ok(),
+    }
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(instruction_pointer) = self.instruction_pointer.clone()
+            && let Some(instructions) = self.session.update(cx, |session, cx| {
+                session.disassemble(
+                    instruction_pointer,
+                    -(self.instructions_before_pointer as i64),
+                    self.instructions_before_pointer + self.instructions_after_pointer,
+                    cx,
+                )
+            })
+            && !Arc::ptr_eq(&instructions, &self.instructions)
+        {
+            self.instructions = instructions;
+        }
+
+        if let Some((address, strategy)) = self.scroll_to
+            && let Some(ix) = self
+                .instructions
+                .iter()
+                .position(|instruction| parse_address(&instruction.address) == Some(address))
+        {
+            self.scroll_handle.scroll_to_item(ix, strategy);
+            self.scroll_to = None;
+        }
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .map(|this| {
+                if self.instructions.is_empty() {
+                    this.child(
+                        h_flex().size_full().justify_center().child(
+                            Label::new("No disassembly available for the selected stack frame")
+                                .color(Color::Muted),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        v_flex()
+                            .size_full()
+                            .child(
+                                Button::new(
+                                    "show-earlier-instructions",
+                                    "Show Earlier Instructions",
+                                )
+                                .full_width()
+                                .label_size(LabelSize::Small)
+                                .on_click(
+                                    cx.listener(|this, _, _, cx| {
+                                        this.show_earlier_instructions(cx)
+                                    }),
+                                ),
+                            )
+                            .child(div().flex_1().min_h_0().child(self.render_list(cx)))
+                            .child(
+                                Button::new("show-later-instructions", "Show Later Instructions")
+                                    .full_width()
+                                    .label_size(LabelSize::Small)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.show_later_instructions(cx)
+                                    })),
+                            ),
+                    )
+                }
+            })
+            .vertical_scrollbar_for(&self.scroll_handle, window, cx)
+    }
+}
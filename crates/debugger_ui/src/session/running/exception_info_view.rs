@@ -0,0 +1,159 @@
+use dap::{ExceptionBreakMode, ExceptionDetails, ExceptionInfoResponse};
+use gpui::{AnyElement, Entity, FocusHandle, Focusable, Subscription, WeakEntity};
+use project::debugger::session::{Session, SessionEvent};
+use ui::prelude::*;
+
+use super::RunningState;
+
+/// Shows why the selected thread stopped on an exception, as reported by the adapter's
+/// `exceptionInfo` response.
+pub(crate) struct ExceptionInfoView {
+    session: Entity<Session>,
+    running_state: WeakEntity<RunningState>,
+    focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl ExceptionInfoView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        running_state: WeakEntity<RunningState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscription = cx.subscribe(&session, |_, _, event, cx| match event {
+            SessionEvent::Stopped(_)
+            | SessionEvent::Threads
+            | SessionEvent::HistoricSnapshotSelected
+            | SessionEvent::ExceptionInfo => cx.notify(),
+            _ => {}
+        });
+
+        Self {
+            session,
+            running_state,
+            focus_handle: cx.focus_handle(),
+            _subscription,
+        }
+    }
+
+    fn render_details(details: &ExceptionDetails, depth: usize, cx: &App) -> AnyElement {
+        let type_name = details
+            .full_type_name
+            .clone()
+            .or_else(|| details.type_name.clone());
+
+        v_flex()
+            .gap_1()
+            .when(depth > 0, |this| {
+                this.pl_3()
+                    .border_l_1()
+                    .border_color(cx.theme().colors().border_variant)
+            })
+            .when_some(type_name, |this, type_name| {
+                this.child(
+                    Label::new(type_name)
+                        .size(LabelSize::Small)
+                        .color(Color::Error),
+                )
+            })
+            .when_some(details.message.clone(), |this, message| {
+                this.child(Label::new(message).size(LabelSize::Small))
+            })
+            .when_some(details.evaluate_name.clone(), |this, evaluate_name| {
+                this.child(
+                    Label::new(format!("Evaluate as: {evaluate_name}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .when_some(details.stack_trace.clone(), |this, stack_trace| {
+                this.child(
+                    v_flex()
+                        .p_1()
+                        .rounded_sm()
+                        .bg(cx.theme().colors().editor_background)
+                        .children(stack_trace.lines().map(|line| {
+                            Label::new(line.to_owned())
+                                .buffer_font(cx)
+                                .size(LabelSize::XSmall)
+                                .color(Color::Muted)
+                        })),
+                )
+            })
+            .children(
+                details
+                    .inner_exception
+                    .iter()
+                    .flatten()
+                    .map(|inner| Self::render_details(inner, depth + 1, cx)),
+            )
+            .into_any()
+    }
+
+    fn render_exception(&self, info: &ExceptionInfoResponse, cx: &App) -> AnyElement {
+        let break_mode = match info.break_mode {
+            ExceptionBreakMode::Never => "Never breaks",
+            ExceptionBreakMode::Always => "Always breaks",
+            ExceptionBreakMode::Unhandled => "Unhandled",
+            ExceptionBreakMode::UserUnhandled => "Unhandled by user code",
+        };
+
+        v_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Icon::new(IconName::Flame).color(Color::Error))
+                    .child(Label::new(info.exception_id.clone()).weight(FontWeight::BOLD))
+                    .child(
+                        Label::new(break_mode)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .when_some(info.description.clone(), |this, description| {
+                this.child(Label::new(description))
+            })
+            .when_some(info.details.as_ref(), |this, details| {
+                this.child(Self::render_details(details, 0, cx))
+            })
+            .into_any()
+    }
+}
+
+impl Focusable for ExceptionInfoView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ExceptionInfoView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let thread_id = self
+            .running_state
+            .read_with(cx, |state, _| state.thread_id())
+            .ok()
+            .flatten();
+        let info = thread_id.and_then(|thread_id| {
+            self.session.update(cx, |session, cx| {
+                session.exception_info(thread_id, cx).cloned()
+            })
+        });
+
+        v_flex()
+            .id("exception-info-view")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_2()
+            .overflow_y_scroll()
+            .map(|this| match info {
+                Some(info) => this.child(self.render_exception(&info, cx)),
+                None => this.child(
+                    h_flex().size_full().justify_center().child(
+                        Label::new("The selected thread did not stop on an exception")
+                            .color(Color::Muted),
+                    ),
+                ),
+            })
+    }
+}
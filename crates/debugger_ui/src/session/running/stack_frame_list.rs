@@ -15,7 +15,7 @@ use util::{
     paths::{PathStyle, is_absolute},
 };
 
-use crate::{StackTraceView, ToggleUserFrames};
+use crate::{StackTraceView, ToggleUserFrames, persistence::DebuggerPaneItem};
 use language::PointUtf16;
 use project::debugger::breakpoint_store::ActiveStackFrame;
 use project::debugger::session::{Session, SessionEvent, StackFrame, ThreadStatus};
@@ -394,6 +394,20 @@ impl StackFrameList {
         let stack_frame_id = stack_frame.id;
         self.opened_stack_frame_id = Some(stack_frame_id);
         let Some(abs_path) = Self::abs_path_from_stack_frame(&stack_frame) else {
+            if stack_frame.instruction_pointer_reference.is_some()
+                && self.session.read(cx).quirks().disassembly_without_source
+            {
+                cx.emit(StackFrameListEvent::SelectedStackFrameChanged(
+                    stack_frame_id,
+                ));
+                let state = self.state.clone();
+                // Deferred, as we may be called while the running state is being updated.
+                return cx.spawn_in(window, async move |_, cx| {
+                    state.update_in(cx, |state, window, cx| {
+                        state.activate_item(DebuggerPaneItem::Disassembly, window, cx)
+                    })
+                });
+            }
             return Task::ready(Err(anyhow!("Project path not found")));
         };
         let row = stack_frame.line.saturating_sub(1) as u32;
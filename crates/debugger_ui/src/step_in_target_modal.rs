@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{DismissEvent, Entity, EventEmitter, Focusable, Render, Subscription, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use ui::{Context, ListItem, ListItemSpacing, prelude::*};
+use workspace::ModalView;
+
+use crate::session::running::RunningState;
+
+/// Lets the user pick which call on the current line to step into, using the adapter's
+/// `stepInTargets` response.
+pub(crate) struct StepInTargetModal {
+    picker: Entity<Picker<StepInTargetDelegate>>,
+    _subscription: Subscription,
+}
+
+impl StepInTargetModal {
+    pub(crate) fn new(
+        running_state: WeakEntity<RunningState>,
+        targets: Vec<dap::StepInTarget>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(
+                StepInTargetDelegate {
+                    running_state,
+                    targets: targets.into(),
+                    matches: Vec::new(),
+                    selected_index: 0,
+                },
+                window,
+                cx,
+            )
+            .modal(true)
+        });
+
+        Self {
+            _subscription: cx.subscribe(&picker, |_, _, _, cx| {
+                cx.emit(DismissEvent);
+            }),
+            picker,
+        }
+    }
+}
+
+impl Render for StepInTargetModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("StepInTargetModal")
+            .track_focus(&self.focus_handle(cx))
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for StepInTargetModal {}
+
+impl Focusable for StepInTargetModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for StepInTargetModal {}
+
+pub(crate) struct StepInTargetDelegate {
+    running_state: WeakEntity<RunningState>,
+    targets: Arc<[dap::StepInTarget]>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for StepInTargetDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select the call to step into".into()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let candidates = self
+            .targets
+            .iter()
+            .enumerate()
+            .map(|(ix, target)| StringMatchCandidate::new(ix, &target.label))
+            .collect::<Vec<_>>();
+
+        cx.spawn(async move |this, cx| {
+            let matches = fuzzy::match_strings(
+                &candidates,
+                &query,
+                true,
+                true,
+                100,
+                &Default::default(),
+                cx.background_executor().clone(),
+            )
+            .await;
+
+            this.update(cx, |this, _| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(target) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|hit| self.targets.get(hit.candidate_id))
+        else {
+            return cx.emit(DismissEvent);
+        };
+
+        let target_id = target.id;
+        self.running_state
+            .update(cx, |state, cx| state.step_in_target(target_id, cx))
+            .ok();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let target = self.targets.get(hit.candidate_id)?;
+
+        let location = target.line.map(|line| match target.column {
+            Some(column) => format!("{line}:{column}"),
+            None => line.to_string(),
+        });
+
+        Some(
+            ListItem::new(("step-in-target", ix))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .child(Label::new(target.label.clone()))
+                        .when_some(location, |this, location| {
+                            this.child(
+                                Label::new(location)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                ),
+        )
+    }
+}
@@ -21,6 +21,12 @@ mod dap_logger;
 #[cfg(test)]
 mod debugger_panel;
 #[cfg(test)]
+mod disassembly_view;
+#[cfg(test)]
+mod exception_info;
+#[cfg(test)]
+mod function_breakpoints;
+#[cfg(test)]
 mod inline_values;
 #[cfg(test)]
 mod module_list;
@@ -31,6 +37,8 @@ mod persistence;
 #[cfg(test)]
 mod stack_frame_list;
 #[cfg(test)]
+mod step_targets;
+#[cfg(test)]
 mod variable_list;
 
 pub fn init_test(cx: &mut gpui::TestAppContext) {
@@ -17,10 +17,10 @@ use editor::{
     ActiveDebugLine, Editor, EditorMode, MultiBuffer,
     actions::{self},
 };
-use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use gpui::{BackgroundExecutor, Entity, TestAppContext, VisualTestContext};
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::session::{Session, ThreadId, ThreadStatus},
 };
 use serde_json::json;
 use std::{
@@ -1950,3 +1950,97 @@ async fn test_adapter_shutdown_with_child_sessions_on_app_quit(
         "Child session should have received disconnect request"
     );
 }
+
+#[gpui::test]
+async fn test_compound_sessions(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let dap_store = project.update(cx, |project, _| project.dap_store());
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let _subscription = project::debugger::test::intercept_debug_sessions(cx, |_| {});
+
+    let debug_panel = workspace
+        .update(cx, |multi, _, cx| {
+            multi.workspace().read(cx).panel::<DebugPanel>(cx).unwrap()
+        })
+        .unwrap();
+    let start_compound = |stop_all: bool, cx: &mut VisualTestContext| {
+        let scenarios = ["Server", "Client"]
+            .into_iter()
+            .map(|label| {
+                DebugTaskDefinition {
+                    adapter: "fake-adapter".into(),
+                    label: label.into(),
+                    config: json!({ "request": "launch" }),
+                    tcp_connection: None,
+                }
+                .to_scenario()
+            })
+            .collect();
+        debug_panel.update_in(cx, |debug_panel, window, cx| {
+            debug_panel.start_compound(
+                task::DebugCompound {
+                    label: "Client and server".into(),
+                    compound: vec!["Server".into(), "Client".into()],
+                    stop_all,
+                },
+                scenarios,
+                task::SharedTaskContext::default(),
+                None,
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        let sessions = dap_store.read_with(cx, |dap_store, _| {
+            dap_store.sessions().cloned().collect::<Vec<_>>()
+        });
+        assert_eq!(sessions.len(), 2);
+        debug_panel.read_with(cx, |debug_panel, _| {
+            for session in &sessions {
+                assert_eq!(
+                    debug_panel.compound_label(session).as_deref(),
+                    Some("Client and server")
+                );
+            }
+        });
+        sessions
+    };
+    let shutdown = async |session: &Entity<Session>, cx: &mut VisualTestContext| {
+        dap_store
+            .update(cx, |dap_store, cx| {
+                dap_store.shutdown_session(session.read(cx).session_id(), cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+    };
+
+    let sessions = start_compound(true, cx);
+    shutdown(&sessions[0], cx).await;
+    assert_eq!(
+        dap_store.read_with(cx, |dap_store, _| dap_store.sessions().count()),
+        0,
+        "Stopping one session of the compound should stop the others"
+    );
+
+    let sessions = start_compound(false, cx);
+    shutdown(&sessions[0], cx).await;
+    let remaining_sessions = dap_store.read_with(cx, |dap_store, _| {
+        dap_store.sessions().cloned().collect::<Vec<_>>()
+    });
+    assert_eq!(
+        remaining_sessions,
+        vec![sessions[1].clone()],
+        "Without `stop_all`, the other sessions should keep running"
+    );
+    debug_panel.read_with(cx, |debug_panel, _| {
+        assert_eq!(
+            debug_panel.compound_label(&sessions[1]).as_deref(),
+            Some("Client and server")
+        );
+    });
+}
@@ -0,0 +1,184 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StackFrame, StoppedEvent,
+    requests::{Disassemble, Initialize, Scopes, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use parking_lot::Mutex;
+use project::{FakeFs, Project};
+use std::sync::Arc;
+use util::path;
+
+const INSTRUCTION_POINTER: u64 = 0x1000;
+
+#[gpui::test]
+async fn test_disassembly_view_pages_and_refreshes_on_stop(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 0,
+                column: 0,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some(format!("{INSTRUCTION_POINTER:#x}")),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let disassemble_requests = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<Disassemble, _>({
+        let disassemble_requests = disassemble_requests.clone();
+        move |_, args| {
+            assert_eq!(args.memory_reference, format!("{INSTRUCTION_POINTER:#x}"));
+            let instruction_offset = args.instruction_offset.unwrap_or_default();
+            disassemble_requests
+                .lock()
+                .push((instruction_offset, args.instruction_count));
+            let instructions = (0..args.instruction_count as i64)
+                .map(|ix| {
+                    let address = INSTRUCTION_POINTER as i64 + 4 * (instruction_offset + ix);
+                    dap::DisassembledInstruction {
+                        address: format!("{address:#x}"),
+                        instruction_bytes: None,
+                        instruction: "nop".into(),
+                        symbol: None,
+                        location: None,
+                        line: None,
+                        column: None,
+                        end_line: None,
+                        end_column: None,
+                        presentation_hint: None,
+                    }
+                })
+                .collect();
+            Ok(dap::DisassembleResponse { instructions })
+        }
+    });
+
+    let stop = async |cx: &mut VisualTestContext| {
+        client
+            .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+                reason: dap::StoppedEventReason::Pause,
+                description: None,
+                thread_id: Some(1),
+                preserve_focus_hint: None,
+                text: None,
+                all_threads_stopped: None,
+                hit_breakpoint_ids: None,
+            }))
+            .await;
+        cx.run_until_parked();
+    };
+    stop(cx).await;
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+    running_state.update_in(cx, |running_state, window, cx| {
+        running_state.select_current_thread(
+            &running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx)),
+            window,
+            cx,
+        );
+        running_state.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    let disassembly_view = running_state.read_with(cx, |running_state, _| {
+        running_state.disassembly_view().clone()
+    });
+    let first_address = |cx: &mut VisualTestContext| {
+        disassembly_view.read_with(cx, |view, _| {
+            view.instructions()
+                .first()
+                .map(|instruction| instruction.address.clone())
+        })
+    };
+    assert_eq!(*disassemble_requests.lock(), vec![(-64, 128)]);
+    assert_eq!(
+        disassembly_view.read_with(cx, |view, _| view.instructions().len()),
+        128
+    );
+    assert_eq!(first_address(cx).as_deref(), Some("0xf00"));
+
+    disassembly_view.update(cx, |view, cx| view.show_earlier_instructions(cx));
+    cx.run_until_parked();
+    disassembly_view.update(cx, |view, cx| view.show_later_instructions(cx));
+    cx.run_until_parked();
+    assert_eq!(
+        *disassemble_requests.lock(),
+        vec![(-64, 128), (-128, 192), (-128, 256)]
+    );
+    assert_eq!(
+        disassembly_view.read_with(cx, |view, _| view.instructions().len()),
+        256
+    );
+    assert_eq!(first_address(cx).as_deref(), Some("0xe00"));
+
+    // The code may have changed while the program ran, so it's disassembled again on the next
+    // stop.
+    client
+        .fake_event(dap::messages::Events::Continued(dap::ContinuedEvent {
+            thread_id: 1,
+            all_threads_continued: Some(true),
+        }))
+        .await;
+    cx.run_until_parked();
+    stop(cx).await;
+    assert_eq!(
+        disassemble_requests.lock().last().copied(),
+        Some((-128, 256))
+    );
+    assert_eq!(disassemble_requests.lock().len(), 4);
+}
@@ -0,0 +1,121 @@
+use crate::tests::{init_test, init_test_workspace, start_debug_session};
+use dap::{
+    ExceptionBreakMode, StoppedEvent, StoppedEventReason,
+    messages::Events,
+    requests::{ExceptionInfo, Initialize, Scopes, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger::session::ThreadId};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use util::path;
+
+#[gpui::test]
+async fn test_exception_info_is_requested_when_stopped_on_exception(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_exception_info_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let exception_info_requests = Arc::new(AtomicUsize::new(0));
+    client.on_request::<ExceptionInfo, _>({
+        let exception_info_requests = exception_info_requests.clone();
+        move |_, args| {
+            assert_eq!(args.thread_id, 1);
+            exception_info_requests.fetch_add(1, Ordering::SeqCst);
+            Ok(dap::ExceptionInfoResponse {
+                exception_id: "ValueError".into(),
+                description: Some("invalid literal for int()".into()),
+                break_mode: ExceptionBreakMode::Unhandled,
+                details: None,
+            })
+        }
+    });
+
+    let stop = async |reason, cx: &mut VisualTestContext| {
+        client
+            .fake_event(Events::Stopped(StoppedEvent {
+                reason,
+                description: None,
+                thread_id: Some(1),
+                preserve_focus_hint: None,
+                text: None,
+                all_threads_stopped: None,
+                hit_breakpoint_ids: None,
+            }))
+            .await;
+        cx.run_until_parked();
+    };
+    let exception_info = |cx: &mut VisualTestContext| {
+        let info = session.update(cx, |session, cx| {
+            session
+                .exception_info(ThreadId(1), cx)
+                .map(|info| (info.exception_id.clone(), info.description.clone()))
+        });
+        cx.run_until_parked();
+        info
+    };
+
+    stop(StoppedEventReason::Exception, cx).await;
+    session.read_with(cx, |session, _| {
+        assert!(session.stopped_on_exception(ThreadId(1)));
+    });
+    exception_info(cx);
+    assert_eq!(
+        exception_info(cx),
+        Some((
+            "ValueError".to_string(),
+            Some("invalid literal for int()".to_string())
+        ))
+    );
+    assert_eq!(
+        exception_info_requests.load(Ordering::SeqCst),
+        1,
+        "The exception info should be requested once per stop"
+    );
+
+    stop(StoppedEventReason::Pause, cx).await;
+    session.read_with(cx, |session, _| {
+        assert!(!session.stopped_on_exception(ThreadId(1)));
+    });
+    assert_eq!(exception_info(cx), None);
+    assert_eq!(
+        exception_info_requests.load(Ordering::SeqCst),
+        1,
+        "The exception info should not be requested for other stops"
+    );
+}
@@ -0,0 +1,136 @@
+use crate::{
+    persistence,
+    tests::{init_test, init_test_workspace, start_debug_session},
+};
+use dap::requests::{Initialize, SetFunctionBreakpoints};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use parking_lot::Mutex;
+use project::{FakeFs, Project};
+use std::sync::Arc;
+use util::path;
+
+fn function_breakpoint(name: &str, condition: Option<&str>) -> dap::FunctionBreakpoint {
+    dap::FunctionBreakpoint {
+        name: name.into(),
+        condition: condition.map(Into::into),
+        hit_condition: None,
+    }
+}
+
+#[gpui::test]
+async fn test_function_breakpoints_are_sent_and_persisted(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let sent_breakpoints = Arc::new(Mutex::new(Vec::<Vec<dap::FunctionBreakpoint>>::new()));
+    let configure = {
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |client: &Arc<dap::client::DebugAdapterClient>| {
+            client.on_request::<Initialize, _>(move |_, _| {
+                Ok(dap::Capabilities {
+                    supports_function_breakpoints: Some(true),
+                    supports_conditional_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+            client.on_request::<SetFunctionBreakpoints, _>({
+                let sent_breakpoints = sent_breakpoints.clone();
+                move |_, args| {
+                    sent_breakpoints.lock().push(args.breakpoints);
+                    Ok(dap::SetFunctionBreakpointsResponse {
+                        breakpoints: Vec::default(),
+                    })
+                }
+            });
+        }
+    };
+
+    let session = start_debug_session(&workspace, cx, configure.clone()).unwrap();
+    cx.run_until_parked();
+    assert!(
+        sent_breakpoints.lock().is_empty(),
+        "Nothing should be sent before a function breakpoint is added"
+    );
+
+    session.update(cx, |session, cx| {
+        session.add_function_breakpoint("main".into(), cx);
+        session.add_function_breakpoint("helper".into(), cx);
+        session.set_function_breakpoint_condition("helper", Some("x > 1".into()), cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        sent_breakpoints.lock().last(),
+        Some(&vec![
+            function_breakpoint("helper", Some("x > 1")),
+            function_breakpoint("main", None),
+        ])
+    );
+
+    session.update(cx, |session, cx| {
+        session.toggle_function_breakpoint("main", cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        sent_breakpoints.lock().last(),
+        Some(&vec![function_breakpoint("helper", Some("x > 1"))]),
+        "Disabled function breakpoints should not be sent"
+    );
+
+    let options = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.sync_adapter_options(&session, cx)
+        })
+    });
+    let adapter_name = session.read_with(cx, |session, _| session.adapter());
+    persistence::serialize_adapter_options(adapter_name.clone(), options)
+        .await
+        .unwrap();
+    let persisted = persistence::get_serialized_adapter_options(&adapter_name)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        persisted
+            .function_breakpoints
+            .iter()
+            .map(|breakpoint| (
+                breakpoint.name.as_str(),
+                breakpoint.condition.as_deref(),
+                breakpoint.enabled
+            ))
+            .collect::<Vec<_>>(),
+        vec![("helper", Some("x > 1"), true), ("main", None, false)]
+    );
+
+    // The next session with the same adapter restores the enabled function breakpoints while
+    // it's being configured.
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+    shutdown_session.await.unwrap();
+    sent_breakpoints.lock().clear();
+
+    let session = start_debug_session(&workspace, cx, configure).unwrap();
+    cx.run_until_parked();
+    assert_eq!(
+        *sent_breakpoints.lock(),
+        vec![vec![function_breakpoint("helper", Some("x > 1"))]]
+    );
+    session.read_with(cx, |session, _| {
+        assert_eq!(
+            session
+                .function_breakpoints()
+                .map(|state| (state.dap.name.as_str(), state.is_enabled))
+                .collect::<Vec<_>>(),
+            vec![("helper", true), ("main", false)]
+        );
+    });
+}
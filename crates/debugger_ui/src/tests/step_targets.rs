@@ -0,0 +1,263 @@
+use crate::{
+    step_in_target_modal::StepInTargetModal,
+    tests::{init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StackFrame, StoppedEvent,
+    client::DebugAdapterClient,
+    requests::{Goto, GotoTargets, Initialize, Scopes, StackTrace, StepIn, StepInTargets, Threads},
+};
+use editor::{
+    Editor, SelectionEffects,
+    actions::{JumpToCursor, StepIntoTarget},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext, WindowHandle};
+use language::Point;
+use parking_lot::Mutex;
+use project::{FakeFs, Project};
+use serde_json::json;
+use std::{path::PathBuf, sync::Arc};
+use util::path;
+use workspace::{MultiWorkspace, OpenOptions};
+
+/// Starts a session stopped in `main.rs`, which is opened and focused with the cursor on the
+/// third line.
+async fn start_stopped_session(
+    capabilities: dap::Capabilities,
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) -> (
+    WindowHandle<MultiWorkspace>,
+    Arc<DebugAdapterClient>,
+    VisualTestContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor);
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {\n    let x = 1;\n    foo(bar(x));\n}\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let mut cx = VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, &mut cx, move |client| {
+        let capabilities = capabilities.clone();
+        client.on_request::<Initialize, _>(move |_, _| Ok(capabilities.clone()));
+    })
+    .unwrap();
+    let client = session.update(&mut cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.rs".into()),
+                    path: Some(path!("/project/main.rs").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 2,
+                column: 5,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let editor = workspace
+        .update(&mut cx, |multi, window, cx| {
+            multi.workspace().update(cx, |workspace, cx| {
+                workspace.open_abs_path(
+                    PathBuf::from(path!("/project/main.rs")),
+                    OpenOptions::default(),
+                    window,
+                    cx,
+                )
+            })
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    editor.update_in(&mut cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
+            selections.select_ranges([Point::new(2, 4)..Point::new(2, 4)])
+        });
+        editor.focus_handle(cx).focus(window, cx);
+    });
+    cx.run_until_parked();
+
+    (workspace, client, cx)
+}
+
+#[gpui::test]
+async fn test_jump_to_cursor(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    let (_, client, mut cx) = start_stopped_session(
+        dap::Capabilities {
+            supports_goto_targets_request: Some(true),
+            ..Default::default()
+        },
+        executor,
+        cx,
+    )
+    .await;
+    let cx = &mut cx;
+
+    client.on_request::<GotoTargets, _>(move |_, args| {
+        assert_eq!(args.source.path.as_deref(), Some(path!("/project/main.rs")));
+        assert_eq!(args.line, 3);
+        Ok(dap::GotoTargetsResponse {
+            targets: vec![
+                dap::GotoTarget {
+                    id: 7,
+                    label: "foo(bar(x))".into(),
+                    line: 3,
+                    column: Some(5),
+                    end_line: None,
+                    end_column: None,
+                    instruction_pointer_reference: None,
+                },
+                dap::GotoTarget {
+                    id: 8,
+                    label: "}".into(),
+                    line: 4,
+                    column: None,
+                    end_line: None,
+                    end_column: None,
+                    instruction_pointer_reference: None,
+                },
+            ],
+        })
+    });
+    let gotos = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<Goto, _>({
+        let gotos = gotos.clone();
+        move |_, args| {
+            gotos.lock().push((args.thread_id, args.target_id));
+            Ok(())
+        }
+    });
+
+    assert!(cx.update(|window, cx| window.is_action_available(&JumpToCursor, cx)));
+    assert!(
+        !cx.update(|window, cx| window.is_action_available(&StepIntoTarget, cx)),
+        "Stepping into targets isn't supported by the adapter"
+    );
+
+    cx.dispatch_action(JumpToCursor);
+    cx.run_until_parked();
+    assert_eq!(
+        *gotos.lock(),
+        vec![(1, 7)],
+        "The thread should jump to the first target of the line"
+    );
+}
+
+#[gpui::test]
+async fn test_step_into_target(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    let (workspace, client, mut cx) = start_stopped_session(
+        dap::Capabilities {
+            supports_step_in_targets_request: Some(true),
+            ..Default::default()
+        },
+        executor,
+        cx,
+    )
+    .await;
+    let cx = &mut cx;
+
+    client.on_request::<StepInTargets, _>(move |_, args| {
+        assert_eq!(args.frame_id, 1);
+        Ok(dap::StepInTargetsResponse {
+            targets: ["foo", "bar"]
+                .into_iter()
+                .enumerate()
+                .map(|(id, label)| dap::StepInTarget {
+                    id: id as u64,
+                    label: label.into(),
+                    line: Some(3),
+                    column: None,
+                    end_line: None,
+                    end_column: None,
+                })
+                .collect(),
+        })
+    });
+    let step_ins = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<StepIn, _>({
+        let step_ins = step_ins.clone();
+        move |_, args| {
+            step_ins.lock().push((args.thread_id, args.target_id));
+            Ok(())
+        }
+    });
+
+    assert!(cx.update(|window, cx| window.is_action_available(&StepIntoTarget, cx)));
+    assert!(
+        !cx.update(|window, cx| window.is_action_available(&JumpToCursor, cx)),
+        "Goto targets aren't supported by the adapter"
+    );
+
+    cx.dispatch_action(StepIntoTarget);
+    cx.run_until_parked();
+    let has_modal = |cx: &mut VisualTestContext| {
+        workspace
+            .update(cx, |multi, _, cx| {
+                multi
+                    .workspace()
+                    .read(cx)
+                    .active_modal::<StepInTargetModal>(cx)
+                    .is_some()
+            })
+            .unwrap()
+    };
+    assert!(
+        has_modal(cx),
+        "The step in targets should be offered in a modal"
+    );
+
+    cx.simulate_input("bar");
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+    assert_eq!(*step_ins.lock(), vec![(1, Some(1))]);
+    assert!(!has_modal(cx));
+}
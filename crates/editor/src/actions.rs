@@ -375,6 +375,10 @@ actions!(
     [
         /// Runs program execution to the current cursor position.
         RunToCursor,
+        /// Moves the next statement to execute to the current cursor position, skipping the code in between.
+        JumpToCursor,
+        /// Picks one of the calls on the current line to step into.
+        StepIntoTarget,
         /// Evaluates the selected text in the debugger context.
         EvaluateSelectedText
     ]
@@ -562,6 +566,10 @@ actions!(
         GoToImplementation,
         /// Goes to implementation in a split pane.
         GoToImplementationSplit,
+        /// Goes to the next bookmark in the editor.
+        GoToNextBookmark,
+        /// Goes to the previous bookmark in the editor.
+        GoToPreviousBookmark,
         /// Goes to the next change in the file.
         GoToNextChange,
         /// Goes to the parent module of the current file.
@@ -803,6 +811,8 @@ actions!(
         Tab,
         /// Removes a tab character or outdents.
         Backtab,
+        /// Toggles a bookmark at the current line.
+        ToggleBookmark,
         /// Toggles a breakpoint at the current line.
         ToggleBreakpoint,
         /// Toggles the case of selected text.
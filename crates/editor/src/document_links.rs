@@ -0,0 +1,134 @@
+use std::ops::Range;
+
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{App, Context, Entity};
+use language::Buffer;
+use project::lsp_store::DocumentLink;
+use text::{BufferId, OffsetRangeExt as _};
+
+use crate::{Editor, LSP_REQUEST_DEBOUNCE_TIMEOUT};
+
+/// The document links of a buffer that overlap its visible excerpts, so that hovering doesn't
+/// query the language servers.
+#[derive(Debug)]
+pub(super) struct VisibleDocumentLinks {
+    version: clock::Global,
+    links: Vec<DocumentLink>,
+}
+
+impl Editor {
+    /// Fetches the document links of the visible buffers and keeps the ones in the visible ranges.
+    /// Called from `update_lsp_data` on scrolls, edits and server events.
+    pub(super) fn refresh_document_links(
+        &mut self,
+        for_buffer: Option<BufferId>,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.mode().is_full() {
+            return;
+        }
+        let Some(provider) = self.semantics_provider.clone() else {
+            return;
+        };
+
+        let mut visible_ranges = HashMap::<BufferId, (Entity<Buffer>, Range<usize>)>::default();
+        for (buffer, _, range) in self.visible_excerpts(true, cx).into_values() {
+            let buffer_id = buffer.read(cx).remote_id();
+            if for_buffer.is_some_and(|for_buffer| for_buffer != buffer_id) {
+                continue;
+            }
+            visible_ranges
+                .entry(buffer_id)
+                .and_modify(|(_, visible_range)| {
+                    visible_range.start = visible_range.start.min(range.start);
+                    visible_range.end = visible_range.end.max(range.end);
+                })
+                .or_insert((buffer, range));
+        }
+
+        self.refresh_document_links_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(LSP_REQUEST_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let Some(tasks) = editor
+                .update(cx, |_, cx| {
+                    visible_ranges
+                        .into_values()
+                        .filter_map(|(buffer, visible_range)| {
+                            let version = buffer.read(cx).version();
+                            let task = provider.document_links(&buffer, cx)?;
+                            Some(async move { (buffer, version, visible_range, task.await) })
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .ok()
+            else {
+                return;
+            };
+
+            let results = join_all(tasks).await;
+            editor
+                .update(cx, |editor, cx| {
+                    if for_buffer.is_none() {
+                        editor.visible_document_links.clear();
+                    }
+                    for (buffer, version, visible_range, links) in results {
+                        let snapshot = buffer.read(cx).snapshot();
+                        let links = links
+                            .into_iter()
+                            .filter(|link| {
+                                let range = link.range.to_offset(&snapshot);
+                                range.start <= visible_range.end && visible_range.start <= range.end
+                            })
+                            .collect();
+                        editor.visible_document_links.insert(
+                            snapshot.remote_id(),
+                            VisibleDocumentLinks { version, links },
+                        );
+                    }
+                })
+                .ok();
+        });
+    }
+
+    /// Returns the cached document link containing the position, unless the buffer changed since
+    /// the links were fetched.
+    pub(super) fn document_link_at(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &App,
+    ) -> Option<DocumentLink> {
+        let buffer = buffer.read(cx);
+        let cached = self.visible_document_links.get(&buffer.remote_id())?;
+        if buffer.version().changed_since(&cached.version) {
+            return None;
+        }
+        cached
+            .links
+            .iter()
+            .find(|link| {
+                link.range.start.cmp(&position, buffer).is_le()
+                    && link.range.end.cmp(&position, buffer).is_ge()
+            })
+            .cloned()
+    }
+
+    /// Replaces a cached link with its resolved version, so it's only resolved once.
+    pub(super) fn cache_resolved_document_link(
+        &mut self,
+        buffer_id: BufferId,
+        resolved_link: &DocumentLink,
+    ) {
+        let Some(cached) = self.visible_document_links.get_mut(&buffer_id) else {
+            return;
+        };
+        if let Some(link) = cached.links.iter_mut().find(|link| {
+            link.server_id == resolved_link.server_id && link.range == resolved_link.range
+        }) {
+            *link = resolved_link.clone();
+        }
+    }
+}
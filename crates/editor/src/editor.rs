@@ -18,6 +18,7 @@ mod clangd_ext;
 pub mod code_context_menus;
 pub mod display_map;
 mod document_colors;
+mod document_links;
 mod document_symbols;
 mod editor_settings;
 mod element;
@@ -158,6 +159,7 @@ use project::{
     CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint, InlayId,
     InvalidationStrategy, Location, LocationLink, LspAction, PrepareRenameResponse, Project,
     ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    bookmark_store::{Bookmark, BookmarkEditAction, BookmarkStore},
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -167,8 +169,8 @@ use project::{
     },
     git_store::GitStoreEvent,
     lsp_store::{
-        BufferSemanticTokens, CacheInlayHints, CompletionDocumentation, FormatTrigger,
-        LspFormatTarget, OpenLspBufferHandle, RefreshForServer,
+        BufferSemanticTokens, CacheInlayHints, CompletionDocumentation, DocumentLink,
+        FormatTrigger, LspFormatTarget, OpenLspBufferHandle, RefreshForServer,
     },
     project_settings::{DiagnosticSeverity, GoToDiagnosticSeverityFilter, ProjectSettings},
 };
@@ -1294,6 +1296,7 @@ pub struct Editor {
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    bookmark_store: Option<Entity<BookmarkStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     pub(crate) gutter_diff_review_indicator: (Option<PhantomDiffReviewIndicator>, Option<Task<()>>),
     pub(crate) diff_review_drag_state: Option<DiffReviewDragState>,
@@ -1350,6 +1353,8 @@ pub struct Editor {
     pub(crate) refresh_matching_bracket_highlights_task: Task<()>,
     refresh_document_symbols_task: Shared<Task<()>>,
     lsp_document_symbols: HashMap<BufferId, Vec<OutlineItem<text::Anchor>>>,
+    refresh_document_links_task: Task<()>,
+    visible_document_links: HashMap<BufferId, document_links::VisibleDocumentLinks>,
     refresh_outline_symbols_at_cursor_at_cursor_task: Task<()>,
     outline_symbols_at_cursor: Option<(BufferId, Vec<OutlineItem<Anchor>>)>,
     sticky_headers_task: Task<()>,
@@ -2351,6 +2356,10 @@ impl Editor {
             (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).breakpoint_store()),
             _ => None,
         };
+        let bookmark_store = match (&mode, project.as_ref()) {
+            (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).bookmark_store()),
+            _ => None,
+        };
 
         let mut code_action_providers = Vec::new();
         let mut load_uncommitted_diff = None;
@@ -2522,6 +2531,7 @@ impl Editor {
             tasks: BTreeMap::default(),
 
             breakpoint_store,
+            bookmark_store,
             gutter_breakpoint_indicator: (None, None),
             gutter_diff_review_indicator: (None, None),
             diff_review_drag_state: None,
@@ -2601,6 +2611,8 @@ impl Editor {
             refresh_matching_bracket_highlights_task: Task::ready(()),
             refresh_document_symbols_task: Task::ready(()).shared(),
             lsp_document_symbols: HashMap::default(),
+            refresh_document_links_task: Task::ready(()),
+            visible_document_links: HashMap::default(),
             refresh_outline_symbols_at_cursor_at_cursor_task: Task::ready(()),
             outline_symbols_at_cursor: None,
             sticky_headers_task: Task::ready(()),
@@ -2621,6 +2633,13 @@ impl Editor {
                     cx.notify();
                 }));
         }
+        if let Some(bookmarks) = editor.bookmark_store.as_ref() {
+            editor
+                ._subscriptions
+                .push(cx.observe(bookmarks, |_, _, cx| {
+                    cx.notify();
+                }));
+        }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
         editor._subscriptions.extend(project_subscriptions);
 
@@ -3324,6 +3343,11 @@ impl Editor {
         self.cursor_offset_on_selection = set_cursor_offset_on_selection;
     }
 
+    pub fn current_line_highlight(&self, cx: &App) -> CurrentLineHighlight {
+        self.current_line_highlight
+            .unwrap_or_else(|| EditorSettings::get_global(cx).current_line_highlight)
+    }
+
     pub fn set_current_line_highlight(
         &mut self,
         current_line_highlight: Option<CurrentLineHighlight>,
@@ -8763,6 +8787,75 @@ impl Editor {
         breakpoint_display_points
     }
 
+    /// Get all display points of bookmarks that will be rendered within editor
+    fn active_bookmarks(
+        &self,
+        range: Range<DisplayRow>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> HashMap<DisplayRow, (Anchor, Bookmark)> {
+        let snapshot = self.snapshot(window, cx);
+        let range = snapshot.display_point_to_point(DisplayPoint::new(range.start, 0), Bias::Left)
+            ..snapshot.display_point_to_point(DisplayPoint::new(range.end, 0), Bias::Right);
+
+        self.bookmarks_in_range(range, &snapshot, cx)
+            .into_iter()
+            .map(|(anchor, bookmark)| {
+                let row = anchor
+                    .to_point(snapshot.buffer_snapshot())
+                    .to_display_point(&snapshot)
+                    .row();
+                (row, (anchor, bookmark))
+            })
+            .collect()
+    }
+
+    /// The bookmarks in all buffers of the editor that lie within `range`, in order.
+    fn bookmarks_in_range(
+        &self,
+        range: Range<Point>,
+        snapshot: &EditorSnapshot,
+        cx: &App,
+    ) -> Vec<(Anchor, Bookmark)> {
+        let (Some(bookmark_store), Some(project)) = (self.bookmark_store.as_ref(), self.project())
+        else {
+            return Vec::new();
+        };
+
+        let multi_buffer_snapshot = snapshot.buffer_snapshot();
+        let mut bookmarks = Vec::new();
+        for (buffer_snapshot, range, excerpt_id) in
+            multi_buffer_snapshot.range_to_buffer_ranges(range.start..=range.end)
+        {
+            let Some(buffer) = project
+                .read(cx)
+                .buffer_for_id(buffer_snapshot.remote_id(), cx)
+            else {
+                continue;
+            };
+            bookmarks.extend(
+                bookmark_store
+                    .read(cx)
+                    .bookmarks(
+                        &buffer,
+                        Some(
+                            buffer_snapshot.anchor_before(range.start)
+                                ..buffer_snapshot.anchor_after(range.end),
+                        ),
+                        buffer_snapshot,
+                        cx,
+                    )
+                    .map(|bookmark| {
+                        (
+                            Anchor::in_buffer(excerpt_id, bookmark.position),
+                            bookmark.clone(),
+                        )
+                    }),
+            );
+        }
+        bookmarks
+    }
+
     fn breakpoint_context_menu(
         &self,
         anchor: Anchor,
@@ -8814,6 +8907,7 @@ impl Editor {
         };
 
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
+        let jump_to_cursor = window.is_action_available(&JumpToCursor, cx);
 
         let toggle_state_msg = breakpoint.as_ref().map_or(None, |bp| match bp.1.state {
             BreakpointState::Enabled => Some("Disable"),
@@ -8842,8 +8936,25 @@ impl Editor {
 
                         window.dispatch_action(Box::new(RunToCursor), cx);
                     })
-                    .separator()
                 })
+                .when(jump_to_cursor, |this| {
+                    let weak_editor = weak_editor.clone();
+                    this.entry("Jump to cursor", None, move |window, cx| {
+                        weak_editor
+                            .update(cx, |editor, cx| {
+                                editor.change_selections(
+                                    SelectionEffects::no_scroll(),
+                                    window,
+                                    cx,
+                                    |s| s.select_ranges([Point::new(row, 0)..Point::new(row, 0)]),
+                                );
+                            })
+                            .ok();
+
+                        window.dispatch_action(Box::new(JumpToCursor), cx);
+                    })
+                })
+                .when(run_to_cursor || jump_to_cursor, |this| this.separator())
                 .when_some(toggle_state_msg, |this, msg| {
                     this.entry(msg, None, {
                         let weak_editor = weak_editor.clone();
@@ -9051,6 +9162,39 @@ impl Editor {
             })
     }
 
+    fn render_bookmark(
+        &self,
+        position: Anchor,
+        row: DisplayRow,
+        bookmark: &Bookmark,
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        let focus_handle = self.focus_handle.clone();
+        let label = bookmark.label.clone();
+
+        IconButton::new(("bookmark_indicator", row.0 as usize), IconName::Bookmark)
+            .icon_size(IconSize::XSmall)
+            .size(ui::ButtonSize::None)
+            .icon_color(Color::Accent)
+            .style(ButtonStyle::Transparent)
+            .on_click(cx.listener(move |editor, _, window, cx| {
+                window.focus(&editor.focus_handle(cx), cx);
+                editor.edit_bookmark_at_anchor(position, BookmarkEditAction::Toggle, cx);
+            }))
+            .tooltip(move |_window, cx| match &label {
+                Some(label) => Tooltip::with_meta_in(
+                    "Remove bookmark",
+                    Some(&ToggleBookmark),
+                    label.to_string(),
+                    &focus_handle,
+                    cx,
+                ),
+                None => {
+                    Tooltip::for_action_in("Remove bookmark", &ToggleBookmark, &focus_handle, cx)
+                }
+            })
+    }
+
     fn build_tasks_context(
         project: &Entity<Project>,
         buffer: &Entity<Buffer>,
@@ -11971,6 +12115,138 @@ impl Editor {
         self.breakpoint_store.clone()
     }
 
+    /// The position of each line with a cursor, along with the bookmark on it, if any. There's one
+    /// entry per line, even when it has several cursors.
+    pub fn bookmarks_at_cursors(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<(Anchor, Option<Bookmark>)> {
+        let snapshot = self.snapshot(window, cx);
+        let multi_buffer_snapshot = snapshot.buffer_snapshot();
+        let mut rows = self
+            .selections
+            .disjoint_anchors_arc()
+            .iter()
+            .map(|selection| selection.head().to_point(multi_buffer_snapshot).row)
+            .collect::<Vec<_>>();
+        rows.dedup();
+
+        rows.into_iter()
+            .map(|row| {
+                let line_start = Point::new(row, 0);
+                let line_end = Point::new(row, multi_buffer_snapshot.line_len(MultiBufferRow(row)));
+                self.bookmarks_in_range(line_start..line_end, &snapshot, cx)
+                    .into_iter()
+                    .next()
+                    .map(|(anchor, bookmark)| (anchor, Some(bookmark)))
+                    .unwrap_or_else(|| (multi_buffer_snapshot.anchor_after(line_start), None))
+            })
+            .collect()
+    }
+
+    pub fn toggle_bookmark(
+        &mut self,
+        _: &ToggleBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.bookmark_store.is_none() {
+            return;
+        }
+
+        for (anchor, _) in self.bookmarks_at_cursors(window, cx) {
+            self.edit_bookmark_at_anchor(anchor, BookmarkEditAction::Toggle, cx);
+        }
+    }
+
+    pub fn edit_bookmark_at_anchor(
+        &mut self,
+        position: Anchor,
+        edit_action: BookmarkEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmark_store) = &self.bookmark_store else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).buffer_for_anchor(position, cx) else {
+            return;
+        };
+
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.edit_bookmark(buffer, position.text_anchor, edit_action, cx);
+        });
+
+        cx.notify();
+    }
+
+    pub fn go_to_next_bookmark(
+        &mut self,
+        _: &GoToNextBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_bookmark(Direction::Next, window, cx);
+    }
+
+    pub fn go_to_previous_bookmark(
+        &mut self,
+        _: &GoToPreviousBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_bookmark(Direction::Prev, window, cx);
+    }
+
+    /// Moves the cursor to the closest bookmark after or before it, wrapping around at the end of
+    /// the editor.
+    fn go_to_bookmark(
+        &mut self,
+        direction: Direction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.snapshot(window, cx);
+        let multi_buffer_snapshot = snapshot.buffer_snapshot();
+        let mut rows = self
+            .bookmarks_in_range(
+                Point::zero()..multi_buffer_snapshot.max_point(),
+                &snapshot,
+                cx,
+            )
+            .into_iter()
+            .map(|(anchor, _)| anchor.to_point(multi_buffer_snapshot).row)
+            .collect::<Vec<_>>();
+        rows.sort_unstable();
+        rows.dedup();
+
+        let cursor_row = self
+            .selections
+            .newest::<Point>(&snapshot.display_snapshot)
+            .head()
+            .row;
+        let row = match direction {
+            Direction::Next => rows.iter().find(|row| **row > cursor_row).or(rows.first()),
+            Direction::Prev => rows
+                .iter()
+                .rev()
+                .find(|row| **row < cursor_row)
+                .or(rows.last()),
+        };
+        let Some(&row) = row else {
+            return;
+        };
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let position = Point::new(row, 0);
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |s| s.select_ranges([position..position]),
+        );
+    }
+
     pub fn prepare_restore_change(
         &self,
         revert_changes: &mut HashMap<BufferId, Vec<(Range<text::Anchor>, Rope)>>,
@@ -18039,8 +18315,8 @@ impl Editor {
                     first_url_or_file = Some(Either::Left(url));
                     None
                 }
-                HoverLink::File(path) => {
-                    first_url_or_file = Some(Either::Right(path));
+                HoverLink::File(path, point) => {
+                    first_url_or_file = Some(Either::Right((path, point)));
                     None
                 }
             })
@@ -18155,18 +18431,25 @@ impl Editor {
                         })?;
                         Ok(Navigated::Yes)
                     }
-                    Some(Either::Right(path)) => {
+                    Some(Either::Right((path, point))) => {
                         // TODO(andrew): respect preview tab settings
                         //               `enable_keep_preview_on_code_navigation` and
                         //               `enable_preview_file_from_code_navigation`
                         let Some(workspace) = workspace else {
                             return Ok(Navigated::No);
                         };
-                        workspace
+                        let item = workspace
                             .update_in(cx, |workspace, window, cx| {
                                 workspace.open_resolved_path(path, window, cx)
                             })?
                             .await?;
+                        if let Some(point) = point
+                            && let Some(opened_editor) = item.downcast::<Editor>()
+                        {
+                            opened_editor.update_in(cx, |editor, window, cx| {
+                                editor.go_to_singleton_buffer_point(point, window, cx)
+                            })?;
+                        }
                         Ok(Navigated::Yes)
                     }
                     None => Ok(Navigated::No),
@@ -20523,6 +20806,98 @@ impl Editor {
         self.do_stage_or_unstage_and_next(false, window, cx);
     }
 
+    pub fn stage_selected_lines(
+        &mut self,
+        _: &::git::StageSelectedLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(true, cx);
+    }
+
+    pub fn unstage_selected_lines(
+        &mut self,
+        _: &::git::UnstageSelectedLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_selected_lines(false, cx);
+    }
+
+    pub fn stage_or_unstage_selected_lines(&mut self, stage: bool, cx: &mut Context<Self>) {
+        if self.delegate_stage_and_restore {
+            return;
+        }
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                for (buffer_id, (buffer_rows, base_text_rows)) in
+                    this.selected_diff_rows(&ranges, cx)
+                {
+                    this.do_stage_or_unstage_lines(
+                        stage,
+                        buffer_id,
+                        &buffer_rows,
+                        &base_text_rows,
+                        cx,
+                    );
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Returns the changed rows under the given ranges, grouped by buffer, as buffer rows for
+    /// added lines and diff base rows for deleted lines.
+    fn selected_diff_rows(
+        &self,
+        ranges: &[Range<Anchor>],
+        cx: &App,
+    ) -> HashMap<BufferId, (Vec<Range<u32>>, Vec<Range<u32>>)> {
+        let multibuffer = self.buffer.read(cx);
+        let snapshot = multibuffer.snapshot(cx);
+        let buffer_ids_by_base_text_id = multibuffer
+            .all_buffer_ids()
+            .into_iter()
+            .filter_map(|buffer_id| {
+                let diff = multibuffer.diff_for(buffer_id)?;
+                let base_text_id = diff.read(cx).base_text_buffer().read(cx).remote_id();
+                Some((base_text_id, buffer_id))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut rows = HashMap::<BufferId, (Vec<Range<u32>>, Vec<Range<u32>>)>::default();
+        for range in ranges {
+            let start = range.start.to_point(&snapshot);
+            let end = range.end.to_point(&snapshot);
+            let end_row = if end.row > start.row && end.column == 0 {
+                end.row
+            } else {
+                end.row + 1
+            };
+            for row_info in snapshot
+                .row_infos(MultiBufferRow(start.row))
+                .take((end_row - start.row) as usize)
+            {
+                let (Some(_), Some(buffer_id), Some(row)) = (
+                    row_info.diff_status,
+                    row_info.buffer_id,
+                    row_info.buffer_row,
+                ) else {
+                    continue;
+                };
+                if multibuffer.buffer(buffer_id).is_some() {
+                    rows.entry(buffer_id).or_default().0.push(row..row + 1);
+                } else if let Some(buffer_id) = buffer_ids_by_base_text_id.get(&buffer_id) {
+                    rows.entry(*buffer_id).or_default().1.push(row..row + 1);
+                }
+            }
+        }
+        rows
+    }
+
     pub fn stage_or_unstage_diff_hunks(
         &mut self,
         stage: bool,
@@ -20670,6 +21045,39 @@ impl Editor {
         None
     }
 
+    fn do_stage_or_unstage_lines(
+        &self,
+        stage: bool,
+        buffer_id: BufferId,
+        buffer_rows: &[Range<u32>],
+        base_text_rows: &[Range<u32>],
+        cx: &mut App,
+    ) {
+        let Some(project) = self.project() else {
+            return;
+        };
+        let Some(buffer) = project.read(cx).buffer_for_id(buffer_id, cx) else {
+            return;
+        };
+        let Some(diff) = self.buffer.read(cx).diff_for(buffer_id) else {
+            return;
+        };
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        let file_exists = buffer_snapshot
+            .file()
+            .is_some_and(|file| file.disk_state().exists());
+        diff.update(cx, |diff, cx| {
+            diff.stage_or_unstage_lines(
+                stage,
+                buffer_rows,
+                base_text_rows,
+                &buffer_snapshot,
+                file_exists,
+                cx,
+            )
+        });
+    }
+
     pub fn expand_selected_diff_hunks(&mut self, cx: &mut Context<Self>) {
         let ranges: Vec<_> = self
             .selections
@@ -21064,6 +21472,10 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn hard_wrap(&self) -> Option<usize> {
+        self.hard_wrap
+    }
+
     pub fn set_hard_wrap(&mut self, hard_wrap: Option<usize>, cx: &mut Context<Self>) {
         self.hard_wrap = hard_wrap;
         cx.notify();
@@ -25270,6 +25682,7 @@ impl Editor {
         self.refresh_document_colors(for_buffer, window, cx);
         self.refresh_folding_ranges(for_buffer, window, cx);
         self.refresh_document_symbols(for_buffer, cx);
+        self.refresh_document_links(for_buffer, cx);
     }
 
     fn register_visible_buffers(&mut self, cx: &mut Context<Self>) {
@@ -26532,6 +26945,19 @@ pub trait SemanticsProvider {
         cx: &mut App,
     ) -> Option<Task<Result<Option<Vec<LocationLink>>>>>;
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Vec<DocumentLink>>>;
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut App,
+    ) -> Task<Result<DocumentLink>>;
+
     fn range_for_rename(
         &self,
         buffer: &Entity<Buffer>,
@@ -27029,6 +27455,32 @@ impl SemanticsProvider for Entity<Project> {
         }))
     }
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Vec<DocumentLink>>> {
+        self.update(cx, |project, cx| {
+            if !project.any_language_server_supports_document_links(buffer.read(cx), cx) {
+                return None;
+            }
+            Some(project.lsp_store().update(cx, |lsp_store, cx| {
+                lsp_store.fetch_document_links(buffer, cx)
+            }))
+        })
+    }
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut App,
+    ) -> Task<Result<DocumentLink>> {
+        self.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+            lsp_store.resolve_document_link(link, buffer, cx)
+        })
+    }
+
     fn supports_inlay_hints(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
         self.update(cx, |project, cx| {
             if project
@@ -27385,6 +27837,7 @@ impl EditorSnapshot {
 
             let show_runnables = self.show_runnables.unwrap_or(gutter_settings.runnables);
             let show_breakpoints = self.show_breakpoints.unwrap_or(gutter_settings.breakpoints);
+            let show_bookmarks = gutter_settings.bookmarks && self.bookmark_store.is_some();
 
             let git_blame_entries_width =
                 self.git_blame_gutter_max_author_length
@@ -27408,7 +27861,7 @@ impl EditorSnapshot {
             let mut left_padding = git_blame_entries_width.unwrap_or(Pixels::ZERO);
             left_padding += if !is_singleton {
                 ch_width * 4.0
-            } else if show_runnables || show_breakpoints {
+            } else if show_runnables || show_breakpoints || show_bookmarks {
                 ch_width * 3.0
             } else if show_git_gutter && show_line_numbers {
                 ch_width * 2.0
@@ -24946,6 +24946,108 @@ async fn test_breakpoint_phantom_indicator_collision_on_toggle(cx: &mut TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_bookmark_toggling_and_navigation(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line\nFifth line",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("main.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+    let abs_path: Arc<Path> = Path::new(path!("/a/main.rs")).into();
+    let bookmark_rows = |cx: &mut VisualTestContext| -> Vec<u32> {
+        project.read_with(cx, |project, cx| {
+            project
+                .bookmark_store()
+                .read(cx)
+                .all_serialized_bookmarks(cx)
+                .get(&abs_path)
+                .map(|bookmarks| bookmarks.iter().map(|bookmark| bookmark.row).collect())
+                .unwrap_or_default()
+        })
+    };
+
+    // Toggling a bookmark on a line with several cursors adds a single bookmark.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([
+                Point::new(1, 0)..Point::new(1, 0),
+                Point::new(1, 3)..Point::new(1, 3),
+                Point::new(3, 2)..Point::new(3, 2),
+            ])
+        });
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+    });
+    assert_eq!(bookmark_rows(cx), vec![1, 3]);
+
+    // Bookmarks move along with edits.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+        });
+        editor.handle_input("Zeroth line\n", window, cx);
+    });
+    assert_eq!(bookmark_rows(cx), vec![2, 4]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_cursor_row(&editor, 2, cx);
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_cursor_row(&editor, 4, cx);
+    // Navigation wraps around at the end of the file.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_cursor_row(&editor, 2, cx);
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_previous_bookmark(&actions::GoToPreviousBookmark, window, cx);
+    });
+    assert_cursor_row(&editor, 4, cx);
+
+    // Toggling again on a bookmarked line removes the bookmark.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+    });
+    assert_eq!(bookmark_rows(cx), vec![2]);
+
+    fn assert_cursor_row(editor: &Entity<Editor>, row: u32, cx: &mut VisualTestContext) {
+        editor.update(cx, |editor, cx| {
+            let snapshot = editor.display_snapshot(cx);
+            assert_eq!(
+                editor.selections.newest::<Point>(&snapshot).head(),
+                Point::new(row, 0)
+            );
+        });
+    }
+}
+
 #[gpui::test]
 async fn test_rename_with_duplicate_edits(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
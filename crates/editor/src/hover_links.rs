@@ -1,17 +1,17 @@
 use crate::{
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToDefinitionSplit, GoToTypeDefinition, GoToTypeDefinitionSplit, GotoDefinitionKind,
-    HighlightKey, Navigated, PointForPosition, SelectPhase,
+    HighlightKey, Navigated, PointForPosition, SelectPhase, SemanticsProvider,
     editor_settings::GoToDefinitionFallback, scroll::ScrollAmount,
 };
-use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window, px};
-use language::{Bias, ToOffset};
+use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, WeakEntity, Window, px};
+use language::{Bias, Point, ToOffset};
 use linkify::{LinkFinder, LinkKind};
 use lsp::LanguageServerId;
-use project::{InlayId, LocationLink, Project, ResolvedPath};
+use project::{InlayId, LocationLink, Project, ResolvedPath, lsp_store::DocumentLink};
 use regex::Regex;
 use settings::Settings;
-use std::{ops::Range, sync::LazyLock};
+use std::{ops::Range, rc::Rc, sync::LazyLock};
 use text::OffsetRangeExt;
 use theme::ActiveTheme as _;
 use util::{ResultExt, TryFutureExt as _, maybe};
@@ -63,7 +63,8 @@ impl RangeInEditor {
 #[derive(Debug, Clone)]
 pub enum HoverLink {
     Url(String),
-    File(ResolvedPath),
+    /// A file to open, with the point to move to in it, if any.
+    File(ResolvedPath, Option<Point>),
     Text(LocationLink),
     InlayHint(lsp::Location, LanguageServerId),
 }
@@ -355,13 +356,28 @@ pub fn show_link_definition(
     }
     let project = editor.project.clone();
     let provider = editor.semantics_provider.clone();
+    let document_link = editor.document_link_at(&buffer, text_anchor, cx);
 
     let snapshot = snapshot.buffer_snapshot().clone();
     hovered_link_state.task = Some(cx.spawn_in(window, async move |this, cx| {
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, text_anchor, cx.clone()) {
+                    let document_link = match (&provider, document_link) {
+                        (Some(provider), Some(document_link)) => {
+                            resolve_document_link(provider, &buffer, document_link, &this, cx).await
+                        }
+                        _ => None,
+                    };
+                    if let Some((link_range, link)) = document_link {
+                        let range = maybe!({
+                            let range = snapshot.anchor_range_in_excerpt(excerpt_id, link_range)?;
+                            Some(RangeInEditor::Text(range))
+                        });
+                        Some((range, vec![link]))
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, text_anchor, cx.clone())
+                    {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let range =
@@ -380,7 +396,7 @@ pub fn show_link_definition(
                             Some(RangeInEditor::Text(range))
                         });
 
-                        Some((range, vec![HoverLink::File(filename)]))
+                        Some((range, vec![HoverLink::File(filename, None)]))
                     } else if let Some(provider) = provider {
                         let task = cx.update(|_, cx| {
                             provider.definitions(&buffer, text_anchor, preferred_kind, cx)
@@ -485,6 +501,60 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Turns a language server provided document link into a hover link, asking the server for the
+/// link's target first if it was sent without one.
+async fn resolve_document_link(
+    provider: &Rc<dyn SemanticsProvider>,
+    buffer: &Entity<language::Buffer>,
+    link: DocumentLink,
+    editor: &WeakEntity<Editor>,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let link = if link.target.is_some() {
+        link
+    } else {
+        let resolved_link = cx
+            .update(|_, cx| provider.resolve_document_link(buffer, link, cx))
+            .ok()?
+            .await
+            .log_err()?;
+        let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+        editor
+            .update(cx, |editor, _| {
+                editor.cache_resolved_document_link(buffer_id, &resolved_link)
+            })
+            .ok();
+        resolved_link
+    };
+    let target = link.target?;
+    let hover_link = match target.to_file_path() {
+        Ok(path) => HoverLink::File(
+            ResolvedPath::AbsPath {
+                path: path.to_string_lossy().into_owned(),
+                is_dir: false,
+            },
+            target
+                .to_string()
+                .rsplit_once('#')
+                .and_then(|(_, fragment)| point_from_fragment(fragment)),
+        ),
+        Err(_) => HoverLink::Url(target.to_string()),
+    };
+    Some((link.range, hover_link))
+}
+
+/// Parses the position in a file link's fragment, like `#L10` or `#L10,5`, into a zero-based
+/// point.
+fn point_from_fragment(fragment: &str) -> Option<Point> {
+    let position = fragment.strip_prefix('L').unwrap_or(fragment);
+    let (row, column) = match position.split_once([',', ':']) {
+        Some((row, column)) => (row, column.parse::<u32>().ok()?),
+        None => (position, 1),
+    };
+    let row = row.parse::<u32>().ok()?;
+    Some(Point::new(row.saturating_sub(1), column.saturating_sub(1)))
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -782,7 +852,7 @@ fn surrounding_filename(
 mod tests {
     use super::*;
     use crate::{
-        DisplayPoint,
+        DisplayPoint, LSP_REQUEST_DEBOUNCE_TIMEOUT,
         display_map::ToDisplayPoint,
         editor_tests::init_test,
         inlays::inlay_hints::tests::{cached_hint_labels, visible_hint_labels},
@@ -794,6 +864,10 @@ mod tests {
     use lsp::request::{GotoDefinition, GotoTypeDefinition};
     use multi_buffer::MultiBufferOffset;
     use settings::InlayHintSettingsContent;
+    use std::sync::{
+        Arc,
+        atomic::{self, AtomicUsize},
+    };
     use util::{assert_set_eq, path};
     use workspace::item::Item;
 
@@ -1388,6 +1462,204 @@ mod tests {
         assert_eq!(cx.opened_url(), Some("https://zed.dev/releases".into()));
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            use serde::Deserialize;ˇ
+        "});
+        let crate_range = cx.lsp_range(indoc! {"
+            use «serde»::Deserialize;
+        "});
+        let trait_range = cx.lsp_range(indoc! {"
+            use serde::«Deserialize»;
+        "});
+        let link_requests = Arc::new(AtomicUsize::new(0));
+        let mut requests = cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>({
+            let link_requests = link_requests.clone();
+            move |_, _, _| {
+                link_requests.fetch_add(1, atomic::Ordering::Release);
+                async move {
+                    Ok(Some(vec![
+                        lsp::DocumentLink {
+                            range: crate_range,
+                            target: Some("https://docs.rs/serde".parse().unwrap()),
+                            tooltip: None,
+                            data: None,
+                        },
+                        lsp::DocumentLink {
+                            range: trait_range,
+                            target: None,
+                            tooltip: None,
+                            data: Some(serde_json::json!("Deserialize")),
+                        },
+                    ]))
+                }
+            }
+        });
+        let resolve_requests = Arc::new(AtomicUsize::new(0));
+        cx.set_request_handler::<lsp::request::DocumentLinkResolve, _, _>({
+            let resolve_requests = resolve_requests.clone();
+            move |_, link, _| {
+                resolve_requests.fetch_add(1, atomic::Ordering::Release);
+                async move {
+                    assert_eq!(link.data, Some(serde_json::json!("Deserialize")));
+                    Ok(lsp::DocumentLink {
+                        target: Some(
+                            "https://docs.rs/serde/latest/serde/trait.Deserialize.html"
+                                .parse()
+                                .unwrap(),
+                        ),
+                        ..link
+                    })
+                }
+            }
+        });
+        cx.background_executor
+            .advance_clock(LSP_REQUEST_DEBOUNCE_TIMEOUT * 2);
+        requests.next().await;
+        cx.background_executor.run_until_parked();
+
+        let crate_coord = cx.pixel_position(indoc! {"
+            use serˇde::Deserialize;
+        "});
+        cx.simulate_mouse_move(crate_coord, None, Modifiers::secondary_key());
+        cx.background_executor
+            .advance_clock(std::time::Duration::from_millis(50));
+        cx.background_executor.run_until_parked();
+        cx.assert_editor_text_highlights(
+            HighlightKey::HoveredLinkState,
+            indoc! {"
+            use «serdeˇ»::Deserialize;
+        "},
+        );
+        cx.simulate_click(crate_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/serde".into()));
+
+        let trait_coord = cx.pixel_position(indoc! {"
+            use serde::Deseˇrialize;
+        "});
+        for _ in 0..2 {
+            cx.simulate_mouse_move(crate_coord, None, Modifiers::secondary_key());
+            cx.background_executor
+                .advance_clock(std::time::Duration::from_millis(50));
+            cx.background_executor.run_until_parked();
+            cx.simulate_mouse_move(trait_coord, None, Modifiers::secondary_key());
+            cx.background_executor
+                .advance_clock(std::time::Duration::from_millis(50));
+            cx.background_executor.run_until_parked();
+            cx.assert_editor_text_highlights(
+                HighlightKey::HoveredLinkState,
+                indoc! {"
+                use serde::«Deserializeˇ»;
+            "},
+            );
+        }
+        cx.simulate_click(trait_coord, Modifiers::secondary_key());
+        assert_eq!(
+            cx.opened_url(),
+            Some("https://docs.rs/serde/latest/serde/trait.Deserialize.html".into())
+        );
+
+        assert_eq!(
+            link_requests.load(atomic::Ordering::Acquire),
+            1,
+            "Hovering should use the links fetched for the visible range"
+        );
+        assert_eq!(
+            resolve_requests.load(atomic::Ordering::Acquire),
+            1,
+            "The link without a target should be resolved once, when it's first hovered"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_document_links_to_file_positions(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+        let fs = cx.update_workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(
+                path!("/root/dir/file2.rs"),
+                "fn one() {}\nfn two() {}\n".as_bytes().to_vec(),
+            )
+            .await;
+
+        cx.set_state(indoc! {"
+            // See file2.rs.ˇ
+        "});
+        let link_range = cx.lsp_range(indoc! {"
+            // See «file2.rs».
+        "});
+        let file_uri = lsp::Uri::from_file_path(path!("/root/dir/file2.rs")).unwrap();
+        let target: lsp::Uri = format!("{file_uri}#L2,4").parse().unwrap();
+        let mut requests =
+            cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(move |_, _, _| {
+                let target = target.clone();
+                async move {
+                    Ok(Some(vec![lsp::DocumentLink {
+                        range: link_range,
+                        target: Some(target),
+                        tooltip: None,
+                        data: None,
+                    }]))
+                }
+            });
+        cx.background_executor
+            .advance_clock(LSP_REQUEST_DEBOUNCE_TIMEOUT * 2);
+        requests.next().await;
+        cx.background_executor.run_until_parked();
+
+        let link_coord = cx.pixel_position(indoc! {"
+            // See fileˇ2.rs.
+        "});
+        cx.simulate_mouse_move(link_coord, None, Modifiers::secondary_key());
+        cx.background_executor
+            .advance_clock(std::time::Duration::from_millis(50));
+        cx.background_executor.run_until_parked();
+        cx.simulate_click(link_coord, Modifiers::secondary_key());
+        cx.background_executor.run_until_parked();
+
+        cx.update_workspace(|workspace, _, cx| {
+            let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+            active_editor.update(cx, |editor, cx| {
+                assert_eq!(
+                    editor.buffer().read(cx).title(cx),
+                    "file2.rs",
+                    "The link should open its target"
+                );
+                let display_snapshot = editor.display_snapshot(cx);
+                assert_eq!(
+                    editor.selections.newest::<Point>(&display_snapshot).head(),
+                    Point::new(1, 3),
+                    "The link's fragment should be the cursor's position"
+                );
+            });
+        });
+    }
+
     #[gpui::test]
     async fn test_urls_at_end_of_buffer(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
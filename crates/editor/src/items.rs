@@ -1673,6 +1673,14 @@ impl SearchableItem for Editor {
     ) {
         let text = self.buffer.read(cx);
         let text = text.snapshot(cx);
+        if query.is_structural() {
+            if let Some(replacement) = structural_replacement(&text, identifier, query) {
+                self.transact(window, cx, |this, _, cx| {
+                    this.edit([(identifier.clone(), replacement)], cx);
+                });
+            }
+            return;
+        }
         let text = text.text_for_range(identifier.clone()).collect::<Vec<_>>();
         let text: Cow<_> = if text.len() == 1 {
             text.first().cloned().unwrap().into()
@@ -1701,7 +1709,14 @@ impl SearchableItem for Editor {
 
         // A regex might have replacement variables so we cannot apply
         // the same replacement to all matches
-        if query.is_regex() {
+        if query.is_structural() {
+            edits = matches
+                .filter_map(|m| {
+                    structural_replacement(&text, m, query)
+                        .map(|replacement| (m.clone(), replacement))
+                })
+                .collect();
+        } else if query.is_regex() {
             edits = matches
                 .filter_map(|m| {
                     let text = text.text_for_range(m.clone()).collect::<Vec<_>>();
@@ -1897,6 +1912,22 @@ pub fn active_match_index(
     }
 }
 
+/// Structural replacements fill their template with the captures of the match,
+/// so they are computed against the syntax tree of the buffer containing it.
+fn structural_replacement(
+    snapshot: &MultiBufferSnapshot,
+    range: &Range<Anchor>,
+    query: &SearchQuery,
+) -> Option<Arc<str>> {
+    let buffer_ranges = snapshot.range_to_buffer_ranges(range.clone());
+    let [(buffer, buffer_range, _)] = buffer_ranges.as_slice() else {
+        return None;
+    };
+    query
+        .structural_replacement_for(buffer, buffer_range.start.0..buffer_range.end.0)
+        .map(Arc::from)
+}
+
 pub fn entry_label_color(selected: bool) -> Color {
     if selected {
         Color::Default
@@ -559,6 +559,7 @@ impl ExampleInstance {
                     reasoning_details: None,
                 }],
                 temperature: None,
+                max_tokens: None,
                 tools: Vec::new(),
                 tool_choice: None,
                 stop: Vec::new(),
@@ -5,6 +5,7 @@ use futures::future::{self, BoxFuture, join_all};
 use git::{
     Oid, RunHook,
     blame::Blame,
+    rebase::{RebaseAction, RebaseStatus, RebaseTodo},
     repository::{
         AskPassDelegate, Branch, CommitDataReader, CommitDetails, CommitOptions, FetchOptions,
         GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder,
@@ -51,6 +52,17 @@ pub struct FakeGitRepositoryState {
     pub simulated_index_write_error_message: Option<String>,
     pub refs: HashMap<String, String>,
     pub graph_commits: Vec<Arc<InitialGraphCommitData>>,
+    /// Commits passed to `cherry_pick`, in the order they were applied.
+    pub cherry_picked_commits: Vec<String>,
+    /// Commits passed to `revert`, in the order they were reverted.
+    pub reverted_commits: Vec<String>,
+    /// The todo list that `rebase_todo` returns.
+    pub rebase_todo: RebaseTodo,
+    /// Todo lists passed to `rebase_interactive`, in the order they were run.
+    pub rebased_todos: Vec<RebaseTodo>,
+    /// The todo list of the rebase in progress, which stops at each `edit` entry.
+    pub rebase_in_progress: Option<RebaseTodo>,
+    pub rebase_status: Option<RebaseStatus>,
 }
 
 impl FakeGitRepositoryState {
@@ -69,6 +81,39 @@ impl FakeGitRepositoryState {
             oids: Default::default(),
             remotes: HashMap::default(),
             graph_commits: Vec::new(),
+            cherry_picked_commits: Vec::new(),
+            reverted_commits: Vec::new(),
+            rebase_todo: RebaseTodo::default(),
+            rebased_todos: Vec::new(),
+            rebase_in_progress: None,
+            rebase_status: None,
+        }
+    }
+
+    /// Applies the entries of the rebase in progress after the first `step`, stopping at the
+    /// next `edit` entry or finishing the rebase.
+    fn advance_rebase(&mut self, step: usize) {
+        let Some(todo) = &self.rebase_in_progress else {
+            return;
+        };
+        let total = todo.entries().count();
+        let stop = todo
+            .entries()
+            .enumerate()
+            .skip(step)
+            .find(|(_, entry)| entry.action == RebaseAction::Edit)
+            .map(|(ix, entry)| (ix + 1, entry.sha.clone()));
+        if let Some((step, stopped_sha)) = stop {
+            self.rebase_status = Some(RebaseStatus {
+                head_name: self.current_branch_name.clone().map(Into::into),
+                step,
+                total,
+                stopped_sha: Some(stopped_sha),
+                interactive: true,
+            });
+        } else {
+            self.rebase_in_progress = None;
+            self.rebase_status = None;
         }
     }
 }
@@ -568,6 +613,87 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn rebase_status(&self) -> BoxFuture<'_, Option<RebaseStatus>> {
+        let fut = self.with_state_async(false, |state| Ok(state.rebase_status.clone()));
+        self.executor
+            .spawn(async move { fut.await.ok().flatten() })
+            .boxed()
+    }
+
+    fn rebase_todo(
+        &self,
+        _base: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<RebaseTodo>> {
+        self.with_state_async(false, |state| Ok(state.rebase_todo.clone()))
+    }
+
+    fn rebase_interactive(
+        &self,
+        _base: Option<String>,
+        todo: RebaseTodo,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.rebase_in_progress.is_some() {
+                bail!("a rebase is already in progress");
+            }
+            state.rebased_todos.push(todo.clone());
+            state.rebase_in_progress = Some(todo);
+            state.advance_rebase(0);
+            Ok(())
+        })
+    }
+
+    fn rebase_continue(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            let step = state
+                .rebase_status
+                .as_ref()
+                .context("no rebase in progress")?
+                .step;
+            state.advance_rebase(step);
+            Ok(())
+        })
+    }
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.rebase_continue(env)
+    }
+
+    fn rebase_abort(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state
+                .rebase_in_progress
+                .take()
+                .context("no rebase in progress")?;
+            state.rebase_status = None;
+            Ok(())
+        })
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            state.cherry_picked_commits.extend(commits);
+            Ok(())
+        })
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            state.reverted_commits.extend(commits);
+            Ok(())
+        })
+    }
+
     fn commit(
         &self,
         _message: gpui::SharedString,
@@ -35,6 +35,7 @@ use serde::{Deserialize, Serialize};
 use smol::io::AsyncWriteExt;
 use std::{
     io::{self, Write},
+    ops::Range,
     path::{Component, Path, PathBuf},
     pin::Pin,
     sync::Arc,
@@ -115,6 +116,8 @@ pub trait Fs: Send + Sync {
         Ok(String::from_utf8(self.load_bytes(path).await?)?)
     }
     async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>>;
+    /// Reads the bytes in `range` of the file, or fewer if the file ends before it.
+    async fn load_byte_range(&self, path: &Path, range: Range<u64>) -> Result<Vec<u8>>;
     async fn atomic_write(&self, path: PathBuf, text: String) -> Result<()>;
     async fn save(&self, path: &Path, text: &Rope, line_ending: LineEnding) -> Result<()>;
     async fn write(&self, path: &Path, content: &[u8]) -> Result<()>;
@@ -768,6 +771,23 @@ impl Fs for RealFs {
         Ok(bytes)
     }
 
+    async fn load_byte_range(&self, path: &Path, range: Range<u64>) -> Result<Vec<u8>> {
+        use std::io::{Read as _, Seek as _};
+
+        let path = path.to_path_buf();
+        self.executor
+            .spawn(async move {
+                let mut file = std::fs::File::open(&path)
+                    .with_context(|| format!("Failed to open file {}", path.display()))?;
+                file.seek(io::SeekFrom::Start(range.start))?;
+                let mut bytes = Vec::new();
+                file.take(range.end.saturating_sub(range.start))
+                    .read_to_end(&mut bytes)?;
+                Ok(bytes)
+            })
+            .await
+    }
+
     #[cfg(not(target_os = "windows"))]
     async fn atomic_write(&self, path: PathBuf, data: String) -> Result<()> {
         smol::unblock(move || {
@@ -2574,6 +2594,13 @@ impl Fs for FakeFs {
         self.load_internal(path).await
     }
 
+    async fn load_byte_range(&self, path: &Path, range: Range<u64>) -> Result<Vec<u8>> {
+        let bytes = self.load_internal(path).await?;
+        let start = (range.start as usize).min(bytes.len());
+        let end = (range.end as usize).clamp(start, bytes.len());
+        Ok(bytes[start..end].to_vec())
+    }
+
     async fn atomic_write(&self, path: PathBuf, data: String) -> Result<()> {
         self.simulate_random_delay().await;
         let path = normalize_path(path.as_path());
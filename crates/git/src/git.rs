@@ -1,6 +1,7 @@
 pub mod blame;
 pub mod commit;
 mod hosting_provider;
+pub mod rebase;
 mod remote;
 pub mod repository;
 pub mod stash;
@@ -37,6 +38,10 @@ actions!(
         StageAndNext,
         /// Unstages the current hunk and moves to the next one.
         UnstageAndNext,
+        /// Stages only the selected lines of the hunks under the selection.
+        StageSelectedLines,
+        /// Unstages only the selected lines of the hunks under the selection.
+        UnstageSelectedLines,
         /// Restores the selected hunks to their original state.
         #[action(deprecated_aliases = ["editor::RevertSelectedHunks"])]
         Restore,
@@ -101,6 +106,12 @@ actions!(
         Clone,
         /// Adds a file to .gitignore.
         AddToGitignore,
+        /// Continues the rebase in progress after resolving conflicts or editing a commit.
+        RebaseContinue,
+        /// Skips the commit the rebase in progress stopped at.
+        RebaseSkip,
+        /// Aborts the rebase in progress, restoring the branch to its original state.
+        RebaseAbort,
     ]
 );
 
@@ -125,6 +136,19 @@ pub struct RestoreFile {
     pub skip_prompt: bool,
 }
 
+/// Opens an editor for reordering, squashing, rewording and dropping commits,
+/// then runs `git rebase --interactive` with the result.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct InteractiveRebase {
+    /// The commit to rebase onto.
+    ///
+    /// Default: the current branch's upstream.
+    #[serde(default)]
+    pub base: Option<String>,
+}
+
 /// The length of a Git short SHA.
 pub const SHORT_SHA_LENGTH: usize = 7;
 
@@ -0,0 +1,411 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use gpui::SharedString;
+
+/// The directory git keeps interactive (and merge-backend) rebase state in.
+pub const REBASE_MERGE_DIR: &str = "rebase-merge";
+/// The directory the apply backend of `git rebase` keeps its state in.
+pub const REBASE_APPLY_DIR: &str = "rebase-apply";
+/// The todo file git hands to `GIT_SEQUENCE_EDITOR`.
+pub const REBASE_TODO_FILE: &str = "git-rebase-todo";
+/// Where reworded messages are stashed inside [`REBASE_MERGE_DIR`] until git asks for them.
+pub const REWORD_MESSAGES_DIR: &str = "zed-messages";
+/// The `git log` format that [`RebaseTodo::from_log`] parses: the short SHA, subject and full
+/// message of each commit, with the fields separated by NUL and the commits by RS.
+pub const REBASE_LOG_FORMAT: &str = "--format=%h%x00%s%x00%B%x1e";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [RebaseAction; 6] = [
+        Self::Pick,
+        Self::Reword,
+        Self::Edit,
+        Self::Squash,
+        Self::Fixup,
+        Self::Drop,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Edit => "edit",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "p" | "pick" => Some(Self::Pick),
+            "r" | "reword" => Some(Self::Reword),
+            "e" | "edit" => Some(Self::Edit),
+            "s" | "squash" => Some(Self::Squash),
+            "f" | "fixup" => Some(Self::Fixup),
+            "d" | "drop" => Some(Self::Drop),
+            _ => None,
+        }
+    }
+
+    /// Whether this action folds the commit into the one before it.
+    pub fn is_fold(&self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The commit's full message, which a [`RebaseAction::Reword`] entry replaces.
+    ///
+    /// When unset, git falls back to the commit's existing message.
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RebaseTodoLine {
+    Commit(RebaseTodoEntry),
+    /// A line Zed doesn't edit, such as `exec`, `break`, `update-ref` or `fixup -C`.
+    Command(String),
+}
+
+/// The contents of a `git-rebase-todo` file, without its comments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RebaseTodo {
+    pub lines: Vec<RebaseTodoLine>,
+}
+
+impl RebaseTodo {
+    pub fn parse(text: &str) -> Self {
+        let lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| match parse_todo_entry(line) {
+                Some(entry) => RebaseTodoLine::Commit(entry),
+                None => RebaseTodoLine::Command(line.to_string()),
+            })
+            .collect();
+        Self { lines }
+    }
+
+    /// Builds the todo list git would start an interactive rebase with, picking each commit in
+    /// the output of `git log --reverse` with [`REBASE_LOG_FORMAT`].
+    pub fn from_log(output: &str) -> Self {
+        let lines = output
+            .split('\x1e')
+            .filter_map(|commit| {
+                let mut fields = commit.trim_start().splitn(3, '\0');
+                let sha = fields.next()?.trim();
+                if sha.is_empty() {
+                    return None;
+                }
+                let subject = fields.next()?;
+                let message = fields.next()?.trim();
+                Some(RebaseTodoLine::Commit(RebaseTodoEntry {
+                    action: RebaseAction::Pick,
+                    sha: sha.to_string().into(),
+                    subject: subject.to_string().into(),
+                    message: Some(message.to_string()),
+                }))
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &RebaseTodoEntry> {
+        self.lines.iter().filter_map(|line| match line {
+            RebaseTodoLine::Commit(entry) => Some(entry),
+            RebaseTodoLine::Command(_) => None,
+        })
+    }
+
+    /// Serializes the todo in the format git reads it back in.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for line in &self.lines {
+            match line {
+                RebaseTodoLine::Commit(entry) => {
+                    writeln!(
+                        text,
+                        "{} {} {}",
+                        entry.action.as_str(),
+                        entry.sha,
+                        entry.subject
+                    )
+                    .ok();
+                }
+                RebaseTodoLine::Command(command) => {
+                    writeln!(text, "{command}").ok();
+                }
+            }
+        }
+        text
+    }
+
+    /// Returns an error describing why git would refuse this todo, if any.
+    pub fn validate(&self) -> Result<(), SharedString> {
+        if let Some(first_kept) = self
+            .entries()
+            .find(|entry| entry.action != RebaseAction::Drop)
+            && first_kept.action.is_fold()
+        {
+            return Err(format!(
+                "cannot {} without a previous commit",
+                first_kept.action.as_str()
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+fn parse_todo_entry(line: &str) -> Option<RebaseTodoEntry> {
+    let mut parts = line.splitn(3, char::is_whitespace);
+    let action = RebaseAction::parse(parts.next()?)?;
+    let sha = parts.next()?;
+    // `fixup -C <sha>` and friends carry flags we don't model.
+    if sha.starts_with('-') || sha.is_empty() {
+        return None;
+    }
+    let subject = parts.next().unwrap_or_default().trim();
+    Some(RebaseTodoEntry {
+        action,
+        sha: sha.to_string().into(),
+        subject: subject.to_string().into(),
+        message: None,
+    })
+}
+
+/// The state of a rebase that stopped partway, read from the `.git` directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebaseStatus {
+    /// The branch being rebased, or `None` for a detached HEAD.
+    pub head_name: Option<SharedString>,
+    /// The 1-based index of the todo item git stopped at.
+    pub step: usize,
+    pub total: usize,
+    /// The commit git stopped at, for an `edit` or a conflict.
+    pub stopped_sha: Option<SharedString>,
+    pub interactive: bool,
+}
+
+impl RebaseStatus {
+    /// Reads the rebase state from the given `.git` directory, if a rebase is in progress.
+    pub fn load(git_dir: &Path) -> Option<Self> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|contents| contents.trim().to_string())
+        };
+        let read_number = |path: &Path| read(path).and_then(|value| value.parse::<usize>().ok());
+
+        let merge_dir = git_dir.join(REBASE_MERGE_DIR);
+        let apply_dir = git_dir.join(REBASE_APPLY_DIR);
+        let (dir, step, total) = if merge_dir.is_dir() {
+            (
+                merge_dir.as_path(),
+                read_number(&merge_dir.join("msgnum")),
+                read_number(&merge_dir.join("end")),
+            )
+        } else if apply_dir.is_dir() && !apply_dir.join("applying").exists() {
+            (
+                apply_dir.as_path(),
+                read_number(&apply_dir.join("next")),
+                read_number(&apply_dir.join("last")),
+            )
+        } else {
+            return None;
+        };
+
+        let head_name = read(&dir.join("head-name"))
+            .filter(|name| name != "detached HEAD")
+            .map(|name| {
+                name.strip_prefix("refs/heads/")
+                    .unwrap_or(&name)
+                    .to_string()
+                    .into()
+            });
+        let stopped_sha = read(&dir.join("stopped-sha"))
+            .or_else(|| read(&dir.join("original-commit")))
+            .filter(|sha| !sha.is_empty())
+            .map(SharedString::from);
+
+        Some(Self {
+            head_name,
+            step: step.unwrap_or_default(),
+            total: total.unwrap_or_default(),
+            stopped_sha,
+            interactive: dir.join("interactive").exists(),
+        })
+    }
+}
+
+/// Returns the todo entry git most recently started working on.
+pub fn last_done_entry(rebase_merge_dir: &Path) -> Option<RebaseTodoEntry> {
+    let done = std::fs::read_to_string(rebase_merge_dir.join("done")).ok()?;
+    RebaseTodo::parse(&done)
+        .lines
+        .into_iter()
+        .rev()
+        .find_map(|line| match line {
+            RebaseTodoLine::Commit(entry) => Some(entry),
+            RebaseTodoLine::Command(_) => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use unindent::Unindent as _;
+
+    #[test]
+    fn test_parse_rebase_todo() {
+        let todo = RebaseTodo::parse(
+            &"
+            pick 1a2b3c4 Add the thing
+            r 5d6e7f8 Fix the thing
+            fixup -C 9a8b7c6 Replace the message
+            exec cargo test
+
+            # Rebase 0011223..9a8b7c6 onto 0011223 (3 commands)
+            #
+            # Commands:
+            # p, pick <commit> = use commit
+            "
+            .unindent(),
+        );
+
+        assert_eq!(
+            todo.lines,
+            vec![
+                RebaseTodoLine::Commit(RebaseTodoEntry {
+                    action: RebaseAction::Pick,
+                    sha: "1a2b3c4".into(),
+                    subject: "Add the thing".into(),
+                    message: None,
+                }),
+                RebaseTodoLine::Commit(RebaseTodoEntry {
+                    action: RebaseAction::Reword,
+                    sha: "5d6e7f8".into(),
+                    subject: "Fix the thing".into(),
+                    message: None,
+                }),
+                RebaseTodoLine::Command("fixup -C 9a8b7c6 Replace the message".into()),
+                RebaseTodoLine::Command("exec cargo test".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rebase_todo_from_log() {
+        let todo = RebaseTodo::from_log(
+            "1a2b3c4\0Add the thing\0Add the thing\n\nWith a body.\n\x1e\n\
+             5d6e7f8\0Fix the thing\0Fix the thing\n\x1e\n",
+        );
+        assert_eq!(
+            todo.lines,
+            vec![
+                RebaseTodoLine::Commit(RebaseTodoEntry {
+                    action: RebaseAction::Pick,
+                    sha: "1a2b3c4".into(),
+                    subject: "Add the thing".into(),
+                    message: Some("Add the thing\n\nWith a body.".into()),
+                }),
+                RebaseTodoLine::Commit(RebaseTodoEntry {
+                    action: RebaseAction::Pick,
+                    sha: "5d6e7f8".into(),
+                    subject: "Fix the thing".into(),
+                    message: Some("Fix the thing".into()),
+                }),
+            ]
+        );
+        assert_eq!(RebaseTodo::from_log(""), RebaseTodo::default());
+    }
+
+    #[test]
+    fn test_rebase_todo_round_trip() {
+        let mut todo = RebaseTodo::parse(
+            &"
+            pick 1a2b3c4 Add the thing
+            pick 5d6e7f8 Fix the thing
+            pick 9a8b7c6 Fix the thing again
+            "
+            .unindent(),
+        );
+        todo.lines.swap(0, 1);
+        if let RebaseTodoLine::Commit(entry) = &mut todo.lines[2] {
+            entry.action = RebaseAction::Fixup;
+        }
+
+        assert_eq!(
+            todo.to_text(),
+            "
+            pick 5d6e7f8 Fix the thing
+            pick 1a2b3c4 Add the thing
+            fixup 9a8b7c6 Fix the thing again
+            "
+            .unindent()
+        );
+        assert_eq!(RebaseTodo::parse(&todo.to_text()), todo);
+    }
+
+    #[test]
+    fn test_validate_rebase_todo() {
+        let mut todo = RebaseTodo::parse("drop 1a2b3c4 First\nsquash 5d6e7f8 Second\n");
+        assert!(todo.validate().is_err());
+
+        if let RebaseTodoLine::Commit(entry) = &mut todo.lines[0] {
+            entry.action = RebaseAction::Pick;
+        }
+        assert!(todo.validate().is_ok());
+    }
+
+    #[test]
+    fn test_load_rebase_status() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(RebaseStatus::load(dir.path()), None);
+
+        let merge_dir = dir.path().join(REBASE_MERGE_DIR);
+        std::fs::create_dir(&merge_dir).unwrap();
+        std::fs::write(merge_dir.join("head-name"), "refs/heads/feature\n").unwrap();
+        std::fs::write(merge_dir.join("msgnum"), "2\n").unwrap();
+        std::fs::write(merge_dir.join("end"), "5\n").unwrap();
+        std::fs::write(merge_dir.join("stopped-sha"), "5d6e7f8\n").unwrap();
+        std::fs::write(merge_dir.join("interactive"), "").unwrap();
+        std::fs::write(
+            merge_dir.join("done"),
+            "pick 1a2b3c4 Add the thing\nreword 5d6e7f8 Fix the thing\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            RebaseStatus::load(dir.path()),
+            Some(RebaseStatus {
+                head_name: Some("feature".into()),
+                step: 2,
+                total: 5,
+                stopped_sha: Some("5d6e7f8".into()),
+                interactive: true,
+            })
+        );
+        assert_eq!(
+            last_done_entry(&merge_dir).map(|entry| entry.action),
+            Some(RebaseAction::Reword)
+        );
+    }
+}
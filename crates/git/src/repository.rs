@@ -1,4 +1,8 @@
 use crate::commit::parse_git_diff_name_status;
+use crate::rebase::{
+    REBASE_LOG_FORMAT, REBASE_MERGE_DIR, REBASE_TODO_FILE, REWORD_MESSAGES_DIR, RebaseAction,
+    RebaseStatus, RebaseTodo, last_done_entry,
+};
 use crate::stash::GitStash;
 use crate::status::{DiffTreeType, GitStatus, StatusCode, TreeDiff};
 use crate::{Oid, RunHook, SHORT_SHA_LENGTH};
@@ -7,7 +11,7 @@ use collections::HashMap;
 use futures::channel::oneshot;
 use futures::future::BoxFuture;
 use futures::io::BufWriter;
-use futures::{AsyncWriteExt, FutureExt as _, select_biased};
+use futures::{AsyncWriteExt, FutureExt as _, StreamExt as _, select_biased};
 use git2::{BranchType, ErrorCode};
 use gpui::{AppContext as _, AsyncApp, BackgroundExecutor, SharedString, Task};
 use parking_lot::Mutex;
@@ -37,6 +41,7 @@ use util::rel_path::RelPath;
 use util::{ResultExt, paths};
 use uuid::Uuid;
 
+use askpass::EditorProxy;
 pub use askpass::{AskPassDelegate, AskPassResult, AskPassSession};
 
 pub const REMOTE_CANCELLED_BY_USER: &str = "Operation cancelled by user";
@@ -722,6 +727,54 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Reads the state of the rebase in progress, if any.
+    fn rebase_status(&self) -> BoxFuture<'_, Option<RebaseStatus>>;
+
+    /// Lists the commits that `git rebase --interactive` would start with, each with its full
+    /// message for rewording.
+    ///
+    /// Without a `base`, the commits are those not on the branch's upstream.
+    fn rebase_todo(
+        &self,
+        base: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<RebaseTodo>>;
+
+    /// Runs `git rebase --interactive` with the given todo list in place of the one git prepares.
+    ///
+    /// Without a `base`, git rebases onto the branch's upstream.
+    fn rebase_interactive(
+        &self,
+        base: Option<String>,
+        todo: RebaseTodo,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_continue(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Applies the changes introduced by the given commits on top of HEAD, in order.
+    ///
+    /// Stopping on conflicts is not an error: the conflicts are left in the
+    /// working tree for the user to resolve.
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates commits reverting the given commits, in order.
+    ///
+    /// Like [`GitRepository::cherry_pick`], stopping on conflicts is not an error.
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
     fn push(
         &self,
         branch_name: String,
@@ -862,6 +915,112 @@ impl RealGitRepository {
             .map(Path::to_path_buf)
     }
 
+    /// Runs a `git rebase` subcommand with Zed standing in as git's editor.
+    ///
+    /// The todo list git prepares is replaced with `todo`, if given. Messages
+    /// for `reword` entries are written when git asks for them; any other
+    /// message is left as git prepared it.
+    fn run_rebase_command(
+        &self,
+        args: Vec<String>,
+        todo: Option<RebaseTodo>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let rebase_merge_dir = self.path().join(REBASE_MERGE_DIR);
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let editor = EditorProxy::new(
+                    {
+                        let working_directory = working_directory.clone();
+                        let rebase_merge_dir = rebase_merge_dir.clone();
+                        let executor = executor.clone();
+                        let mut todo = todo;
+                        move |path| {
+                            let path = working_directory.join(path);
+                            let rebase_merge_dir = rebase_merge_dir.clone();
+                            if path.file_name() == Some(OsStr::new(REBASE_TODO_FILE))
+                                && let Some(todo) = todo.take()
+                            {
+                                executor.spawn(async move {
+                                    write_rebase_todo(&path, &rebase_merge_dir, &todo)
+                                        .await
+                                        .log_err();
+                                })
+                            } else {
+                                executor.spawn(async move {
+                                    write_reword_message(&path, &rebase_merge_dir)
+                                        .await
+                                        .log_err();
+                                })
+                            }
+                        }
+                    },
+                    executor,
+                )
+                .await?;
+
+                let output = new_smol_command(git_binary_path)
+                    .current_dir(&working_directory)
+                    .envs(env.iter())
+                    .env("GIT_SEQUENCE_EDITOR", editor.editor_command())
+                    .env("GIT_EDITOR", editor.editor_command())
+                    .args(args)
+                    .output()
+                    .await?;
+                drop(editor);
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to rebase:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    /// Runs `git cherry-pick` or `git revert`, treating a stop on conflicts as success.
+    ///
+    /// Git leaves `head_file` behind when it stops on a conflict, which the
+    /// repository's merge details pick up like any other merge conflict.
+    fn run_sequencer_command(
+        &self,
+        command: &'static [&'static str],
+        head_file: &'static str,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let head_path = self.path().join(head_file);
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(!commits.is_empty(), "no commits given");
+                let was_in_progress = smol::fs::metadata(&head_path).await.is_ok();
+                let output = new_smol_command(git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(command)
+                    .args(&commits)
+                    .output()
+                    .await?;
+                let stopped_on_conflict =
+                    !was_in_progress && smol::fs::metadata(&head_path).await.is_ok();
+                anyhow::ensure!(
+                    output.status.success() || stopped_on_conflict,
+                    "Failed to {}:\n{}",
+                    command[0],
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
     async fn any_git_binary_help_output(&self) -> SharedString {
         if let Some(output) = self.any_git_binary_help_output.lock().clone() {
             return output;
@@ -2018,6 +2177,82 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn rebase_status(&self) -> BoxFuture<'_, Option<RebaseStatus>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { RebaseStatus::load(&git_dir) })
+            .boxed()
+    }
+
+    fn rebase_todo(
+        &self,
+        base: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<RebaseTodo>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let base = base.unwrap_or_else(|| "@{upstream}".to_string());
+                // Like `git rebase`, leave out merges and commits whose changes are already
+                // upstream.
+                let output = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .envs(HashMap::clone(&env))
+                    .run(&[
+                        "log",
+                        "--reverse",
+                        "--no-merges",
+                        "--right-only",
+                        "--cherry-pick",
+                        REBASE_LOG_FORMAT,
+                        &format!("{base}...HEAD"),
+                    ])
+                    .await?;
+                Ok(RebaseTodo::from_log(&output))
+            })
+            .boxed()
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: Option<String>,
+        todo: RebaseTodo,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["rebase".to_string(), "--interactive".to_string()];
+        args.extend(base);
+        self.run_rebase_command(args, Some(todo), env)
+    }
+
+    fn rebase_continue(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_rebase_command(vec!["rebase".into(), "--continue".into()], None, env)
+    }
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_rebase_command(vec!["rebase".into(), "--skip".into()], None, env)
+    }
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_rebase_command(vec!["rebase".into(), "--abort".into()], None, env)
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_sequencer_command(&["cherry-pick"], "CHERRY_PICK_HEAD", commits, env)
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.run_sequencer_command(&["revert", "--no-edit"], "REVERT_HEAD", commits, env)
+    }
+
     fn commit(
         &self,
         message: SharedString,
@@ -2987,6 +3222,53 @@ struct GitBinaryCommandError {
     status: ExitStatus,
 }
 
+/// Replaces the todo list git prepared with `todo`, stashing the messages of `reword` entries
+/// for [`write_reword_message`].
+async fn write_rebase_todo(
+    todo_path: &Path,
+    rebase_merge_dir: &Path,
+    todo: &RebaseTodo,
+) -> Result<()> {
+    let messages_dir = rebase_merge_dir.join(REWORD_MESSAGES_DIR);
+    for entry in todo.entries() {
+        if entry.action == RebaseAction::Reword
+            && let Some(message) = &entry.message
+        {
+            smol::fs::create_dir_all(&messages_dir).await?;
+            smol::fs::write(messages_dir.join(entry.sha.as_ref()), message).await?;
+        }
+    }
+    smol::fs::write(todo_path, todo.to_text()).await?;
+    Ok(())
+}
+
+async fn write_reword_message(message_path: &Path, rebase_merge_dir: &Path) -> Result<()> {
+    let Some(entry) = last_done_entry(rebase_merge_dir) else {
+        return Ok(());
+    };
+    if entry.action != RebaseAction::Reword {
+        return Ok(());
+    }
+
+    // Git may expand or abbreviate the SHAs we wrote to the todo list.
+    let Ok(mut stored_messages) =
+        smol::fs::read_dir(rebase_merge_dir.join(REWORD_MESSAGES_DIR)).await
+    else {
+        return Ok(());
+    };
+    while let Some(stored_message) = stored_messages.next().await {
+        let stored_message = stored_message?;
+        let name = stored_message.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(entry.sha.as_ref()) || entry.sha.starts_with(name.as_ref()) {
+            let message = smol::fs::read_to_string(stored_message.path()).await?;
+            smol::fs::write(message_path, message).await?;
+            break;
+        }
+    }
+    Ok(())
+}
+
 async fn run_git_command(
     env: Arc<HashMap<String, String>>,
     ask_pass: AskPassDelegate,
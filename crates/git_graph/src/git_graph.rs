@@ -2,15 +2,15 @@ use collections::{BTreeMap, HashMap};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
-    parse_git_remote_url,
+    SHORT_SHA_LENGTH, parse_git_remote_url,
     repository::{CommitDiff, InitialGraphCommitData, LogOrder, LogSource},
 };
 use git_ui::commit_tooltip::CommitAvatar;
 use gpui::{
-    AnyElement, App, Bounds, ClipboardItem, Context, Corner, DefiniteLength, ElementId, Entity,
-    EventEmitter, FocusHandle, Focusable, FontWeight, Hsla, InteractiveElement, ParentElement,
-    PathBuilder, Pixels, Point, Render, ScrollWheelEvent, SharedString, Styled, Subscription, Task,
-    WeakEntity, Window, actions, anchored, deferred, point, px,
+    Action, AnyElement, App, Bounds, ClipboardItem, Context, Corner, DefiniteLength, DismissEvent,
+    ElementId, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, Hsla, InteractiveElement,
+    ParentElement, PathBuilder, Pixels, Point, PromptLevel, Render, ScrollWheelEvent, SharedString,
+    Styled, Subscription, Task, WeakEntity, Window, actions, anchored, deferred, point, px,
 };
 use project::{
     Project,
@@ -28,6 +28,7 @@ use ui::{
 use workspace::{
     Workspace,
     item::{Item, ItemEvent, SerializableItem},
+    notifications::DetachAndPromptErr,
 };
 
 pub struct GitGraphFeatureFlag;
@@ -49,6 +50,10 @@ actions!(
         Open,
         /// Opens the commit view for the selected commit.
         OpenCommitView,
+        /// Cherry-picks the selected commit onto the current branch.
+        CherryPickCommit,
+        /// Reverts the selected commit on the current branch.
+        RevertCommit,
     ]
 );
 
@@ -880,6 +885,114 @@ impl GitGraph {
         cx.notify();
     }
 
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        idx: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_entry(idx, cx);
+
+        let focus_handle = self.focus_handle.clone();
+        let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
+            menu.context(focus_handle)
+                .action("Cherry-Pick Commit", CherryPickCommit.boxed_clone())
+                .action("Revert Commit", RevertCommit.boxed_clone())
+        });
+
+        window.focus(&context_menu.focus_handle(cx), cx);
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    window.focus(&this.focus_handle, cx);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn cherry_pick_commit(
+        &mut self,
+        _: &CherryPickCommit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.apply_to_selected_commit(
+            "Cherry-pick",
+            "Failed to cherry-pick commit",
+            window,
+            cx,
+            |repo, sha, cx| repo.cherry_pick(vec![sha], cx),
+        );
+    }
+
+    fn revert_commit(&mut self, _: &RevertCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.apply_to_selected_commit(
+            "Revert",
+            "Failed to revert commit",
+            window,
+            cx,
+            |repo, sha, cx| repo.revert(vec![sha], cx),
+        );
+    }
+
+    /// Asks for confirmation, then applies the operation to the selected commit.
+    fn apply_to_selected_commit(
+        &mut self,
+        action_label: &'static str,
+        error_message: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        operation: impl FnOnce(
+            &mut Repository,
+            String,
+            &mut Context<Repository>,
+        ) -> futures::channel::oneshot::Receiver<anyhow::Result<()>>
+        + 'static,
+    ) {
+        let Some(commit) = self
+            .selected_entry_idx
+            .and_then(|idx| self.graph_data.commits.get(idx))
+        else {
+            return;
+        };
+        let sha = commit.data.sha.to_string();
+        let Some(repository) = self.project.read(cx).active_repository(cx) else {
+            return;
+        };
+
+        let answer = window.prompt(
+            PromptLevel::Info,
+            &format!(
+                "{} commit {}?",
+                action_label,
+                sha.get(..SHORT_SHA_LENGTH).unwrap_or(sha.as_str())
+            ),
+            None,
+            &[action_label, "Cancel"],
+            cx,
+        );
+
+        cx.spawn_in(window, async move |_, cx| {
+            if answer.await != Ok(0) {
+                return anyhow::Ok(());
+            }
+            repository
+                .update(cx, |repo, cx| operation(repo, sha, cx))
+                .await??;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err(error_message, window, cx, |_, _, _| None);
+    }
+
     fn get_remote(
         &self,
         repository: &Repository,
@@ -1579,9 +1692,23 @@ impl Render for GitGraph {
                                     .when(is_selected, |row| {
                                         row.bg(cx.theme().colors().element_selected)
                                     })
-                                    .on_click(move |_, _, cx| {
+                                    .on_click({
+                                        let weak = weak.clone();
+                                        move |_, _, cx| {
+                                            weak.update(cx, |this, cx| {
+                                                this.select_entry(index, cx);
+                                            })
+                                            .ok();
+                                        }
+                                    })
+                                    .on_mouse_down(MouseButton::Right, move |event, window, cx| {
                                         weak.update(cx, |this, cx| {
-                                            this.select_entry(index, cx);
+                                            this.deploy_context_menu(
+                                                event.position,
+                                                index,
+                                                window,
+                                                cx,
+                                            );
                                         })
                                         .ok();
                                     })
@@ -1604,6 +1731,8 @@ impl Render for GitGraph {
             .bg(cx.theme().colors().editor_background)
             .key_context("GitGraph")
             .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cherry_pick_commit))
+            .on_action(cx.listener(Self::revert_commit))
             .child(content)
             .children(self.context_menu.as_ref().map(|(menu, position, _)| {
                 deferred(
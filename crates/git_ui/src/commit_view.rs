@@ -10,8 +10,8 @@ use git::{
     parse_git_remote_url,
 };
 use gpui::{
-    AnyElement, App, AppContext as _, AsyncApp, AsyncWindowContext, ClipboardItem, Context,
-    Element, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement,
+    Action, AnyElement, App, AppContext as _, AsyncApp, AsyncWindowContext, ClipboardItem, Context,
+    Corner, Element, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement,
     ParentElement, PromptLevel, Render, Styled, Task, WeakEntity, Window, actions,
 };
 use language::{
@@ -27,7 +27,7 @@ use std::{
     sync::Arc,
 };
 use theme::ActiveTheme;
-use ui::{ButtonLike, DiffStat, Tooltip, prelude::*};
+use ui::{ButtonLike, ContextMenu, DiffStat, PopoverMenu, Tooltip, prelude::*};
 use util::{ResultExt, paths::PathStyle, rel_path::RelPath, truncate_and_trailoff};
 use workspace::item::TabTooltipContent;
 use workspace::{
@@ -42,7 +42,18 @@ use workspace::{
 use crate::commit_tooltip::CommitAvatar;
 use crate::git_panel::GitPanel;
 
-actions!(git, [ApplyCurrentStash, PopCurrentStash, DropCurrentStash,]);
+actions!(
+    git,
+    [
+        ApplyCurrentStash,
+        PopCurrentStash,
+        DropCurrentStash,
+        /// Cherry-picks the commit shown in the active commit view onto the current branch.
+        CherryPickCurrentCommit,
+        /// Reverts the commit shown in the active commit view on the current branch.
+        RevertCurrentCommit,
+    ]
+);
 
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
@@ -55,6 +66,12 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &PopCurrentStash, window, cx| {
             CommitView::pop_stash(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &CherryPickCurrentCommit, window, cx| {
+            CommitView::cherry_pick(workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &RevertCurrentCommit, window, cx| {
+            CommitView::revert(workspace, window, cx);
+        });
     })
     .detach();
 }
@@ -619,20 +636,107 @@ impl CommitView {
                                     .children(commit_diff_stat),
                             ),
                     )
-                    .children(remote_info.map(|(provider_name, url)| {
-                        let icon = match provider_name.as_str() {
-                            "GitHub" => IconName::Github,
-                            _ => IconName::Link,
-                        };
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .children(remote_info.map(|(provider_name, url)| {
+                                let icon = match provider_name.as_str() {
+                                    "GitHub" => IconName::Github,
+                                    _ => IconName::Link,
+                                };
+
+                                Button::new(
+                                    "view_on_provider",
+                                    format!("View on {}", provider_name),
+                                )
+                                .icon(icon)
+                                .icon_color(Color::Muted)
+                                .icon_size(IconSize::Small)
+                                .icon_position(IconPosition::Start)
+                                .on_click(move |_, _, cx| cx.open_url(&url))
+                            }))
+                            .when(self.stash.is_none(), |this| {
+                                this.child(self.render_commit_menu(cx))
+                            }),
+                    ),
+            )
+    }
 
-                        Button::new("view_on_provider", format!("View on {}", provider_name))
-                            .icon(icon)
-                            .icon_color(Color::Muted)
-                            .icon_size(IconSize::Small)
-                            .icon_position(IconPosition::Start)
-                            .on_click(move |_, _, cx| cx.open_url(&url))
-                    })),
+    fn render_commit_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle(cx);
+        PopoverMenu::new("commit-view-menu")
+            .trigger(
+                IconButton::new("commit-view-menu-trigger", IconName::Ellipsis)
+                    .icon_size(IconSize::Small)
+                    .icon_color(Color::Muted),
             )
+            .menu(move |window, cx| {
+                let focus_handle = focus_handle.clone();
+                Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                    menu.context(focus_handle)
+                        .action("Cherry-Pick Commit", CherryPickCurrentCommit.boxed_clone())
+                        .action("Revert Commit", RevertCurrentCommit.boxed_clone())
+                }))
+            })
+            .anchor(Corner::TopRight)
+    }
+
+    fn cherry_pick(workspace: &mut Workspace, window: &mut Window, cx: &mut App) {
+        Self::commit_action(workspace, "Cherry-pick", window, cx, |repo, sha, cx| {
+            repo.cherry_pick(vec![sha], cx)
+        });
+    }
+
+    fn revert(workspace: &mut Workspace, window: &mut Window, cx: &mut App) {
+        Self::commit_action(workspace, "Revert", window, cx, |repo, sha, cx| {
+            repo.revert(vec![sha], cx)
+        });
+    }
+
+    fn commit_action(
+        workspace: &mut Workspace,
+        str_action: &str,
+        window: &mut Window,
+        cx: &mut App,
+        action: impl FnOnce(
+            &mut Repository,
+            String,
+            &mut Context<Repository>,
+        ) -> futures::channel::oneshot::Receiver<Result<()>>
+        + 'static,
+    ) {
+        let Some(commit_view) = workspace.active_item_as::<CommitView>(cx) else {
+            return;
+        };
+        let commit_view = commit_view.read(cx);
+        if commit_view.stash.is_some() {
+            return;
+        }
+        let sha = commit_view.commit.sha.to_string();
+        let repository = commit_view.repository.clone();
+        let answer = window.prompt(
+            PromptLevel::Info,
+            &format!(
+                "{} commit {}?",
+                str_action,
+                sha.get(..7).unwrap_or(sha.as_str())
+            ),
+            None,
+            &[str_action, "Cancel"],
+            cx,
+        );
+
+        window
+            .spawn(cx, async move |cx| {
+                if answer.await != Ok(0) {
+                    return anyhow::Ok(());
+                }
+                repository
+                    .update(cx, |repo, cx| action(repo, sha, cx))
+                    .await??;
+                anyhow::Ok(())
+            })
+            .detach_and_notify_err(workspace.weak_handle(), window, cx);
     }
 
     fn apply_stash(workspace: &mut Workspace, window: &mut Window, cx: &mut App) {
@@ -5,8 +5,8 @@ use buffer_diff::BufferDiff;
 use editor::{Editor, EditorEvent, MultiBuffer};
 use futures::{FutureExt, select_biased};
 use gpui::{
-    AnyElement, App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, FocusHandle,
-    Focusable, IntoElement, Render, Task, WeakEntity, Window,
+    AnyElement, App, AppContext as _, AsyncApp, AsyncWindowContext, Context, Entity, EventEmitter,
+    FocusHandle, Focusable, IntoElement, Render, Task, WeakEntity, Window,
 };
 use language::{Buffer, LanguageRegistry};
 use project::Project;
@@ -52,29 +52,54 @@ impl FileDiffView {
             let new_buffer = project
                 .update(cx, |project, cx| project.open_local_buffer(&new_path, cx))
                 .await?;
-            let languages = project.update(cx, |project, _| project.languages().clone());
-
-            let buffer_diff = build_buffer_diff(&old_buffer, &new_buffer, languages, cx).await?;
-
-            workspace.update_in(cx, |workspace, window, cx| {
-                let diff_view = cx.new(|cx| {
-                    FileDiffView::new(
-                        old_buffer,
-                        new_buffer,
-                        buffer_diff,
-                        project.clone(),
-                        window,
-                        cx,
-                    )
-                });
+            Self::add_to_workspace(old_buffer, new_buffer, project, workspace, cx).await
+        })
+    }
 
-                let pane = workspace.active_pane();
-                pane.update(cx, |pane, cx| {
-                    pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
-                });
+    /// Opens a diff between two buffers that are already loaded, such as a buffer holding an
+    /// older version of a file and the file's own buffer.
+    pub fn open_buffers(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            Self::add_to_workspace(old_buffer, new_buffer, project, workspace, cx).await
+        })
+    }
 
-                diff_view
-            })
+    async fn add_to_workspace(
+        old_buffer: Entity<Buffer>,
+        new_buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<Entity<Self>> {
+        let languages = project.update(cx, |project, _| project.languages().clone());
+
+        let buffer_diff = build_buffer_diff(&old_buffer, &new_buffer, languages, cx).await?;
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let diff_view = cx.new(|cx| {
+                FileDiffView::new(
+                    old_buffer,
+                    new_buffer,
+                    buffer_diff,
+                    project.clone(),
+                    window,
+                    cx,
+                )
+            });
+
+            let pane = workspace.active_pane();
+            pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
+            });
+
+            diff_view
         })
     }
 
@@ -3,6 +3,7 @@ use crate::commit_modal::CommitModal;
 use crate::commit_tooltip::CommitTooltip;
 use crate::commit_view::CommitView;
 use crate::project_diff::{self, Diff, ProjectDiff};
+use crate::rebase_modal::RebaseTodoModal;
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::{branch_picker, picker_prompt, render_remote_button};
 use crate::{
@@ -21,6 +22,7 @@ use editor::{
 };
 use editor::{EditorStyle, RewrapOptions};
 use futures::StreamExt as _;
+use futures::channel::oneshot;
 use git::commit::ParsedCommitMessage;
 use git::repository::{
     Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
@@ -31,8 +33,9 @@ use git::stash::GitStash;
 use git::status::StageStatus;
 use git::{Amend, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
 use git::{
-    ExpandCommitEditor, GitHostingProviderRegistry, RestoreTrackedFiles, StageAll, StashAll,
-    StashApply, StashPop, TrashUntrackedFiles, UnstageAll,
+    ExpandCommitEditor, GitHostingProviderRegistry, InteractiveRebase, RebaseAbort, RebaseContinue,
+    RebaseSkip, RestoreTrackedFiles, SHORT_SHA_LENGTH, StageAll, StashAll, StashApply, StashPop,
+    TrashUntrackedFiles, UnstageAll,
 };
 use gpui::{
     Action, AsyncApp, AsyncWindowContext, Bounds, ClickEvent, Corner, DismissEvent, Empty, Entity,
@@ -170,6 +173,11 @@ fn git_panel_context_menu(
             .action_disabled_when(!state.has_stash_items, "Stash Pop", StashPop.boxed_clone())
             .action("View Stash", zed_actions::git::ViewStash.boxed_clone())
             .separator()
+            .action(
+                "Interactive Rebase",
+                InteractiveRebase::default().boxed_clone(),
+            )
+            .separator()
             .action("Open Diff", project_diff::Diff.boxed_clone())
             .separator()
             .action_disabled_when(
@@ -728,7 +736,8 @@ impl GitPanel {
                         _,
                         RepositoryEvent::StatusesChanged
                         | RepositoryEvent::BranchChanged
-                        | RepositoryEvent::MergeHeadsChanged,
+                        | RepositoryEvent::MergeHeadsChanged
+                        | RepositoryEvent::RebaseStatusChanged,
                         true,
                     )
                     | GitStoreEvent::RepositoryAdded
@@ -2005,6 +2014,105 @@ impl GitPanel {
         .detach();
     }
 
+    pub(crate) fn interactive_rebase(
+        &mut self,
+        base: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Interactive Rebase Started");
+        let todo = repo.update(cx, |repo, _| repo.rebase_todo(base.clone()));
+        cx.spawn_in(window, async move |this, cx| {
+            let todo = match todo.await? {
+                Ok(todo) => todo,
+                Err(e) => {
+                    return this.update(cx, |this, cx| this.show_error_toast("rebase", e, cx));
+                }
+            };
+
+            // The todo list is edited before the rebase is queued, so that other git operations
+            // can run while the modal is open.
+            let (tx, rx) = oneshot::channel();
+            this.update_in(cx, |this, window, cx| {
+                this.workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        RebaseTodoModal::new("Interactive Rebase".into(), todo, tx, window, cx)
+                    });
+                })
+            })??;
+            // The modal drops the sender when the rebase is cancelled.
+            let Ok(todo) = rx.await else {
+                return Ok(());
+            };
+
+            let result = repo
+                .update(cx, |repo, cx| repo.rebase_interactive(base, todo, cx))
+                .await?;
+            this.update(cx, |this, cx| {
+                if let Err(e) = result {
+                    this.show_error_toast("rebase", e, cx);
+                }
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn rebase_continue(
+        &mut self,
+        _: &RebaseContinue,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.run_rebase_step("rebase continue", cx, |repo, cx| repo.rebase_continue(cx));
+    }
+
+    pub(crate) fn rebase_skip(
+        &mut self,
+        _: &RebaseSkip,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.run_rebase_step("rebase skip", cx, |repo, cx| repo.rebase_skip(cx));
+    }
+
+    pub(crate) fn rebase_abort(
+        &mut self,
+        _: &RebaseAbort,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.run_rebase_step("rebase abort", cx, |repo, cx| repo.rebase_abort(cx));
+    }
+
+    fn run_rebase_step(
+        &mut self,
+        operation: &'static str,
+        cx: &mut Context<Self>,
+        step: impl FnOnce(
+            &mut Repository,
+            &mut Context<Repository>,
+        ) -> oneshot::Receiver<anyhow::Result<()>>,
+    ) {
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        let step = repo.update(cx, step);
+        cx.spawn(async move |this, cx| {
+            let result = step.await?;
+            this.update(cx, |this, cx| {
+                if let Err(e) = result {
+                    this.show_error_toast(operation, e, cx);
+                }
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn commit_message_buffer(&self, cx: &App) -> Entity<Buffer> {
         self.commit_editor
             .read(cx)
@@ -2702,6 +2810,7 @@ impl GitPanel {
                     tool_choice: None,
                     stop: Vec::new(),
                     temperature,
+                    max_tokens: None,
                     thinking_allowed: false,
                     thinking_effort: None,
                 };
@@ -4448,6 +4557,99 @@ impl GitPanel {
             )
     }
 
+    fn render_rebase_status(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let repo = self.active_repository.as_ref()?.read(cx);
+        let rebase = repo.rebase.as_ref()?;
+
+        let branch = rebase
+            .head_name
+            .clone()
+            .unwrap_or_else(|| "detached HEAD".into());
+        let mut title = format!("Rebasing {branch}");
+        if rebase.total > 0 {
+            title.push_str(&format!(" ({}/{})", rebase.step, rebase.total));
+        }
+        let has_conflicts = self.conflicted_count > 0;
+        let detail = if has_conflicts {
+            match self.conflicted_count {
+                1 => "Resolve 1 conflict, then continue".to_string(),
+                count => format!("Resolve {count} conflicts, then continue"),
+            }
+        } else if let Some(sha) = &rebase.stopped_sha {
+            format!(
+                "Stopped at {}",
+                sha.chars().take(SHORT_SHA_LENGTH).collect::<String>()
+            )
+        } else {
+            "Stopped".to_string()
+        };
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            h_flex()
+                .py_1p5()
+                .px_2()
+                .gap_1p5()
+                .justify_between()
+                .border_b_1()
+                .border_color(cx.theme().colors().border.opacity(0.8))
+                .child(
+                    v_flex()
+                        .flex_grow()
+                        .overflow_hidden()
+                        .child(Label::new(title).size(LabelSize::Small).truncate())
+                        .child(
+                            Label::new(detail)
+                                .size(LabelSize::Small)
+                                .color(if has_conflicts {
+                                    Color::Warning
+                                } else {
+                                    Color::Muted
+                                })
+                                .truncate(),
+                        ),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            panel_button("Abort")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "git rebase --abort",
+                                    &RebaseAbort,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.rebase_abort(&RebaseAbort, window, cx)
+                                })),
+                        )
+                        .child(
+                            panel_button("Skip")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "git rebase --skip",
+                                    &RebaseSkip,
+                                    &focus_handle,
+                                ))
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.rebase_skip(&RebaseSkip, window, cx)
+                                })),
+                        )
+                        .child(
+                            panel_filled_button("Continue")
+                                .tooltip(Tooltip::for_action_title_in(
+                                    "git rebase --continue",
+                                    &RebaseContinue,
+                                    &focus_handle,
+                                ))
+                                .disabled(has_conflicts)
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.rebase_continue(&RebaseContinue, window, cx)
+                                })),
+                        ),
+                ),
+        )
+    }
+
     fn render_previous_commit(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
         let active_repository = self.active_repository.as_ref()?;
         let branch = active_repository.read(cx).branch.as_ref()?;
@@ -5469,6 +5671,9 @@ impl Render for GitPanel {
                     .on_action(cx.listener(Self::generate_commit_message_action))
                     .on_action(cx.listener(Self::stash_all))
                     .on_action(cx.listener(Self::stash_pop))
+                    .on_action(cx.listener(Self::rebase_continue))
+                    .on_action(cx.listener(Self::rebase_skip))
+                    .on_action(cx.listener(Self::rebase_abort))
             })
             .on_action(cx.listener(Self::collapse_selected_entry))
             .on_action(cx.listener(Self::expand_selected_entry))
@@ -5499,6 +5704,7 @@ impl Render for GitPanel {
                 v_flex()
                     .size_full()
                     .children(self.render_panel_header(window, cx))
+                    .children(self.render_rebase_status(cx))
                     .map(|this| {
                         if let Some(repo) = self.active_repository.clone()
                             && has_entries
@@ -0,0 +1,431 @@
+use editor::Editor;
+use futures::channel::oneshot;
+use git::rebase::{RebaseAction, RebaseTodo, RebaseTodoLine};
+use gpui::{
+    AppContext as _, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ScrollHandle,
+    actions,
+};
+use menu::{Cancel, Confirm, SelectNext, SelectPrevious};
+use ui::{ContextMenu, DropdownMenu, KeyBinding, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use workspace::ModalView;
+
+actions!(
+    git_rebase,
+    [
+        /// Moves the selected commit up in the rebase todo list.
+        MoveUp,
+        /// Moves the selected commit down in the rebase todo list.
+        MoveDown,
+        /// Uses the selected commit as is.
+        Pick,
+        /// Uses the selected commit, but edits its message.
+        Reword,
+        /// Stops after the selected commit so it can be amended.
+        Edit,
+        /// Melds the selected commit into the previous one, combining their messages.
+        Squash,
+        /// Melds the selected commit into the previous one, keeping only the previous message.
+        Fixup,
+        /// Removes the selected commit.
+        DropCommit,
+    ]
+);
+
+struct TodoRow {
+    line: RebaseTodoLine,
+    message_editor: Option<Entity<Editor>>,
+}
+
+/// Lets the user edit the todo list of a `git rebase --interactive` before it runs.
+///
+/// Dismissing the modal without confirming cancels the rebase.
+pub(crate) struct RebaseTodoModal {
+    operation: SharedString,
+    rows: Vec<TodoRow>,
+    selected_ix: usize,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    tx: Option<oneshot::Sender<RebaseTodo>>,
+}
+
+impl EventEmitter<DismissEvent> for RebaseTodoModal {}
+impl ModalView for RebaseTodoModal {}
+impl Focusable for RebaseTodoModal {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl RebaseTodoModal {
+    pub fn new(
+        operation: SharedString,
+        todo: RebaseTodo,
+        tx: oneshot::Sender<RebaseTodo>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let rows = todo
+            .lines
+            .into_iter()
+            .map(|line| TodoRow {
+                line,
+                message_editor: None,
+            })
+            .collect();
+        Self {
+            operation,
+            rows,
+            selected_ix: 0,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+            tx: Some(tx),
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let todo = self.todo(cx);
+        if let Err(error) = todo.validate() {
+            self.error = Some(error);
+            cx.notify();
+            return;
+        }
+        if let Some(tx) = self.tx.take() {
+            tx.send(todo).ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn todo(&self, cx: &App) -> RebaseTodo {
+        let lines = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut line = row.line.clone();
+                if let RebaseTodoLine::Commit(entry) = &mut line
+                    && let Some(editor) = &row.message_editor
+                {
+                    entry.message = Some(editor.read(cx).text(cx));
+                }
+                line
+            })
+            .collect();
+        RebaseTodo { lines }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix + 1 < self.rows.len() {
+            self.select(self.selected_ix + 1, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &SelectPrevious,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.selected_ix > 0 {
+            self.select(self.selected_ix - 1, cx);
+        }
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_ix = ix;
+        self.scroll_handle.scroll_to_item(ix);
+        cx.notify();
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix > 0 {
+            self.rows.swap(self.selected_ix, self.selected_ix - 1);
+            self.error = None;
+            self.select(self.selected_ix - 1, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_ix + 1 < self.rows.len() {
+            self.rows.swap(self.selected_ix, self.selected_ix + 1);
+            self.error = None;
+            self.select(self.selected_ix + 1, cx);
+        }
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(row) = self.rows.get_mut(ix) else {
+            return;
+        };
+        let RebaseTodoLine::Commit(entry) = &mut row.line else {
+            return;
+        };
+        entry.action = action;
+        if action == RebaseAction::Reword && row.message_editor.is_none() {
+            let message = entry
+                .message
+                .clone()
+                .unwrap_or_else(|| entry.subject.to_string());
+            row.message_editor = Some(cx.new(|cx| {
+                let mut editor = Editor::auto_height(3, 10, window, cx);
+                editor.set_text(message, window, cx);
+                editor
+            }));
+        }
+        self.error = None;
+        self.selected_ix = ix;
+        cx.notify();
+    }
+
+    fn render_row(&self, ix: usize, window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
+        let row = &self.rows[ix];
+        let selected = ix == self.selected_ix;
+        let item = ListItem::new(("rebase-todo-entry", ix))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected)
+            .on_click(cx.listener(move |this, _, _, cx| this.select(ix, cx)));
+
+        match &row.line {
+            RebaseTodoLine::Commit(entry) => {
+                let this = cx.weak_entity();
+                let menu = ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for action in RebaseAction::ALL {
+                        let this = this.clone();
+                        menu = menu.entry(action.as_str(), None, move |window, cx| {
+                            this.update(cx, |this, cx| this.set_action(ix, action, window, cx))
+                                .ok();
+                        });
+                    }
+                    menu
+                });
+                let dropped = entry.action == RebaseAction::Drop;
+                let subject_color = if dropped {
+                    Color::Disabled
+                } else if entry.action.is_fold() {
+                    Color::Muted
+                } else {
+                    Color::Default
+                };
+                item.child(
+                    h_flex()
+                        .gap_2()
+                        .child(
+                            DropdownMenu::new(
+                                ("rebase-todo-action", ix),
+                                entry.action.as_str(),
+                                menu,
+                            )
+                            .trigger_size(ButtonSize::Compact),
+                        )
+                        .child(
+                            Label::new(
+                                entry
+                                    .sha
+                                    .chars()
+                                    .take(git::SHORT_SHA_LENGTH)
+                                    .collect::<String>(),
+                            )
+                            .buffer_font(cx)
+                            .color(Color::Muted),
+                        )
+                        .child(
+                            Label::new(entry.subject.clone())
+                                .color(subject_color)
+                                .when(dropped, |label| label.strikethrough())
+                                .truncate(),
+                        ),
+                )
+                .into_any_element()
+            }
+            RebaseTodoLine::Command(command) => item
+                .child(
+                    Label::new(command.clone())
+                        .buffer_font(cx)
+                        .color(Color::Muted)
+                        .truncate(),
+                )
+                .into_any_element(),
+        }
+    }
+
+    fn render_message_editor(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let row = self.rows.get(self.selected_ix)?;
+        let RebaseTodoLine::Commit(entry) = &row.line else {
+            return None;
+        };
+        if entry.action != RebaseAction::Reword {
+            return None;
+        }
+        let editor = row.message_editor.clone()?;
+        Some(
+            v_flex()
+                .p_2()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Label::new(format!("New message for {}", entry.subject))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                )
+                .child(
+                    div()
+                        .p_1()
+                        .rounded_sm()
+                        .bg(cx.theme().colors().editor_background)
+                        .child(editor),
+                )
+                .into_any_element(),
+        )
+    }
+}
+
+impl Render for RebaseTodoModal {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows = (0..self.rows.len())
+            .map(|ix| self.render_row(ix, window, cx))
+            .collect::<Vec<_>>();
+        let focus_handle = self.focus_handle.clone();
+
+        v_flex()
+            .key_context("RebaseTodo")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(Headline::new(self.operation.clone()).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                v_flex()
+                    .id("rebase-todo-list")
+                    .key_context("RebaseTodoList")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::move_up))
+                    .on_action(cx.listener(Self::move_down))
+                    .on_action(cx.listener(|this, _: &Pick, window, cx| {
+                        this.set_action(this.selected_ix, RebaseAction::Pick, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &Reword, window, cx| {
+                        this.set_action(this.selected_ix, RebaseAction::Reword, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &Edit, window, cx| {
+                        this.set_action(this.selected_ix, RebaseAction::Edit, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &Squash, window, cx| {
+                        this.set_action(this.selected_ix, RebaseAction::Squash, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &Fixup, window, cx| {
+                        this.set_action(this.selected_ix, RebaseAction::Fixup, window, cx)
+                    }))
+                    .on_action(cx.listener(|this, _: &DropCommit, window, cx| {
+                        this.set_action(this.selected_ix, RebaseAction::Drop, window, cx)
+                    }))
+                    .max_h(rems(24.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .py_1()
+                    .children(rows),
+            )
+            .children(self.render_message_editor(cx))
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_1()
+                    .justify_between()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                IconButton::new("move-up", IconName::ArrowUp)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip({
+                                        let focus_handle = focus_handle.clone();
+                                        move |_window, cx| {
+                                            Tooltip::for_action_in(
+                                                "Move Up",
+                                                &MoveUp,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                        }
+                                    })
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.move_up(&MoveUp, window, cx)
+                                    })),
+                            )
+                            .child(
+                                IconButton::new("move-down", IconName::ArrowDown)
+                                    .icon_size(IconSize::Small)
+                                    .tooltip({
+                                        let focus_handle = focus_handle.clone();
+                                        move |_window, cx| {
+                                            Tooltip::for_action_in(
+                                                "Move Down",
+                                                &MoveDown,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                        }
+                                    })
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.move_down(&MoveDown, window, cx)
+                                    })),
+                            )
+                            .children(self.error.clone().map(|error| {
+                                Label::new(error).size(LabelSize::Small).color(Color::Error)
+                            })),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Button::new("cancel", "Cancel")
+                                    .key_binding(KeyBinding::for_action_in(
+                                        &Cancel,
+                                        &focus_handle,
+                                        cx,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.cancel(&Cancel, window, cx)
+                                    })),
+                            )
+                            .child(
+                                Button::new("start-rebase", "Start Rebase")
+                                    .style(ButtonStyle::Filled)
+                                    .key_binding(KeyBinding::for_action_in(
+                                        &Confirm,
+                                        &focus_handle,
+                                        cx,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.confirm(&Confirm, window, cx)
+                                    })),
+                            ),
+                    ),
+            )
+    }
+}
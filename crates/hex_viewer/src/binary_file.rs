@@ -0,0 +1,411 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result, anyhow};
+use fs::{Fs, RemoveOptions, RenameOptions};
+use futures::{StreamExt as _, TryStreamExt as _, stream};
+use git::repository::is_binary_content;
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Task};
+use project::{Project, ProjectEntryId, ProjectPath, image_store::is_image_file};
+use util::ResultExt as _;
+
+/// How many bytes are read from disk at once when searching and saving.
+const CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+/// How many leading bytes of a file [`is_binary_content`] looks at.
+const BINARY_SNIFF_LEN: u64 = 8000;
+
+/// A file shown byte by byte. Bytes are read from disk on demand, and edits are kept in memory
+/// until the file is saved.
+pub struct BinaryFile {
+    fs: Arc<dyn Fs>,
+    abs_path: PathBuf,
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    len: u64,
+    edits: BTreeMap<u64, u8>,
+}
+
+pub enum BinaryFileEvent {
+    Edited,
+    Saved,
+}
+
+impl EventEmitter<BinaryFileEvent> for BinaryFile {}
+
+impl BinaryFile {
+    pub fn new(
+        fs: Arc<dyn Fs>,
+        abs_path: PathBuf,
+        project_path: ProjectPath,
+        entry_id: Option<ProjectEntryId>,
+        len: u64,
+    ) -> Self {
+        Self {
+            fs,
+            abs_path,
+            project_path,
+            entry_id,
+            len,
+            edits: BTreeMap::new(),
+        }
+    }
+
+    /// Opens any file in a local project, regardless of its contents.
+    pub fn open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Entity<Self>> {
+        let project = project.read(cx);
+        if !project.is_local() {
+            return None;
+        }
+        let entry = project.entry_for_path(path, cx)?;
+        if !entry.is_file() {
+            return None;
+        }
+        let abs_path = project.absolute_path(path, cx)?;
+        let fs = project.fs().clone();
+        let (entry_id, len) = (entry.id, entry.size);
+        let path = path.clone();
+        Some(cx.new(|_| BinaryFile::new(fs, abs_path, path, Some(entry_id), len)))
+    }
+
+    pub fn abs_path(&self) -> &Path {
+        &self.abs_path
+    }
+
+    /// The size of the file in bytes. Edits overwrite bytes, so they never change it.
+    pub fn size(&self) -> u64 {
+        self.len
+    }
+
+    pub fn edited_byte(&self, offset: u64) -> Option<u8> {
+        self.edits.get(&offset).copied()
+    }
+
+    pub fn set_byte(&mut self, offset: u64, byte: u8, cx: &mut Context<Self>) {
+        if offset < self.len {
+            self.edits.insert(offset, byte);
+            cx.emit(BinaryFileEvent::Edited);
+        }
+    }
+
+    pub fn discard_edits(&mut self, cx: &mut Context<Self>) {
+        if !self.edits.is_empty() {
+            self.edits.clear();
+            cx.emit(BinaryFileEvent::Edited);
+        }
+    }
+
+    /// Reads the bytes in `range`, with the current edits applied.
+    pub fn read_bytes(&self, range: Range<u64>, cx: &App) -> Task<Result<Vec<u8>>> {
+        let fs = self.fs.clone();
+        let abs_path = self.abs_path.clone();
+        let edits = self.edits_in(range.clone());
+        cx.background_spawn(async move {
+            let mut bytes = fs.load_byte_range(&abs_path, range.clone()).await?;
+            apply_edits(&mut bytes, range.start, &edits);
+            Ok(bytes)
+        })
+    }
+
+    /// Finds the first occurrence of `pattern` at or after `start`, with the current edits applied.
+    pub fn find(&self, pattern: Vec<u8>, start: u64, cx: &App) -> Task<Result<Option<u64>>> {
+        let fs = self.fs.clone();
+        let abs_path = self.abs_path.clone();
+        let len = self.len;
+        let edits = self.edits_in(start..len);
+        cx.background_spawn(async move {
+            if pattern.is_empty() {
+                return Ok(None);
+            }
+            // Consecutive chunks overlap, so that matches spanning two of them are found.
+            let overlap = pattern.len() as u64 - 1;
+            let mut offset = start;
+            while offset + overlap < len {
+                let range = offset..(offset + CHUNK_SIZE + overlap).min(len);
+                let mut chunk = fs.load_byte_range(&abs_path, range.clone()).await?;
+                apply_edits(&mut chunk, range.start, &edits);
+                if let Some(ix) = chunk
+                    .windows(pattern.len())
+                    .position(|window| window == pattern.as_slice())
+                {
+                    return Ok(Some(offset + ix as u64));
+                }
+                offset += CHUNK_SIZE;
+            }
+            Ok(None)
+        })
+    }
+
+    /// Writes the edits back to disk, replacing the file atomically.
+    ///
+    /// The file is copied in chunks to a temporary file next to it, with the edits applied, which
+    /// is then renamed over it. This way only one chunk is in memory at a time.
+    pub fn save(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        if self.edits.is_empty() {
+            return Task::ready(Ok(()));
+        }
+        let fs = self.fs.clone();
+        let abs_path = self.abs_path.clone();
+        let len = self.len;
+        let edits = self.edits.clone();
+        cx.spawn(async move |this, cx| {
+            let file_name = abs_path
+                .file_name()
+                .context("saving a file without a name")?
+                .to_string_lossy();
+            let temp_path = abs_path.with_file_name(format!(".{file_name}.save"));
+            let chunks = stream::try_unfold(0, |offset| {
+                let (fs, abs_path, edits) = (&fs, &abs_path, &edits);
+                async move {
+                    if offset >= len {
+                        return Ok(None);
+                    }
+                    let range = offset..(offset + CHUNK_SIZE).min(len);
+                    let mut chunk = fs
+                        .load_byte_range(abs_path, range.clone())
+                        .await
+                        .map_err(io::Error::other)?;
+                    apply_edits(&mut chunk, range.start, edits);
+                    Ok::<_, io::Error>(Some((chunk, range.end)))
+                }
+            });
+            let mut content = chunks.boxed().into_async_read();
+            let written = match fs
+                .create_file_with(&temp_path, Pin::new(&mut content))
+                .await
+            {
+                Ok(()) => {
+                    let options = RenameOptions {
+                        overwrite: true,
+                        ..RenameOptions::default()
+                    };
+                    fs.rename(&temp_path, &abs_path, options).await
+                }
+                Err(error) => Err(error),
+            };
+            if let Err(error) = written {
+                let options = RemoveOptions {
+                    ignore_if_not_exists: true,
+                    ..RemoveOptions::default()
+                };
+                fs.remove_file(&temp_path, options).await.log_err();
+                return Err(error);
+            }
+            this.update(cx, |this, cx| {
+                // Keep the edits made while saving.
+                this.edits
+                    .retain(|offset, byte| edits.get(offset) != Some(byte));
+                cx.emit(BinaryFileEvent::Saved);
+            })
+        })
+    }
+
+    fn edits_in(&self, range: Range<u64>) -> BTreeMap<u64, u8> {
+        self.edits
+            .range(range)
+            .map(|(offset, byte)| (*offset, *byte))
+            .collect()
+    }
+}
+
+/// Only opened for files that the other items failed to open, as the hex view is registered as a
+/// fallback. Images are left to the image viewer even then.
+impl project::ProjectItem for BinaryFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        if is_image_file(project, path, cx) {
+            return None;
+        }
+        let file = Self::open(project, path, cx)?;
+        let sniff_len = file.read(cx).size().min(BINARY_SNIFF_LEN);
+        let head = file.read(cx).read_bytes(0..sniff_len, cx);
+        let path = path.path.clone();
+        Some(cx.spawn(async move |_| {
+            if is_binary_content(&head.await?) {
+                Ok(file)
+            } else {
+                Err(anyhow!("{path:?} is not a binary file"))
+            }
+        }))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        !self.edits.is_empty()
+    }
+}
+
+fn apply_edits(bytes: &mut [u8], start: u64, edits: &BTreeMap<u64, u8>) {
+    let end = start + bytes.len() as u64;
+    for (offset, byte) in edits.range(start..end) {
+        bytes[(offset - start) as usize] = *byte;
+    }
+}
+
+/// Parses a search pattern, which is either hex digits like `de ad BE EF`,
+/// or text in double quotes like `"ELF"` to search for its UTF-8 bytes.
+pub fn parse_byte_pattern(pattern: &str) -> Option<Vec<u8>> {
+    let pattern = pattern.trim();
+    if let Some(text) = pattern
+        .strip_prefix('"')
+        .and_then(|pattern| pattern.strip_suffix('"'))
+    {
+        return Some(text.as_bytes().to_vec()).filter(|bytes| !bytes.is_empty());
+    }
+    let digits = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<_>>>()?;
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    Some(
+        digits
+            .chunks(2)
+            .map(|pair| (pair[0] << 4) | pair[1])
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use project::{ProjectItem as _, WorktreeId};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::{path, rel_path::rel_path};
+
+    #[test]
+    fn test_parse_byte_pattern() {
+        assert_eq!(
+            parse_byte_pattern("de ad BE EF"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(parse_byte_pattern("\"ELF\""), Some(b"ELF".to_vec()));
+        assert_eq!(parse_byte_pattern("abc"), None);
+        assert_eq!(parse_byte_pattern("zz"), None);
+        assert_eq!(parse_byte_pattern(""), None);
+    }
+
+    #[gpui::test]
+    async fn test_only_binary_files_are_opened(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({ "firmware.txt": "not really firmware" }),
+        )
+        .await;
+        fs.insert_file(path!("/dir/firmware"), b"\x7fELF\x02\x01\x01\x00".to_vec())
+            .await;
+        fs.insert_file(
+            path!("/dir/logo.png"),
+            b"\x89PNG\r\n\x1a\n\x00\x00".to_vec(),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let try_open = |name: &str, cx: &mut TestAppContext| {
+            let path = ProjectPath {
+                worktree_id,
+                path: rel_path(name).into(),
+            };
+            cx.update(|cx| BinaryFile::try_open(&project, &path, cx))
+        };
+
+        let file = try_open("firmware", cx).unwrap().await.unwrap();
+        file.read_with(cx, |file, _| assert_eq!(file.size(), 8));
+        assert!(
+            try_open("firmware.txt", cx).unwrap().await.is_err(),
+            "Text files should be left to the editor's view for broken files"
+        );
+        assert!(
+            try_open("logo.png", cx).is_none(),
+            "Images should be left to the image viewer"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_binary_file_edits(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({})).await;
+        let mut contents = vec![0u8; 10_000];
+        contents[9_000..9_004].copy_from_slice(b"\x7fELF");
+        fs.insert_file(path!("/dir/firmware.bin"), contents.clone())
+            .await;
+
+        let file = cx.new(|_| {
+            BinaryFile::new(
+                fs.clone(),
+                PathBuf::from(path!("/dir/firmware.bin")),
+                ProjectPath {
+                    worktree_id: WorktreeId::from_usize(0),
+                    path: rel_path("firmware.bin").into(),
+                },
+                None,
+                contents.len() as u64,
+            )
+        });
+
+        let found = file
+            .read_with(cx, |file, cx| file.find(b"ELF".to_vec(), 0, cx))
+            .await
+            .unwrap();
+        assert_eq!(found, Some(9_001));
+
+        file.update(cx, |file, cx| {
+            file.set_byte(16, 0xca, cx);
+            file.set_byte(17, 0xfe, cx);
+            assert!(project::ProjectItem::is_dirty(file));
+        });
+        let bytes = file
+            .read_with(cx, |file, cx| file.read_bytes(15..19, cx))
+            .await
+            .unwrap();
+        assert_eq!(bytes, [0x00, 0xca, 0xfe, 0x00]);
+        let found = file
+            .read_with(cx, |file, cx| file.find(vec![0xca, 0xfe], 0, cx))
+            .await
+            .unwrap();
+        assert_eq!(found, Some(16));
+
+        file.update(cx, |file, cx| file.save(cx)).await.unwrap();
+        file.read_with(cx, |file, _| {
+            assert!(!project::ProjectItem::is_dirty(file));
+        });
+        let saved = fs
+            .load_bytes(Path::new(path!("/dir/firmware.bin")))
+            .await
+            .unwrap();
+        assert_eq!(saved.len(), contents.len());
+        assert_eq!(&saved[15..19], &[0x00, 0xca, 0xfe, 0x00]);
+        assert_eq!(&saved[9_000..9_004], b"\x7fELF");
+    }
+}
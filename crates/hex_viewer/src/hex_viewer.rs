@@ -0,0 +1,693 @@
+mod binary_file;
+
+use std::{fmt::Write as _, ops::Range, path::Path};
+
+use editor::Editor;
+use file_icons::FileIcons;
+use gpui::{
+    AnyElement, App, ClipboardItem, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, KeyDownEvent, ListHorizontalSizingBehavior, MouseButton,
+    MouseDownEvent, ParentElement, Render, ScrollStrategy, SharedString, Styled, Subscription,
+    Task, UniformListScrollHandle, Window, actions, uniform_list,
+};
+use large_file_viewer::PageCache;
+use project::Project;
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::prelude::*;
+use util::{ResultExt as _, paths::PathExt, size::format_file_size};
+use workspace::{
+    ItemSettings, Pane, Workspace,
+    invalid_item_view::InvalidItemView,
+    item::{Item, ItemBufferKind, ItemEvent, ProjectItem, SaveOptions, TabContentParams},
+};
+use zed_actions::hex_viewer::OpenAsHex;
+
+pub use crate::binary_file::*;
+
+actions!(
+    hex_viewer,
+    [
+        /// Focuses the input for jumping to a byte offset.
+        GoToOffset,
+        /// Focuses the input for finding a byte pattern.
+        Find,
+        /// Selects the next occurrence of the byte pattern being found.
+        FindNext,
+        /// Copies the selected bytes as hex.
+        CopyAsHex,
+        /// Toggles whether typing hex digits overwrites the byte at the cursor.
+        ToggleEditing,
+        /// Moves the cursor to the previous byte.
+        MoveLeft,
+        /// Moves the cursor to the next byte.
+        MoveRight,
+        /// Moves the cursor to the previous row.
+        MoveUp,
+        /// Moves the cursor to the next row.
+        MoveDown,
+        /// Extends the selection to the previous byte.
+        SelectLeft,
+        /// Extends the selection to the next byte.
+        SelectRight,
+        /// Extends the selection to the previous row.
+        SelectUp,
+        /// Extends the selection to the next row.
+        SelectDown
+    ]
+);
+
+const BYTES_PER_ROW: u64 = 16;
+/// Bytes are read from disk and cached in pages of this size.
+const BYTES_PER_PAGE: u64 = 64 * 1024;
+const MAX_CACHED_PAGES: usize = 64;
+/// Larger selections are not copied, as the clipboard would hold three times as many bytes.
+const MAX_COPY_LEN: u64 = 16 * 1024 * 1024;
+
+/// Shows a [`BinaryFile`] as rows of hex bytes next to their ASCII characters.
+pub struct HexView {
+    file: Entity<BinaryFile>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pages: PageCache<Vec<u8>>,
+    offset_editor: Entity<Editor>,
+    find_editor: Entity<Editor>,
+    cursor: u64,
+    selection_tail: Option<u64>,
+    editing: bool,
+    /// Whether the high nibble of the byte at the cursor was just typed.
+    typed_high_nibble: bool,
+    status: Option<SharedString>,
+    pending_task: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl HexView {
+    pub fn new(file: Entity<BinaryFile>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let offset_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Go to offset (hex)…", window, cx);
+            editor
+        });
+        let find_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Find bytes, or \"text\"…", window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&file, |this, _, event, cx| {
+            match event {
+                BinaryFileEvent::Edited => {}
+                // The pages hold bytes with the edits made when they were read applied,
+                // which are now the bytes on disk.
+                BinaryFileEvent::Saved => this.pages.clear(),
+            }
+            cx.emit(ItemEvent::UpdateTab);
+            cx.notify();
+        });
+        Self {
+            file,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            pages: PageCache::new(MAX_CACHED_PAGES),
+            offset_editor,
+            find_editor,
+            cursor: 0,
+            selection_tail: None,
+            editing: false,
+            typed_high_nibble: false,
+            status: None,
+            pending_task: None,
+            _subscription: subscription,
+        }
+    }
+
+    fn selection(&self) -> Range<u64> {
+        let tail = self.selection_tail.unwrap_or(self.cursor);
+        self.cursor.min(tail)..self.cursor.max(tail) + 1
+    }
+
+    fn select(&mut self, offset: u64, extend: bool, cx: &mut Context<Self>) {
+        let len = self.file.read(cx).size();
+        if len == 0 {
+            return;
+        }
+        if !extend {
+            self.selection_tail = None;
+        } else if self.selection_tail.is_none() {
+            self.selection_tail = Some(self.cursor);
+        }
+        self.cursor = offset.min(len - 1);
+        self.typed_high_nibble = false;
+        self.scroll_handle
+            .scroll_to_item((self.cursor / BYTES_PER_ROW) as usize, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn move_by(&mut self, delta: i64, extend: bool, cx: &mut Context<Self>) {
+        let offset = self.cursor.saturating_add_signed(delta);
+        self.select(offset, extend, cx);
+    }
+
+    fn move_left(&mut self, _: &MoveLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(-1, false, cx);
+    }
+
+    fn move_right(&mut self, _: &MoveRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(1, false, cx);
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(-(BYTES_PER_ROW as i64), false, cx);
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(BYTES_PER_ROW as i64, false, cx);
+    }
+
+    fn select_left(&mut self, _: &SelectLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(-1, true, cx);
+    }
+
+    fn select_right(&mut self, _: &SelectRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(1, true, cx);
+    }
+
+    fn select_up(&mut self, _: &SelectUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(-(BYTES_PER_ROW as i64), true, cx);
+    }
+
+    fn select_down(&mut self, _: &SelectDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_by(BYTES_PER_ROW as i64, true, cx);
+    }
+
+    fn go_to_offset(&mut self, _: &GoToOffset, window: &mut Window, cx: &mut Context<Self>) {
+        self.offset_editor.update(cx, |editor, cx| {
+            editor.select_all(&Default::default(), window, cx);
+        });
+        window.focus(&self.offset_editor.focus_handle(cx), cx);
+    }
+
+    fn find(&mut self, _: &Find, window: &mut Window, cx: &mut Context<Self>) {
+        self.find_editor.update(cx, |editor, cx| {
+            editor.select_all(&Default::default(), window, cx);
+        });
+        window.focus(&self.find_editor.focus_handle(cx), cx);
+    }
+
+    fn confirm_go_to_offset(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.offset_editor.read(cx).text(cx);
+        let text = text.trim();
+        let text = text.strip_prefix("0x").unwrap_or(text);
+        let Ok(offset) = u64::from_str_radix(text, 16) else {
+            self.status = Some("Invalid offset".into());
+            cx.notify();
+            return;
+        };
+        self.status = None;
+        self.select(offset, false, cx);
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn find_next(&mut self, _: &FindNext, _: &mut Window, cx: &mut Context<Self>) {
+        let query = self.find_editor.read(cx).text(cx);
+        let Some(pattern) = parse_byte_pattern(&query) else {
+            self.status = Some("Enter hex bytes like \"de ad\", or text in quotes".into());
+            cx.notify();
+            return;
+        };
+        let pattern_len = pattern.len() as u64;
+        let start = if self.selection_tail.is_some() {
+            self.selection().start + 1
+        } else {
+            self.cursor
+        };
+        let find = self.file.read(cx).find(pattern, start, cx);
+        self.status = Some("Searching…".into());
+        self.pending_task = Some(cx.spawn(async move |this, cx| {
+            let found = find.await;
+            this.update(cx, |this, cx| {
+                this.status = match found {
+                    Ok(Some(offset)) => {
+                        this.select(offset, false, cx);
+                        this.select(offset + pattern_len - 1, true, cx);
+                        None
+                    }
+                    Ok(None) => Some("No more matches".into()),
+                    Err(error) => Some(format!("Search failed: {error}").into()),
+                };
+                cx.notify();
+            })
+            .log_err();
+        }));
+        cx.notify();
+    }
+
+    fn copy_as_hex(&mut self, _: &CopyAsHex, _: &mut Window, cx: &mut Context<Self>) {
+        let selection = self.selection();
+        if selection.end - selection.start > MAX_COPY_LEN {
+            self.status = Some(
+                format!(
+                    "Selections over {} can't be copied",
+                    format_file_size(MAX_COPY_LEN, false)
+                )
+                .into(),
+            );
+            cx.notify();
+            return;
+        }
+        let read_bytes = self.file.read(cx).read_bytes(selection, cx);
+        self.pending_task = Some(cx.spawn(async move |this, cx| {
+            let Some(bytes) = read_bytes.await.log_err() else {
+                return;
+            };
+            let hex = bytes.iter().fold(String::new(), |mut hex, byte| {
+                if !hex.is_empty() {
+                    hex.push(' ');
+                }
+                write!(hex, "{byte:02x}").ok();
+                hex
+            });
+            this.update(cx, |_, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(hex))
+            })
+            .log_err();
+        }));
+    }
+
+    fn toggle_editing(&mut self, _: &ToggleEditing, _: &mut Window, cx: &mut Context<Self>) {
+        self.editing = !self.editing;
+        self.typed_high_nibble = false;
+        cx.notify();
+    }
+
+    fn byte_at(&self, offset: u64, cx: &App) -> Option<u8> {
+        self.file.read(cx).edited_byte(offset).or_else(|| {
+            let page = self.pages.get(offset / BYTES_PER_PAGE)?;
+            page.get((offset % BYTES_PER_PAGE) as usize).copied()
+        })
+    }
+
+    fn handle_key_down(
+        &mut self,
+        event: &KeyDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let modifiers = &event.keystroke.modifiers;
+        if !self.editing
+            || !self.focus_handle.is_focused(window)
+            || modifiers.control
+            || modifiers.alt
+            || modifiers.platform
+        {
+            return;
+        }
+        let Some(digit) = event
+            .keystroke
+            .key_char
+            .as_deref()
+            .filter(|key| key.len() == 1)
+            .and_then(|key| u8::from_str_radix(key, 16).ok())
+        else {
+            return;
+        };
+        cx.stop_propagation();
+        let Some(byte) = self.byte_at(self.cursor, cx) else {
+            return;
+        };
+        let cursor = self.cursor;
+        let typed_high_nibble = self.typed_high_nibble;
+        let byte = if typed_high_nibble {
+            (byte & 0xf0) | digit
+        } else {
+            (digit << 4) | (byte & 0x0f)
+        };
+        self.selection_tail = None;
+        self.file
+            .update(cx, |file, cx| file.set_byte(cursor, byte, cx));
+        if typed_high_nibble {
+            self.move_by(1, false, cx);
+        } else {
+            self.typed_high_nibble = true;
+        }
+    }
+
+    fn load_page(&mut self, page: u64, cx: &mut Context<Self>) {
+        if !self.pages.start_loading(page) {
+            return;
+        }
+        let range = page * BYTES_PER_PAGE..(page + 1) * BYTES_PER_PAGE;
+        let read_bytes = self.file.read(cx).read_bytes(range, cx);
+        cx.spawn(async move |this, cx| {
+            let bytes = read_bytes.await;
+            this.update(cx, |this, cx| {
+                this.pages.finish_loading(page, bytes.log_err());
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_rows(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        let len = self.file.read(cx).size();
+        let offset_width = format!("{len:x}").len().max(8);
+        let colors = cx.theme().colors();
+        let (selection_color, modified_color) =
+            (colors.element_selected, cx.theme().status().modified);
+        let selection = self.selection();
+        range
+            .map(|row| {
+                let row_start = row as u64 * BYTES_PER_ROW;
+                let row_end = (row_start + BYTES_PER_ROW).min(len);
+                if self.pages.get(row_start / BYTES_PER_PAGE).is_none() {
+                    self.load_page(row_start / BYTES_PER_PAGE, cx);
+                }
+
+                let mut hex_cells = Vec::new();
+                let mut ascii_cells = Vec::new();
+                for offset in row_start..row_end {
+                    let byte = self.byte_at(offset, cx);
+                    let is_selected = selection.contains(&offset);
+                    let is_edited = self.file.read(cx).edited_byte(offset).is_some();
+                    let cell = |id: &'static str, text: String| {
+                        div()
+                            .id((id, offset))
+                            .when(is_selected, |this| this.bg(selection_color))
+                            .when(is_edited, |this| this.text_color(modified_color))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                                    this.select(offset, event.modifiers.shift, cx);
+                                    window.focus(&this.focus_handle, cx);
+                                }),
+                            )
+                            .child(text)
+                    };
+                    hex_cells.push(cell(
+                        "hex",
+                        byte.map_or_else(|| "  ".to_string(), |byte| format!("{byte:02x}")),
+                    ));
+                    ascii_cells.push(cell(
+                        "ascii",
+                        match byte {
+                            Some(byte @ 0x20..=0x7e) => char::from(byte).to_string(),
+                            Some(_) => ".".to_string(),
+                            None => " ".to_string(),
+                        },
+                    ));
+                }
+
+                h_flex()
+                    .id(row)
+                    .gap_4()
+                    .px_2()
+                    .child(
+                        Label::new(format!("{row_start:0offset_width$x}"))
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .w(rems(BYTES_PER_ROW as f32 * 1.75))
+                            .children(hex_cells),
+                    )
+                    .child(h_flex().children(ascii_cells))
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_header(&self, cx: &Context<Self>) -> impl IntoElement {
+        let file = self.file.read(cx);
+        let selection = self.selection();
+        let position = if selection.end - selection.start > 1 {
+            format!(
+                "0x{:x}–0x{:x} ({} bytes)",
+                selection.start,
+                selection.end - 1,
+                selection.end - selection.start
+            )
+        } else {
+            format!("0x{:x}", self.cursor)
+        };
+        let input = |editor: &Entity<Editor>| {
+            div()
+                .w(rems(14.))
+                .px_2()
+                .py_1()
+                .border_1()
+                .rounded_md()
+                .border_color(cx.theme().colors().border)
+                .child(editor.clone())
+        };
+
+        h_flex()
+            .p_2()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(format!(
+                            "{} · {position}",
+                            format_file_size(file.size(), false)
+                        ))
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                    )
+                    .child(
+                        Button::new(
+                            "toggle-editing",
+                            if self.editing { "Editing" } else { "Read-only" },
+                        )
+                        .label_size(LabelSize::Small)
+                        .toggle_state(self.editing)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.toggle_editing(&ToggleEditing, window, cx)
+                        })),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .children(
+                        self.status
+                            .clone()
+                            .map(|status| Label::new(status).size(LabelSize::Small)),
+                    )
+                    .child(input(&self.offset_editor).on_action(cx.listener(
+                        |this, _: &menu::Confirm, window, cx| this.confirm_go_to_offset(window, cx),
+                    )))
+                    .child(input(&self.find_editor).on_action(cx.listener(
+                        |this, _: &menu::Confirm, window, cx| this.find_next(&FindNext, window, cx),
+                    ))),
+            )
+    }
+}
+
+impl Render for HexView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let row_count = self.file.read(cx).size().div_ceil(BYTES_PER_ROW) as usize;
+        let settings = ThemeSettings::get_global(cx);
+        v_flex()
+            .key_context("HexView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::go_to_offset))
+            .on_action(cx.listener(Self::find))
+            .on_action(cx.listener(Self::find_next))
+            .on_action(cx.listener(Self::copy_as_hex))
+            .on_action(cx.listener(Self::toggle_editing))
+            .on_action(cx.listener(Self::move_left))
+            .on_action(cx.listener(Self::move_right))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::select_left))
+            .on_action(cx.listener(Self::select_right))
+            .on_action(cx.listener(Self::select_up))
+            .on_action(cx.listener(Self::select_down))
+            .on_key_down(cx.listener(Self::handle_key_down))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_header(cx))
+            .child(
+                uniform_list(
+                    "hex-rows",
+                    row_count,
+                    cx.processor(|this, range: Range<usize>, _window, cx| {
+                        this.render_rows(range, cx)
+                    }),
+                )
+                .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+                .track_scroll(&self.scroll_handle)
+                .flex_1()
+                .font(settings.buffer_font.clone())
+                .text_size(settings.buffer_font_size(cx)),
+            )
+    }
+}
+
+impl Focusable for HexView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<ItemEvent> for HexView {}
+
+impl Item for HexView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.file.entity_id(), self.file.read(cx))
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let abs_path = self.file.read(cx).abs_path().compact();
+        Some(abs_path.to_string_lossy().into_owned().into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        self.file
+            .read(cx)
+            .abs_path()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned().into())
+            .unwrap_or_default()
+    }
+
+    fn tab_icon(&self, _: &Window, cx: &App) -> Option<Icon> {
+        let path = self.file.read(cx).abs_path();
+        ItemSettings::get_global(cx)
+            .file_icons
+            .then(|| FileIcons::get_icon(path, cx))
+            .flatten()
+            .map(Icon::from_path)
+    }
+
+    fn buffer_kind(&self, _: &App) -> ItemBufferKind {
+        ItemBufferKind::Singleton
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        project::ProjectItem::is_dirty(self.file.read(cx))
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        _: SaveOptions,
+        _: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        self.file.update(cx, |file, cx| file.save(cx))
+    }
+
+    fn reload(
+        &mut self,
+        _: Entity<Project>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        self.file.update(cx, |file, cx| file.discard_edits(cx));
+        self.pages.clear();
+        cx.notify();
+        Task::ready(Ok(()))
+    }
+}
+
+impl ProjectItem for HexView {
+    type Item = BinaryFile;
+
+    fn for_project_item(
+        _: Entity<Project>,
+        _: Option<&Pane>,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, window, cx)
+    }
+
+    fn for_broken_project_item(
+        abs_path: &Path,
+        is_local: bool,
+        e: &anyhow::Error,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<InvalidItemView>
+    where
+        Self: Sized,
+    {
+        Some(InvalidItemView::new(abs_path, is_local, e, window, cx))
+    }
+}
+
+/// Replaces the active item with a hex view of its file, including files that failed to open as text.
+fn open_as_hex(
+    workspace: &mut Workspace,
+    _: &OpenAsHex,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_item) = workspace.active_item(cx) else {
+        return;
+    };
+    if active_item.is_dirty(cx) {
+        workspace.show_error(
+            &"Save or discard the changes to reopen this file as hex",
+            cx,
+        );
+        return;
+    }
+    let project = workspace.project().clone();
+    let project_path = active_item.project_path(cx).or_else(|| {
+        let invalid_item = active_item.downcast::<InvalidItemView>()?;
+        project
+            .read(cx)
+            .find_project_path(&invalid_item.read(cx).abs_path, cx)
+    });
+    let Some(file) = project_path.and_then(|path| BinaryFile::open(&project, &path, cx)) else {
+        return;
+    };
+    let hex_view = cx.new(|cx| HexView::new(file, window, cx));
+    workspace.active_pane().update(cx, |pane, cx| {
+        let index = pane.index_for_item(active_item.as_ref());
+        pane.remove_item(active_item.item_id(), false, false, window, cx);
+        pane.add_item(Box::new(hex_view), true, true, index, window, cx);
+    });
+}
+
+pub fn init(cx: &mut App) {
+    workspace::register_fallback_project_item::<HexView>(cx);
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(open_as_hex);
+    })
+    .detach();
+}
@@ -0,0 +1,1042 @@
+use std::ops::Range;
+
+use anyhow::Result;
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, uniform_list,
+};
+use language::{Point, ToPoint as _};
+use project::{CallHierarchyItem, Location, Project, TypeHierarchyItem};
+use ui::{ListItem, ListItemSpacing, Tooltip, WithScrollbar, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Pane, SplitDirection, Toast, Workspace,
+    item::{Item, ItemEvent},
+    notifications::NotificationId,
+};
+use zed_actions::hierarchy_view::{
+    ShowIncomingCalls, ShowOutgoingCalls, ShowSubtypes, ShowSupertypes,
+};
+
+actions!(
+    hierarchy_view,
+    [
+        /// Expands the selected entry, fetching its children if needed.
+        ExpandSelectedEntry,
+        /// Collapses the selected entry.
+        CollapseSelectedEntry,
+        /// Switches the view between incoming and outgoing calls, or between supertypes and subtypes.
+        ToggleDirection,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+            HierarchyView::deploy(workspace, HierarchyKind::IncomingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+            HierarchyView::deploy(workspace, HierarchyKind::OutgoingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSupertypes, window, cx| {
+            HierarchyView::deploy(workspace, HierarchyKind::Supertypes, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSubtypes, window, cx| {
+            HierarchyView::deploy(workspace, HierarchyKind::Subtypes, window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HierarchyKind {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::IncomingCalls => "Incoming Calls",
+            Self::OutgoingCalls => "Outgoing Calls",
+            Self::Supertypes => "Supertypes",
+            Self::Subtypes => "Subtypes",
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            Self::IncomingCalls => Self::OutgoingCalls,
+            Self::OutgoingCalls => Self::IncomingCalls,
+            Self::Supertypes => Self::Subtypes,
+            Self::Subtypes => Self::Supertypes,
+        }
+    }
+
+    fn not_found_message(self) -> &'static str {
+        match self {
+            Self::IncomingCalls | Self::OutgoingCalls => {
+                "No call hierarchy found for the symbol under the cursor"
+            }
+            Self::Supertypes | Self::Subtypes => {
+                "No type hierarchy found for the symbol under the cursor"
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum HierarchyItem {
+    Call(CallHierarchyItem),
+    Type(TypeHierarchyItem),
+}
+
+impl HierarchyItem {
+    fn name(&self) -> &str {
+        match self {
+            Self::Call(item) => &item.name,
+            Self::Type(item) => &item.name,
+        }
+    }
+
+    fn detail(&self) -> Option<&String> {
+        match self {
+            Self::Call(item) => item.detail.as_ref(),
+            Self::Type(item) => item.detail.as_ref(),
+        }
+    }
+
+    fn location(&self) -> Location {
+        match self {
+            Self::Call(item) => Location {
+                buffer: item.buffer.clone(),
+                range: item.selection_range.clone(),
+            },
+            Self::Type(item) => Location {
+                buffer: item.buffer.clone(),
+                range: item.selection_range.clone(),
+            },
+        }
+    }
+}
+
+struct HierarchyChild {
+    item: HierarchyItem,
+    call_sites: Vec<Location>,
+}
+
+struct HierarchyNode {
+    item: HierarchyItem,
+    /// Where the calls between this node and its parent happen, empty for the roots
+    /// and for type hierarchies.
+    call_sites: Vec<Location>,
+    depth: usize,
+    expanded: bool,
+    children: NodeChildren,
+}
+
+enum NodeChildren {
+    Unresolved,
+    Loading { _task: Task<()> },
+    Resolved(Vec<usize>),
+}
+
+/// A tree of the callers, callees, supertypes or subtypes of the symbols a hierarchy was
+/// requested for.
+///
+/// Children are requested from the language server lazily, when a node is expanded.
+pub struct HierarchyView {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    origin_pane: WeakEntity<Pane>,
+    kind: HierarchyKind,
+    roots: Vec<HierarchyItem>,
+    nodes: Vec<HierarchyNode>,
+    visible_entries: Vec<usize>,
+    selected_node: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl HierarchyView {
+    fn deploy(
+        workspace: &mut Workspace,
+        kind: HierarchyKind,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+            let head = editor.selections.newest_anchor().head();
+            editor.buffer().read(cx).text_anchor_for_position(head, cx)
+        }) else {
+            return;
+        };
+        let project = workspace.project().clone();
+        let origin_pane = workspace.active_pane().downgrade();
+        let prepare: Task<Result<Vec<HierarchyItem>>> =
+            project.update(cx, |project, cx| match kind {
+                HierarchyKind::IncomingCalls | HierarchyKind::OutgoingCalls => {
+                    let items = project.prepare_call_hierarchy(&buffer, position, cx);
+                    cx.spawn(async move |_, _| {
+                        Ok(items.await?.into_iter().map(HierarchyItem::Call).collect())
+                    })
+                }
+                HierarchyKind::Supertypes | HierarchyKind::Subtypes => {
+                    let items = project.prepare_type_hierarchy(&buffer, position, cx);
+                    cx.spawn(async move |_, _| {
+                        // Type hierarchy support can't be told from the server capabilities, and
+                        // servers without it answer with an error, so treat it as no types found.
+                        let items = items.await.log_err().unwrap_or_default();
+                        Ok(items.into_iter().map(HierarchyItem::Type).collect())
+                    })
+                }
+            });
+        cx.spawn_in(window, async move |workspace, cx| {
+            let roots = prepare.await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                if roots.is_empty() {
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<HierarchyView>(),
+                            kind.not_found_message(),
+                        )
+                        .autohide(),
+                        cx,
+                    );
+                    return;
+                }
+
+                let existing = workspace
+                    .panes()
+                    .iter()
+                    .find_map(|pane| pane.read(cx).items_of_type::<HierarchyView>().next());
+                if let Some(existing) = existing {
+                    existing.update(cx, |view, cx| {
+                        view.origin_pane = origin_pane;
+                        view.set_roots(roots, kind, cx);
+                    });
+                    workspace.activate_item(&existing, true, true, window, cx);
+                } else {
+                    let workspace_handle = cx.entity().downgrade();
+                    let view = cx.new(|cx| {
+                        Self::new(roots, kind, project, workspace_handle, origin_pane, cx)
+                    });
+                    workspace.split_item(SplitDirection::Right, Box::new(view), window, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn new(
+        roots: Vec<HierarchyItem>,
+        kind: HierarchyKind,
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        origin_pane: WeakEntity<Pane>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            project,
+            workspace,
+            origin_pane,
+            kind,
+            roots: Vec::new(),
+            nodes: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_node: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        };
+        this.set_roots(roots, kind, cx);
+        this
+    }
+
+    fn set_roots(
+        &mut self,
+        roots: Vec<HierarchyItem>,
+        kind: HierarchyKind,
+        cx: &mut Context<Self>,
+    ) {
+        self.kind = kind;
+        self.roots = roots;
+        self.nodes = self
+            .roots
+            .iter()
+            .map(|item| HierarchyNode {
+                item: item.clone(),
+                call_sites: Vec::new(),
+                depth: 0,
+                expanded: false,
+                children: NodeChildren::Unresolved,
+            })
+            .collect();
+        self.selected_node = (!self.nodes.is_empty()).then_some(0);
+        for root_ix in 0..self.nodes.len() {
+            self.expand_node(root_ix, cx);
+        }
+        self.update_visible_entries(cx);
+        cx.emit(ItemEvent::UpdateTab);
+    }
+
+    fn toggle_direction(&mut self, _: &ToggleDirection, _: &mut Window, cx: &mut Context<Self>) {
+        let roots = self.roots.clone();
+        self.set_roots(roots, self.kind.toggled(), cx);
+    }
+
+    fn expand_node(&mut self, node_ix: usize, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get_mut(node_ix) else {
+            return;
+        };
+        node.expanded = true;
+        if !matches!(node.children, NodeChildren::Unresolved) {
+            return;
+        }
+
+        let item = node.item.clone();
+        let children = self.fetch_children(&item, cx);
+        let task = cx.spawn(async move |this, cx| {
+            let children = children.await.log_err().unwrap_or_default();
+            this.update(cx, |this, cx| {
+                this.insert_children(node_ix, children);
+                this.update_visible_entries(cx);
+            })
+            .ok();
+        });
+        if let Some(node) = self.nodes.get_mut(node_ix) {
+            node.children = NodeChildren::Loading { _task: task };
+        }
+    }
+
+    fn fetch_children(
+        &self,
+        item: &HierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<HierarchyChild>>> {
+        match (item, self.kind) {
+            (
+                HierarchyItem::Call(item),
+                HierarchyKind::IncomingCalls | HierarchyKind::OutgoingCalls,
+            ) => {
+                let calls = self.project.update(cx, |project, cx| {
+                    if self.kind == HierarchyKind::IncomingCalls {
+                        project.incoming_calls(item, cx)
+                    } else {
+                        project.outgoing_calls(item, cx)
+                    }
+                });
+                cx.spawn(async move |_, _| {
+                    Ok(calls
+                        .await?
+                        .into_iter()
+                        .map(|call| HierarchyChild {
+                            item: HierarchyItem::Call(call.item),
+                            call_sites: call.call_sites,
+                        })
+                        .collect())
+                })
+            }
+            (HierarchyItem::Type(item), HierarchyKind::Supertypes | HierarchyKind::Subtypes) => {
+                let types = self.project.update(cx, |project, cx| {
+                    if self.kind == HierarchyKind::Supertypes {
+                        project.supertypes(item, cx)
+                    } else {
+                        project.subtypes(item, cx)
+                    }
+                });
+                cx.spawn(async move |_, _| {
+                    Ok(types
+                        .await?
+                        .into_iter()
+                        .map(|item| HierarchyChild {
+                            item: HierarchyItem::Type(item),
+                            call_sites: Vec::new(),
+                        })
+                        .collect())
+                })
+            }
+            _ => Task::ready(Ok(Vec::new())),
+        }
+    }
+
+    fn insert_children(&mut self, parent_ix: usize, children: Vec<HierarchyChild>) {
+        let depth = self.nodes[parent_ix].depth + 1;
+        let first_child_ix = self.nodes.len();
+        self.nodes
+            .extend(children.into_iter().map(|child| HierarchyNode {
+                item: child.item,
+                call_sites: child.call_sites,
+                depth,
+                expanded: false,
+                children: NodeChildren::Unresolved,
+            }));
+        self.nodes[parent_ix].children =
+            NodeChildren::Resolved((first_child_ix..self.nodes.len()).collect());
+    }
+
+    fn toggle_expanded(&mut self, node_ix: usize, cx: &mut Context<Self>) {
+        if self.nodes[node_ix].expanded {
+            self.nodes[node_ix].expanded = false;
+        } else {
+            self.expand_node(node_ix, cx);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        self.visible_entries.clear();
+        let roots = self.nodes.iter().take_while(|node| node.depth == 0).count();
+        let mut stack = (0..roots).rev().collect::<Vec<_>>();
+        while let Some(node_ix) = stack.pop() {
+            self.visible_entries.push(node_ix);
+            let node = &self.nodes[node_ix];
+            if node.expanded
+                && let NodeChildren::Resolved(children) = &node.children
+            {
+                stack.extend(children.iter().rev().copied());
+            }
+        }
+
+        if self
+            .selected_node
+            .is_some_and(|node_ix| !self.visible_entries.contains(&node_ix))
+        {
+            self.selected_node = self.visible_entries.first().copied();
+        }
+        cx.notify();
+    }
+
+    fn selected_entry(&self) -> Option<usize> {
+        let selected_node = self.selected_node?;
+        self.visible_entries
+            .iter()
+            .position(|node_ix| *node_ix == selected_node)
+    }
+
+    fn select_entry(&mut self, entry_ix: usize, cx: &mut Context<Self>) {
+        if let Some(node_ix) = self.visible_entries.get(entry_ix) {
+            self.selected_node = Some(*node_ix);
+            self.scroll_handle
+                .scroll_to_item(entry_ix, ScrollStrategy::Center);
+            cx.notify();
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next = match self.selected_entry() {
+            Some(entry_ix) if entry_ix + 1 < self.visible_entries.len() => entry_ix + 1,
+            _ => 0,
+        };
+        self.select_entry(next, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous = match self.selected_entry() {
+            Some(entry_ix) if entry_ix > 0 => entry_ix - 1,
+            _ => self.visible_entries.len().saturating_sub(1),
+        };
+        self.select_entry(previous, cx);
+    }
+
+    fn select_first(&mut self, _: &menu::SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_entry(0, cx);
+    }
+
+    fn select_last(&mut self, _: &menu::SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_entry(self.visible_entries.len().saturating_sub(1), cx);
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(node_ix) = self.selected_node else {
+            return;
+        };
+        if self.nodes[node_ix].expanded {
+            if let NodeChildren::Resolved(children) = &self.nodes[node_ix].children
+                && let Some(first_child) = children.first()
+            {
+                self.selected_node = Some(*first_child);
+                cx.notify();
+            }
+        } else {
+            self.expand_node(node_ix, cx);
+            self.update_visible_entries(cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(node_ix) = self.selected_node else {
+            return;
+        };
+        if self.nodes[node_ix].expanded {
+            self.nodes[node_ix].expanded = false;
+            self.update_visible_entries(cx);
+        } else if let Some(parent_ix) = self.parent_of(node_ix) {
+            self.selected_node = Some(parent_ix);
+            cx.notify();
+        }
+    }
+
+    fn parent_of(&self, node_ix: usize) -> Option<usize> {
+        self.nodes.iter().position(|node| match &node.children {
+            NodeChildren::Resolved(children) => children.contains(&node_ix),
+            _ => false,
+        })
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(node_ix) = self.selected_node {
+            self.open_item(node_ix, window, cx);
+        }
+    }
+
+    fn secondary_confirm(
+        &mut self,
+        _: &menu::SecondaryConfirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(node_ix) = self.selected_node {
+            self.open_call_site(node_ix, window, cx);
+        }
+    }
+
+    fn open_item(&mut self, node_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let location = self.nodes[node_ix].item.location();
+        self.open_location(location, window, cx);
+    }
+
+    fn open_call_site(&mut self, node_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        match self.nodes[node_ix].call_sites.first().cloned() {
+            Some(call_site) => self.open_location(call_site, window, cx),
+            None => self.open_item(node_ix, window, cx),
+        }
+    }
+
+    fn open_location(&mut self, location: Location, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let range = {
+            let buffer = location.buffer.read(cx);
+            location.range.start.to_point(buffer)..location.range.end.to_point(buffer)
+        };
+        let origin_pane = self.origin_pane.upgrade();
+        workspace.update(cx, |workspace, cx| {
+            let pane = origin_pane
+                .filter(|pane| workspace.panes().contains(pane))
+                .unwrap_or_else(|| workspace.adjacent_pane(window, cx));
+            let editor = workspace.open_project_item::<Editor>(
+                pane,
+                location.buffer,
+                true,
+                true,
+                true,
+                true,
+                window,
+                cx,
+            );
+            editor.update(cx, |editor, cx| {
+                editor.change_selections(
+                    SelectionEffects::scroll(Autoscroll::center()),
+                    window,
+                    cx,
+                    |selections| selections.select_ranges([range]),
+                );
+            });
+        });
+    }
+
+    fn render_entries(
+        &mut self,
+        range: Range<usize>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        range
+            .filter_map(|entry_ix| {
+                let node_ix = *self.visible_entries.get(entry_ix)?;
+                let node = &self.nodes[node_ix];
+                let item_location = node.item.location();
+                let buffer = item_location.buffer.read(cx);
+                let file_name = buffer
+                    .file()
+                    .map(|file| file.file_name(cx).to_string())
+                    .unwrap_or_else(|| "untitled".to_string());
+                let Point { row, .. } = item_location.range.start.to_point(buffer);
+                let location = format!("{file_name}:{}", row + 1);
+                let toggle = match &node.children {
+                    NodeChildren::Resolved(children) if children.is_empty() => None,
+                    _ => Some(node.expanded),
+                };
+                let is_loading = matches!(node.children, NodeChildren::Loading { .. });
+                let call_count = node.call_sites.len();
+
+                Some(
+                    ListItem::new(("hierarchy-entry", entry_ix))
+                        .spacing(ListItemSpacing::Sparse)
+                        .indent_level(node.depth)
+                        .indent_step_size(px(12.))
+                        .toggle_state(self.selected_node == Some(node_ix))
+                        .toggle(toggle)
+                        .on_toggle(cx.listener(move |this, _, _, cx| {
+                            this.selected_node = Some(node_ix);
+                            this.toggle_expanded(node_ix, cx);
+                        }))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.selected_node = Some(node_ix);
+                            this.open_item(node_ix, window, cx);
+                        }))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(Label::new(node.item.name().to_string()))
+                                .when_some(node.item.detail().cloned(), |this, detail| {
+                                    this.child(
+                                        Label::new(detail)
+                                            .color(Color::Muted)
+                                            .size(LabelSize::Small)
+                                            .truncate(),
+                                    )
+                                })
+                                .child(
+                                    Label::new(location)
+                                        .color(Color::Muted)
+                                        .size(LabelSize::Small),
+                                ),
+                        )
+                        .end_slot::<AnyElement>(if is_loading {
+                            Some(
+                                Label::new("Loading…")
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small)
+                                    .into_any_element(),
+                            )
+                        } else if call_count > 1 {
+                            Some(
+                                Label::new(format!("{call_count} calls"))
+                                    .color(Color::Muted)
+                                    .size(LabelSize::Small)
+                                    .into_any_element(),
+                            )
+                        } else {
+                            None
+                        })
+                        .into_any_element(),
+                )
+            })
+            .collect()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let toggle_label = self.kind.toggled().label();
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(self.kind.label()).size(LabelSize::Small))
+            .child(
+                IconButton::new("toggle-direction", IconName::ArrowRightLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip(move |_window, cx| {
+                        Tooltip::for_action(format!("Show {toggle_label}"), &ToggleDirection, cx)
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_direction(&ToggleDirection, window, cx);
+                    })),
+            )
+    }
+}
+
+impl EventEmitter<ItemEvent> for HierarchyView {}
+
+impl Focusable for HierarchyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for HierarchyView {
+    type Event = ItemEvent;
+
+    fn tab_content_text(&self, _detail: usize, _: &App) -> SharedString {
+        match self.roots.as_slice() {
+            [root] => format!("{}: {}", self.kind.label(), root.name()).into(),
+            _ => self.kind.label().into(),
+        }
+    }
+
+    fn tab_icon(&self, _: &Window, _: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::ListTree))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Hierarchy View Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for HierarchyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("HierarchyView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::secondary_confirm))
+            .on_action(cx.listener(Self::toggle_direction))
+            .child(self.render_header(cx))
+            .child(
+                v_flex()
+                    .id("hierarchy-entries")
+                    .flex_grow()
+                    .overflow_hidden()
+                    .child(
+                        uniform_list(
+                            "hierarchy-entries",
+                            self.visible_entries.len(),
+                            cx.processor(Self::render_entries),
+                        )
+                        .p_1()
+                        .track_scroll(&self.scroll_handle)
+                        .flex_grow(),
+                    )
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::FakeLspAdapter;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use std::sync::Arc;
+    use util::{path, rel_path::rel_path};
+    use workspace::{AppState, MultiWorkspace};
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            crate::init(cx);
+            editor::init(cx);
+            state
+        })
+    }
+
+    fn range(line: u32, start: u32, end: u32) -> lsp::Range {
+        lsp::Range::new(
+            lsp::Position::new(line, start),
+            lsp::Position::new(line, end),
+        )
+    }
+
+    fn call_item(name: &str, line: u32) -> lsp::CallHierarchyItem {
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: None,
+            uri: lsp::Uri::from_file_path(path!("/dir/main.rs")).unwrap(),
+            range: range(line, 0, 15),
+            selection_range: range(line, 3, 4),
+            data: None,
+        }
+    }
+
+    fn type_item(name: &str, line: u32) -> lsp::TypeHierarchyItem {
+        lsp::TypeHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::INTERFACE,
+            tags: None,
+            detail: None,
+            uri: lsp::Uri::from_file_path(path!("/dir/main.rs")).unwrap(),
+            range: range(line, 0, 15),
+            selection_range: range(line, 6, 7),
+            data: None,
+        }
+    }
+
+    /// Opens `main.rs` with the cursor on the third line, with a fake language server answering
+    /// the hierarchy requests.
+    async fn open_main_rs(
+        text: &str,
+        capabilities: lsp::ServerCapabilities,
+        initializer: impl 'static + Send + Sync + Fn(&mut lsp::FakeLanguageServer),
+        cx: &mut TestAppContext,
+    ) -> (Entity<Workspace>, Entity<Editor>, &mut VisualTestContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "main.rs": text }))
+            .await;
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| {
+            project.languages().add(language::rust_lang());
+            project.languages().clone()
+        });
+        let _fake_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities,
+                initializer: Some(Box::new(initializer)),
+                ..FakeLspAdapter::default()
+            },
+        );
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = cx.read(|cx| multi_workspace.read(cx).workspace().clone());
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("main.rs")), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        cx.run_until_parked();
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
+                selections.select_ranges([Point::new(2, 3)..Point::new(2, 3)])
+            });
+        });
+        (workspace, editor, cx)
+    }
+
+    fn hierarchy_view(
+        workspace: &Entity<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> Entity<HierarchyView> {
+        workspace.read_with(cx, |workspace, cx| {
+            workspace
+                .panes()
+                .iter()
+                .find_map(|pane| pane.read(cx).items_of_type::<HierarchyView>().next())
+                .expect("no hierarchy view was opened")
+        })
+    }
+
+    /// The visible entries of the view, indented by their depth, with the selected one marked.
+    fn entries(view: &Entity<HierarchyView>, cx: &mut VisualTestContext) -> Vec<String> {
+        view.read_with(cx, |view, _| {
+            view.visible_entries
+                .iter()
+                .map(|&node_ix| {
+                    let node = &view.nodes[node_ix];
+                    let selected = if view.selected_node == Some(node_ix) {
+                        "> "
+                    } else {
+                        ""
+                    };
+                    format!("{}{selected}{}", "  ".repeat(node.depth), node.item.name())
+                })
+                .collect()
+        })
+    }
+
+    #[gpui::test]
+    async fn test_call_hierarchy(cx: &mut TestAppContext) {
+        let (workspace, editor, cx) = open_main_rs(
+            "fn a() { c(); }\nfn b() { c(); }\nfn c() { d(); }\nfn d() {}\n",
+            lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            |server| {
+                server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+                    |params, _| async move {
+                        assert_eq!(
+                            params.text_document_position_params.position,
+                            lsp::Position::new(2, 3)
+                        );
+                        Ok(Some(vec![call_item("c", 2)]))
+                    },
+                );
+                server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+                    |params, _| async move {
+                        let callers = match params.item.name.as_str() {
+                            "c" => vec![call_item("a", 0), call_item("b", 1)],
+                            _ => Vec::new(),
+                        };
+                        Ok(Some(
+                            callers
+                                .into_iter()
+                                .map(|from| lsp::CallHierarchyIncomingCall {
+                                    from_ranges: vec![range(from.range.start.line, 9, 10)],
+                                    from,
+                                })
+                                .collect(),
+                        ))
+                    },
+                );
+                server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+                    |params, _| async move {
+                        let callees = match params.item.name.as_str() {
+                            "c" => vec![call_item("d", 3)],
+                            _ => Vec::new(),
+                        };
+                        Ok(Some(
+                            callees
+                                .into_iter()
+                                .map(|to| lsp::CallHierarchyOutgoingCall {
+                                    to,
+                                    from_ranges: vec![range(2, 9, 10)],
+                                })
+                                .collect(),
+                        ))
+                    },
+                );
+            },
+            cx,
+        )
+        .await;
+
+        cx.dispatch_action(ShowIncomingCalls);
+        cx.run_until_parked();
+        let view = hierarchy_view(&workspace, cx);
+        assert_eq!(entries(&view, cx), vec!["> c", "  a", "  b"]);
+        assert_eq!(
+            cx.read(|cx| view.read(cx).tab_content_text(0, cx).to_string()),
+            "Incoming Calls: c"
+        );
+
+        // Expanding a caller without callers of its own leaves nothing to show under it.
+        view.update_in(cx, |view, window, cx| {
+            view.select_next(&menu::SelectNext, window, cx);
+            view.expand_selected_entry(&ExpandSelectedEntry, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(entries(&view, cx), vec!["c", "  > a", "  b"]);
+        view.read_with(cx, |view, _| {
+            assert!(matches!(
+                &view.nodes[1].children,
+                NodeChildren::Resolved(children) if children.is_empty()
+            ));
+        });
+
+        view.update_in(cx, |view, window, cx| {
+            view.collapse_selected_entry(&CollapseSelectedEntry, window, cx);
+            view.collapse_selected_entry(&CollapseSelectedEntry, window, cx);
+        });
+        assert_eq!(entries(&view, cx), vec!["> c", "  a", "  b"]);
+        view.update_in(cx, |view, window, cx| {
+            view.collapse_selected_entry(&CollapseSelectedEntry, window, cx);
+        });
+        assert_eq!(entries(&view, cx), vec!["> c"]);
+
+        view.update_in(cx, |view, window, cx| {
+            view.toggle_direction(&ToggleDirection, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(entries(&view, cx), vec!["> c", "  d"]);
+
+        // Confirming an entry selects its symbol in the editor the hierarchy was requested from.
+        view.update_in(cx, |view, window, cx| {
+            view.select_next(&menu::SelectNext, window, cx);
+            view.confirm(&menu::Confirm, window, cx);
+        });
+        cx.run_until_parked();
+        editor.update(cx, |editor, cx| {
+            let snapshot = editor.display_snapshot(cx);
+            assert_eq!(
+                editor.selections.newest::<Point>(&snapshot).range(),
+                Point::new(3, 3)..Point::new(3, 4)
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_type_hierarchy(cx: &mut TestAppContext) {
+        let (workspace, editor, cx) = open_main_rs(
+            "trait A {}\ntrait B: A {}\ntrait C: B {}\nstruct D;\nimpl C for D {}\n",
+            lsp::ServerCapabilities::default(),
+            |server| {
+                server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+                    |_, _| async move { Ok(Some(vec![type_item("C", 2)])) },
+                );
+                server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+                    |params, _| async move {
+                        Ok(Some(match params.item.name.as_str() {
+                            "C" => vec![type_item("B", 1)],
+                            "B" => vec![type_item("A", 0)],
+                            _ => Vec::new(),
+                        }))
+                    },
+                );
+                server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+                    |params, _| async move {
+                        Ok(Some(match params.item.name.as_str() {
+                            "C" => vec![type_item("D", 3)],
+                            _ => Vec::new(),
+                        }))
+                    },
+                );
+            },
+            cx,
+        )
+        .await;
+
+        cx.dispatch_action(ShowSupertypes);
+        cx.run_until_parked();
+        let view = hierarchy_view(&workspace, cx);
+        assert_eq!(entries(&view, cx), vec!["> C", "  B"]);
+
+        view.update_in(cx, |view, window, cx| {
+            view.select_last(&menu::SelectLast, window, cx);
+            view.expand_selected_entry(&ExpandSelectedEntry, window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(entries(&view, cx), vec!["C", "  > B", "    A"]);
+
+        // Asking for another hierarchy from the editor reuses the open view.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.activate_item(&editor, true, true, window, cx);
+        });
+        cx.dispatch_action(ShowSubtypes);
+        cx.run_until_parked();
+        assert_eq!(hierarchy_view(&workspace, cx), view);
+        assert_eq!(entries(&view, cx), vec!["> C", "  D"]);
+    }
+}
@@ -4516,6 +4516,101 @@ impl BufferSnapshot {
             .filter_map(|(range, obj)| (obj == TextObject::InsideFunction).then_some(range))
     }
 
+    /// Returns the parts of `range` that hold prose rather than code: comments, strings and markup
+    /// text, as captured by each language's highlights query. Escape sequences, code spans and URIs
+    /// within those are left out. Buffers without a grammar are prose throughout.
+    pub fn prose_ranges(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        if self
+            .language()
+            .is_none_or(|language| language.grammar().is_none())
+        {
+            return vec![range];
+        }
+
+        let captures = self.syntax.captures(range.clone(), self, |grammar| {
+            grammar
+                .highlights_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+        let capture_names = captures
+            .grammars()
+            .iter()
+            .map(|&grammar| {
+                grammar
+                    .highlights_config
+                    .as_ref()
+                    .map(|config| config.query.capture_names())
+            })
+            .collect::<Vec<_>>();
+
+        let mut prose_ranges = Vec::<Range<usize>>::new();
+        let mut code_ranges = Vec::new();
+        for capture in captures {
+            let Some(name) = capture_names[capture.grammar_index]
+                .and_then(|names| names.get(capture.index as usize))
+            else {
+                continue;
+            };
+            let capture_range = capture.node.byte_range();
+            if is_capture_in(
+                name,
+                &[
+                    "string.escape",
+                    "string.regex",
+                    "string.special",
+                    "text.literal",
+                    "link_uri",
+                ],
+            ) {
+                code_ranges.push(capture_range);
+            } else if is_capture_in(
+                name,
+                &[
+                    "comment",
+                    "string",
+                    "text",
+                    "title",
+                    "emphasis",
+                    "link_text",
+                ],
+            ) {
+                prose_ranges.push(capture_range);
+            }
+        }
+
+        prose_ranges.sort_unstable_by_key(|prose_range| prose_range.start);
+        code_ranges.sort_unstable_by_key(|code_range| code_range.start);
+        let mut merged_ranges = Vec::<Range<usize>>::new();
+        for prose_range in prose_ranges {
+            match merged_ranges.last_mut() {
+                Some(last) if last.end >= prose_range.start => {
+                    last.end = last.end.max(prose_range.end);
+                }
+                _ => merged_ranges.push(prose_range),
+            }
+        }
+
+        let mut result = Vec::new();
+        for merged_range in merged_ranges {
+            let mut start = merged_range.start.max(range.start);
+            let end = merged_range.end.min(range.end);
+            for code_range in &code_ranges {
+                if code_range.end <= start || code_range.start >= end {
+                    continue;
+                }
+                if code_range.start > start {
+                    result.push(start..code_range.start);
+                }
+                start = start.max(code_range.end);
+            }
+            if start < end {
+                result.push(start..end);
+            }
+        }
+        result
+    }
+
     /// For each grammar in the language, runs the provided
     /// [`tree_sitter::Query`] against the given range.
     pub fn matches(
@@ -5404,6 +5499,15 @@ fn indent_size_for_text(text: impl Iterator<Item = char>) -> IndentSize {
     result
 }
 
+/// Whether a highlight capture like `comment.doc` is one of `names`, or more specific than one of them.
+fn is_capture_in(capture_name: &str, names: &[&str]) -> bool {
+    names.iter().any(|name| {
+        capture_name
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
 impl Clone for BufferSnapshot {
     fn clone(&self) -> Self {
         Self {
@@ -1139,6 +1139,29 @@ fn test_text_objects(cx: &mut App) {
     )
 }
 
+#[gpui::test]
+fn test_prose_ranges(cx: &mut App) {
+    let text = indoc! {r#"
+        /* Helo wrold */
+        fn main() {
+            let greeting = "Hi thre\n";
+        }
+    "#};
+
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(rust_lang(), cx));
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+    let prose = snapshot
+        .prose_ranges(0..text.len())
+        .into_iter()
+        .map(|range| &text[range])
+        .collect::<Vec<_>>();
+    assert_eq!(prose, ["/* Helo wrold */", "\"Hi thre", "\""]);
+
+    let buffer = cx.new(|cx| Buffer::local(text, cx));
+    let snapshot = buffer.update(cx, |buffer, _| buffer.snapshot());
+    assert_eq!(snapshot.prose_ranges(3..10), [3..10]);
+}
+
 #[gpui::test]
 fn test_text_objects_with_has_parent_predicate(cx: &mut App) {
     use std::borrow::Cow;
@@ -7,7 +7,7 @@ use ec4rs::{
     property::{FinalNewline, IndentSize, IndentStyle, MaxLineLen, TabWidth, TrimTrailingWs},
 };
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
-use gpui::{App, Modifiers, SharedString};
+use gpui::{App, Global, Modifiers, SharedString};
 use itertools::{Either, Itertools};
 use settings::{DocumentFoldingRanges, DocumentSymbols, IntoGpui, SemanticTokens};
 
@@ -16,9 +16,10 @@ pub use settings::{
     Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
     RewrapBehavior, ShowWhitespaceSetting, SoftWrap, WordsCompletionMode,
 };
-use settings::{RegisterSetting, Settings, SettingsLocation, SettingsStore};
+use settings::{RegisterSetting, Settings, SettingsLocation, SettingsStore, WorktreeId};
 use shellexpand;
 use std::{borrow::Cow, num::NonZeroU32, path::Path, sync::Arc};
+use util::rel_path::RelPath;
 
 /// Returns the settings for the specified language from the provided file.
 pub fn language_settings<'a>(
@@ -172,6 +173,10 @@ pub struct LanguageSettings {
     ///
     /// Default: `true`
     pub word_diff_enabled: bool,
+    /// Whether to check the spelling of comments, strings and prose.
+    ///
+    /// Default: `false`
+    pub spell_check: bool,
     /// Whether to use tree-sitter bracket queries to detect and colorize the brackets in the editor.
     pub colorize_brackets: bool,
 }
@@ -484,13 +489,22 @@ impl AllLanguageSettings {
                 .read(cx)
                 .properties(location.worktree_id, location.path)
         });
+        let overrides = cx
+            .try_global::<LanguageSettingsOverrides>()
+            .map(|overrides| overrides.get(location))
+            .filter(|overrides| *overrides != LanguageSettingsOverride::default());
+        if editorconfig_properties.is_none() && overrides.is_none() {
+            return Cow::Borrowed(settings);
+        }
+
+        let mut settings = settings.clone();
         if let Some(editorconfig_properties) = editorconfig_properties {
-            let mut settings = settings.clone();
             merge_with_editorconfig(&mut settings, &editorconfig_properties);
-            Cow::Owned(settings)
-        } else {
-            Cow::Borrowed(settings)
         }
+        if let Some(overrides) = overrides {
+            overrides.apply(&mut settings);
+        }
+        Cow::Owned(settings)
     }
 
     /// Returns whether edit predictions are enabled for the given path.
@@ -510,6 +524,91 @@ impl AllLanguageSettings {
     }
 }
 
+/// Language settings changed at runtime, e.g. with vim's `:set` and `:setlocal`.
+///
+/// These take precedence over the settings files and `.editorconfig`, and last
+/// until they are cleared or Zed is restarted.
+#[derive(Default)]
+pub struct LanguageSettingsOverrides {
+    global: LanguageSettingsOverride,
+    files: HashMap<WorktreeId, HashMap<Arc<RelPath>, LanguageSettingsOverride>>,
+}
+
+impl Global for LanguageSettingsOverrides {}
+
+/// The subset of language settings that can be changed at runtime.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LanguageSettingsOverride {
+    pub tab_size: Option<NonZeroU32>,
+    pub hard_tabs: Option<bool>,
+    pub show_whitespaces: Option<ShowWhitespaceSetting>,
+    pub spell_check: Option<bool>,
+}
+
+impl LanguageSettingsOverride {
+    fn merge(&mut self, other: &Self) {
+        self.tab_size = other.tab_size.or(self.tab_size);
+        self.hard_tabs = other.hard_tabs.or(self.hard_tabs);
+        self.show_whitespaces = other.show_whitespaces.or(self.show_whitespaces);
+        self.spell_check = other.spell_check.or(self.spell_check);
+    }
+
+    fn apply(&self, settings: &mut LanguageSettings) {
+        if let Some(tab_size) = self.tab_size {
+            settings.tab_size = tab_size;
+        }
+        if let Some(hard_tabs) = self.hard_tabs {
+            settings.hard_tabs = hard_tabs;
+        }
+        if let Some(show_whitespaces) = self.show_whitespaces {
+            settings.show_whitespaces = show_whitespaces;
+        }
+        if let Some(spell_check) = self.spell_check {
+            settings.spell_check = spell_check;
+        }
+    }
+}
+
+impl LanguageSettingsOverrides {
+    fn get(&self, location: Option<SettingsLocation>) -> LanguageSettingsOverride {
+        let mut result = self.global.clone();
+        if let Some(location) = location
+            && let Some(file) = self
+                .files
+                .get(&location.worktree_id)
+                .and_then(|files| files.get(location.path))
+        {
+            result.merge(file);
+        }
+        result
+    }
+
+    /// Updates the overrides that apply to every buffer.
+    pub fn update_global(cx: &mut App, update: impl FnOnce(&mut LanguageSettingsOverride)) {
+        update(&mut cx.default_global::<Self>().global);
+        Self::notify(cx);
+    }
+
+    /// Updates the overrides that apply to buffers for the given file.
+    pub fn update_for_file(
+        file: &Arc<dyn File>,
+        cx: &mut App,
+        update: impl FnOnce(&mut LanguageSettingsOverride),
+    ) {
+        let worktree_id = file.worktree_id(cx);
+        let path = file.path().clone();
+        let files = cx.default_global::<Self>().files.entry(worktree_id);
+        let file_overrides = files.or_default().entry(path).or_default();
+        update(file_overrides);
+        Self::notify(cx);
+    }
+
+    fn notify(cx: &mut App) {
+        // Overrides are read alongside the settings, so settings observers need to hear about them.
+        SettingsStore::update(cx, |_, _| {});
+    }
+}
+
 fn merge_with_editorconfig(settings: &mut LanguageSettings, cfg: &EditorconfigProperties) {
     let preferred_line_length = cfg.get::<MaxLineLen>().ok().and_then(|v| match v {
         MaxLineLen::Value(u) => Some(u as u32),
@@ -650,6 +749,7 @@ impl settings::Settings for AllLanguageSettings {
                 },
                 debuggers: settings.debuggers.unwrap(),
                 word_diff_enabled: settings.word_diff_enabled.unwrap(),
+                spell_check: settings.spell_check.unwrap(),
             }
         }
 
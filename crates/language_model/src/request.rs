@@ -450,6 +450,8 @@ pub struct LanguageModelRequest {
     pub tool_choice: Option<LanguageModelToolChoice>,
     pub stop: Vec<String>,
     pub temperature: Option<f32>,
+    /// The most tokens to generate, when fewer than the model's own limit.
+    pub max_tokens: Option<u64>,
     pub thinking_allowed: bool,
     pub thinking_effort: Option<String>,
 }
@@ -689,7 +689,9 @@ pub fn into_anthropic(
     anthropic::Request {
         model,
         messages: new_messages,
-        max_tokens: max_output_tokens,
+        max_tokens: request.max_tokens.map_or(max_output_tokens, |max_tokens| {
+            max_tokens.min(max_output_tokens)
+        }),
         system: if system_message.is_empty() {
             None
         } else {
@@ -1075,6 +1077,27 @@ mod tests {
     use anthropic::AnthropicModelMode;
     use language_model::{LanguageModelRequestMessage, MessageContent};
 
+    #[test]
+    fn test_max_tokens_is_capped_at_the_model_limit() {
+        let max_tokens = |max_tokens| {
+            let request = LanguageModelRequest {
+                max_tokens,
+                ..Default::default()
+            };
+            into_anthropic(
+                request,
+                "claude-3-5-sonnet".to_string(),
+                0.7,
+                4096,
+                AnthropicModelMode::Default,
+            )
+            .max_tokens
+        };
+        assert_eq!(max_tokens(None), 4096);
+        assert_eq!(max_tokens(Some(100)), 100);
+        assert_eq!(max_tokens(Some(10_000)), 4096);
+    }
+
     #[test]
     fn test_cache_control_only_on_last_segment() {
         let request = LanguageModelRequest {
@@ -1095,6 +1118,7 @@ mod tests {
             intent: None,
             stop: vec![],
             temperature: None,
+            max_tokens: None,
             tools: vec![],
             tool_choice: None,
             thinking_allowed: true,
@@ -1158,6 +1182,7 @@ mod tests {
             intent: None,
             stop: vec![],
             temperature: None,
+            max_tokens: None,
             tools: vec![],
             tool_choice: None,
             thinking_allowed: true,
@@ -951,7 +951,9 @@ pub fn into_bedrock(
     Ok(bedrock::Request {
         model,
         messages: new_messages,
-        max_tokens: max_output_tokens,
+        max_tokens: request.max_tokens.map_or(max_output_tokens, |max_tokens| {
+            max_tokens.min(max_output_tokens)
+        }),
         system: Some(system_message),
         tools: Some(tool_config),
         thinking: if request.thinking_allowed {
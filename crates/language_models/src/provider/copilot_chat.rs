@@ -921,6 +921,7 @@ fn into_copilot_responses(
         tool_choice,
         stop: _,
         temperature,
+        max_tokens: _,
         thinking_allowed: _,
         thinking_effort: _,
     } = request;
@@ -497,7 +497,11 @@ pub fn into_mistral(
         model: model.id().to_string(),
         messages,
         stream,
-        max_tokens: max_output_tokens,
+        max_tokens: request
+            .max_tokens
+            .into_iter()
+            .chain(max_output_tokens)
+            .min(),
         temperature: request.temperature,
         response_format: None,
         tool_choice: match request.tool_choice {
@@ -855,6 +859,7 @@ mod tests {
                 },
             ],
             temperature: Some(0.5),
+            max_tokens: None,
             tools: vec![],
             tool_choice: None,
             thread_id: None,
@@ -891,6 +896,7 @@ mod tests {
             tools: vec![],
             tool_choice: None,
             temperature: None,
+            max_tokens: None,
             thread_id: None,
             prompt_id: None,
             intent: None,
@@ -501,7 +501,11 @@ pub fn into_open_ai(
         stream,
         stop: request.stop,
         temperature: request.temperature.or(Some(1.0)),
-        max_completion_tokens: max_output_tokens,
+        max_completion_tokens: request
+            .max_tokens
+            .into_iter()
+            .chain(max_output_tokens)
+            .min(),
         parallel_tool_calls: if supports_parallel_tool_calls && !request.tools.is_empty() {
             // Disable parallel tool calls, as the Agent currently expects a maximum of one per turn.
             Some(false)
@@ -552,6 +556,7 @@ pub fn into_open_ai_response(
         tool_choice,
         stop: _,
         temperature,
+        max_tokens,
         thinking_allowed: _,
         thinking_effort: _,
     } = request;
@@ -577,7 +582,7 @@ pub fn into_open_ai_response(
         stream,
         temperature,
         top_p: None,
-        max_output_tokens,
+        max_output_tokens: max_tokens.into_iter().chain(max_output_tokens).min(),
         parallel_tool_calls: if tools.is_empty() {
             None
         } else {
@@ -1433,6 +1438,7 @@ mod tests {
             tool_choice: None,
             stop: vec![],
             temperature: None,
+            max_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
         };
@@ -1570,6 +1576,7 @@ mod tests {
             tool_choice: Some(LanguageModelToolChoice::Any),
             stop: vec!["<STOP>".into()],
             temperature: None,
+            max_tokens: None,
             thinking_allowed: false,
             thinking_effort: None,
         };
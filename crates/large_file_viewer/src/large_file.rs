@@ -0,0 +1,340 @@
+use std::{
+    ops::{ControlFlow, Range},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use fs::Fs;
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, Task};
+use project::{Project, ProjectEntryId, ProjectPath};
+use settings::Settings as _;
+
+use crate::LargeFileViewerSettings;
+
+/// How many bytes are read from disk at once, when indexing, reading lines or searching.
+const CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+/// The line index stores the offset of every line that is a multiple of this, so it stays small
+/// for files with hundreds of millions of lines, at the cost of skipping up to this many lines when reading.
+const LINES_PER_CHECKPOINT: u64 = 1024;
+/// Lines are cut off after this many bytes, so that files without newlines don't have to fit in memory.
+pub const MAX_LINE_LEN: usize = 16 * 1024;
+
+/// A file that is too large to be loaded into a buffer. Its lines are read from disk on demand.
+pub struct LargeFile {
+    fs: Arc<dyn Fs>,
+    abs_path: PathBuf,
+    project_path: ProjectPath,
+    entry_id: Option<ProjectEntryId>,
+    len: u64,
+    line_index: LineIndex,
+    _index_file: Task<()>,
+}
+
+pub enum LargeFileEvent {
+    /// More of the file was indexed, so the known line count grew.
+    Indexed,
+}
+
+impl EventEmitter<LargeFileEvent> for LargeFile {}
+
+impl LargeFile {
+    pub fn new(
+        fs: Arc<dyn Fs>,
+        abs_path: PathBuf,
+        project_path: ProjectPath,
+        entry_id: Option<ProjectEntryId>,
+        len: u64,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let index_file = cx.spawn({
+            let fs = fs.clone();
+            let abs_path = abs_path.clone();
+            async move |this, cx| {
+                let mut offset = 0;
+                while offset < len {
+                    let range = offset..(offset + CHUNK_SIZE).min(len);
+                    let chunk = match fs.load_byte_range(&abs_path, range).await {
+                        Ok(chunk) if !chunk.is_empty() => chunk,
+                        Ok(_) => break,
+                        Err(error) => {
+                            log::error!("failed to index {abs_path:?}: {error:#}");
+                            break;
+                        }
+                    };
+                    offset += chunk.len() as u64;
+                    let updated = this.update(cx, |this, cx| {
+                        this.line_index.push_chunk(&chunk);
+                        cx.emit(LargeFileEvent::Indexed);
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            fs,
+            abs_path,
+            project_path,
+            entry_id,
+            len,
+            line_index: LineIndex::new(),
+            _index_file: index_file,
+        }
+    }
+
+    pub fn abs_path(&self) -> &Path {
+        &self.abs_path
+    }
+
+    pub fn project_path(&self) -> &ProjectPath {
+        &self.project_path
+    }
+
+    /// The size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.len
+    }
+
+    /// The number of lines found so far. This grows while the file is being indexed.
+    pub fn line_count(&self) -> u64 {
+        self.line_index.line_count
+    }
+
+    pub fn is_indexed(&self) -> bool {
+        self.line_index.scanned >= self.len
+    }
+
+    /// Reads the lines in `rows`, each cut off after [`MAX_LINE_LEN`] bytes.
+    pub fn read_lines(&self, rows: Range<u64>, cx: &App) -> Task<Result<Vec<String>>> {
+        let (mut row, offset) = self.line_index.checkpoint_for_row(rows.start);
+        let fs = self.fs.clone();
+        let abs_path = self.abs_path.clone();
+        let len = self.len;
+        cx.background_spawn(async move {
+            let mut lines = Vec::new();
+            scan_lines(fs.as_ref(), &abs_path, offset..len, |line| {
+                if row >= rows.end {
+                    return ControlFlow::Break(());
+                }
+                if row >= rows.start {
+                    lines.push(String::from_utf8_lossy(line).into_owned());
+                }
+                row += 1;
+                ControlFlow::Continue(())
+            })
+            .await?;
+            Ok(lines)
+        })
+    }
+
+    /// Finds the first line at or after `start_row` that contains `query`, ignoring ASCII case.
+    pub fn find(&self, query: &str, start_row: u64, cx: &App) -> Task<Result<Option<u64>>> {
+        let (mut row, offset) = self.line_index.checkpoint_for_row(start_row);
+        let fs = self.fs.clone();
+        let abs_path = self.abs_path.clone();
+        let len = self.len;
+        let query = query.to_ascii_lowercase().into_bytes();
+        cx.background_spawn(async move {
+            if query.is_empty() {
+                return Ok(None);
+            }
+            let mut found = None;
+            let mut lowercase_line = Vec::new();
+            scan_lines(fs.as_ref(), &abs_path, offset..len, |line| {
+                if row >= start_row {
+                    lowercase_line.clear();
+                    lowercase_line.extend(line.iter().map(u8::to_ascii_lowercase));
+                    if lowercase_line
+                        .windows(query.len())
+                        .any(|window| window == query.as_slice())
+                    {
+                        found = Some(row);
+                        return ControlFlow::Break(());
+                    }
+                }
+                row += 1;
+                ControlFlow::Continue(())
+            })
+            .await?;
+            Ok(found)
+        })
+    }
+}
+
+impl project::ProjectItem for LargeFile {
+    fn try_open(
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        cx: &mut App,
+    ) -> Option<Task<Result<Entity<Self>>>> {
+        let threshold = LargeFileViewerSettings::get_global(cx).threshold_bytes();
+        let project = project.read(cx);
+        // Remote files are loaded by the host, which does not page them.
+        if !project.is_local() {
+            return None;
+        }
+        let entry = project.entry_for_path(path, cx)?;
+        if !entry.is_file() || entry.size < threshold {
+            return None;
+        }
+        let abs_path = project.absolute_path(path, cx)?;
+        let fs = project.fs().clone();
+        let (entry_id, len) = (entry.id, entry.size);
+        let path = path.clone();
+        Some(Task::ready(Ok(cx.new(|cx| {
+            LargeFile::new(fs, abs_path, path, Some(entry_id), len, cx)
+        }))))
+    }
+
+    fn entry_id(&self, _: &App) -> Option<ProjectEntryId> {
+        self.entry_id
+    }
+
+    fn project_path(&self, _: &App) -> Option<ProjectPath> {
+        Some(self.project_path.clone())
+    }
+
+    fn is_dirty(&self) -> bool {
+        false
+    }
+}
+
+/// Offsets of every [`LINES_PER_CHECKPOINT`]th line, found by scanning the file front to back.
+struct LineIndex {
+    checkpoints: Vec<u64>,
+    line_count: u64,
+    scanned: u64,
+}
+
+impl LineIndex {
+    fn new() -> Self {
+        Self {
+            checkpoints: vec![0],
+            line_count: 1,
+            scanned: 0,
+        }
+    }
+
+    fn push_chunk(&mut self, chunk: &[u8]) {
+        for (ix, _) in chunk.iter().enumerate().filter(|(_, byte)| **byte == b'\n') {
+            if self.line_count.is_multiple_of(LINES_PER_CHECKPOINT) {
+                self.checkpoints.push(self.scanned + ix as u64 + 1);
+            }
+            self.line_count += 1;
+        }
+        self.scanned += chunk.len() as u64;
+    }
+
+    /// The closest indexed line at or before `row`, and its offset.
+    fn checkpoint_for_row(&self, row: u64) -> (u64, u64) {
+        let ix = ((row / LINES_PER_CHECKPOINT) as usize).min(self.checkpoints.len() - 1);
+        (ix as u64 * LINES_PER_CHECKPOINT, self.checkpoints[ix])
+    }
+}
+
+/// Calls `f` with each line in `range` of the file, without its line ending.
+async fn scan_lines(
+    fs: &dyn Fs,
+    path: &Path,
+    range: Range<u64>,
+    mut f: impl FnMut(&[u8]) -> ControlFlow<()>,
+) -> Result<()> {
+    let mut line = Vec::new();
+    let mut offset = range.start;
+    while offset < range.end {
+        let chunk = fs
+            .load_byte_range(path, offset..(offset + CHUNK_SIZE).min(range.end))
+            .await?;
+        if chunk.is_empty() {
+            break;
+        }
+        offset += chunk.len() as u64;
+        let mut rest = chunk.as_slice();
+        while let Some(newline_ix) = rest.iter().position(|byte| *byte == b'\n') {
+            push_truncated(&mut line, &rest[..newline_ix]);
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            if f(&line).is_break() {
+                return Ok(());
+            }
+            line.clear();
+            rest = &rest[newline_ix + 1..];
+        }
+        push_truncated(&mut line, rest);
+    }
+    _ = f(&line);
+    Ok(())
+}
+
+fn push_truncated(line: &mut Vec<u8>, bytes: &[u8]) {
+    let remaining = MAX_LINE_LEN.saturating_sub(line.len());
+    line.extend_from_slice(&bytes[..bytes.len().min(remaining)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use project::WorktreeId;
+    use serde_json::json;
+    use util::{path, rel_path::rel_path};
+
+    #[gpui::test]
+    async fn test_large_file_lines(cx: &mut TestAppContext) {
+        let text = (0..3000)
+            .map(|row| format!("line {row}\r\n"))
+            .collect::<String>();
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ "log.txt": text.clone() }))
+            .await;
+
+        let file = cx.new(|cx| {
+            LargeFile::new(
+                fs.clone(),
+                PathBuf::from(path!("/dir/log.txt")),
+                ProjectPath {
+                    worktree_id: WorktreeId::from_usize(0),
+                    path: rel_path("log.txt").into(),
+                },
+                None,
+                text.len() as u64,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        file.read_with(cx, |file, _| {
+            assert!(file.is_indexed());
+            assert_eq!(file.line_count(), 3001);
+            assert_eq!(file.line_index.checkpoints.len(), 3);
+        });
+
+        let lines = file
+            .read_with(cx, |file, cx| file.read_lines(2046..2050, cx))
+            .await
+            .unwrap();
+        assert_eq!(lines, ["line 2046", "line 2047", "line 2048", "line 2049"]);
+
+        let lines = file
+            .read_with(cx, |file, cx| file.read_lines(2998..3005, cx))
+            .await
+            .unwrap();
+        assert_eq!(lines, ["line 2998", "line 2999", ""]);
+
+        let found = file
+            .read_with(cx, |file, cx| file.find("LINE 12", 200, cx))
+            .await
+            .unwrap();
+        assert_eq!(found, Some(1200));
+
+        let found = file
+            .read_with(cx, |file, cx| file.find("line 12", 1300, cx))
+            .await
+            .unwrap();
+        assert_eq!(found, None);
+    }
+}
@@ -0,0 +1,371 @@
+mod large_file;
+mod large_file_viewer_settings;
+mod page_cache;
+
+use std::{ops::Range, path::Path};
+
+use editor::Editor;
+use file_icons::FileIcons;
+use gpui::{
+    AnyElement, App, Context, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, ListHorizontalSizingBehavior, ParentElement, Render, ScrollStrategy, SharedString,
+    Styled, Subscription, Task, UniformListScrollHandle, Window, actions, uniform_list,
+};
+use project::Project;
+use settings::Settings;
+use theme::ThemeSettings;
+use ui::prelude::*;
+use util::{ResultExt as _, paths::PathExt, size::format_file_size};
+use workspace::{
+    ItemSettings, Pane,
+    invalid_item_view::InvalidItemView,
+    item::{Item, ItemBufferKind, ProjectItem, TabContentParams},
+};
+
+pub use crate::large_file::*;
+pub use crate::large_file_viewer_settings::*;
+pub use crate::page_cache::*;
+
+actions!(
+    large_file_viewer,
+    [
+        /// Focuses the input for jumping to a line.
+        GoToLine,
+        /// Focuses the input for finding text in the file.
+        Find,
+        /// Jumps to the next line containing the text being found.
+        FindNext
+    ]
+);
+
+/// Lines are read from disk and cached in pages of this many lines.
+const LINES_PER_PAGE: u64 = 256;
+/// Pages furthest from the one last loaded are dropped when more than this many are cached.
+const MAX_CACHED_PAGES: usize = 64;
+
+/// A read-only view of a [`LargeFile`], which only keeps the lines around the visible ones in memory.
+/// It has no syntax highlighting or language server support, as those need the whole file in a buffer.
+pub struct LargeFileView {
+    file: Entity<LargeFile>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pages: PageCache<Vec<SharedString>>,
+    line_editor: Entity<Editor>,
+    find_editor: Entity<Editor>,
+    highlighted_row: Option<u64>,
+    find_status: Option<SharedString>,
+    pending_find: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl LargeFileView {
+    pub fn new(file: Entity<LargeFile>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let line_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Go to line…", window, cx);
+            editor
+        });
+        let find_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Find…", window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&file, |_, _, event, cx| match event {
+            LargeFileEvent::Indexed => cx.notify(),
+        });
+        Self {
+            file,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            pages: PageCache::new(MAX_CACHED_PAGES),
+            line_editor,
+            find_editor,
+            highlighted_row: None,
+            find_status: None,
+            pending_find: None,
+            _subscription: subscription,
+        }
+    }
+
+    fn go_to_line(&mut self, _: &GoToLine, window: &mut Window, cx: &mut Context<Self>) {
+        self.line_editor.update(cx, |editor, cx| {
+            editor.select_all(&Default::default(), window, cx);
+        });
+        window.focus(&self.line_editor.focus_handle(cx), cx);
+    }
+
+    fn find(&mut self, _: &Find, window: &mut Window, cx: &mut Context<Self>) {
+        self.find_editor.update(cx, |editor, cx| {
+            editor.select_all(&Default::default(), window, cx);
+        });
+        window.focus(&self.find_editor.focus_handle(cx), cx);
+    }
+
+    fn confirm_go_to_line(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.line_editor.read(cx).text(cx);
+        let Ok(line) = text.trim().parse::<u64>() else {
+            return;
+        };
+        let row = line
+            .saturating_sub(1)
+            .min(self.file.read(cx).line_count().saturating_sub(1));
+        self.highlight_row(row, cx);
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn find_next(&mut self, _: &FindNext, _: &mut Window, cx: &mut Context<Self>) {
+        let query = self.find_editor.read(cx).text(cx);
+        if query.is_empty() {
+            return;
+        }
+        let start_row = self.highlighted_row.map_or_else(
+            || self.scroll_handle.logical_scroll_top_index() as u64,
+            |row| row + 1,
+        );
+        let find = self.file.read(cx).find(&query, start_row, cx);
+        self.find_status = Some("Searching…".into());
+        self.pending_find = Some(cx.spawn(async move |this, cx| {
+            let found = find.await;
+            this.update(cx, |this, cx| {
+                this.find_status = match found {
+                    Ok(Some(row)) => {
+                        this.highlight_row(row, cx);
+                        None
+                    }
+                    Ok(None) => Some("No more matches".into()),
+                    Err(error) => Some(format!("Search failed: {error}").into()),
+                };
+                cx.notify();
+            })
+            .log_err();
+        }));
+        cx.notify();
+    }
+
+    fn highlight_row(&mut self, row: u64, cx: &mut Context<Self>) {
+        self.highlighted_row = Some(row);
+        self.scroll_handle
+            .scroll_to_item(row as usize, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn load_page(&mut self, page: u64, cx: &mut Context<Self>) {
+        if !self.pages.start_loading(page) {
+            return;
+        }
+        let rows = page * LINES_PER_PAGE..(page + 1) * LINES_PER_PAGE;
+        let read_lines = self.file.read(cx).read_lines(rows, cx);
+        cx.spawn(async move |this, cx| {
+            let lines = read_lines.await;
+            this.update(cx, |this, cx| {
+                let lines = lines
+                    .log_err()
+                    .map(|lines| lines.into_iter().map(SharedString::from).collect());
+                this.pages.finish_loading(page, lines);
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_lines(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        let line_number_width = self.file.read(cx).line_count().to_string().len();
+        let highlight = cx.theme().colors().editor_active_line_background;
+        range
+            .map(|row| {
+                let row = row as u64;
+                let page = row / LINES_PER_PAGE;
+                let text = match self.pages.get(page) {
+                    Some(lines) => lines
+                        .get((row % LINES_PER_PAGE) as usize)
+                        .cloned()
+                        .unwrap_or_default(),
+                    None => {
+                        self.load_page(page, cx);
+                        SharedString::default()
+                    }
+                };
+                h_flex()
+                    .id(row as usize)
+                    .gap_4()
+                    .px_2()
+                    .when(self.highlighted_row == Some(row), |this| this.bg(highlight))
+                    .child(
+                        Label::new(format!("{:>line_number_width$}", row + 1))
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(div().whitespace_nowrap().child(text))
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_header(&self, cx: &Context<Self>) -> impl IntoElement {
+        let file = self.file.read(cx);
+        let mut status = format!(
+            "Read-only large file mode · {} · {} lines",
+            format_file_size(file.size(), false),
+            file.line_count()
+        );
+        if !file.is_indexed() {
+            status.push_str(" (indexing…)");
+        }
+        let input = |editor: &Entity<Editor>| {
+            div()
+                .w(rems(14.))
+                .px_2()
+                .py_1()
+                .border_1()
+                .rounded_md()
+                .border_color(cx.theme().colors().border)
+                .child(editor.clone())
+        };
+
+        h_flex()
+            .p_2()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(status)
+                    .color(Color::Muted)
+                    .size(LabelSize::Small),
+            )
+            .child(
+                h_flex()
+                    .gap_2()
+                    .children(
+                        self.find_status
+                            .clone()
+                            .map(|status| Label::new(status).size(LabelSize::Small)),
+                    )
+                    .child(input(&self.line_editor).on_action(cx.listener(
+                        |this, _: &menu::Confirm, window, cx| this.confirm_go_to_line(window, cx),
+                    )))
+                    .child(input(&self.find_editor).on_action(cx.listener(
+                        |this, _: &menu::Confirm, window, cx| this.find_next(&FindNext, window, cx),
+                    ))),
+            )
+    }
+}
+
+impl Render for LargeFileView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let line_count = self.file.read(cx).line_count() as usize;
+        let settings = ThemeSettings::get_global(cx);
+        v_flex()
+            .key_context("LargeFileView")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::go_to_line))
+            .on_action(cx.listener(Self::find))
+            .on_action(cx.listener(Self::find_next))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_header(cx))
+            .child(
+                uniform_list(
+                    "large-file-lines",
+                    line_count,
+                    cx.processor(|this, range: Range<usize>, _window, cx| {
+                        this.render_lines(range, cx)
+                    }),
+                )
+                .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+                .track_scroll(&self.scroll_handle)
+                .flex_1()
+                .font(settings.buffer_font.clone())
+                .text_size(settings.buffer_font_size(cx)),
+            )
+    }
+}
+
+impl Focusable for LargeFileView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<()> for LargeFileView {}
+
+impl Item for LargeFileView {
+    type Event = ();
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        f(self.file.entity_id(), self.file.read(cx))
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let abs_path = self.file.read(cx).abs_path().compact();
+        Some(abs_path.to_string_lossy().into_owned().into())
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .single_line()
+            .color(params.text_color())
+            .when(params.preview, |this| this.italic())
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _: usize, cx: &App) -> SharedString {
+        self.file
+            .read(cx)
+            .abs_path()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned().into())
+            .unwrap_or_default()
+    }
+
+    fn tab_icon(&self, _: &Window, cx: &App) -> Option<Icon> {
+        let path = self.file.read(cx).abs_path();
+        ItemSettings::get_global(cx)
+            .file_icons
+            .then(|| FileIcons::get_icon(path, cx))
+            .flatten()
+            .map(Icon::from_path)
+    }
+
+    fn buffer_kind(&self, _: &App) -> ItemBufferKind {
+        ItemBufferKind::Singleton
+    }
+}
+
+impl ProjectItem for LargeFileView {
+    type Item = LargeFile;
+
+    fn for_project_item(
+        _: Entity<Project>,
+        _: Option<&Pane>,
+        item: Entity<Self::Item>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(item, window, cx)
+    }
+
+    fn for_broken_project_item(
+        abs_path: &Path,
+        is_local: bool,
+        e: &anyhow::Error,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<InvalidItemView>
+    where
+        Self: Sized,
+    {
+        Some(InvalidItemView::new(abs_path, is_local, e, window, cx))
+    }
+}
+
+pub fn init(cx: &mut App) {
+    workspace::register_project_item::<LargeFileView>(cx);
+}
@@ -0,0 +1,79 @@
+use collections::{HashMap, HashSet};
+
+/// Holds the pages of a file that were read from disk for display, dropping the ones furthest
+/// from the page last loaded once more than `max_pages` are cached.
+pub struct PageCache<T> {
+    pages: HashMap<u64, T>,
+    loading: HashSet<u64>,
+    max_pages: usize,
+}
+
+impl<T> PageCache<T> {
+    pub fn new(max_pages: usize) -> Self {
+        Self {
+            pages: HashMap::default(),
+            loading: HashSet::default(),
+            max_pages,
+        }
+    }
+
+    pub fn get(&self, page: u64) -> Option<&T> {
+        self.pages.get(&page)
+    }
+
+    /// Marks `page` as being loaded, returning `false` if it is already cached or loading.
+    pub fn start_loading(&mut self, page: u64) -> bool {
+        !self.pages.contains_key(&page) && self.loading.insert(page)
+    }
+
+    /// Stores a page that was loaded. Pass `None` when loading failed, so it can be retried.
+    pub fn finish_loading(&mut self, page: u64, contents: Option<T>) {
+        self.loading.remove(&page);
+        let Some(contents) = contents else {
+            return;
+        };
+        self.pages.insert(page, contents);
+        while self.pages.len() > self.max_pages
+            && let Some(furthest_page) = self
+                .pages
+                .keys()
+                .copied()
+                .max_by_key(|cached_page| cached_page.abs_diff(page))
+        {
+            self.pages.remove(&furthest_page);
+        }
+    }
+
+    /// Drops all pages, e.g. after the file changed on disk.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+        self.loading.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_cache_eviction() {
+        let mut cache = PageCache::new(3);
+        for page in [0, 1, 2] {
+            assert!(cache.start_loading(page));
+            cache.finish_loading(page, Some(page * 10));
+        }
+        assert!(!cache.start_loading(1));
+
+        assert!(cache.start_loading(3));
+        assert!(!cache.start_loading(3));
+        cache.finish_loading(3, Some(30));
+        assert_eq!(cache.get(0), None);
+        assert_eq!(cache.get(1), Some(&10));
+        assert_eq!(cache.get(3), Some(&30));
+
+        assert!(cache.start_loading(7));
+        cache.finish_loading(7, None);
+        assert_eq!(cache.get(7), None);
+        assert!(cache.start_loading(7));
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use fs::{Fs, RemoveOptions};
+use futures::StreamExt as _;
+use gpui::{App, AppContext as _, Context, Entity, Global, Task};
+use sha2::{Digest as _, Sha256};
+use text::Rope;
+use time::OffsetDateTime;
+use util::ResultExt as _;
+
+/// The zstd level that snapshots are compressed with.
+const COMPRESSION_LEVEL: i32 = 3;
+/// Snapshots larger than this fraction of the maximum size aren't kept, so that a single huge
+/// file can't push out the history of every other file.
+const MAX_SNAPSHOT_FRACTION: u64 = 10;
+
+/// A version of a file, as it was when it was opened, saved or changed on disk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Where the compressed contents are stored.
+    pub path: PathBuf,
+    /// When the snapshot was taken, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    content_hash: u64,
+}
+
+impl Snapshot {
+    /// Parses the name of a snapshot file, `<timestamp_ms>-<content_hash>.zst`.
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.strip_suffix(".zst")?;
+        let (timestamp_ms, content_hash) = name.split_once('-')?;
+        Some(Self {
+            timestamp_ms: timestamp_ms.parse().ok()?,
+            content_hash: u64::from_str_radix(content_hash, 16).ok()?,
+            path,
+        })
+    }
+
+    pub fn timestamp(&self) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp_nanos(self.timestamp_ms as i128 * 1_000_000)
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+    }
+}
+
+/// Keeps compressed snapshots of files in a directory, with a subdirectory per file, and deletes
+/// the oldest ones when they take up more than `max_size` bytes.
+pub struct HistoryStore {
+    fs: Arc<dyn Fs>,
+    dir: PathBuf,
+    max_size: u64,
+    /// The size of all snapshots, once they've been counted.
+    total_size: Option<u64>,
+    prune_task: Option<Task<()>>,
+}
+
+struct GlobalHistoryStore(Entity<HistoryStore>);
+
+impl Global for GlobalHistoryStore {}
+
+impl HistoryStore {
+    pub fn new(fs: Arc<dyn Fs>, dir: PathBuf, max_size: u64) -> Self {
+        Self {
+            fs,
+            dir,
+            max_size,
+            total_size: None,
+            prune_task: None,
+        }
+    }
+
+    pub(crate) fn set_global(history_store: Entity<Self>, cx: &mut App) {
+        cx.set_global(GlobalHistoryStore(history_store));
+    }
+
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalHistoryStore>()
+            .map(|history_store| history_store.0.clone())
+    }
+
+    pub fn set_max_size(&mut self, max_size: u64, cx: &mut Context<Self>) {
+        if self.max_size != max_size {
+            self.max_size = max_size;
+            self.prune(cx);
+        }
+    }
+
+    /// The directory holding the snapshots of the file at `abs_path`.
+    fn file_dir(&self, abs_path: &Path) -> PathBuf {
+        let hash = Sha256::digest(abs_path.to_string_lossy().as_bytes());
+        self.dir.join(hex::encode(&hash[..16]))
+    }
+
+    /// Stores a snapshot of the file at `abs_path` with the given contents, unless they're the
+    /// same as in its latest snapshot or too large to keep.
+    pub fn record(
+        &mut self,
+        abs_path: &Path,
+        text: Rope,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let fs = self.fs.clone();
+        let dir = self.file_dir(abs_path);
+        let max_snapshot_size = self.max_size / MAX_SNAPSHOT_FRACTION;
+        cx.spawn(async move |this, cx| {
+            let size = cx
+                .background_spawn(async move {
+                    let text = text.to_string();
+                    let content_hash = content_hash(&text);
+                    let latest = snapshots_in_dir(fs.as_ref(), &dir).await?.pop();
+                    if latest
+                        .as_ref()
+                        .is_some_and(|latest| latest.content_hash == content_hash)
+                    {
+                        return anyhow::Ok(None);
+                    }
+
+                    let data = zstd::encode_all(text.as_bytes(), COMPRESSION_LEVEL)?;
+                    if data.len() as u64 > max_snapshot_size {
+                        return Ok(None);
+                    }
+                    let now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis() as u64;
+                    // Keep the snapshots ordered even when two are taken in the same millisecond.
+                    let timestamp_ms =
+                        latest.map_or(now, |latest| now.max(latest.timestamp_ms + 1));
+                    fs.create_dir(&dir).await?;
+                    fs.write(
+                        &dir.join(format!("{timestamp_ms}-{content_hash:016x}.zst")),
+                        &data,
+                    )
+                    .await?;
+                    Ok(Some(data.len() as u64))
+                })
+                .await?;
+            if let Some(size) = size {
+                this.update(cx, |this, cx| {
+                    if let Some(total_size) = &mut this.total_size {
+                        *total_size += size;
+                    }
+                    if this
+                        .total_size
+                        .is_none_or(|total_size| total_size > this.max_size)
+                    {
+                        this.prune(cx);
+                    }
+                })?;
+            }
+            Ok(())
+        })
+    }
+
+    /// The snapshots of the file at `abs_path`, newest first.
+    pub fn snapshots(&self, abs_path: &Path, cx: &App) -> Task<Result<Vec<Snapshot>>> {
+        let fs = self.fs.clone();
+        let dir = self.file_dir(abs_path);
+        cx.background_spawn(async move {
+            let mut snapshots = snapshots_in_dir(fs.as_ref(), &dir).await?;
+            snapshots.reverse();
+            Ok(snapshots)
+        })
+    }
+
+    /// Loads the contents of a snapshot.
+    pub fn load(&self, snapshot: &Snapshot, cx: &App) -> Task<Result<String>> {
+        let fs = self.fs.clone();
+        let path = snapshot.path.clone();
+        cx.background_spawn(async move {
+            let data = fs.load_bytes(&path).await?;
+            let text = zstd::decode_all(data.as_slice())
+                .with_context(|| format!("decompressing snapshot {}", path.display()))?;
+            Ok(String::from_utf8(text)?)
+        })
+    }
+
+    /// Counts the size of all snapshots, deleting the oldest ones while they're over the limit.
+    fn prune(&mut self, cx: &mut Context<Self>) {
+        if self.prune_task.is_some() {
+            return;
+        }
+        let fs = self.fs.clone();
+        let dir = self.dir.clone();
+        let max_size = self.max_size;
+        self.prune_task = Some(cx.spawn(async move |this, cx| {
+            let total_size = cx
+                .background_spawn(async move { prune_dir(fs.as_ref(), &dir, max_size).await })
+                .await;
+            this.update(cx, |this, _| {
+                this.total_size = total_size.log_err();
+                this.prune_task = None;
+            })
+            .ok();
+        }));
+    }
+}
+
+fn content_hash(text: &str) -> u64 {
+    let hash = Sha256::digest(text.as_bytes());
+    u64::from_be_bytes(hash[..8].try_into().unwrap())
+}
+
+/// The snapshots in `dir`, oldest first.
+async fn snapshots_in_dir(fs: &dyn Fs, dir: &Path) -> Result<Vec<Snapshot>> {
+    if !fs.is_dir(dir).await {
+        return Ok(Vec::new());
+    }
+    let mut snapshots = Vec::new();
+    let mut entries = fs.read_dir(dir).await?;
+    while let Some(entry) = entries.next().await {
+        if let Some(snapshot) = Snapshot::from_path(entry?) {
+            snapshots.push(snapshot);
+        }
+    }
+    snapshots.sort_by_key(|snapshot| snapshot.timestamp_ms);
+    Ok(snapshots)
+}
+
+/// Deletes the oldest snapshots in `dir` until they take up at most `max_size` bytes, returning
+/// the size of those that are left.
+async fn prune_dir(fs: &dyn Fs, dir: &Path, max_size: u64) -> Result<u64> {
+    if !fs.is_dir(dir).await {
+        return Ok(0);
+    }
+    let mut snapshots = Vec::new();
+    let mut snapshot_counts = HashMap::default();
+    let mut file_dirs = fs.read_dir(dir).await?;
+    while let Some(file_dir) = file_dirs.next().await {
+        let file_dir = file_dir?;
+        let file_snapshots = snapshots_in_dir(fs, &file_dir).await?;
+        snapshot_counts.insert(file_dir.clone(), file_snapshots.len());
+        for snapshot in file_snapshots {
+            let size = fs
+                .metadata(&snapshot.path)
+                .await?
+                .map_or(0, |metadata| metadata.len);
+            snapshots.push((snapshot, size, file_dir.clone()));
+        }
+    }
+    snapshots.sort_by_key(|(snapshot, _, _)| snapshot.timestamp_ms);
+
+    let mut total_size = snapshots.iter().map(|(_, size, _)| size).sum::<u64>();
+    for (snapshot, size, file_dir) in snapshots {
+        if total_size <= max_size {
+            break;
+        }
+        fs.remove_file(&snapshot.path, RemoveOptions::default())
+            .await?;
+        total_size -= size;
+        let Some(count) = snapshot_counts.get_mut(&file_dir) else {
+            continue;
+        };
+        *count -= 1;
+        if *count == 0 {
+            fs.remove_dir(
+                &file_dir,
+                RemoveOptions {
+                    recursive: true,
+                    ignore_if_not_exists: true,
+                },
+            )
+            .await?;
+        }
+    }
+    Ok(total_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_record_and_prune_snapshots(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        let history_store =
+            cx.new(|_| HistoryStore::new(fs.clone(), PathBuf::from(path!("/history")), u64::MAX));
+        let main_rs = Path::new(path!("/project/src/main.rs"));
+        let lib_rs = Path::new(path!("/project/src/lib.rs"));
+
+        for (path, text) in [
+            (main_rs, "fn main() {}\n"),
+            (main_rs, "fn main() {}\n"),
+            (lib_rs, "pub fn lib() {}\n"),
+            (main_rs, "fn main() {\n    lib();\n}\n"),
+        ] {
+            history_store
+                .update(cx, |history_store, cx| {
+                    history_store.record(path, Rope::from(text), cx)
+                })
+                .await
+                .unwrap();
+        }
+        cx.run_until_parked();
+
+        let load_all = |path: &'static Path, cx: &mut TestAppContext| {
+            let snapshots =
+                history_store.read_with(cx, |history_store, cx| history_store.snapshots(path, cx));
+            let history_store = history_store.clone();
+            let mut cx = cx.to_async();
+            async move {
+                let mut texts = Vec::new();
+                for snapshot in snapshots.await.unwrap() {
+                    let text = history_store
+                        .read_with(&mut cx, |history_store, cx| {
+                            history_store.load(&snapshot, cx)
+                        })
+                        .await
+                        .unwrap();
+                    texts.push(text);
+                }
+                texts
+            }
+        };
+        assert_eq!(
+            load_all(main_rs, cx).await,
+            ["fn main() {\n    lib();\n}\n", "fn main() {}\n"]
+        );
+        assert_eq!(load_all(lib_rs, cx).await, ["pub fn lib() {}\n"]);
+
+        // Only the newest snapshot fits, so the others are deleted, along with the directory
+        // of the file that has none left.
+        let newest = history_store
+            .read_with(cx, |history_store, cx| history_store.snapshots(main_rs, cx))
+            .await
+            .unwrap()
+            .remove(0);
+        let newest_size = fs.metadata(&newest.path).await.unwrap().unwrap().len;
+        history_store.update(cx, |history_store, cx| {
+            history_store.set_max_size(newest_size, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            load_all(main_rs, cx).await,
+            ["fn main() {\n    lib();\n}\n"]
+        );
+        assert_eq!(load_all(lib_rs, cx).await, Vec::<String>::new());
+        history_store.read_with(cx, |history_store, _| {
+            assert_eq!(history_store.total_size, Some(newest_size))
+        });
+
+        // A snapshot that would take up too much of the space isn't kept at all.
+        history_store
+            .update(cx, |history_store, cx| {
+                history_store.record(main_rs, Rope::from("fn main() {\n    todo!();\n}\n"), cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            load_all(main_rs, cx).await,
+            ["fn main() {\n    lib();\n}\n"]
+        );
+    }
+}
@@ -0,0 +1,206 @@
+mod history_store;
+mod local_history_settings;
+mod timeline;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use fs::Fs;
+use gpui::{App, AppContext as _, Entity, Task, actions};
+use language::{Buffer, BufferEvent};
+use project::{Project, buffer_store::BufferStoreEvent};
+use settings::{Settings as _, SettingsStore};
+use text::Rope;
+use workspace::Workspace;
+
+pub use crate::history_store::{HistoryStore, Snapshot};
+pub use crate::local_history_settings::LocalHistorySettings;
+pub use crate::timeline::TimelineModal;
+
+actions!(
+    local_history,
+    [
+        /// Lists the snapshots of the active file that local history has kept, to compare them
+        /// with the file or restore them.
+        ShowHistory,
+    ]
+);
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+    let max_size = LocalHistorySettings::get_global(cx).max_size_bytes();
+    let history_store =
+        cx.new(|_| HistoryStore::new(fs, paths::local_history_dir().clone(), max_size));
+    cx.observe_global::<SettingsStore>({
+        let history_store = history_store.downgrade();
+        move |cx| {
+            let max_size = LocalHistorySettings::get_global(cx).max_size_bytes();
+            history_store
+                .update(cx, |history_store, cx| {
+                    history_store.set_max_size(max_size, cx)
+                })
+                .ok();
+        }
+    })
+    .detach();
+    HistoryStore::set_global(history_store, cx);
+
+    cx.observe_new(|project: &mut Project, _, cx| {
+        if !project.is_local() {
+            return;
+        }
+        cx.subscribe(&project.buffer_store().clone(), |_, _, event, cx| {
+            if let BufferStoreEvent::BufferAdded(buffer) = event {
+                // Remember the version the file was loaded at, so the first save can keep the
+                // contents it overwrites without snapshotting every file that gets opened.
+                let mut loaded_version = {
+                    let buffer = buffer.read(cx);
+                    let on_disk = buffer.file().is_some_and(|file| file.disk_state().exists());
+                    (on_disk && !buffer.is_dirty()).then(|| buffer.saved_version().clone())
+                };
+                cx.subscribe(buffer, move |_, buffer, event, cx| match event {
+                    BufferEvent::Saved => {
+                        let loaded_text = loaded_version
+                            .take()
+                            .map(|version| buffer.read(cx).rope_for_version(&version));
+                        record_buffer(&buffer, loaded_text, cx);
+                    }
+                    BufferEvent::Reloaded => {
+                        loaded_version = None;
+                        record_buffer(&buffer, None, cx);
+                    }
+                    _ => {}
+                })
+                .detach();
+            }
+        })
+        .detach();
+    })
+    .detach();
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ShowHistory, window, cx| {
+            TimelineModal::toggle(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Stores a snapshot of the buffer's contents when it belongs to a file on the local file system,
+/// preceded by `previous_text` when the file's earlier contents haven't been recorded yet.
+fn record_buffer(buffer: &Entity<Buffer>, previous_text: Option<Rope>, cx: &mut App) {
+    if !LocalHistorySettings::get_global(cx).enabled {
+        return;
+    }
+    let Some(history_store) = HistoryStore::global(cx) else {
+        return;
+    };
+    let buffer = buffer.read(cx);
+    let Some(abs_path) = buffer
+        .file()
+        .and_then(|file| file.as_local())
+        .map(|file| file.abs_path(cx))
+    else {
+        return;
+    };
+    let text = buffer.as_rope().clone();
+    let record_previous = previous_text.map(|previous_text| {
+        history_store.update(cx, |history_store, cx| {
+            history_store.record(&abs_path, previous_text, cx)
+        })
+    });
+    cx.spawn(async move |cx| {
+        if let Some(record_previous) = record_previous {
+            record_previous.await?;
+        }
+        history_store
+            .update(cx, |history_store, cx| {
+                history_store.record(&abs_path, text, cx)
+            })
+            .await
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Replaces the contents of the buffer with the snapshot's. This is a single edit that can be
+/// undone, and the file isn't saved.
+pub(crate) fn restore_snapshot(
+    history_store: &Entity<HistoryStore>,
+    snapshot: &Snapshot,
+    buffer: Entity<Buffer>,
+    cx: &mut App,
+) -> Task<Result<()>> {
+    let load = history_store.read(cx).load(snapshot, cx);
+    cx.spawn(async move |cx| {
+        let text = load.await?;
+        let diff = buffer
+            .read_with(cx, |buffer, cx| buffer.diff(text, cx))
+            .await;
+        buffer.update(cx, |buffer, cx| buffer.apply_diff(diff, cx));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use std::path::Path;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_restore_contents_overwritten_by_save(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ "main.rs": "fn main() {}\n" }))
+            .await;
+        cx.update(|cx| {
+            settings::init(cx);
+            init(fs.clone(), cx);
+        });
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        // Opening a file doesn't snapshot it.
+        let history_store = cx.update(HistoryStore::global).unwrap();
+        let snapshots = history_store
+            .read_with(cx, |history_store, cx| {
+                history_store.snapshots(Path::new(path!("/project/main.rs")), cx)
+            })
+            .await
+            .unwrap();
+        assert!(snapshots.is_empty());
+
+        buffer.update(cx, |buffer, cx| {
+            buffer.set_text("fn main() {\n    todo!();\n}\n", cx)
+        });
+        project
+            .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let snapshots = history_store
+            .read_with(cx, |history_store, cx| {
+                history_store.snapshots(Path::new(path!("/project/main.rs")), cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(snapshots.len(), 2);
+
+        let original = snapshots.last().unwrap();
+        cx.update(|cx| restore_snapshot(&history_store, original, buffer.clone(), cx))
+            .await
+            .unwrap();
+        buffer.read_with(cx, |buffer, _| {
+            assert_eq!(buffer.text(), "fn main() {}\n");
+            assert!(buffer.is_dirty());
+        });
+    }
+}
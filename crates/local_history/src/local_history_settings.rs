@@ -0,0 +1,30 @@
+use settings::{RegisterSetting, Settings};
+
+/// The settings for the local history of files.
+#[derive(Clone, Debug, RegisterSetting)]
+pub struct LocalHistorySettings {
+    /// Whether to keep snapshots of the files in local projects.
+    ///
+    /// Default: true
+    pub enabled: bool,
+    /// The most disk space, in megabytes, that snapshots take up.
+    ///
+    /// Default: 256
+    pub max_size_mb: u64,
+}
+
+impl LocalHistorySettings {
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+impl Settings for LocalHistorySettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let local_history = content.local_history.clone().unwrap();
+        Self {
+            enabled: local_history.enabled.unwrap(),
+            max_size_mb: local_history.max_size_mb.unwrap(),
+        }
+    }
+}
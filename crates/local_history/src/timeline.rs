@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use editor::Editor;
+use git_ui::file_diff_view::FileDiffView;
+use gpui::{
+    App, AppContext as _, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    Render, Task, WeakEntity, Window,
+};
+use language::Buffer;
+use picker::{Picker, PickerDelegate};
+use time::{OffsetDateTime, UtcOffset};
+use time_format::TimestampFormat;
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::{HistoryStore, Snapshot, restore_snapshot};
+
+/// Lists the snapshots of a file, newest first. Confirming one opens a diff between it and the
+/// file's buffer, and the secondary confirm restores it.
+pub struct TimelineModal {
+    picker: Entity<Picker<TimelineDelegate>>,
+}
+
+impl TimelineModal {
+    pub(crate) fn toggle(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(history_store) = HistoryStore::global(cx) else {
+            return;
+        };
+        let Some(buffer) = workspace
+            .active_item_as::<Editor>(cx)
+            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+        else {
+            return;
+        };
+        let Some(abs_path) = buffer
+            .read(cx)
+            .file()
+            .and_then(|file| file.as_local())
+            .map(|file| file.abs_path(cx))
+        else {
+            return;
+        };
+
+        let snapshots = history_store.read(cx).snapshots(&abs_path, cx);
+        cx.spawn_in(window, async move |workspace, cx| {
+            let snapshots = snapshots.await?;
+            let weak_workspace = workspace.clone();
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.toggle_modal(window, cx, move |window, cx| {
+                    let delegate = TimelineDelegate {
+                        modal: cx.entity().downgrade(),
+                        workspace: weak_workspace,
+                        history_store,
+                        buffer,
+                        snapshots,
+                        selected_index: 0,
+                    };
+                    let picker =
+                        cx.new(|cx| Picker::nonsearchable_uniform_list(delegate, window, cx));
+                    Self { picker }
+                });
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl Render for TimelineModal {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("LocalHistoryTimeline")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for TimelineModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for TimelineModal {}
+impl ModalView for TimelineModal {}
+
+pub struct TimelineDelegate {
+    modal: WeakEntity<TimelineModal>,
+    workspace: WeakEntity<Workspace>,
+    history_store: Entity<HistoryStore>,
+    buffer: Entity<Buffer>,
+    snapshots: Vec<Snapshot>,
+    selected_index: usize,
+}
+
+impl TimelineDelegate {
+    /// Opens a diff between the snapshot and the current contents of the buffer.
+    fn open_diff(&self, snapshot: &Snapshot, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let load = self.history_store.read(cx).load(snapshot, cx);
+        let buffer = self.buffer.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let text = load.await?;
+            let old_buffer = workspace.update(cx, |workspace, cx| {
+                let language = buffer.read(cx).language().cloned();
+                workspace.project().update(cx, |project, cx| {
+                    project.create_local_buffer(&text, language, false, cx)
+                })
+            })?;
+            workspace
+                .update_in(cx, |_, window, cx| {
+                    FileDiffView::open_buffers(old_buffer, buffer, workspace.clone(), window, cx)
+                })?
+                .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn restore(&self, snapshot: &Snapshot, cx: &mut Context<Picker<Self>>) {
+        restore_snapshot(&self.history_store, snapshot, self.buffer.clone(), cx)
+            .detach_and_log_err(cx);
+    }
+}
+
+impl PickerDelegate for TimelineDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a version…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some(
+            "No history yet. A snapshot is kept when the file is opened and each time it's saved."
+                .into(),
+        )
+    }
+
+    fn match_count(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        _query: String,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        Task::ready(())
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(snapshot) = self.snapshots.get(self.selected_index) else {
+            return;
+        };
+        if secondary {
+            self.restore(snapshot, cx);
+        } else {
+            self.open_diff(snapshot, window, cx);
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let snapshot = self.snapshots.get(ix)?;
+        let now = OffsetDateTime::now_utc();
+        let local_offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+        let format = |format| {
+            time_format::format_localized_timestamp(snapshot.timestamp(), now, local_offset, format)
+        };
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(format(TimestampFormat::EnhancedAbsolute)))
+                        .child(
+                            Label::new(format(TimestampFormat::Relative))
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                )
+                .end_hover_slot(
+                    IconButton::new(("restore-snapshot", ix), IconName::RotateCcw)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Restore This Version"))
+                        .on_click(cx.listener(move |picker, _, window, cx| {
+                            cx.stop_propagation();
+                            if let Some(snapshot) = picker.delegate.snapshots.get(ix) {
+                                picker.delegate.restore(snapshot, cx);
+                            }
+                            picker.delegate.dismissed(window, cx);
+                        })),
+                ),
+        )
+    }
+}
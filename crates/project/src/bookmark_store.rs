@@ -0,0 +1,600 @@
+//! Module for managing bookmarks in a project.
+//!
+//! Bookmarks mark lines that the user wants to come back to. They're anchored in their buffer, so
+//! they move along with edits, and can be given a label describing what they point at.
+use anyhow::{Context as _, Result};
+use collections::BTreeMap;
+use gpui::{App, AppContext, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
+use language::{
+    Buffer, BufferEvent, BufferSnapshot,
+    proto::{deserialize_anchor, serialize_anchor},
+};
+use rpc::{AnyProtoClient, TypedEnvelope, proto};
+use std::{ops::Range, path::Path, sync::Arc};
+use text::{Point, PointUtf16};
+
+use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub position: text::Anchor,
+    pub label: Option<Arc<str>>,
+}
+
+impl Bookmark {
+    fn to_proto(&self) -> proto::Bookmark {
+        proto::Bookmark {
+            position: Some(serialize_anchor(&self.position)),
+            label: self.label.as_ref().map(|label| label.to_string()),
+        }
+    }
+
+    fn from_proto(bookmark: proto::Bookmark) -> Option<Self> {
+        Some(Self {
+            position: deserialize_anchor(bookmark.position?)?,
+            label: bookmark.label.map(Into::into),
+        })
+    }
+}
+
+/// A bookmark as it's stored in the database, by row rather than by anchor.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SerializedBookmark {
+    pub row: u32,
+    pub label: Option<Arc<str>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum BookmarkEditAction {
+    /// Removes the bookmark on the row of the given position, or adds one if there's none.
+    Toggle,
+    /// Sets the label of the bookmark on the row of the given position, adding one if there's
+    /// none. An empty label removes the label, keeping the bookmark.
+    SetLabel(Arc<str>),
+}
+
+#[derive(Clone)]
+struct BookmarksInFile {
+    buffer: Entity<Buffer>,
+    /// Sorted by position.
+    bookmarks: Vec<Bookmark>,
+    _subscription: Arc<Subscription>,
+}
+
+impl BookmarksInFile {
+    fn new(buffer: Entity<Buffer>, cx: &mut Context<BookmarkStore>) -> Self {
+        let subscription = Arc::from(cx.subscribe(&buffer, BookmarkStore::on_buffer_event));
+
+        BookmarksInFile {
+            buffer,
+            bookmarks: Vec::new(),
+            _subscription: subscription,
+        }
+    }
+
+    fn sort(&mut self, snapshot: &text::BufferSnapshot) {
+        self.bookmarks
+            .sort_by(|a, b| a.position.cmp(&b.position, snapshot));
+    }
+}
+
+#[derive(Clone)]
+struct RemoteBookmarkStore {
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
+}
+
+#[derive(Clone)]
+enum BookmarkStoreMode {
+    Local,
+    Remote(RemoteBookmarkStore),
+}
+
+pub struct BookmarkStore {
+    buffer_store: Entity<BufferStore>,
+    worktree_store: Entity<WorktreeStore>,
+    bookmarks: BTreeMap<Arc<Path>, BookmarksInFile>,
+    downstream_client: Option<(AnyProtoClient, u64)>,
+    // E.g ssh
+    mode: BookmarkStoreMode,
+}
+
+pub enum BookmarkStoreEvent {
+    BookmarksUpdated(Arc<Path>),
+    BookmarksCleared(Vec<Arc<Path>>),
+}
+
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
+impl BookmarkStore {
+    pub fn init(client: &AnyProtoClient) {
+        client.add_entity_request_handler(Self::handle_edit_bookmark);
+        client.add_entity_message_handler(Self::handle_bookmarks_for_file);
+    }
+
+    pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+        BookmarkStore {
+            buffer_store,
+            worktree_store,
+            bookmarks: BTreeMap::new(),
+            downstream_client: None,
+            mode: BookmarkStoreMode::Local,
+        }
+    }
+
+    pub(crate) fn remote(
+        upstream_project_id: u64,
+        upstream_client: AnyProtoClient,
+        buffer_store: Entity<BufferStore>,
+        worktree_store: Entity<WorktreeStore>,
+    ) -> Self {
+        BookmarkStore {
+            buffer_store,
+            worktree_store,
+            bookmarks: BTreeMap::new(),
+            downstream_client: None,
+            mode: BookmarkStoreMode::Remote(RemoteBookmarkStore {
+                upstream_client,
+                upstream_project_id,
+            }),
+        }
+    }
+
+    pub fn shared(&mut self, project_id: u64, downstream_client: AnyProtoClient) {
+        self.downstream_client = Some((downstream_client, project_id));
+    }
+
+    pub(crate) fn unshared(&mut self, cx: &mut Context<Self>) {
+        self.downstream_client.take();
+
+        cx.notify();
+    }
+
+    async fn handle_bookmarks_for_file(
+        this: Entity<Self>,
+        message: TypedEnvelope<proto::BookmarksForFile>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let abs_path = Arc::<Path>::from(Path::new(&message.payload.path));
+        if message.payload.bookmarks.is_empty() {
+            this.update(&mut cx, |this, cx| {
+                if this.bookmarks.remove(&abs_path).is_some() {
+                    this.send_bookmarks_for_file(&abs_path);
+                    cx.emit(BookmarkStoreEvent::BookmarksCleared(vec![abs_path]));
+                    cx.notify();
+                }
+            });
+            return Ok(());
+        }
+
+        let buffer = this
+            .update(&mut cx, |this, cx| {
+                let path = this
+                    .worktree_store
+                    .read(cx)
+                    .project_path_for_absolute_path(&abs_path, cx)?;
+                Some(
+                    this.buffer_store
+                        .update(cx, |this, cx| this.open_buffer(path, cx)),
+                )
+            })
+            .context("Invalid project path")?
+            .await?;
+
+        this.update(&mut cx, move |this, cx| {
+            let snapshot = buffer.read(cx).text_snapshot();
+            let bookmarks_in_file = this
+                .bookmarks
+                .entry(abs_path.clone())
+                .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+            bookmarks_in_file.bookmarks = message
+                .payload
+                .bookmarks
+                .into_iter()
+                .filter_map(Bookmark::from_proto)
+                .collect();
+            bookmarks_in_file.sort(&snapshot);
+            // Pass the bookmarks on to our own collaborators, e.g. when sharing a remote project.
+            this.send_bookmarks_for_file(&abs_path);
+
+            cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+            cx.notify();
+        });
+
+        Ok(())
+    }
+
+    async fn handle_edit_bookmark(
+        this: Entity<Self>,
+        message: TypedEnvelope<proto::EditBookmark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let path = this
+            .update(&mut cx, |this, cx| {
+                this.worktree_store
+                    .read(cx)
+                    .project_path_for_absolute_path(message.payload.path.as_ref(), cx)
+            })
+            .context("Could not resolve provided abs path")?;
+        let buffer = this
+            .update(&mut cx, |this, cx| {
+                this.buffer_store.read(cx).get_by_path(&path)
+            })
+            .context("Could not find buffer for a given path")?;
+        let bookmark = message
+            .payload
+            .bookmark
+            .context("Bookmark not present in RPC payload")?;
+        let bookmark = Bookmark::from_proto(bookmark).context("Could not deserialize bookmark")?;
+        let action = match proto::BookmarkEditKind::from_i32(message.payload.kind) {
+            Some(proto::BookmarkEditKind::SetLabel) => {
+                BookmarkEditAction::SetLabel(bookmark.label.unwrap_or_default())
+            }
+            None | Some(proto::BookmarkEditKind::Toggle) => BookmarkEditAction::Toggle,
+        };
+
+        this.update(&mut cx, |this, cx| {
+            this.edit_bookmark(buffer, bookmark.position, action, cx);
+        });
+        Ok(proto::Ack {})
+    }
+
+    pub(crate) fn broadcast(&self) {
+        if let Some((client, project_id)) = &self.downstream_client {
+            for (path, bookmarks_in_file) in &self.bookmarks {
+                let _ = client.send(proto::BookmarksForFile {
+                    project_id: *project_id,
+                    path: path.to_str().map(ToOwned::to_owned).unwrap(),
+                    bookmarks: bookmarks_in_file
+                        .bookmarks
+                        .iter()
+                        .map(Bookmark::to_proto)
+                        .collect(),
+                });
+            }
+        }
+    }
+
+    fn on_buffer_event(
+        &mut self,
+        buffer: Entity<Buffer>,
+        event: &BufferEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            BufferEvent::Saved => {
+                if let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) {
+                    cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+                }
+            }
+            BufferEvent::FileHandleChanged => {
+                let entity_id = buffer.entity_id();
+                let Some(old_path) = self
+                    .bookmarks
+                    .iter()
+                    .find(|(_, in_file)| in_file.buffer.entity_id() == entity_id)
+                    .map(|(path, _)| path.clone())
+                else {
+                    return;
+                };
+
+                if buffer
+                    .read(cx)
+                    .file()
+                    .is_none_or(|f| f.disk_state().is_deleted())
+                {
+                    self.bookmarks.remove(&old_path);
+                    cx.emit(BookmarkStoreEvent::BookmarksCleared(vec![old_path]));
+                    cx.notify();
+                    return;
+                }
+
+                if let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx)
+                    && abs_path != old_path
+                    && !self.bookmarks.contains_key(&abs_path)
+                    && let Some(bookmarks_in_file) = self.bookmarks.remove(&old_path)
+                {
+                    self.bookmarks.insert(abs_path.clone(), bookmarks_in_file);
+                    cx.emit(BookmarkStoreEvent::BookmarksCleared(vec![old_path]));
+                    cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+                    cx.notify();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        worktree::File::from_dyn(buffer.read(cx).file())
+            .map(|file| file.worktree.read(cx).absolutize(&file.path))
+            .map(Arc::<Path>::from)
+    }
+
+    /// Adds, removes or relabels the bookmark on the row of `position`. There's at most one
+    /// bookmark per row.
+    pub fn edit_bookmark(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: text::Anchor,
+        action: BookmarkEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).text_snapshot();
+        let row = position.summary::<Point>(&snapshot).row;
+
+        let bookmarks_in_file = self
+            .bookmarks
+            .entry(abs_path.clone())
+            .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+        let existing = bookmarks_in_file
+            .bookmarks
+            .iter()
+            .position(|bookmark| bookmark.position.summary::<Point>(&snapshot).row == row);
+
+        let (edited, kind) = match action {
+            BookmarkEditAction::Toggle => {
+                let edited = match existing {
+                    Some(ix) => bookmarks_in_file.bookmarks.remove(ix),
+                    None => {
+                        let bookmark = Bookmark {
+                            position,
+                            label: None,
+                        };
+                        bookmarks_in_file.bookmarks.push(bookmark.clone());
+                        bookmark
+                    }
+                };
+                (edited, proto::BookmarkEditKind::Toggle)
+            }
+            BookmarkEditAction::SetLabel(label) => {
+                let label = Some(label).filter(|label| !label.trim().is_empty());
+                let edited = match existing {
+                    Some(ix) => {
+                        let bookmark = &mut bookmarks_in_file.bookmarks[ix];
+                        bookmark.label = label;
+                        bookmark.clone()
+                    }
+                    None => {
+                        let bookmark = Bookmark { position, label };
+                        bookmarks_in_file.bookmarks.push(bookmark.clone());
+                        bookmark
+                    }
+                };
+                (edited, proto::BookmarkEditKind::SetLabel)
+            }
+        };
+        bookmarks_in_file.sort(&snapshot);
+
+        if bookmarks_in_file.bookmarks.is_empty() {
+            self.bookmarks.remove(&abs_path);
+        }
+        if let BookmarkStoreMode::Remote(remote) = &self.mode {
+            cx.background_spawn(remote.upstream_client.request(proto::EditBookmark {
+                project_id: remote.upstream_project_id,
+                path: abs_path.to_str().map(ToOwned::to_owned).unwrap(),
+                bookmark: Some(edited.to_proto()),
+                kind: kind.into(),
+            }))
+            .detach();
+        }
+        self.send_bookmarks_for_file(&abs_path);
+
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+        cx.notify();
+    }
+
+    fn send_bookmarks_for_file(&self, abs_path: &Arc<Path>) {
+        if let Some((client, project_id)) = &self.downstream_client {
+            let bookmarks = self
+                .bookmarks
+                .get(abs_path)
+                .map(|bookmarks_in_file| {
+                    bookmarks_in_file
+                        .bookmarks
+                        .iter()
+                        .map(Bookmark::to_proto)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let _ = client.send(proto::BookmarksForFile {
+                project_id: *project_id,
+                path: abs_path.to_str().map(ToOwned::to_owned).unwrap(),
+                bookmarks,
+            });
+        }
+    }
+
+    pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
+        let bookmarks = std::mem::take(&mut self.bookmarks);
+        let paths = bookmarks.keys().cloned().collect::<Vec<_>>();
+        if let BookmarkStoreMode::Remote(remote) = &self.mode {
+            for (path, bookmarks_in_file) in &bookmarks {
+                for bookmark in &bookmarks_in_file.bookmarks {
+                    cx.background_spawn(remote.upstream_client.request(proto::EditBookmark {
+                        project_id: remote.upstream_project_id,
+                        path: path.to_str().map(ToOwned::to_owned).unwrap(),
+                        bookmark: Some(bookmark.to_proto()),
+                        kind: proto::BookmarkEditKind::Toggle.into(),
+                    }))
+                    .detach();
+                }
+            }
+        }
+        for path in &paths {
+            self.send_bookmarks_for_file(path);
+        }
+
+        cx.emit(BookmarkStoreEvent::BookmarksCleared(paths));
+        cx.notify();
+    }
+
+    /// The bookmarks in `buffer`, in order, optionally limited to those within `range`.
+    pub fn bookmarks<'a>(
+        &'a self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<text::Anchor>>,
+        buffer_snapshot: &'a BufferSnapshot,
+        cx: &App,
+    ) -> impl Iterator<Item = &'a Bookmark> + 'a {
+        let abs_path = Self::abs_path_from_buffer(buffer, cx);
+        abs_path
+            .and_then(|path| self.bookmarks.get(&path))
+            .into_iter()
+            .flat_map(|bookmarks_in_file| &bookmarks_in_file.bookmarks)
+            .filter(move |bookmark| {
+                range.as_ref().is_none_or(|range| {
+                    bookmark.position.cmp(&range.start, buffer_snapshot).is_ge()
+                        && bookmark.position.cmp(&range.end, buffer_snapshot).is_le()
+                })
+            })
+    }
+
+    pub fn bookmark_at_row(
+        &self,
+        buffer: &Entity<Buffer>,
+        row: u32,
+        cx: &App,
+    ) -> Option<&Bookmark> {
+        let abs_path = Self::abs_path_from_buffer(buffer, cx)?;
+        let bookmarks_in_file = self.bookmarks.get(&abs_path)?;
+        let snapshot = bookmarks_in_file.buffer.read(cx).text_snapshot();
+        bookmarks_in_file
+            .bookmarks
+            .iter()
+            .find(|bookmark| bookmark.position.summary::<Point>(&snapshot).row == row)
+    }
+
+    /// All bookmarks in the project, with the buffer they're in, ordered by path.
+    pub fn all_bookmarks(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<Path>, &Entity<Buffer>, &[Bookmark])> {
+        self.bookmarks.iter().map(|(path, bookmarks_in_file)| {
+            (
+                path,
+                &bookmarks_in_file.buffer,
+                bookmarks_in_file.bookmarks.as_slice(),
+            )
+        })
+    }
+
+    pub fn all_serialized_bookmarks(
+        &self,
+        cx: &App,
+    ) -> BTreeMap<Arc<Path>, Vec<SerializedBookmark>> {
+        self.bookmarks
+            .iter()
+            .map(|(path, bookmarks_in_file)| {
+                let snapshot = bookmarks_in_file.buffer.read(cx).snapshot();
+                (
+                    path.clone(),
+                    bookmarks_in_file
+                        .bookmarks
+                        .iter()
+                        .map(|bookmark| SerializedBookmark {
+                            row: snapshot
+                                .summary_for_anchor::<PointUtf16>(&bookmark.position)
+                                .row,
+                            label: bookmark.label.clone(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Restores the bookmarks stored in the database, replacing the current ones. In a remote
+    /// project, they're sent upstream as well, since the host doesn't store them.
+    pub fn with_serialized_bookmarks(
+        &self,
+        bookmarks: BTreeMap<Arc<Path>, Vec<SerializedBookmark>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let is_remote = matches!(self.mode, BookmarkStoreMode::Remote(_));
+        let worktree_store = self.worktree_store.downgrade();
+        let buffer_store = self.buffer_store.downgrade();
+        cx.spawn(async move |this, cx| {
+            let mut new_bookmarks = BTreeMap::default();
+            for (path, serialized_bookmarks) in bookmarks {
+                if serialized_bookmarks.is_empty() {
+                    continue;
+                }
+                // Worktrees aren't created on the host just to restore bookmarks.
+                let (worktree, relative_path) = if is_remote {
+                    let worktree =
+                        worktree_store.read_with(cx, |this, cx| this.find_worktree(&path, cx))?;
+                    let Some(worktree) = worktree else {
+                        log::error!("skipping bookmarks in {path:?}, which isn't in the project");
+                        continue;
+                    };
+                    worktree
+                } else {
+                    worktree_store
+                        .update(cx, |this, cx| {
+                            this.find_or_create_worktree(&path, false, cx)
+                        })?
+                        .await?
+                };
+                let buffer = buffer_store
+                    .update(cx, |this, cx| {
+                        let path = ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path: relative_path,
+                        };
+                        this.open_buffer(path, cx)
+                    })?
+                    .await;
+                let buffer = match buffer {
+                    Ok(buffer) => buffer,
+                    Err(error) => {
+                        log::error!("failed to open buffer for bookmarks in {path:?}: {error:#}");
+                        continue;
+                    }
+                };
+                let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+
+                let mut bookmarks_in_file =
+                    this.update(cx, |_, cx| BookmarksInFile::new(buffer, cx))?;
+                for bookmark in serialized_bookmarks {
+                    let point = PointUtf16::new(bookmark.row, 0);
+                    if point > snapshot.max_point_utf16() {
+                        log::error!("skipping a deserialized bookmark that's out of range");
+                        continue;
+                    }
+                    bookmarks_in_file.bookmarks.push(Bookmark {
+                        position: snapshot.anchor_after(point),
+                        label: bookmark.label,
+                    });
+                }
+                bookmarks_in_file.sort(&snapshot);
+                new_bookmarks.insert(path, bookmarks_in_file);
+            }
+
+            this.update(cx, |this, cx| {
+                this.bookmarks = new_bookmarks;
+                if let BookmarkStoreMode::Remote(remote) = &this.mode {
+                    for (path, bookmarks_in_file) in &this.bookmarks {
+                        for bookmark in &bookmarks_in_file.bookmarks {
+                            // Setting the label adds the bookmark when the host has none on its
+                            // row, rather than removing it like a toggle would.
+                            cx.background_spawn(remote.upstream_client.request(
+                                proto::EditBookmark {
+                                    project_id: remote.upstream_project_id,
+                                    path: path.to_str().map(ToOwned::to_owned).unwrap(),
+                                    bookmark: Some(bookmark.to_proto()),
+                                    kind: proto::BookmarkEditKind::SetLabel.into(),
+                                },
+                            ))
+                            .detach();
+                        }
+                    }
+                }
+                this.broadcast();
+                cx.notify();
+            })
+        })
+    }
+}
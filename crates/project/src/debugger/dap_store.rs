@@ -108,11 +108,22 @@ pub struct PersistedExceptionBreakpoint {
     pub enabled: bool,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PersistedFunctionBreakpoint {
+    pub name: String,
+    pub condition: Option<String>,
+    pub hit_condition: Option<String>,
+    pub enabled: bool,
+}
+
 /// Represents best-effort serialization of adapter state during last session (e.g. watches)
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct PersistedAdapterOptions {
     /// Which exception breakpoints were enabled during the last session with this adapter?
     pub exception_breakpoints: BTreeMap<String, PersistedExceptionBreakpoint>,
+    /// Function breakpoints set during the last session with this adapter.
+    #[serde(default)]
+    pub function_breakpoints: Vec<PersistedFunctionBreakpoint>,
 }
 
 impl DapStore {
@@ -911,8 +922,18 @@ impl DapStore {
                 )
             })
             .collect();
+        let function_breakpoints = session
+            .function_breakpoints()
+            .map(|state| PersistedFunctionBreakpoint {
+                name: state.dap.name.clone(),
+                condition: state.dap.condition.clone(),
+                hit_condition: state.dap.hit_condition.clone(),
+                enabled: state.is_enabled,
+            })
+            .collect();
         let options = Arc::new(PersistedAdapterOptions {
             exception_breakpoints,
+            function_breakpoints,
         });
         self.adapter_options.insert(adapter, options.clone());
         options
@@ -2,12 +2,14 @@ use super::breakpoint_store::{
     BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand,
-    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
-    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, ExceptionInfoCommand, GotoCommand,
+    GotoTargetsCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand,
+    LocationsCommand, ModulesCommand, NextCommand, PauseCommand, RestartCommand,
+    RestartStackFrameCommand, ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints,
+    SetFunctionBreakpointsCommand, SetInstructionBreakpointsCommand, SetVariableValueCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepInTargetsCommand,
+    StepOutCommand, TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use crate::debugger::breakpoint_store::BreakpointSessionState;
@@ -70,6 +72,8 @@ use worktree::Worktree;
 
 const MAX_TRACKED_OUTPUT_EVENTS: usize = 5000;
 const DEBUG_HISTORY_LIMIT: usize = 10;
+/// How many disassembled ranges are kept at once, evicting the ones furthest from the newest.
+const MAX_CACHED_DISASSEMBLIES: usize = 16;
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, PartialOrd, Ord, Eq)]
 #[repr(transparent)]
@@ -152,6 +156,18 @@ pub struct DataBreakpointState {
     pub context: Arc<DataBreakpointContext>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionBreakpointState {
+    pub dap: dap::FunctionBreakpoint,
+    pub is_enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstructionBreakpointState {
+    pub dap: dap::InstructionBreakpoint,
+    pub is_enabled: bool,
+}
+
 pub enum SessionState {
     /// Represents a session that is building/initializing
     /// even if a session doesn't have a pre build task this state
@@ -176,9 +192,10 @@ pub struct RunningMode {
 pub struct SessionQuirks {
     pub compact: bool,
     pub prefer_thread_name: bool,
+    pub disassembly_without_source: bool,
 }
 
-fn client_source(abs_path: &Path) -> dap::Source {
+pub(super) fn client_source(abs_path: &Path) -> dap::Source {
     dap::Source {
         name: abs_path
             .file_name()
@@ -447,6 +464,8 @@ impl RunningMode {
         let supports_exception_filters = capabilities
             .supports_exception_filter_options
             .unwrap_or_default();
+        let supports_function_breakpoints =
+            SetFunctionBreakpointsCommand::is_supported(capabilities);
         let this = self.clone();
         let worktree = self.worktree().clone();
         let mut filters = capabilities
@@ -519,6 +538,34 @@ impl RunningMode {
                         .ok();
                 }
 
+                if supports_function_breakpoints
+                    && let Some(defaults) = adapter_defaults.as_ref()
+                    && !defaults.function_breakpoints.is_empty()
+                {
+                    let breakpoints = session.update(cx, |this, _| {
+                        for breakpoint in &defaults.function_breakpoints {
+                            this.function_breakpoints
+                                .entry(breakpoint.name.clone())
+                                .or_insert_with(|| FunctionBreakpointState {
+                                    dap: dap::FunctionBreakpoint {
+                                        name: breakpoint.name.clone(),
+                                        condition: breakpoint.condition.clone(),
+                                        hit_condition: breakpoint.hit_condition.clone(),
+                                    },
+                                    is_enabled: breakpoint.enabled,
+                                });
+                        }
+                        this.function_breakpoints
+                            .values()
+                            .filter_map(|state| state.is_enabled.then(|| state.dap.clone()))
+                            .collect::<Vec<_>>()
+                    })?;
+
+                    this.request(SetFunctionBreakpointsCommand { breakpoints })
+                        .await
+                        .log_err();
+                }
+
                 if configuration_done_supported {
                     this.request(ConfigurationDone {})
                 } else {
@@ -682,6 +729,9 @@ pub struct SessionSnapshot {
     locations: HashMap<u64, dap::LocationsResponse>,
     modules: Vec<dap::Module>,
     loaded_sources: Vec<dap::Source>,
+    /// Threads whose last stop was caused by an exception.
+    exception_threads: HashSet<ThreadId>,
+    exception_info: HashMap<ThreadId, dap::ExceptionInfoResponse>,
 }
 
 type IsEnabled = bool;
@@ -709,6 +759,9 @@ pub struct Session {
     ignore_breakpoints: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
     data_breakpoints: BTreeMap<String, DataBreakpointState>,
+    function_breakpoints: BTreeMap<String, FunctionBreakpointState>,
+    instruction_breakpoints: BTreeMap<String, InstructionBreakpointState>,
+    disassembly: HashMap<DisassembleCommand, Arc<[dap::DisassembledInstruction]>>,
     background_tasks: Vec<Task<()>>,
     restart_task: Option<Task<()>>,
     task_context: SharedTaskContext,
@@ -806,6 +859,8 @@ pub enum SessionEvent {
         sender: mpsc::Sender<Result<u32>>,
     },
     DataBreakpointInfo,
+    Disassembly,
+    ExceptionInfo,
     ConsoleOutput,
     HistoricSnapshotSelected,
 }
@@ -883,6 +938,9 @@ impl Session {
                 ignore_breakpoints: false,
                 breakpoint_store,
                 data_breakpoints: Default::default(),
+                function_breakpoints: Default::default(),
+                instruction_breakpoints: Default::default(),
+                disassembly: HashMap::default(),
                 exception_breakpoints: Default::default(),
                 label,
                 adapter,
@@ -1494,6 +1552,19 @@ impl Session {
                 .thread_states
                 .stop_thread(ThreadId(thread_id));
 
+            self.active_snapshot
+                .exception_info
+                .remove(&ThreadId(thread_id));
+            if event.reason == dap::StoppedEventReason::Exception {
+                self.active_snapshot
+                    .exception_threads
+                    .insert(ThreadId(thread_id));
+            } else {
+                self.active_snapshot
+                    .exception_threads
+                    .remove(&ThreadId(thread_id));
+            }
+
             self.invalidate_state(
                 &StackTraceCommand {
                     thread_id,
@@ -1771,6 +1842,9 @@ impl Session {
         self.invalidate_command_type::<ThreadsCommand>();
         self.invalidate_command_type::<DataBreakpointInfoCommand>();
         self.invalidate_command_type::<ReadMemory>();
+        self.invalidate_command_type::<DisassembleCommand>();
+        self.disassembly.clear();
+        self.invalidate_command_type::<ExceptionInfoCommand>();
         let executor = self.as_running().map(|running| running.executor.clone());
         if let Some(executor) = executor {
             self.memory.clear(&executor);
@@ -1980,6 +2054,64 @@ impl Session {
         );
     }
 
+    /// Returns the instructions surrounding `memory_reference`, starting `instruction_offset`
+    /// instructions away from it, once the adapter responded.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Option<Arc<[dap::DisassembledInstruction]>> {
+        let command = DisassembleCommand {
+            memory_reference,
+            instruction_offset,
+            instruction_count,
+        };
+        self.fetch(
+            command.clone(),
+            {
+                let command = command.clone();
+                move |this, instructions, cx| {
+                    let Some(instructions) = instructions.log_err() else {
+                        return;
+                    };
+                    this.disassembly.insert(command.clone(), instructions);
+                    this.evict_disassembly(&command);
+                    cx.emit(SessionEvent::Disassembly);
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+
+        self.disassembly.get(&command).cloned()
+    }
+
+    /// Drops the cached ranges furthest from `newest` until at most [`MAX_CACHED_DISASSEMBLIES`]
+    /// remain, forgetting their requests so that they're disassembled again when needed.
+    fn evict_disassembly(&mut self, newest: &DisassembleCommand) {
+        while self.disassembly.len() > MAX_CACHED_DISASSEMBLIES
+            && let Some(furthest) = self
+                .disassembly
+                .keys()
+                .filter(|cached| *cached != newest)
+                .max_by_key(|cached| {
+                    if cached.memory_reference == newest.memory_reference {
+                        cached
+                            .instruction_offset
+                            .abs_diff(newest.instruction_offset)
+                    } else {
+                        u64::MAX
+                    }
+                })
+                .cloned()
+        {
+            self.disassembly.remove(&furthest);
+            self.invalidate_state(&furthest.into());
+        }
+    }
+
     pub fn ignore_breakpoints(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2086,6 +2218,155 @@ impl Session {
         self.send_data_breakpoints(cx);
     }
 
+    pub fn function_breakpoints(&self) -> impl Iterator<Item = &FunctionBreakpointState> {
+        self.function_breakpoints.values()
+    }
+
+    /// Adds a breakpoint on the function called `name`, re-enabling it if it already exists.
+    pub fn add_function_breakpoint(&mut self, name: String, cx: &mut Context<Self>) {
+        self.function_breakpoints
+            .entry(name.clone())
+            .and_modify(|state| state.is_enabled = true)
+            .or_insert_with(|| FunctionBreakpointState {
+                dap: dap::FunctionBreakpoint {
+                    name,
+                    condition: None,
+                    hit_condition: None,
+                },
+                is_enabled: true,
+            });
+        self.send_function_breakpoints(cx);
+    }
+
+    pub fn remove_function_breakpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        if self.function_breakpoints.remove(name).is_some() {
+            self.send_function_breakpoints(cx);
+        }
+    }
+
+    pub fn toggle_function_breakpoint(&mut self, name: &str, cx: &mut Context<Self>) {
+        if let Some(state) = self.function_breakpoints.get_mut(name) {
+            state.is_enabled = !state.is_enabled;
+            self.send_function_breakpoints(cx);
+        }
+    }
+
+    pub fn set_function_breakpoint_condition(
+        &mut self,
+        name: &str,
+        condition: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(state) = self.function_breakpoints.get_mut(name) {
+            state.dap.condition = condition;
+            self.send_function_breakpoints(cx);
+        }
+    }
+
+    pub fn set_function_breakpoint_hit_condition(
+        &mut self,
+        name: &str,
+        hit_condition: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(state) = self.function_breakpoints.get_mut(name) {
+            state.dap.hit_condition = hit_condition;
+            self.send_function_breakpoints(cx);
+        }
+    }
+
+    fn send_function_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if let Some(mode) = self.as_running() {
+            let breakpoints = self
+                .function_breakpoints
+                .values()
+                .filter_map(|state| state.is_enabled.then(|| state.dap.clone()))
+                .collect();
+            let command = SetFunctionBreakpointsCommand { breakpoints };
+            mode.request(command).detach_and_log_err(cx);
+        }
+    }
+
+    /// Returns whether the last stop of `thread_id` was caused by an exception.
+    pub fn stopped_on_exception(&self, thread_id: ThreadId) -> bool {
+        self.session_state().exception_threads.contains(&thread_id)
+    }
+
+    /// Returns details about the exception that stopped `thread_id`, if any.
+    pub fn exception_info(
+        &mut self,
+        thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) -> Option<&dap::ExceptionInfoResponse> {
+        if !self.stopped_on_exception(thread_id) {
+            return None;
+        }
+
+        self.fetch(
+            ExceptionInfoCommand {
+                thread_id: thread_id.0,
+            },
+            move |this, result, cx| {
+                let Some(result) = result.log_err() else {
+                    return;
+                };
+
+                this.active_snapshot
+                    .exception_info
+                    .insert(thread_id, result);
+                cx.emit(SessionEvent::ExceptionInfo);
+                cx.notify();
+            },
+            cx,
+        );
+
+        self.session_state().exception_info.get(&thread_id)
+    }
+
+    pub fn instruction_breakpoints(&self) -> impl Iterator<Item = &InstructionBreakpointState> {
+        self.instruction_breakpoints.values()
+    }
+
+    /// Sets a breakpoint on the instruction at `instruction_reference`, or removes it if one exists.
+    pub fn toggle_instruction_breakpoint(
+        &mut self,
+        instruction_reference: String,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .instruction_breakpoints
+            .remove(&instruction_reference)
+            .is_none()
+        {
+            self.instruction_breakpoints.insert(
+                instruction_reference.clone(),
+                InstructionBreakpointState {
+                    dap: dap::InstructionBreakpoint {
+                        instruction_reference,
+                        offset: None,
+                        condition: None,
+                        hit_condition: None,
+                        mode: None,
+                    },
+                    is_enabled: true,
+                },
+            );
+        }
+        self.send_instruction_breakpoints(cx);
+    }
+
+    fn send_instruction_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if let Some(mode) = self.as_running() {
+            let breakpoints = self
+                .instruction_breakpoints
+                .values()
+                .filter_map(|state| state.is_enabled.then(|| state.dap.clone()))
+                .collect();
+            let command = SetInstructionBreakpointsCommand { breakpoints };
+            mode.request(command).detach_and_log_err(cx);
+        }
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2343,6 +2624,28 @@ impl Session {
         thread_id: ThreadId,
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
+    ) {
+        self.step_in_inner(thread_id, granularity, None, cx);
+    }
+
+    /// Steps into one of the targets returned by [`Session::step_in_targets`], for lines
+    /// that contain several calls.
+    pub fn step_in_target(
+        &mut self,
+        thread_id: ThreadId,
+        target_id: u64,
+        granularity: SteppingGranularity,
+        cx: &mut Context<Self>,
+    ) {
+        self.step_in_inner(thread_id, granularity, Some(target_id), cx);
+    }
+
+    fn step_in_inner(
+        &mut self,
+        thread_id: ThreadId,
+        granularity: SteppingGranularity,
+        target_id: Option<u64>,
+        cx: &mut Context<Self>,
     ) {
         self.select_historic_snapshot(None, cx);
 
@@ -2359,6 +2662,7 @@ impl Session {
                 granularity: supports_stepping_granularity.then(|| granularity),
                 single_thread: supports_single_thread_execution_requests,
             },
+            target_id,
         };
 
         self.active_snapshot.thread_states.process_step(thread_id);
@@ -2435,6 +2739,55 @@ impl Session {
         .detach();
     }
 
+    pub fn step_in_targets(
+        &mut self,
+        stack_frame_id: StackFrameId,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::StepInTarget>>> {
+        self.request(
+            StepInTargetsCommand { stack_frame_id },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    /// Returns the locations execution can be moved to on the given (zero-based) row.
+    pub fn goto_targets(
+        &mut self,
+        path: Arc<Path>,
+        row: u32,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::GotoTarget>>> {
+        self.request(
+            GotoTargetsCommand {
+                path,
+                line: row as u64 + 1,
+                column: None,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    /// Moves the next statement to execute on the thread to a target returned by
+    /// [`Session::goto_targets`], without running the code in between.
+    pub fn goto(&mut self, thread_id: ThreadId, target_id: u64, cx: &mut Context<Self>) {
+        self.select_historic_snapshot(None, cx);
+
+        let command = GotoCommand {
+            thread_id: thread_id.0,
+            target_id,
+        };
+
+        self.active_snapshot.thread_states.process_step(thread_id);
+        self.request(
+            command,
+            Self::on_step_response::<GotoCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
     pub fn stack_frames(
         &mut self,
         thread_id: ThreadId,
@@ -28,6 +28,7 @@ use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, Oid, RunHook,
     blame::Blame,
     parse_git_remote_url,
+    rebase::{RebaseAction, RebaseStatus, RebaseTodo, RebaseTodoEntry, RebaseTodoLine},
     repository::{
         Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
         GitRepository, GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder,
@@ -275,6 +276,7 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    pub rebase: Option<RebaseStatus>,
 }
 
 type JobId = u64;
@@ -396,6 +398,7 @@ pub enum RepositoryEvent {
     MergeHeadsChanged,
     BranchChanged,
     StashEntriesChanged,
+    RebaseStatusChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
     GitGraphCountUpdated((LogOrder, LogSource), usize),
 }
@@ -520,6 +523,13 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_run_hook);
         client.add_entity_request_handler(Self::handle_reset);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_rebase_todo);
+        client.add_entity_request_handler(Self::handle_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_rebase_continue);
+        client.add_entity_request_handler(Self::handle_rebase_skip);
+        client.add_entity_request_handler(Self::handle_rebase_abort);
         client.add_entity_request_handler(Self::handle_show);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_file_history);
@@ -2548,6 +2558,121 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.cherry_pick(envelope.payload.commits, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commits, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let todo = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(envelope.payload.base)
+            })
+            .await??;
+        Ok(proto::GitRebaseTodoResponse {
+            lines: rebase_todo_to_proto(&todo),
+        })
+    }
+
+    async fn handle_rebase_interactive(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let todo = proto_to_rebase_todo(envelope.payload.todo)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_interactive(envelope.payload.base, todo, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_continue(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseContinue>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_continue(cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_skip(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseSkip>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_skip(cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_abort(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseAbort>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_abort(cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_checkout_files(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitCheckoutFiles>,
@@ -3433,6 +3558,7 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            rebase: None,
             path_style,
         }
     }
@@ -3468,6 +3594,7 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase: self.rebase.as_ref().map(rebase_status_to_proto),
         }
     }
 
@@ -3539,6 +3666,7 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            rebase: self.rebase.as_ref().map(rebase_status_to_proto),
         }
     }
 
@@ -4241,6 +4369,59 @@ impl Repository {
         })
     }
 
+    pub fn cherry_pick(
+        &mut self,
+        commits: Vec<String>,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git cherry-pick".into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(commits, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commits,
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn revert(&mut self, commits: Vec<String>, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(Some("git revert".into()), move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.revert(commits, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    client
+                        .request(proto::GitRevert {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commits,
+                        })
+                        .await?;
+                    Ok(())
+                }
+            }
+        })
+    }
+
     pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
         let id = self.id;
         self.send_job(None, move |git_repo, _cx| async move {
@@ -4998,6 +5179,138 @@ impl Repository {
         })
     }
 
+    /// Lists the commits an interactive rebase onto `base` would start with, for the user to
+    /// edit before calling [`Self::rebase_interactive`].
+    pub fn rebase_todo(&mut self, base: Option<String>) -> oneshot::Receiver<Result<RebaseTodo>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState {
+                    backend,
+                    environment,
+                    ..
+                }) => backend.rebase_todo(base, environment).await,
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+                    proto_to_rebase_todo(response.lines)
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        base: Option<String>,
+        todo: RebaseTodo,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git rebase --interactive".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_interactive(base, todo, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseInteractive {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                base,
+                                todo: rebase_todo_to_proto(&todo),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_continue(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git rebase --continue".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_continue(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseContinue {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_skip(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git rebase --skip".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_skip(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseSkip {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_abort(&mut self, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git rebase --abort".into()),
+            move |git_repo, _cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_abort(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseAbort {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
     pub fn run_hook(&mut self, hook: RunHook, _cx: &mut App) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
         self.send_job(
@@ -5868,6 +6181,11 @@ impl Repository {
         self.snapshot.stash_entries = new_stash_entries;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
+        let new_rebase = update.rebase.as_ref().map(proto_to_rebase_status);
+        if self.snapshot.rebase != new_rebase {
+            cx.emit(RepositoryEvent::RebaseStatusChanged)
+        }
+        self.snapshot.rebase = new_rebase;
 
         let edits = update
             .removed_statuses
@@ -6571,6 +6889,68 @@ fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
     }
 }
 
+fn rebase_status_to_proto(status: &RebaseStatus) -> proto::RebaseStatus {
+    proto::RebaseStatus {
+        head_name: status.head_name.as_ref().map(ToString::to_string),
+        step: status.step as u64,
+        total: status.total as u64,
+        stopped_sha: status.stopped_sha.as_ref().map(ToString::to_string),
+        interactive: status.interactive,
+    }
+}
+
+fn proto_to_rebase_status(proto: &proto::RebaseStatus) -> RebaseStatus {
+    RebaseStatus {
+        head_name: proto.head_name.clone().map(SharedString::from),
+        step: proto.step as usize,
+        total: proto.total as usize,
+        stopped_sha: proto.stopped_sha.clone().map(SharedString::from),
+        interactive: proto.interactive,
+    }
+}
+
+fn rebase_todo_to_proto(todo: &RebaseTodo) -> Vec<proto::RebaseTodoLine> {
+    todo.lines
+        .iter()
+        .map(|line| proto::RebaseTodoLine {
+            line: Some(match line {
+                RebaseTodoLine::Commit(entry) => {
+                    proto::rebase_todo_line::Line::Commit(proto::RebaseTodoEntry {
+                        action: entry.action.as_str().to_string(),
+                        sha: entry.sha.to_string(),
+                        subject: entry.subject.to_string(),
+                        message: entry.message.clone(),
+                    })
+                }
+                RebaseTodoLine::Command(command) => {
+                    proto::rebase_todo_line::Line::Command(command.clone())
+                }
+            }),
+        })
+        .collect()
+}
+
+fn proto_to_rebase_todo(lines: Vec<proto::RebaseTodoLine>) -> Result<RebaseTodo> {
+    let lines = lines
+        .into_iter()
+        .map(|line| {
+            Ok(match line.line.context("missing rebase todo line")? {
+                proto::rebase_todo_line::Line::Commit(entry) => {
+                    RebaseTodoLine::Commit(RebaseTodoEntry {
+                        action: RebaseAction::parse(&entry.action)
+                            .with_context(|| format!("invalid rebase action {:?}", entry.action))?,
+                        sha: entry.sha.into(),
+                        subject: entry.subject.into(),
+                        message: entry.message,
+                    })
+                }
+                proto::rebase_todo_line::Line::Command(command) => RebaseTodoLine::Command(command),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(RebaseTodo { lines })
+}
+
 async fn compute_snapshot(
     id: RepositoryId,
     work_directory_abs_path: Arc<Path>,
@@ -6618,6 +6998,11 @@ async fn compute_snapshot(
         events.push(RepositoryEvent::BranchChanged);
     }
 
+    let rebase = backend.rebase_status().await;
+    if rebase != prev_snapshot.rebase {
+        events.push(RepositoryEvent::RebaseStatusChanged);
+    }
+
     let remote_origin_url = backend.remote_url("origin").await;
     let remote_upstream_url = backend.remote_url("upstream").await;
 
@@ -6633,6 +7018,7 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        rebase,
     };
 
     Ok((snapshot, events))
@@ -0,0 +1,159 @@
+//! Diagnostics found in the output of tasks by their problem matchers.
+//!
+//! They are stored like the diagnostics of a language server, under a reserved server id, with the
+//! task's label as their source, so that each task only replaces its own diagnostics when it runs again.
+
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use collections::HashMap;
+use gpui::Context;
+use language::{Diagnostic, DiagnosticEntry, PointUtf16, Unclipped};
+use lsp::{DiagnosticSeverity, LanguageServerId};
+use task::{Problem, ProblemSeverity};
+use util::{paths::normalize_lexically, post_inc};
+
+use crate::lsp_store::{DocumentDiagnostics, DocumentDiagnosticsUpdate, LspStore};
+
+/// The server id that diagnostics reported by tasks are stored under.
+pub const TASK_DIAGNOSTICS_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX - 1);
+
+impl LspStore {
+    /// Replaces the diagnostics previously reported by the task labeled `task_label` with `problems`.
+    /// Relative paths of the problems are resolved against `cwd`, or the worktree containing them.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_label: &str,
+        cwd: Option<PathBuf>,
+        problems: Vec<Problem>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let Some(local) = self.as_local() else {
+            return Ok(());
+        };
+
+        let mut diagnostics_by_path = HashMap::<PathBuf, Vec<_>>::default();
+        for (worktree_id, diagnostics_for_tree) in &local.diagnostics {
+            let Some(worktree) = self
+                .worktree_store
+                .read(cx)
+                .worktree_for_id(*worktree_id, cx)
+            else {
+                continue;
+            };
+            for (path, diagnostics_by_server_id) in diagnostics_for_tree {
+                let reported_by_task =
+                    diagnostics_by_server_id.iter().any(|(server_id, entries)| {
+                        *server_id == TASK_DIAGNOSTICS_SERVER_ID
+                            && entries
+                                .iter()
+                                .any(|entry| entry.diagnostic.source.as_deref() == Some(task_label))
+                    });
+                if reported_by_task {
+                    diagnostics_by_path
+                        .entry(worktree.read(cx).absolutize(path))
+                        .or_default();
+                }
+            }
+        }
+
+        for problem in problems {
+            let Some(abs_path) = self.resolve_problem_path(&problem, cwd.as_deref(), cx) else {
+                log::debug!("skipping task problem in unknown file {:?}", problem.path);
+                continue;
+            };
+            let group_id = post_inc(&mut self.as_local_mut().unwrap().next_diagnostic_group_id);
+            diagnostics_by_path
+                .entry(abs_path)
+                .or_default()
+                .push(problem_to_diagnostic(task_label, problem, group_id));
+        }
+
+        let updates = diagnostics_by_path
+            .into_iter()
+            .map(
+                |(document_abs_path, diagnostics)| DocumentDiagnosticsUpdate {
+                    diagnostics: DocumentDiagnostics {
+                        diagnostics,
+                        document_abs_path,
+                        version: None,
+                    },
+                    result_id: None,
+                    registration_id: None,
+                    server_id: TASK_DIAGNOSTICS_SERVER_ID,
+                    disk_based_sources: Cow::Borrowed(&[]),
+                },
+            )
+            .collect();
+        self.merge_diagnostic_entries(
+            updates,
+            |_, diagnostic, _| diagnostic.source.as_deref() != Some(task_label),
+            cx,
+        )
+    }
+
+    fn resolve_problem_path(
+        &self,
+        problem: &Problem,
+        cwd: Option<&Path>,
+        cx: &Context<Self>,
+    ) -> Option<PathBuf> {
+        let worktree_store = self.worktree_store.read(cx);
+        let candidates = if problem.path.is_absolute() {
+            vec![problem.path.clone()]
+        } else if let Some(cwd) = cwd {
+            vec![cwd.join(&problem.path)]
+        } else {
+            worktree_store
+                .visible_worktrees(cx)
+                .map(|worktree| worktree.read(cx).abs_path().join(&problem.path))
+                .collect()
+        };
+        candidates.into_iter().find_map(|candidate| {
+            let abs_path = normalize_lexically(&candidate).ok()?;
+            let (worktree, path) = worktree_store.find_worktree(&abs_path, cx)?;
+            worktree.read(cx).entry_for_path(&path)?;
+            Some(abs_path)
+        })
+    }
+}
+
+fn problem_to_diagnostic(
+    task_label: &str,
+    problem: Problem,
+    group_id: usize,
+) -> DiagnosticEntry<Unclipped<PointUtf16>> {
+    let start = PointUtf16::new(
+        problem.line.saturating_sub(1),
+        problem.column.unwrap_or(1).saturating_sub(1),
+    );
+    let end = match (problem.end_line, problem.end_column) {
+        (end_line, Some(end_column)) => PointUtf16::new(
+            end_line.unwrap_or(problem.line).saturating_sub(1),
+            end_column.saturating_sub(1),
+        ),
+        (Some(end_line), None) => PointUtf16::new(end_line.saturating_sub(1), u32::MAX),
+        (None, None) if problem.column.is_none() => PointUtf16::new(start.row, u32::MAX),
+        (None, None) => start,
+    };
+    DiagnosticEntry {
+        range: Unclipped(start)..Unclipped(end.max(start)),
+        diagnostic: Diagnostic {
+            source: Some(task_label.to_string()),
+            code: problem.code.map(lsp::NumberOrString::String),
+            severity: match problem.severity {
+                ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+            },
+            message: problem.message,
+            group_id,
+            is_primary: true,
+            is_disk_based: true,
+            ..Diagnostic::default()
+        },
+    }
+}
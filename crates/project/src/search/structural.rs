@@ -0,0 +1,606 @@
+//! Structural search: matching code by its syntax tree instead of its text.
+//!
+//! A structural pattern is either a tree-sitter query, or a piece of code with metavariables like
+//! `foo($A, $B)`, where each metavariable matches a whole syntax node.
+
+use std::{fmt, ops::Range, sync::Arc};
+
+use anyhow::{Result, bail};
+use collections::HashMap;
+use language::{BufferSnapshot, LanguageName, Node, SyntaxLayer};
+use parking_lot::Mutex;
+use streaming_iterator::StreamingIterator as _;
+use tree_sitter::{Query, QueryCursor};
+
+/// The maximum number of steps spent on matching a code pattern at a single position,
+/// to bound the backtracking of patterns with many metavariables.
+const MAX_STEPS_PER_POSITION: usize = 10_000;
+
+/// The name of the capture that holds the range of a tree-sitter query's match.
+const MATCH_CAPTURE_NAME: &str = "match";
+
+pub struct StructuralQuery {
+    pattern: StructuralPattern,
+    compiled_queries: Mutex<HashMap<LanguageName, Option<Arc<Query>>>>,
+}
+
+enum StructuralPattern {
+    /// A tree-sitter query, compiled for the language of each syntax layer it runs on.
+    Query(String),
+    /// A code pattern, split into literal code and metavariables.
+    Code(Vec<PatternToken>),
+}
+
+#[derive(Debug, PartialEq)]
+enum PatternToken {
+    /// Code that has to match the text of consecutive syntax tokens, ignoring whitespace between them.
+    Literal(Vec<char>),
+    /// `$NAME` matches a single syntax node, `$_` matches one without capturing it.
+    Node(Option<String>),
+    /// `$$$NAME` matches any number of consecutive sibling nodes, `$$$` matches them without capturing them.
+    Nodes(Option<String>),
+}
+
+/// A match of a [`StructuralQuery`] in a buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructuralMatch {
+    pub range: Range<usize>,
+    /// The ranges captured by the match, by the name of their metavariable or query capture.
+    pub captures: Vec<(String, Range<usize>)>,
+}
+
+impl StructuralQuery {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            bail!("structural search pattern is empty");
+        }
+        let pattern = if is_tree_sitter_query(pattern) {
+            StructuralPattern::Query(pattern.to_string())
+        } else {
+            StructuralPattern::Code(parse_code_pattern(pattern))
+        };
+        Ok(Self {
+            pattern,
+            compiled_queries: Mutex::default(),
+        })
+    }
+
+    /// Literal words that occur in every match of the pattern,
+    /// which allow skipping files that cannot contain any matches without parsing them.
+    pub fn required_words(&self) -> Vec<String> {
+        let StructuralPattern::Code(tokens) = &self.pattern else {
+            return Vec::new();
+        };
+        let mut words = Vec::new();
+        for token in tokens {
+            if let PatternToken::Literal(chars) = token {
+                let literal = chars.iter().collect::<String>();
+                words.extend(
+                    literal
+                        .split(|c: char| !is_word_char(c))
+                        .filter(|word| !word.is_empty())
+                        .map(ToOwned::to_owned),
+                );
+            }
+        }
+        words
+    }
+
+    /// Finds the non-overlapping matches of the pattern in `range` of the buffer's syntax trees.
+    pub fn find_matches(
+        &self,
+        buffer: &BufferSnapshot,
+        range: Range<usize>,
+    ) -> Vec<StructuralMatch> {
+        let mut matches = Vec::new();
+        for layer in buffer.syntax_layers_for_range(range.clone(), true) {
+            match &self.pattern {
+                StructuralPattern::Query(query) => {
+                    if let Some(query) = self.compiled_query(query, &layer) {
+                        find_query_matches(&query, &layer, buffer, range.clone(), &mut matches);
+                    }
+                }
+                StructuralPattern::Code(pattern) => {
+                    find_code_matches(pattern, &layer, buffer, range.clone(), &mut matches)
+                }
+            }
+        }
+        matches.sort_by_key(|mat| (mat.range.start, usize::MAX - mat.range.end));
+        let mut last_end = 0;
+        matches.retain(|mat| {
+            let overlaps = mat.range.start < last_end;
+            if !overlaps {
+                last_end = mat.range.end;
+            }
+            !overlaps
+        });
+        matches
+    }
+
+    fn compiled_query(&self, query: &str, layer: &SyntaxLayer) -> Option<Arc<Query>> {
+        let grammar = layer.language.grammar()?;
+        self.compiled_queries
+            .lock()
+            .entry(layer.language.name())
+            .or_insert_with(|| match Query::new(&grammar.ts_language, query) {
+                Ok(query) => Some(Arc::new(query)),
+                Err(e) => {
+                    log::debug!(
+                        "structural search query is invalid for {}: {e}",
+                        layer.language.name()
+                    );
+                    None
+                }
+            })
+            .clone()
+    }
+}
+
+impl fmt::Debug for StructuralQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("StructuralQuery");
+        match &self.pattern {
+            StructuralPattern::Query(query) => debug.field("query", query),
+            StructuralPattern::Code(tokens) => debug.field("pattern", tokens),
+        };
+        debug.finish_non_exhaustive()
+    }
+}
+
+impl StructuralMatch {
+    /// Expands `$NAME` and `${NAME}` in `template` to the text of the corresponding capture,
+    /// and `$$` to a single `$`. Names without a capture are left as they are.
+    pub fn replacement(&self, template: &str, buffer: &BufferSnapshot) -> String {
+        let mut replacement = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(dollar_ix) = rest.find('$') {
+            replacement.push_str(&rest[..dollar_ix]);
+            rest = &rest[dollar_ix + 1..];
+            if let Some(after_dollar) = rest.strip_prefix('$') {
+                replacement.push('$');
+                rest = after_dollar;
+                continue;
+            }
+
+            let (name, reference_len) = match rest.strip_prefix('{') {
+                Some(braced) => match braced.find('}') {
+                    Some(end) => (&braced[..end], end + 2),
+                    None => ("", 0),
+                },
+                None => {
+                    let end = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+                    (&rest[..end], end)
+                }
+            };
+            match self.captures.iter().find(|(capture, _)| capture == name) {
+                Some((_, range)) if !name.is_empty() => {
+                    replacement.extend(buffer.text_for_range(range.clone()));
+                    rest = &rest[reference_len..];
+                }
+                _ => replacement.push('$'),
+            }
+        }
+        replacement.push_str(rest);
+        replacement
+    }
+}
+
+/// Patterns starting with a node and containing a capture are tree-sitter queries,
+/// as the captures determine the range of their matches.
+fn is_tree_sitter_query(pattern: &str) -> bool {
+    (pattern.starts_with('(') || pattern.starts_with('['))
+        && pattern
+            .split('@')
+            .skip(1)
+            .any(|after_at| after_at.starts_with(is_word_char))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_metavariable_char(c: char) -> bool {
+    c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'
+}
+
+fn parse_code_pattern(pattern: &str) -> Vec<PatternToken> {
+    fn push_literal(tokens: &mut Vec<PatternToken>, literal: &mut String) {
+        let collapsed = literal.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !collapsed.is_empty() {
+            tokens.push(PatternToken::Literal(collapsed.chars().collect()));
+        }
+        literal.clear();
+    }
+
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        if c == '$' {
+            let (dollars, is_nodes) = if rest.starts_with("$$$") {
+                (3, true)
+            } else {
+                (1, false)
+            };
+            let name_len = rest[dollars..]
+                .find(|c| !is_metavariable_char(c))
+                .unwrap_or(rest.len() - dollars);
+            let name = &rest[dollars..dollars + name_len];
+            if is_nodes || !name.is_empty() {
+                push_literal(&mut tokens, &mut literal);
+                let name = (!name.is_empty() && name != "_").then(|| name.to_string());
+                tokens.push(if is_nodes {
+                    PatternToken::Nodes(name)
+                } else {
+                    PatternToken::Node(name)
+                });
+                rest = &rest[dollars + name_len..];
+                continue;
+            }
+        }
+        literal.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    push_literal(&mut tokens, &mut literal);
+    tokens
+}
+
+fn find_query_matches(
+    query: &Query,
+    layer: &SyntaxLayer,
+    buffer: &BufferSnapshot,
+    range: Range<usize>,
+    matches: &mut Vec<StructuralMatch>,
+) {
+    let capture_names = query.capture_names();
+    let mut cursor = QueryCursor::new();
+    cursor.set_byte_range(range.clone());
+    let text = |node: Node<'_>| buffer.text_for_range(node.byte_range()).map(str::as_bytes);
+    let mut query_matches = cursor.matches(query, layer.node(), text);
+    while let Some(query_match) = query_matches.next() {
+        let captures = query_match
+            .captures
+            .iter()
+            .map(|capture| {
+                (
+                    capture_names[capture.index as usize].to_string(),
+                    capture.node.byte_range(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let match_range = match captures.iter().find(|(name, _)| name == MATCH_CAPTURE_NAME) {
+            Some((_, range)) => range.clone(),
+            None => {
+                let Some(start) = captures.iter().map(|(_, range)| range.start).min() else {
+                    continue;
+                };
+                let end = captures
+                    .iter()
+                    .map(|(_, range)| range.end)
+                    .max()
+                    .unwrap_or(start);
+                start..end
+            }
+        };
+        if range.start <= match_range.start && match_range.end <= range.end {
+            matches.push(StructuralMatch {
+                range: match_range,
+                captures,
+            });
+        }
+    }
+}
+
+/// A token of the syntax tree: either a leaf node,
+/// or text inside of a node that none of its children cover.
+struct SyntaxToken<'a> {
+    range: Range<usize>,
+    node: Option<Node<'a>>,
+}
+
+fn find_code_matches(
+    pattern: &[PatternToken],
+    layer: &SyntaxLayer,
+    buffer: &BufferSnapshot,
+    range: Range<usize>,
+    matches: &mut Vec<StructuralMatch>,
+) {
+    let text = buffer.text_for_range(range.clone()).collect::<String>();
+    let tokens = syntax_tokens(layer.node(), &text, range.clone());
+    let mut matcher = CodeMatcher {
+        pattern,
+        tokens: &tokens,
+        text: &text,
+        text_offset: range.start,
+        range_end: range.end,
+        captures: Vec::new(),
+        steps: 0,
+    };
+
+    let mut token_ix = 0;
+    while token_ix < tokens.len() {
+        matcher.captures.clear();
+        matcher.steps = 0;
+        match matcher.match_pattern(0, token_ix) {
+            Some(end_ix) if end_ix > token_ix => {
+                matches.push(StructuralMatch {
+                    range: tokens[token_ix].range.start..tokens[end_ix - 1].range.end,
+                    captures: matcher
+                        .captures
+                        .drain(..)
+                        .filter_map(|(name, range)| Some((name?, range)))
+                        .collect(),
+                });
+                token_ix = end_ix;
+            }
+            _ => token_ix += 1,
+        }
+    }
+}
+
+fn syntax_tokens<'a>(root: Node<'a>, text: &str, range: Range<usize>) -> Vec<SyntaxToken<'a>> {
+    let mut tokens = Vec::new();
+    let mut previous_end = range.start;
+    let push_gap = |tokens: &mut Vec<SyntaxToken<'a>>, gap: Range<usize>| {
+        let gap_text = &text[gap.start - range.start..gap.end - range.start];
+        let trimmed = gap_text.trim();
+        if !trimmed.is_empty() {
+            let start = gap.start + (gap_text.len() - gap_text.trim_start().len());
+            tokens.push(SyntaxToken {
+                range: start..start + trimmed.len(),
+                node: None,
+            });
+        }
+    };
+
+    let mut cursor = root.walk();
+    loop {
+        let node = cursor.node();
+        let node_range = node.byte_range();
+        let intersects = node_range.start < range.end && node_range.end > range.start;
+        if intersects && node.child_count() == 0 {
+            if range.start <= node_range.start
+                && node_range.end <= range.end
+                && !node_range.is_empty()
+            {
+                if previous_end < node_range.start {
+                    push_gap(&mut tokens, previous_end..node_range.start);
+                }
+                previous_end = node_range.end;
+                tokens.push(SyntaxToken {
+                    range: node_range,
+                    node: Some(node),
+                });
+            }
+        } else if intersects && cursor.goto_first_child() {
+            continue;
+        }
+
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return tokens;
+            }
+        }
+    }
+}
+
+struct CodeMatcher<'a, 'tree> {
+    pattern: &'a [PatternToken],
+    tokens: &'a [SyntaxToken<'tree>],
+    text: &'a str,
+    text_offset: usize,
+    range_end: usize,
+    captures: Vec<(Option<String>, Range<usize>)>,
+    steps: usize,
+}
+
+impl<'tree> CodeMatcher<'_, 'tree> {
+    /// Matches the pattern from `pattern_ix` on against the tokens from `token_ix` on,
+    /// returning the index of the first token after the match.
+    fn match_pattern(&mut self, pattern_ix: usize, token_ix: usize) -> Option<usize> {
+        self.steps += 1;
+        if self.steps > MAX_STEPS_PER_POSITION {
+            return None;
+        }
+        let Some(pattern_token) = self.pattern.get(pattern_ix) else {
+            return Some(token_ix);
+        };
+
+        match pattern_token {
+            PatternToken::Literal(literal) => {
+                let next_token_ix = self.match_literal(literal, token_ix)?;
+                self.match_pattern(pattern_ix + 1, next_token_ix)
+            }
+            PatternToken::Node(name) => {
+                let nodes = self.nodes_starting_at(token_ix);
+                for node in nodes.into_iter().filter(|node| node.is_named()) {
+                    let end_ix = self.token_ix_after(node.end_byte());
+                    if let Some(end_ix) =
+                        self.match_capture(name, pattern_ix, token_ix, end_ix, node.byte_range())
+                    {
+                        return Some(end_ix);
+                    }
+                }
+                None
+            }
+            PatternToken::Nodes(name) => {
+                let mut ends = Vec::new();
+                for first in self.nodes_starting_at(token_ix) {
+                    let mut node = Some(first);
+                    while let Some(current) = node
+                        && current.end_byte() <= self.range_end
+                    {
+                        ends.push(current.end_byte());
+                        node = current.next_sibling();
+                    }
+                }
+                ends.sort_unstable();
+                ends.dedup();
+
+                let start = self.tokens.get(token_ix).map(|token| token.range.start);
+                for end in ends.into_iter().rev() {
+                    let end_ix = self.token_ix_after(end);
+                    let range = start.unwrap_or(end)..end;
+                    if let Some(end_ix) =
+                        self.match_capture(name, pattern_ix, token_ix, end_ix, range)
+                    {
+                        return Some(end_ix);
+                    }
+                }
+                // No nodes at all.
+                let empty_start = start.unwrap_or(self.range_end);
+                self.match_capture(
+                    name,
+                    pattern_ix,
+                    token_ix,
+                    token_ix,
+                    empty_start..empty_start,
+                )
+            }
+        }
+    }
+
+    /// Captures `range` under `name` and matches the rest of the pattern after it,
+    /// requiring repeated metavariables to capture the same text.
+    fn match_capture(
+        &mut self,
+        name: &Option<String>,
+        pattern_ix: usize,
+        token_ix: usize,
+        end_ix: usize,
+        range: Range<usize>,
+    ) -> Option<usize> {
+        debug_assert!(token_ix <= end_ix);
+        if let Some(name) = name
+            && let Some((_, previous)) = self
+                .captures
+                .iter()
+                .find(|(capture, _)| capture.as_ref() == Some(name))
+            && self.text_for(previous.clone()) != self.text_for(range.clone())
+        {
+            return None;
+        }
+
+        self.captures.push((name.clone(), range));
+        let result = self.match_pattern(pattern_ix + 1, end_ix);
+        if result.is_none() {
+            self.captures.pop();
+        }
+        result
+    }
+
+    /// Matches literal code against the tokens from `token_ix` on. Each token has to be matched completely,
+    /// and whitespace in the literal only has to be present inside of tokens, like in string literals.
+    fn match_literal(&self, literal: &[char], mut token_ix: usize) -> Option<usize> {
+        let mut literal_ix = 0;
+        while literal_ix < literal.len() {
+            if literal[literal_ix] == ' ' {
+                literal_ix += 1;
+                continue;
+            }
+
+            let token = self.tokens.get(token_ix)?;
+            let mut last_char = None;
+            let mut in_whitespace = false;
+            for c in self.text_for(token.range.clone()).chars() {
+                if c.is_whitespace() {
+                    if !in_whitespace {
+                        if literal.get(literal_ix) != Some(&' ') {
+                            return None;
+                        }
+                        literal_ix += 1;
+                        in_whitespace = true;
+                    }
+                    continue;
+                }
+                in_whitespace = false;
+                if literal.get(literal_ix) != Some(&c) {
+                    return None;
+                }
+                literal_ix += 1;
+                last_char = Some(c);
+            }
+
+            // Words that are a single token in the literal must not be split across tokens.
+            if let Some(last_char) = last_char
+                && let Some(next_char) = literal.get(literal_ix)
+                && is_word_char(last_char)
+                && is_word_char(*next_char)
+            {
+                return None;
+            }
+            token_ix += 1;
+        }
+        Some(token_ix)
+    }
+
+    /// The nodes starting at the given token, from the largest to the smallest.
+    fn nodes_starting_at(&self, token_ix: usize) -> Vec<Node<'tree>> {
+        let Some(token) = self.tokens.get(token_ix) else {
+            return Vec::new();
+        };
+        let Some(leaf) = token.node else {
+            return Vec::new();
+        };
+
+        let mut nodes = vec![leaf];
+        let mut node = leaf;
+        while let Some(parent) = node.parent() {
+            if parent.start_byte() != leaf.start_byte() || parent.end_byte() > self.range_end {
+                break;
+            }
+            if parent.byte_range() != node.byte_range() {
+                nodes.push(parent);
+            }
+            node = parent;
+        }
+        nodes.reverse();
+        nodes
+    }
+
+    fn token_ix_after(&self, offset: usize) -> usize {
+        self.tokens
+            .partition_point(|token| token.range.start < offset)
+    }
+
+    fn text_for(&self, range: Range<usize>) -> &str {
+        &self.text[range.start - self.text_offset..range.end - self.text_offset]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_code_pattern() {
+        assert_eq!(
+            parse_code_pattern("foo($A,   $_)\n.bar($$$ARGS) + $$$ - $x"),
+            vec![
+                PatternToken::Literal("foo(".chars().collect()),
+                PatternToken::Node(Some("A".to_string())),
+                PatternToken::Literal(",".chars().collect()),
+                PatternToken::Node(None),
+                PatternToken::Literal(") .bar(".chars().collect()),
+                PatternToken::Nodes(Some("ARGS".to_string())),
+                PatternToken::Literal(") +".chars().collect()),
+                PatternToken::Nodes(None),
+                PatternToken::Literal("- $x".chars().collect()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tree_sitter_query_detection() {
+        assert!(is_tree_sitter_query(
+            "(call_expression function: (identifier) @name)"
+        ));
+        assert!(is_tree_sitter_query(
+            "[(integer_literal) (float_literal)] @number"
+        ));
+        assert!(!is_tree_sitter_query("(a + b)"));
+        assert!(!is_tree_sitter_query("foo(@x)"));
+        assert!(!is_tree_sitter_query("(a, b) @ c"));
+    }
+}
@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet, VecDeque};
 use dap::DapRegistry;
 use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, WeakEntity};
@@ -21,8 +21,8 @@ use lsp::{LanguageServerId, LanguageServerName};
 use paths::{debug_task_file_name, task_file_name};
 use settings::{InvalidSettingsError, parse_json_with_comments};
 use task::{
-    DebugScenario, ResolvedTask, SharedTaskContext, TaskContext, TaskId, TaskTemplate,
-    TaskTemplates, TaskVariables, VariableName,
+    DebugCompound, DebugScenario, DebugTaskFileEntry, ResolvedTask, SharedTaskContext, TaskContext,
+    TaskId, TaskTemplate, TaskTemplates, TaskVariables, VariableName,
 };
 use text::{BufferId, Point, ToPoint};
 use util::{NumericPrefixWithSuffix, ResultExt as _, post_inc, rel_path::RelPath};
@@ -43,6 +43,7 @@ pub struct Inventory {
     last_scheduled_scenarios: VecDeque<(DebugScenario, DebugScenarioContext)>,
     templates_from_settings: InventoryFor<TaskTemplate>,
     scenarios_from_settings: InventoryFor<DebugScenario>,
+    compounds_from_settings: InventoryFor<DebugCompound>,
 }
 
 impl std::fmt::Debug for Inventory {
@@ -52,6 +53,7 @@ impl std::fmt::Debug for Inventory {
             .field("last_scheduled_scenarios", &self.last_scheduled_scenarios)
             .field("templates_from_settings", &self.templates_from_settings)
             .field("scenarios_from_settings", &self.scenarios_from_settings)
+            .field("compounds_from_settings", &self.compounds_from_settings)
             .finish()
     }
 }
@@ -73,6 +75,12 @@ impl InventoryContents for DebugScenario {
     const LABEL: &'static str = "debug scenarios";
 }
 
+impl InventoryContents for DebugCompound {
+    const GLOBAL_SOURCE_FILE: &'static str = "debug.json";
+
+    const LABEL: &'static str = "compound debug scenarios";
+}
+
 #[derive(Debug)]
 struct InventoryFor<T> {
     global: HashMap<PathBuf, Vec<T>>,
@@ -130,6 +138,44 @@ impl<T> Default for InventoryFor<T> {
     }
 }
 
+/// A task that has to finish successfully before the task depending on it is started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskDependency {
+    pub source_kind: TaskSourceKind,
+    pub template: TaskTemplate,
+    /// The tasks this dependency depends on itself.
+    pub dependencies: Vec<TaskDependency>,
+}
+
+fn resolve_task_dependencies(
+    task: &TaskTemplate,
+    templates: &[(TaskSourceKind, TaskTemplate)],
+    chain: &mut Vec<String>,
+) -> Result<Vec<TaskDependency>> {
+    task.depends_on
+        .iter()
+        .map(|label| {
+            if chain.contains(label) {
+                anyhow::bail!("task dependency cycle: {} -> {label}", chain.join(" -> "));
+            }
+            let (source_kind, template) = templates
+                .iter()
+                .find(|(_, template)| &template.label == label)
+                .with_context(|| {
+                    format!("task {:?} depends on unknown task {label:?}", task.label)
+                })?;
+            chain.push(label.clone());
+            let dependencies = resolve_task_dependencies(template, templates, chain)?;
+            chain.pop();
+            Ok(TaskDependency {
+                source_kind: source_kind.clone(),
+                template: template.clone(),
+                dependencies,
+            })
+        })
+        .collect()
+}
+
 /// Kind of a source the tasks are fetched from, used to display more source information in the UI.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum TaskSourceKind {
@@ -246,6 +292,7 @@ impl Inventory {
             last_scheduled_scenarios: VecDeque::default(),
             templates_from_settings: InventoryFor::default(),
             scenarios_from_settings: InventoryFor::default(),
+            compounds_from_settings: InventoryFor::default(),
         })
     }
 
@@ -629,6 +676,50 @@ impl Inventory {
         self.scenarios_from_settings.worktree_scenarios(worktree)
     }
 
+    /// Lists the compound debug scenarios from the global debug.json and, if given, the worktree's debug files.
+    pub fn list_debug_compounds(
+        &self,
+        worktree_id: Option<WorktreeId>,
+    ) -> Vec<(TaskSourceKind, DebugCompound)> {
+        worktree_id
+            .into_iter()
+            .flat_map(|worktree_id| self.compounds_from_settings.worktree_scenarios(worktree_id))
+            .chain(self.compounds_from_settings.global_scenarios())
+            .collect()
+    }
+
+    /// Resolves the debug scenarios a compound refers to by their labels, preferring the worktree's
+    /// scenarios over global ones.
+    pub fn compound_scenarios(
+        &self,
+        compound: &DebugCompound,
+        worktree_id: Option<WorktreeId>,
+    ) -> Result<Vec<DebugScenario>> {
+        let scenarios = worktree_id
+            .into_iter()
+            .flat_map(|worktree_id| self.worktree_scenarios_from_settings(worktree_id))
+            .chain(self.global_debug_scenarios_from_settings())
+            .map(|(_, scenario)| scenario)
+            .collect::<Vec<_>>();
+
+        compound
+            .compound
+            .iter()
+            .map(|label| {
+                scenarios
+                    .iter()
+                    .find(|scenario| &scenario.label == label)
+                    .cloned()
+                    .with_context(|| {
+                        format!(
+                            "compound debug scenario {:?} refers to unknown scenario {label:?}",
+                            compound.label
+                        )
+                    })
+            })
+            .collect()
+    }
+
     fn worktree_templates_from_settings(
         &self,
         worktree: WorktreeId,
@@ -636,6 +727,25 @@ impl Inventory {
         self.templates_from_settings.worktree_scenarios(worktree)
     }
 
+    /// Resolves the tasks `task` depends on by their labels, along with their own dependencies,
+    /// preferring the worktree's tasks over global ones.
+    /// Fails if a dependency is unknown or if the dependencies form a cycle.
+    pub fn task_dependencies(
+        &self,
+        task: &TaskTemplate,
+        worktree_id: Option<WorktreeId>,
+    ) -> Result<Vec<TaskDependency>> {
+        if task.depends_on.is_empty() {
+            return Ok(Vec::new());
+        }
+        let templates = worktree_id
+            .into_iter()
+            .flat_map(|worktree_id| self.worktree_templates_from_settings(worktree_id))
+            .chain(self.global_templates_from_settings())
+            .collect::<Vec<_>>();
+        resolve_task_dependencies(task, &templates, &mut vec![task.label.clone()])
+    }
+
     /// Updates in-memory task metadata from the JSON string given.
     /// Will fail if the JSON is not a valid array of objects, but will continue if any object will not parse into a [`TaskTemplate`].
     ///
@@ -776,12 +886,35 @@ impl Inventory {
             }
         };
 
-        let new_templates = raw_tasks
-            .into_iter()
-            .filter_map(|raw_template| {
-                serde_json::from_value::<DebugScenario>(raw_template).log_err()
-            })
-            .collect::<Vec<_>>();
+        let mut new_templates = Vec::new();
+        let mut new_compounds = Vec::new();
+        for raw_template in raw_tasks {
+            match serde_json::from_value::<DebugTaskFileEntry>(raw_template).log_err() {
+                Some(DebugTaskFileEntry::Scenario(scenario)) => new_templates.push(scenario),
+                Some(DebugTaskFileEntry::Compound(compound)) => new_compounds.push(compound),
+                None => {}
+            }
+        }
+
+        match &location {
+            TaskSettingsLocation::Global(path) => {
+                self.compounds_from_settings
+                    .global
+                    .insert(path.to_path_buf(), new_compounds);
+            }
+            TaskSettingsLocation::Worktree(location) => {
+                let worktree_compounds = self
+                    .compounds_from_settings
+                    .worktree
+                    .entry(location.worktree_id)
+                    .or_default();
+                if new_compounds.is_empty() {
+                    worktree_compounds.remove(location.path);
+                } else {
+                    worktree_compounds.insert(Arc::from(location.path), new_compounds);
+                }
+            }
+        }
 
         let parsed_scenarios = &mut self.scenarios_from_settings;
         let mut new_definitions: HashMap<_, _> = new_templates
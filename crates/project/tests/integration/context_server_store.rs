@@ -1,6 +1,6 @@
 use anyhow::Result;
 use context_server::test::create_fake_transport;
-use context_server::{ContextServer, ContextServerId};
+use context_server::{ContextServer, ContextServerId, types};
 use gpui::{AppContext, AsyncApp, Entity, Subscription, Task, TestAppContext, UpdateGlobal as _};
 use http_client::{FakeHttpClient, Response};
 use project::context_server_store::registry::ContextServerDescriptorRegistry;
@@ -639,6 +639,114 @@ async fn test_server_ids_includes_disabled_servers(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_context_server_request_handlers(cx: &mut TestAppContext) {
+    const SERVER_ID: &str = "mcp-1";
+
+    let (_fs, project) = setup_context_server_test(cx, json!({"code.rs": ""}), vec![]).await;
+
+    let registry = cx.new(|_| ContextServerDescriptorRegistry::new());
+    let store = cx.new(|cx| {
+        ContextServerStore::test(
+            registry.clone(),
+            project.read(cx).worktree_store(),
+            Some(project.downgrade()),
+            cx,
+        )
+    });
+
+    let advertised_capabilities = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let transport = Arc::new(
+        create_fake_transport(SERVER_ID, cx.executor())
+            .on_request::<types::requests::Initialize, _>({
+                let advertised_capabilities = advertised_capabilities.clone();
+                move |params| {
+                    advertised_capabilities.lock().push((
+                        params.capabilities.sampling.is_some(),
+                        params.capabilities.elicitation.is_some(),
+                    ));
+                    async move {
+                        types::InitializeResponse {
+                            protocol_version: types::ProtocolVersion(
+                                types::LATEST_PROTOCOL_VERSION.to_string(),
+                            ),
+                            server_info: types::Implementation {
+                                name: SERVER_ID.to_string(),
+                                version: "1.0.0".to_string(),
+                            },
+                            capabilities: types::ServerCapabilities::default(),
+                            meta: None,
+                        }
+                    }
+                }
+            }),
+    );
+    let server = Arc::new(ContextServer::new(
+        ContextServerId(SERVER_ID.into()),
+        transport.clone(),
+    ));
+    server.on_request::<types::requests::CreateMessage>(|params, _| {
+        let text = match &params.messages[0].content {
+            types::MessageContent::Text { text, .. } => text.clone(),
+            _ => String::new(),
+        };
+        Task::ready(Ok(types::CreateMessageResult {
+            role: types::Role::Assistant,
+            content: types::MessageContent::Text {
+                text: format!("echo: {text}"),
+                annotations: None,
+            },
+            model: "fake".to_string(),
+            stop_reason: None,
+        }))
+    });
+
+    store.update(cx, |store, cx| store.test_start_server(server, cx));
+    cx.run_until_parked();
+    assert_eq!(
+        *advertised_capabilities.lock(),
+        vec![(true, false)],
+        "Only requests with a handler should be advertised"
+    );
+
+    transport.request_client::<types::requests::CreateMessage>(
+        1,
+        types::CreateMessageRequest {
+            messages: vec![types::SamplingMessage {
+                role: types::Role::User,
+                content: types::MessageContent::Text {
+                    text: "hi".to_string(),
+                    annotations: None,
+                },
+            }],
+            model_preferences: None,
+            system_prompt: None,
+            include_context: None,
+            temperature: None,
+            max_tokens: 100,
+            stop_sequences: None,
+            metadata: None,
+        },
+    );
+    cx.run_until_parked();
+    let response = transport.next_client_response().await;
+    assert_eq!(response["id"], 1);
+    assert_eq!(response["result"]["content"]["text"], "echo: hi");
+
+    transport.request_client::<types::requests::Elicitation>(
+        2,
+        serde_json::from_value(json!({
+            "message": "Who are you?",
+            "requestedSchema": { "type": "object", "properties": {} },
+        }))
+        .unwrap(),
+    );
+    cx.run_until_parked();
+    let response = transport.next_client_response().await;
+    assert_eq!(response["id"], 2);
+    assert_eq!(response["error"]["code"], -32601);
+}
+
 fn set_context_server_configuration(
     context_servers: Vec<(Arc<str>, settings::ContextServerSettingsContent)>,
     cx: &mut TestAppContext,
@@ -1012,3 +1012,124 @@ mod git_traversal {
         pretty_assertions::assert_eq!(found_statuses, expected_statuses);
     }
 }
+
+mod rebase_tests {
+    use crate::Project;
+
+    use fs::FakeFs;
+    use git::rebase::{RebaseAction, RebaseStatus, RebaseTodo, RebaseTodoEntry, RebaseTodoLine};
+    use gpui::{BackgroundExecutor, TestAppContext};
+    use serde_json::json;
+    use util::path;
+
+    fn entry(action: RebaseAction, sha: &str, subject: &str) -> RebaseTodoLine {
+        RebaseTodoLine::Commit(RebaseTodoEntry {
+            action,
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+            message: Some(subject.to_string()),
+        })
+    }
+
+    #[gpui::test]
+    async fn test_interactive_rebase(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "one\n",
+            }),
+        )
+        .await;
+        let todo = RebaseTodo {
+            lines: vec![
+                entry(RebaseAction::Pick, "1111111", "First"),
+                entry(RebaseAction::Pick, "2222222", "Second"),
+                entry(RebaseAction::Pick, "3333333", "Third"),
+            ],
+        };
+        fs.with_git_state(path!("/project/.git").as_ref(), true, {
+            let todo = todo.clone();
+            move |state| {
+                state.current_branch_name = Some("main".into());
+                state.rebase_todo = todo;
+            }
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repo = project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+
+        let fetched_todo = repo
+            .update(cx, |repo, _| repo.rebase_todo(None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched_todo, todo);
+
+        // Stop at the edited commit, then finish the rebase.
+        let mut edited_todo = fetched_todo.clone();
+        edited_todo.lines[1] = entry(RebaseAction::Edit, "2222222", "Second");
+        repo.update(cx, |repo, cx| {
+            repo.rebase_interactive(None, edited_todo.clone(), cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        cx.run_until_parked();
+        repo.read_with(cx, |repo, _| {
+            assert_eq!(
+                repo.snapshot().rebase,
+                Some(RebaseStatus {
+                    head_name: Some("main".into()),
+                    step: 2,
+                    total: 3,
+                    stopped_sha: Some("2222222".into()),
+                    interactive: true,
+                })
+            );
+        });
+
+        repo.update(cx, |repo, cx| repo.rebase_continue(cx))
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repo.read_with(cx, |repo, _| assert_eq!(repo.snapshot().rebase, None));
+
+        // Aborting a rebase that stopped discards it.
+        repo.update(cx, |repo, cx| {
+            repo.rebase_interactive(None, edited_todo.clone(), cx)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        cx.run_until_parked();
+        repo.read_with(cx, |repo, _| assert!(repo.snapshot().rebase.is_some()));
+        repo.update(cx, |repo, cx| repo.rebase_abort(cx))
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repo.read_with(cx, |repo, _| assert_eq!(repo.snapshot().rebase, None));
+
+        // There's nothing left to continue.
+        assert!(
+            repo.update(cx, |repo, cx| repo.rebase_continue(cx))
+                .await
+                .unwrap()
+                .is_err()
+        );
+
+        fs.with_git_state(path!("/project/.git").as_ref(), false, |state| {
+            assert_eq!(state.rebased_todos, [edited_todo.clone(), edited_todo]);
+        })
+        .unwrap();
+    }
+}
@@ -81,7 +81,7 @@ use std::{
     time::Duration,
 };
 use sum_tree::SumTree;
-use task::{ResolvedTask, ShellKind, TaskContext};
+use task::{Problem, ProblemSeverity, ResolvedTask, ShellKind, TaskContext};
 use text::{Anchor, PointUtf16, ReplicaId, ToOffset, Unclipped};
 use unindent::Unindent as _;
 use util::{
@@ -3610,6 +3610,117 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "src": { "a.rs": "one two three", "b.rs": "four five" } }),
+    )
+    .await;
+
+    let project = Project::test(fs, [Path::new(path!("/dir"))], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let problem = |path: &str, line, column, severity| Problem {
+        path: PathBuf::from(path),
+        line,
+        column: Some(column),
+        end_line: None,
+        end_column: None,
+        severity,
+        code: None,
+        message: format!("problem in {path}"),
+    };
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_task_diagnostics(
+                "check",
+                Some(PathBuf::from(path!("/dir"))),
+                vec![
+                    problem("src/a.rs", 1, 5, ProblemSeverity::Error),
+                    problem("src/b.rs", 1, 1, ProblemSeverity::Warning),
+                    problem("src/missing.rs", 1, 1, ProblemSeverity::Error),
+                ],
+                cx,
+            )
+            .unwrap();
+        lsp_store
+            .update_task_diagnostics(
+                "lint",
+                None,
+                vec![problem("src/a.rs", 1, 9, ProblemSeverity::Warning)],
+                cx,
+            )
+            .unwrap();
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 2,
+            }
+        );
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/src/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        assert_eq!(
+            snapshot
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .map(|entry| (
+                    entry.range,
+                    entry.diagnostic.source,
+                    entry.diagnostic.severity
+                ))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    Point::new(0, 4)..Point::new(0, 5),
+                    Some("check".to_string()),
+                    DiagnosticSeverity::ERROR
+                ),
+                (
+                    Point::new(0, 8)..Point::new(0, 9),
+                    Some("lint".to_string()),
+                    DiagnosticSeverity::WARNING
+                ),
+            ]
+        );
+    });
+
+    // Running a task again only replaces the diagnostics it reported itself.
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_task_diagnostics("check", Some(PathBuf::from(path!("/dir"))), Vec::new(), cx)
+            .unwrap();
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        );
+    });
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .map(|entry| entry.diagnostic.source)
+                .collect::<Vec<_>>(),
+            [Some("lint".to_string())]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4158,6 +4269,279 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b(); }",
+            "b.rs": "fn b() { c(); }\nfn c() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let lsp_item = |name: &str, path: &str, line: u32| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line, 10)),
+        selection_range: lsp::Range::new(lsp::Position::new(line, 3), lsp::Position::new(line, 4)),
+        data: Some(json!({ "name": name })),
+    };
+    let b_item = lsp_item("b", path!("/dir/b.rs"), 0);
+    let a_item = lsp_item("a", path!("/dir/a.rs"), 0);
+    let c_item = lsp_item("c", path!("/dir/b.rs"), 1);
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 3)
+                );
+                Ok(Some(vec![b_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+        let b_item = b_item.clone();
+        move |params, _| {
+            let b_item = b_item.clone();
+            let a_item = a_item.clone();
+            async move {
+                assert_eq!(params.item, b_item);
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: a_item,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(0, 9),
+                        lsp::Position::new(0, 10),
+                    )],
+                }]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+        move |params, _| {
+            let b_item = b_item.clone();
+            let c_item = c_item.clone();
+            async move {
+                assert_eq!(params.item, b_item);
+                Ok(Some(vec![lsp::CallHierarchyOutgoingCall {
+                    to: c_item,
+                    from_ranges: vec![lsp::Range::new(
+                        lsp::Position::new(0, 9),
+                        lsp::Position::new(0, 10),
+                    )],
+                }]))
+            }
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 3, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let item = items.into_iter().next().unwrap();
+    assert_eq!(item.name, "b");
+    assert_eq!(item.buffer, buffer);
+    cx.update(|cx| {
+        assert_eq!(item.selection_range.to_offset(buffer.read(cx)), 3..4);
+    });
+
+    let incoming_calls = project
+        .update(cx, |project, cx| project.incoming_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(incoming_calls.len(), 1);
+    cx.update(|cx| {
+        let call = &incoming_calls[0];
+        assert_eq!(call.item.name, "a");
+        let caller_buffer = call.item.buffer.read(cx);
+        assert_eq!(
+            caller_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/a.rs")),
+        );
+        assert_eq!(call.call_sites.len(), 1);
+        assert_eq!(call.call_sites[0].buffer, call.item.buffer);
+        assert_eq!(call.call_sites[0].range.to_offset(caller_buffer), 9..10);
+    });
+
+    let outgoing_calls = project
+        .update(cx, |project, cx| project.outgoing_calls(&item, cx))
+        .await
+        .unwrap();
+    assert_eq!(outgoing_calls.len(), 1);
+    cx.update(|cx| {
+        let call = &outgoing_calls[0];
+        assert_eq!(call.item.name, "c");
+        assert_eq!(call.item.buffer, buffer);
+        assert_eq!(call.call_sites.len(), 1);
+        assert_eq!(call.call_sites[0].buffer, buffer);
+        assert_eq!(call.call_sites[0].range.to_offset(buffer.read(cx)), 9..10);
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "shape.rs": "trait Shape {}",
+            "circle.rs": "struct Circle;\nimpl Shape for Circle {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp("Rust", FakeLspAdapter::default());
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/shape.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let lsp_item = |name: &str, kind, path: &str| lsp::TypeHierarchyItem {
+        name: name.to_string(),
+        kind,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 14)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 11)),
+        data: None,
+    };
+    let shape_item = lsp_item("Shape", lsp::SymbolKind::INTERFACE, path!("/dir/shape.rs"));
+    let circle_item = lsp_item("Circle", lsp::SymbolKind::STRUCT, path!("/dir/circle.rs"));
+
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let shape_item = shape_item.clone();
+        move |params, _| {
+            let shape_item = shape_item.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 7)
+                );
+                Ok(Some(vec![shape_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>({
+        let shape_item = shape_item.clone();
+        let circle_item = circle_item.clone();
+        move |params, _| {
+            let shape_item = shape_item.clone();
+            let circle_item = circle_item.clone();
+            async move {
+                assert_eq!(params.item, shape_item);
+                Ok(Some(vec![circle_item]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        move |params, _| {
+            let circle_item = circle_item.clone();
+            async move {
+                assert_eq!(params.item, circle_item);
+                Ok(None)
+            }
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 7, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let shape = items.into_iter().next().unwrap();
+    assert_eq!(shape.name, "Shape");
+    assert_eq!(shape.buffer, buffer);
+    cx.update(|cx| {
+        assert_eq!(shape.selection_range.to_offset(buffer.read(cx)), 6..11);
+    });
+
+    let subtypes = project
+        .update(cx, |project, cx| project.subtypes(&shape, cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    let circle = subtypes.into_iter().next().unwrap();
+    assert_eq!(circle.name, "Circle");
+    assert_eq!(circle.kind, lsp::SymbolKind::STRUCT);
+    cx.update(|cx| {
+        assert_eq!(
+            circle
+                .buffer
+                .read(cx)
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/circle.rs")),
+        );
+    });
+
+    let supertypes = project
+        .update(cx, |project, cx| project.supertypes(&circle, cx))
+        .await
+        .unwrap();
+    assert!(supertypes.is_empty());
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -7198,6 +7582,74 @@ async fn test_search_with_unicode(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_structural_search(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "fn main() {\n    foo(1, 2);\n    bar(foo(3, 4));\n}\n",
+            "two.rs": "fn other() {\n    foo(5);\n}\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+
+    let query = SearchQuery::structural(
+        "foo($A, $B)",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        search(&project, query.clone(), cx).await.unwrap(),
+        HashMap::from_iter([(path!("dir/one.rs").to_string(), vec![16..25, 35..44])])
+    );
+
+    assert_eq!(
+        search(
+            &project,
+            SearchQuery::structural(
+                r#"(call_expression function: (identifier) @name (#eq? @name "bar")) @match"#,
+                false,
+                Default::default(),
+                Default::default(),
+                false,
+                None,
+            )
+            .unwrap(),
+            cx
+        )
+        .await
+        .unwrap(),
+        HashMap::from_iter([(path!("dir/one.rs").to_string(), vec![31..45])])
+    );
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/one.rs"), cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    let query = query.with_replacement("baz($B, $A)".to_string());
+    buffer.read_with(cx, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        assert_eq!(
+            query.structural_replacement_for(&snapshot, 16..25),
+            Some("baz(2, 1)".to_string())
+        );
+        assert_eq!(query.structural_replacement_for(&snapshot, 12..25), None);
+    });
+}
+
 #[gpui::test]
 async fn test_create_entry(cx: &mut gpui::TestAppContext) {
     init_test(cx);
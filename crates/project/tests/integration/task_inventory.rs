@@ -560,6 +560,100 @@ async fn test_inventory_static_task_filters(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_task_dependencies(cx: &mut TestAppContext) {
+    init_test(cx);
+    let inventory = cx.update(|cx| Inventory::new(cx));
+    inventory.update(cx, |inventory, _| {
+        inventory
+            .update_file_based_tasks(
+                TaskSettingsLocation::Global(tasks_file()),
+                Some(
+                    &json!([
+                        { "label": "build", "command": "make" },
+                        { "label": "migrate", "command": "make migrate", "depends_on": ["build"] },
+                        { "label": "serve", "command": "make serve" },
+                        {
+                            "label": "dev",
+                            "depends_on": ["migrate", "serve"],
+                            "depends_order": "sequence"
+                        },
+                        { "label": "test", "command": "make test", "depends_on": ["build"] },
+                        { "label": "check", "depends_on": ["migrate", "test"] },
+                        { "label": "missing", "command": "true", "depends_on": ["nope"] },
+                        { "label": "ping", "command": "true", "depends_on": ["pong"] },
+                        { "label": "pong", "command": "true", "depends_on": ["ping"] },
+                    ])
+                    .to_string(),
+                ),
+            )
+            .unwrap();
+    });
+
+    fn labels(dependencies: &[TaskDependency]) -> Vec<String> {
+        dependencies
+            .iter()
+            .flat_map(|dependency| {
+                labels(&dependency.dependencies)
+                    .into_iter()
+                    .chain([dependency.template.label.clone()])
+            })
+            .collect()
+    }
+
+    let templates = inventory
+        .update(cx, |inventory, cx| {
+            inventory.list_tasks(None, None, None, cx)
+        })
+        .await;
+    let template = |label: &str| {
+        templates
+            .iter()
+            .find(|(_, template)| template.label == label)
+            .unwrap()
+            .1
+            .clone()
+    };
+
+    let dev = template("dev");
+    let dependencies = inventory
+        .read_with(cx, |inventory, _| inventory.task_dependencies(&dev, None))
+        .unwrap();
+    assert_eq!(labels(&dependencies), vec!["build", "migrate", "serve"]);
+    assert_eq!(dependencies[0].dependencies.len(), 1);
+
+    // A task that several dependencies share is not a cycle. It's listed under each of them, and
+    // only run once.
+    let check = template("check");
+    let dependencies = inventory
+        .read_with(cx, |inventory, _| inventory.task_dependencies(&check, None))
+        .unwrap();
+    assert_eq!(
+        labels(&dependencies),
+        vec!["build", "migrate", "build", "test"]
+    );
+
+    let missing = template("missing");
+    let error = inventory
+        .read_with(cx, |inventory, _| {
+            inventory.task_dependencies(&missing, None)
+        })
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("unknown task \"nope\""),
+        "{error}"
+    );
+
+    let ping = template("ping");
+    let error = inventory
+        .read_with(cx, |inventory, _| inventory.task_dependencies(&ping, None))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "task dependency cycle: ping -> pong -> ping"
+    );
+}
+
 fn init_test(_cx: &mut TestAppContext) {
     zlog::init_test();
     TaskStore::init(None);
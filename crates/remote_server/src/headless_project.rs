@@ -15,6 +15,7 @@ use project::{
     AgentRegistryStore, LspStore, LspStoreEvent, ManifestTree, PrettierStore, ProjectEnvironment,
     ProjectPath, ToolchainStore, WorktreeId,
     agent_server_store::AgentServerStore,
+    bookmark_store::BookmarkStore,
     buffer_store::{BufferStore, BufferStoreEvent},
     context_server_store::ContextServerStore,
     debugger::{breakpoint_store::BreakpointStore, dap_store::DapStore},
@@ -54,6 +55,7 @@ pub struct HeadlessProject {
     pub task_store: Entity<TaskStore>,
     pub dap_store: Entity<DapStore>,
     pub breakpoint_store: Entity<BreakpointStore>,
+    pub bookmark_store: Entity<BookmarkStore>,
     pub agent_server_store: Entity<AgentServerStore>,
     pub context_server_store: Entity<ContextServerStore>,
     pub settings_observer: Entity<SettingsObserver>,
@@ -141,6 +143,13 @@ impl HeadlessProject {
             breakpoint_store
         });
 
+        let bookmark_store = cx.new(|_| {
+            let mut bookmark_store =
+                BookmarkStore::local(worktree_store.clone(), buffer_store.clone());
+            bookmark_store.shared(REMOTE_SERVER_PROJECT_ID, session.clone());
+            bookmark_store
+        });
+
         let dap_store = cx.new(|cx| {
             let mut dap_store = DapStore::new_local(
                 http_client.clone(),
@@ -276,6 +285,7 @@ impl HeadlessProject {
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &toolchain_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &dap_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &breakpoint_store);
+        session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &bookmark_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &settings_observer);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &git_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &agent_server_store);
@@ -323,6 +333,7 @@ impl HeadlessProject {
         DapStore::init(&session, cx);
         // todo(debugger): Re init breakpoint store when we set it up for collab
         BreakpointStore::init(&session);
+        BookmarkStore::init(&session);
         GitStore::init(&session);
         AgentServerStore::init_headless(&session);
         ContextServerStore::init_headless(&session);
@@ -338,6 +349,7 @@ impl HeadlessProject {
             task_store,
             dap_store,
             breakpoint_store,
+            bookmark_store,
             agent_server_store,
             context_server_store,
             languages,
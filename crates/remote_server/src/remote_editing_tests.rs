@@ -5,7 +5,7 @@ use crate::headless_project::HeadlessProject;
 use agent::{AgentTool, ReadFileTool, ReadFileToolInput, Templates, Thread, ToolCallEventStream};
 use client::{Client, UserStore};
 use clock::FakeSystemClock;
-use collections::{HashMap, HashSet};
+use collections::{BTreeMap, HashMap, HashSet};
 use language_model::{LanguageModelToolResultContent, fake_provider::FakeLanguageModel};
 use prompt_store::ProjectContext;
 
@@ -25,6 +25,7 @@ use node_runtime::NodeRuntime;
 use project::{
     ProgressToken, Project,
     agent_server_store::AgentServerCommand,
+    bookmark_store::SerializedBookmark,
     search::{SearchQuery, SearchResult},
 };
 use remote::RemoteClient;
@@ -2062,6 +2063,69 @@ async fn test_remote_external_agent_server(
     assert!(login.is_none());
 }
 
+#[gpui::test]
+async fn test_remote_restore_bookmarks(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                "src": {
+                    "lib.rs": "fn one() -> usize {\n    1\n}\n",
+                },
+            },
+        }),
+    )
+    .await;
+
+    let (project, headless) = init_test(&fs, cx, server_cx).await;
+    project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let path: Arc<Path> = Path::new(path!("/code/project1/src/lib.rs")).into();
+    let bookmarks = BTreeMap::from_iter([(
+        path.clone(),
+        vec![SerializedBookmark {
+            row: 1,
+            label: Some("one".into()),
+        }],
+    )]);
+    project
+        .update(cx, |project, cx| {
+            project.bookmark_store().update(cx, |store, cx| {
+                store.with_serialized_bookmarks(bookmarks.clone(), cx)
+            })
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    server_cx.run_until_parked();
+
+    project.read_with(cx, |project, cx| {
+        assert_eq!(
+            project
+                .bookmark_store()
+                .read(cx)
+                .all_serialized_bookmarks(cx),
+            bookmarks
+        );
+    });
+    headless.read_with(server_cx, |headless, cx| {
+        assert_eq!(
+            headless
+                .bookmark_store
+                .read(cx)
+                .all_serialized_bookmarks(cx),
+            bookmarks
+        );
+    });
+}
+
 pub async fn init_test(
     server_fs: &Arc<FakeFs>,
     cx: &mut TestAppContext,
@@ -126,6 +126,43 @@ fn convert_outputs(
         .collect()
 }
 
+/// Converts an output message from the kernel into the form it takes in the ipynb `outputs` array.
+fn saved_output(content: &JupyterMessageContent) -> Option<nbformat::v4::Output> {
+    let output = match content {
+        JupyterMessageContent::StreamContent(stream) => serde_json::json!({
+            "output_type": "stream",
+            "name": stream.name,
+            "text": stream.text,
+        }),
+        JupyterMessageContent::DisplayData(display_data) => serde_json::json!({
+            "output_type": "display_data",
+            "data": display_data.data,
+            "metadata": display_data.metadata,
+        }),
+        JupyterMessageContent::ExecuteResult(execute_result) => serde_json::json!({
+            "output_type": "execute_result",
+            "execution_count": execute_result.execution_count,
+            "data": execute_result.data,
+            "metadata": execute_result.metadata,
+        }),
+        JupyterMessageContent::ErrorOutput(error) => serde_json::json!({
+            "output_type": "error",
+            "ename": error.ename,
+            "evalue": error.evalue,
+            "traceback": error.traceback,
+        }),
+        _ => return None,
+    };
+    serde_json::from_value(output).log_err()
+}
+
+fn execution_count(count: &impl serde::Serialize) -> Option<i32> {
+    serde_json::to_value(count)
+        .ok()
+        .and_then(|v| v.as_i64())
+        .map(|v| v as i32)
+}
+
 impl Cell {
     pub fn id(&self, cx: &App) -> CellId {
         match self {
@@ -135,6 +172,14 @@ impl Cell {
         }
     }
 
+    pub fn metadata(&self, cx: &App) -> CellMetadata {
+        match self {
+            Cell::Code(code_cell) => code_cell.read(cx).metadata().clone(),
+            Cell::Markdown(markdown_cell) => markdown_cell.read(cx).metadata().clone(),
+            Cell::Raw(raw_cell) => raw_cell.read(cx).metadata().clone(),
+        }
+    }
+
     pub fn current_source(&self, cx: &App) -> String {
         match self {
             Cell::Code(code_cell) => code_cell.read(cx).current_source(cx),
@@ -196,7 +241,7 @@ impl Cell {
                 outputs,
             } => {
                 let text = source.join("");
-                let outputs = convert_outputs(outputs, window, cx);
+                let rendered_outputs = convert_outputs(outputs, window, cx);
 
                 Cell::Code(cx.new(|cx| {
                     CodeCell::load(
@@ -204,7 +249,8 @@ impl Cell {
                         metadata.clone(),
                         *execution_count,
                         text,
-                        outputs,
+                        rendered_outputs,
+                        outputs.clone(),
                         notebook_language,
                         window,
                         cx,
@@ -215,13 +261,7 @@ impl Cell {
                 id,
                 metadata,
                 source,
-            } => Cell::Raw(cx.new(|_| RawCell {
-                id: id.clone(),
-                metadata: metadata.clone(),
-                source: source.join(""),
-                selected: false,
-                cell_position: None,
-            })),
+            } => Cell::Raw(cx.new(|_| RawCell::new(id.clone(), metadata.clone(), source.join("")))),
         }
     }
 }
@@ -601,6 +641,11 @@ pub struct CodeCell {
     source: String,
     editor: Entity<editor::Editor>,
     outputs: Vec<Output>,
+    /// The outputs as they are written to the notebook file, kept alongside the rendered ones
+    /// so that images, tables and other rich outputs survive a save.
+    saved_outputs: Vec<nbformat::v4::Output>,
+    outputs_changed: bool,
+    clear_outputs_on_next_output: bool,
     selected: bool,
     cell_position: Option<CellPosition>,
     language_task: Task<()>,
@@ -663,6 +708,9 @@ impl CodeCell {
             source,
             editor: editor_view,
             outputs: Vec::new(),
+            saved_outputs: Vec::new(),
+            outputs_changed: false,
+            clear_outputs_on_next_output: false,
             selected: false,
             cell_position: None,
             language_task,
@@ -691,6 +739,7 @@ impl CodeCell {
         execution_count: Option<i32>,
         source: String,
         outputs: Vec<Output>,
+        saved_outputs: Vec<nbformat::v4::Output>,
         notebook_language: Shared<Task<Option<Arc<Language>>>>,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -739,6 +788,9 @@ impl CodeCell {
             source,
             editor: editor_view,
             outputs,
+            saved_outputs,
+            outputs_changed: false,
+            clear_outputs_on_next_output: false,
             selected: false,
             cell_position: None,
             language_task,
@@ -762,40 +814,91 @@ impl CodeCell {
     }
 
     pub fn is_dirty(&self, cx: &App) -> bool {
-        self.editor.read(cx).buffer().read(cx).is_dirty(cx)
+        self.outputs_changed || self.editor.read(cx).buffer().read(cx).is_dirty(cx)
+    }
+
+    pub fn did_save(&mut self) {
+        self.outputs_changed = false;
     }
 
     pub fn to_nbformat_cell(&self, cx: &App) -> nbformat::v4::Cell {
         let source = self.current_source(cx);
         let source_lines: Vec<String> = source.lines().map(|l| format!("{}\n", l)).collect();
 
-        let outputs = self.outputs_to_nbformat(cx);
-
         nbformat::v4::Cell::Code {
             id: self.id.clone(),
             metadata: self.metadata.clone(),
             execution_count: self.execution_count,
             source: source_lines,
-            outputs,
+            outputs: self.saved_outputs.clone(),
         }
     }
 
-    fn outputs_to_nbformat(&self, cx: &App) -> Vec<nbformat::v4::Output> {
-        self.outputs
-            .iter()
-            .filter_map(|output| output.to_nbformat(cx))
-            .collect()
-    }
-
     pub fn has_outputs(&self) -> bool {
         !self.outputs.is_empty()
     }
 
     pub fn clear_outputs(&mut self) {
+        if self.has_outputs() || !self.saved_outputs.is_empty() {
+            self.outputs_changed = true;
+        }
         self.outputs.clear();
+        self.saved_outputs.clear();
         self.execution_duration = None;
     }
 
+    /// Appends a stream chunk to the last output when it continues the same stream, the way
+    /// notebook files keep a single output for each run of stdout or stderr.
+    fn append_to_stream(&mut self, content: &JupyterMessageContent, cx: &mut App) -> bool {
+        if self.clear_outputs_on_next_output {
+            return false;
+        }
+        let JupyterMessageContent::StreamContent(stream) = content else {
+            return false;
+        };
+        let Some(nbformat::v4::Output::Stream { name, text }) = saved_output(content) else {
+            return false;
+        };
+        let (
+            Some(nbformat::v4::Output::Stream {
+                name: last_name,
+                text: last_text,
+            }),
+            Some(Output::Stream {
+                content: last_stream,
+            }),
+        ) = (self.saved_outputs.last_mut(), self.outputs.last())
+        else {
+            return false;
+        };
+        if *last_name != name {
+            return false;
+        }
+        last_text.0.push_str(&text.0);
+        last_stream.update(cx, |last_stream, cx| {
+            last_stream.append_text(&stream.text, cx);
+            cx.notify();
+        });
+        self.outputs_changed = true;
+        true
+    }
+
+    fn set_execution_count(&mut self, execution_count: Option<i32>) {
+        if self.execution_count != execution_count {
+            self.execution_count = execution_count;
+            self.outputs_changed = true;
+        }
+    }
+
+    fn push_output(&mut self, output: Output, content: &JupyterMessageContent) {
+        if std::mem::take(&mut self.clear_outputs_on_next_output) {
+            self.clear_outputs();
+        }
+        self.outputs.push(output);
+        self.saved_outputs.extend(saved_output(content));
+        self.outputs_changed = true;
+    }
+
     pub fn start_execution(&mut self) {
         self.execution_start_time = Some(Instant::now());
         self.execution_duration = None;
@@ -836,36 +939,48 @@ impl CodeCell {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        match &message.content {
+        let content = &message.content;
+        match content {
             JupyterMessageContent::StreamContent(stream) => {
-                self.outputs.push(Output::Stream {
-                    content: cx.new(|cx| TerminalOutput::from(&stream.text, window, cx)),
-                });
+                if !self.append_to_stream(content, cx) {
+                    let output = Output::Stream {
+                        content: cx.new(|cx| TerminalOutput::from(&stream.text, window, cx)),
+                    };
+                    self.push_output(output, content);
+                }
             }
             JupyterMessageContent::DisplayData(display_data) => {
-                self.outputs
-                    .push(Output::new(&display_data.data, None, window, cx));
+                let output = Output::new(&display_data.data, None, window, cx);
+                self.push_output(output, content);
             }
             JupyterMessageContent::ExecuteResult(execute_result) => {
-                self.outputs
-                    .push(Output::new(&execute_result.data, None, window, cx));
+                let output = Output::new(&execute_result.data, None, window, cx);
+                self.push_output(output, content);
             }
             JupyterMessageContent::ExecuteInput(input) => {
-                self.execution_count = serde_json::to_value(&input.execution_count)
-                    .ok()
-                    .and_then(|v| v.as_i64())
-                    .map(|v| v as i32);
+                self.set_execution_count(execution_count(&input.execution_count));
             }
-            JupyterMessageContent::ExecuteReply(_) => {
+            JupyterMessageContent::ExecuteReply(reply) => {
+                if let Some(execution_count) = execution_count(&reply.execution_count) {
+                    self.set_execution_count(Some(execution_count));
+                }
                 self.finish_execution();
             }
             JupyterMessageContent::ErrorOutput(error) => {
-                self.outputs.push(Output::ErrorOutput(ErrorView {
+                let output = Output::ErrorOutput(ErrorView {
                     ename: error.ename.clone(),
                     evalue: error.evalue.clone(),
                     traceback: cx
                         .new(|cx| TerminalOutput::from(&error.traceback.join("\n"), window, cx)),
-                }));
+                });
+                self.push_output(output, content);
+            }
+            JupyterMessageContent::ClearOutput(clear) => {
+                if clear.wait {
+                    self.clear_outputs_on_next_output = true;
+                } else {
+                    self.clear_outputs();
+                }
             }
             _ => {}
         }
@@ -1310,6 +1425,16 @@ pub struct RawCell {
 }
 
 impl RawCell {
+    pub fn new(id: CellId, metadata: CellMetadata, source: String) -> Self {
+        Self {
+            id,
+            metadata,
+            source,
+            selected: false,
+            cell_position: None,
+        }
+    }
+
     pub fn to_nbformat_cell(&self) -> nbformat::v4::Cell {
         let source_lines: Vec<String> = self.source.lines().map(|l| format!("{}\n", l)).collect();
 
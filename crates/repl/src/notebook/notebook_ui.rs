@@ -23,8 +23,8 @@ use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation};
 
 use super::{Cell, CellEvent, CellPosition, MarkdownCellEvent, RenderableCell};
 
-use nbformat::v4::CellId;
 use nbformat::v4::Metadata as NotebookMetadata;
+use nbformat::v4::{CellId, CellType};
 use serde_json;
 use uuid::Uuid;
 
@@ -59,6 +59,16 @@ actions!(
         AddMarkdownBlock,
         /// Adds a new code cell.
         AddCodeBlock,
+        /// Deletes the current cell.
+        DeleteCell,
+        /// Changes the current cell into a code cell.
+        ConvertToCodeCell,
+        /// Changes the current cell into a markdown cell.
+        ConvertToMarkdownCell,
+        /// Changes the current cell into a raw cell.
+        ConvertToRawCell,
+        /// Restores the most recently deleted cell.
+        UndoDeleteCell,
         /// Restarts the kernel.
         RestartKernel,
         /// Interrupts the current execution.
@@ -107,10 +117,16 @@ pub struct NotebookEditor {
     selected_cell_index: usize,
     cell_order: Vec<CellId>,
     original_cell_order: Vec<CellId>,
+    /// Whether a cell changed type since the notebook was last saved.
+    cell_types_changed: bool,
     cell_map: HashMap<CellId, Cell>,
+    /// Deleted cells along with the index they were at, most recently deleted last.
+    deleted_cells: Vec<(usize, CellId, Cell)>,
     kernel: Kernel,
     kernel_specification: Option<KernelSpecification>,
     execution_requests: HashMap<String, CellId>,
+    /// Execute requests made while the kernel was starting, sent once it is running.
+    pending_execution_requests: Vec<JupyterMessage>,
     kernel_picker_handle: PopoverMenuHandle<Picker<KernelPickerDelegate>>,
 }
 
@@ -141,83 +157,7 @@ impl NotebookEditor {
             let cell_id = cell.id();
             cell_order.push(cell_id.clone());
             let cell_entity = Cell::load(&cell, &languages, notebook_language.clone(), window, cx);
-
-            match &cell_entity {
-                Cell::Code(code_cell) => {
-                    let cell_id_for_focus = cell_id.clone();
-                    cx.subscribe(code_cell, move |this, cell, event, cx| match event {
-                        CellEvent::Run(cell_id) => this.execute_cell(cell_id.clone(), cx),
-                        CellEvent::FocusedIn(_) => {
-                            if let Some(index) = this
-                                .cell_order
-                                .iter()
-                                .position(|id| id == &cell_id_for_focus)
-                            {
-                                this.selected_cell_index = index;
-                                cx.notify();
-                            }
-                        }
-                    })
-                    .detach();
-
-                    let cell_id_for_editor = cell_id.clone();
-                    let editor = code_cell.read(cx).editor().clone();
-                    cx.subscribe(&editor, move |this, _editor, event, cx| {
-                        if let editor::EditorEvent::Focused = event {
-                            if let Some(index) = this
-                                .cell_order
-                                .iter()
-                                .position(|id| id == &cell_id_for_editor)
-                            {
-                                this.selected_cell_index = index;
-                                cx.notify();
-                            }
-                        }
-                    })
-                    .detach();
-                }
-                Cell::Markdown(markdown_cell) => {
-                    let cell_id_for_focus = cell_id.clone();
-                    cx.subscribe(
-                        markdown_cell,
-                        move |_this, cell, event: &MarkdownCellEvent, cx| {
-                            match event {
-                                MarkdownCellEvent::FinishedEditing => {
-                                    cell.update(cx, |cell, cx| {
-                                        cell.reparse_markdown(cx);
-                                    });
-                                }
-                                MarkdownCellEvent::Run(_cell_id) => {
-                                    // run is handled separately by move_to_next_cell
-                                    // Just reparse here
-                                    cell.update(cx, |cell, cx| {
-                                        cell.reparse_markdown(cx);
-                                    });
-                                }
-                            }
-                        },
-                    )
-                    .detach();
-
-                    let cell_id_for_editor = cell_id.clone();
-                    let editor = markdown_cell.read(cx).editor().clone();
-                    cx.subscribe(&editor, move |this, _editor, event, cx| {
-                        if let editor::EditorEvent::Focused = event {
-                            if let Some(index) = this
-                                .cell_order
-                                .iter()
-                                .position(|id| id == &cell_id_for_editor)
-                            {
-                                this.selected_cell_index = index;
-                                cx.notify();
-                            }
-                        }
-                    })
-                    .detach();
-                }
-                Cell::Raw(_) => {}
-            }
-
+            Self::subscribe_to_cell(cell_id.clone(), &cell_entity, cx);
             cell_map.insert(cell_id.clone(), cell_entity);
         }
 
@@ -239,10 +179,13 @@ impl NotebookEditor {
             selected_cell_index: 0,
             cell_order: cell_order.clone(),
             original_cell_order: cell_order.clone(),
+            cell_types_changed: false,
             cell_map: cell_map.clone(),
+            deleted_cells: Vec::new(),
             kernel: Kernel::Shutdown, // TODO: use recommended kernel after the implementation is done in repl
             kernel_specification: None,
             execution_requests: HashMap::default(),
+            pending_execution_requests: Vec::new(),
             kernel_picker_handle: PopoverMenuHandle::default(),
         };
         editor.launch_kernel(window, cx);
@@ -256,6 +199,49 @@ impl NotebookEditor {
         editor
     }
 
+    fn subscribe_to_cell(cell_id: CellId, cell: &Cell, cx: &mut Context<Self>) {
+        let editor = match cell {
+            Cell::Code(code_cell) => {
+                cx.subscribe(code_cell, |this, _cell, event, cx| match event {
+                    CellEvent::Run(cell_id) => this.execute_cell(cell_id.clone(), cx),
+                    CellEvent::FocusedIn(cell_id) => this.select_cell_with_id(cell_id, cx),
+                })
+                .detach();
+                code_cell.read(cx).editor().clone()
+            }
+            Cell::Markdown(markdown_cell) => {
+                cx.subscribe(
+                    markdown_cell,
+                    |_this, cell, event: &MarkdownCellEvent, cx| match event {
+                        // Moving on to the next cell after a run is handled by `run_current_cell`.
+                        MarkdownCellEvent::FinishedEditing | MarkdownCellEvent::Run(_) => {
+                            cell.update(cx, |cell, cx| {
+                                cell.reparse_markdown(cx);
+                            });
+                        }
+                    },
+                )
+                .detach();
+                markdown_cell.read(cx).editor().clone()
+            }
+            Cell::Raw(_) => return,
+        };
+
+        cx.subscribe(&editor, move |this, _editor, event, cx| {
+            if let editor::EditorEvent::Focused = event {
+                this.select_cell_with_id(&cell_id, cx);
+            }
+        })
+        .detach();
+    }
+
+    fn select_cell_with_id(&mut self, cell_id: &CellId, cx: &mut Context<Self>) {
+        if let Some(index) = self.cell_order.iter().position(|id| id == cell_id) {
+            self.selected_cell_index = index;
+            cx.notify();
+        }
+    }
+
     fn refresh_language(&mut self, cx: &mut Context<Self>) {
         let notebook_language = self.notebook_item.read(cx).notebook_language();
         let task = cx.spawn(async move |this, cx| {
@@ -277,7 +263,7 @@ impl NotebookEditor {
     }
 
     fn has_structural_changes(&self) -> bool {
-        self.cell_order != self.original_cell_order
+        self.cell_types_changed || self.cell_order != self.original_cell_order
     }
 
     fn has_content_changes(&self, cx: &App) -> bool {
@@ -307,11 +293,13 @@ impl NotebookEditor {
 
     pub fn mark_as_saved(&mut self, cx: &mut Context<Self>) {
         self.original_cell_order = self.cell_order.clone();
+        self.cell_types_changed = false;
 
         for cell in self.cell_map.values() {
             match cell {
                 Cell::Code(code_cell) => {
                     code_cell.update(cx, |code_cell, cx| {
+                        code_cell.did_save();
                         let editor = code_cell.editor();
                         editor.update(cx, |editor, cx| {
                             editor.buffer().update(cx, |buffer, cx| {
@@ -444,6 +432,9 @@ impl NotebookEditor {
                 match kernel {
                     Ok(kernel) => {
                         this.update(cx, |editor, cx| {
+                            for message in editor.pending_execution_requests.drain(..) {
+                                kernel.request_tx().try_send(message).ok();
+                            }
                             editor.kernel = Kernel::RunningKernel(kernel);
                             cx.notify();
                         })
@@ -452,6 +443,7 @@ impl NotebookEditor {
                     Err(err) => {
                         this.update(cx, |editor, cx| {
                             editor.kernel = Kernel::ErroredLaunch(err.to_string());
+                            editor.abort_executions(cx);
                             cx.notify();
                         })
                         .ok();
@@ -479,7 +471,7 @@ impl NotebookEditor {
             kernel.force_shutdown(window, cx).detach();
         }
 
-        self.execution_requests.clear();
+        self.abort_executions(cx);
 
         self.launch_kernel_with_spec(spec, window, cx);
     }
@@ -491,6 +483,7 @@ impl NotebookEditor {
             }
 
             self.kernel = Kernel::Restarting;
+            self.abort_executions(cx);
             cx.notify();
 
             self.launch_kernel_with_spec(spec, window, cx);
@@ -512,27 +505,24 @@ impl NotebookEditor {
     }
 
     fn execute_cell(&mut self, cell_id: CellId, cx: &mut Context<Self>) {
-        let code = if let Some(Cell::Code(cell)) = self.cell_map.get(&cell_id) {
-            let editor = cell.read(cx).editor().clone();
-            let buffer = editor.read(cx).buffer().read(cx);
-            buffer
-                .as_singleton()
-                .map(|b| b.read(cx).text())
-                .unwrap_or_default()
-        } else {
+        let Some(Cell::Code(cell)) = self.cell_map.get(&cell_id).cloned() else {
             return;
         };
-
-        if let Some(Cell::Code(cell)) = self.cell_map.get(&cell_id) {
-            cell.update(cx, |cell, cx| {
-                if cell.has_outputs() {
-                    cell.clear_outputs();
-                }
-                cell.start_execution();
-                cx.notify();
-            });
+        // Without a running or starting kernel, the cell would never finish executing.
+        if !matches!(
+            self.kernel,
+            Kernel::RunningKernel(_) | Kernel::StartingKernel(_) | Kernel::Restarting
+        ) {
+            return;
         }
 
+        let code = cell.read(cx).current_source(cx);
+        cell.update(cx, |cell, cx| {
+            cell.clear_outputs();
+            cell.start_execution();
+            cx.notify();
+        });
+
         let request = ExecuteRequest {
             code,
             ..Default::default()
@@ -540,10 +530,25 @@ impl NotebookEditor {
         let message: JupyterMessage = request.into();
         let msg_id = message.header.msg_id.clone();
 
-        self.execution_requests.insert(msg_id, cell_id.clone());
+        self.execution_requests.insert(msg_id, cell_id);
 
         if let Kernel::RunningKernel(kernel) = &mut self.kernel {
             kernel.request_tx().try_send(message).ok();
+        } else {
+            self.pending_execution_requests.push(message);
+        }
+    }
+
+    /// Stops tracking every in-flight execution, e.g. because the kernel that was running them went away.
+    fn abort_executions(&mut self, cx: &mut Context<Self>) {
+        self.pending_execution_requests.clear();
+        for (_, cell_id) in self.execution_requests.drain() {
+            if let Some(Cell::Code(cell)) = self.cell_map.get(&cell_id) {
+                cell.update(cx, |cell, cx| {
+                    cell.finish_execution();
+                    cx.notify();
+                });
+            }
         }
     }
 
@@ -633,25 +638,129 @@ impl NotebookEditor {
     }
 
     fn move_cell_up(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell up triggered");
-        if self.selected_cell_index > 0 {
-            self.cell_order
-                .swap(self.selected_cell_index, self.selected_cell_index - 1);
+        let index = self.selected_cell_index;
+        if index > 0 && index < self.cell_order.len() {
+            self.cell_order.swap(index, index - 1);
+            self.cell_list.splice(index - 1..index + 1, 2);
             self.selected_cell_index -= 1;
             cx.notify();
         }
     }
 
     fn move_cell_down(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell down triggered");
-        if !self.cell_order.is_empty() && self.selected_cell_index < self.cell_order.len() - 1 {
-            self.cell_order
-                .swap(self.selected_cell_index, self.selected_cell_index + 1);
+        let index = self.selected_cell_index;
+        if index + 1 < self.cell_order.len() {
+            self.cell_order.swap(index, index + 1);
+            self.cell_list.splice(index..index + 2, 2);
             self.selected_cell_index += 1;
             cx.notify();
         }
     }
 
+    fn delete_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let index = self.selected_cell_index;
+        if index >= self.cell_order.len() {
+            return;
+        }
+
+        let cell_id = self.cell_order.remove(index);
+        if let Some(cell) = self.cell_map.remove(&cell_id) {
+            self.deleted_cells.push((index, cell_id.clone(), cell));
+        }
+        self.execution_requests.retain(|_, id| *id != cell_id);
+        self.cell_list.splice(index..index + 1, 0);
+        self.selected_cell_index = index.min(self.cell_order.len().saturating_sub(1));
+        cx.notify();
+    }
+
+    fn undo_delete_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((index, cell_id, cell)) = self.deleted_cells.pop() else {
+            return;
+        };
+
+        let index = index.min(self.cell_order.len());
+        self.cell_order.insert(index, cell_id.clone());
+        self.cell_map.insert(cell_id, cell);
+        self.cell_list.splice(index..index, 1);
+        self.selected_cell_index = index;
+        cx.notify();
+    }
+
+    fn convert_cell(&mut self, cell_type: CellType, window: &mut Window, cx: &mut Context<Self>) {
+        let index = self.selected_cell_index;
+        let Some(cell_id) = self.cell_order.get(index).cloned() else {
+            return;
+        };
+        let Some(cell) = self.cell_map.get(&cell_id) else {
+            return;
+        };
+        if matches!(
+            (cell, &cell_type),
+            (Cell::Code(_), CellType::Code)
+                | (Cell::Markdown(_), CellType::Markdown)
+                | (Cell::Raw(_), CellType::Raw)
+        ) {
+            return;
+        }
+
+        let source = cell.current_source(cx);
+        let metadata = cell.metadata(cx);
+        let new_cell = match cell_type {
+            CellType::Code => {
+                let notebook_language = self.notebook_language.clone();
+                Cell::Code(cx.new(|cx| {
+                    super::CodeCell::new(
+                        cell_id.clone(),
+                        metadata,
+                        source,
+                        notebook_language,
+                        window,
+                        cx,
+                    )
+                }))
+            }
+            CellType::Markdown => {
+                let languages = self.languages.clone();
+                Cell::Markdown(cx.new(|cx| {
+                    super::MarkdownCell::new(
+                        cell_id.clone(),
+                        metadata,
+                        source,
+                        languages,
+                        window,
+                        cx,
+                    )
+                }))
+            }
+            CellType::Raw => {
+                Cell::Raw(cx.new(|_| super::RawCell::new(cell_id.clone(), metadata, source)))
+            }
+        };
+
+        self.execution_requests.retain(|_, id| *id != cell_id);
+        Self::subscribe_to_cell(cell_id.clone(), &new_cell, cx);
+        self.cell_map.insert(cell_id, new_cell);
+        self.cell_types_changed = true;
+        self.cell_list.splice(index..index + 1, 1);
+        cx.notify();
+    }
+
+    /// Inserts a cell after the selected one and selects it.
+    fn insert_cell(&mut self, cell_id: CellId, cell: Cell, cx: &mut Context<Self>) {
+        let insert_index = if self.cell_order.is_empty() {
+            0
+        } else {
+            self.selected_cell_index + 1
+        };
+        Self::subscribe_to_cell(cell_id.clone(), &cell, cx);
+        self.cell_order.insert(insert_index, cell_id.clone());
+        self.cell_map.insert(cell_id, cell);
+        self.selected_cell_index = insert_index;
+
+        self.cell_list.reset(self.cell_order.len());
+        cx.notify();
+    }
+
     fn add_markdown_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let new_cell_id: CellId = Uuid::new_v4().into();
         let languages = self.languages.clone();
@@ -669,46 +778,7 @@ impl NotebookEditor {
             )
         });
 
-        let insert_index = if self.cell_order.is_empty() {
-            0
-        } else {
-            self.selected_cell_index + 1
-        };
-        self.cell_order.insert(insert_index, new_cell_id.clone());
-        self.cell_map
-            .insert(new_cell_id.clone(), Cell::Markdown(markdown_cell.clone()));
-        self.selected_cell_index = insert_index;
-
-        cx.subscribe(
-            &markdown_cell,
-            move |_this, cell, event: &MarkdownCellEvent, cx| match event {
-                MarkdownCellEvent::FinishedEditing | MarkdownCellEvent::Run(_) => {
-                    cell.update(cx, |cell, cx| {
-                        cell.reparse_markdown(cx);
-                    });
-                }
-            },
-        )
-        .detach();
-
-        let cell_id_for_editor = new_cell_id.clone();
-        let editor = markdown_cell.read(cx).editor().clone();
-        cx.subscribe(&editor, move |this, _editor, event, cx| {
-            if let editor::EditorEvent::Focused = event {
-                if let Some(index) = this
-                    .cell_order
-                    .iter()
-                    .position(|id| id == &cell_id_for_editor)
-                {
-                    this.selected_cell_index = index;
-                    cx.notify();
-                }
-            }
-        })
-        .detach();
-
-        self.cell_list.reset(self.cell_order.len());
-        cx.notify();
+        self.insert_cell(new_cell_id, Cell::Markdown(markdown_cell), cx);
     }
 
     fn add_code_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -728,46 +798,7 @@ impl NotebookEditor {
             )
         });
 
-        let insert_index = if self.cell_order.is_empty() {
-            0
-        } else {
-            self.selected_cell_index + 1
-        };
-        self.cell_order.insert(insert_index, new_cell_id.clone());
-        self.cell_map
-            .insert(new_cell_id.clone(), Cell::Code(code_cell.clone()));
-        self.selected_cell_index = insert_index;
-
-        let cell_id_for_run = new_cell_id.clone();
-        cx.subscribe(&code_cell, move |this, _cell, event, cx| match event {
-            CellEvent::Run(cell_id) => this.execute_cell(cell_id.clone(), cx),
-            CellEvent::FocusedIn(_) => {
-                if let Some(index) = this.cell_order.iter().position(|id| id == &cell_id_for_run) {
-                    this.selected_cell_index = index;
-                    cx.notify();
-                }
-            }
-        })
-        .detach();
-
-        let cell_id_for_editor = new_cell_id.clone();
-        let editor = code_cell.read(cx).editor().clone();
-        cx.subscribe(&editor, move |this, _editor, event, cx| {
-            if let editor::EditorEvent::Focused = event {
-                if let Some(index) = this
-                    .cell_order
-                    .iter()
-                    .position(|id| id == &cell_id_for_editor)
-                {
-                    this.selected_cell_index = index;
-                    cx.notify();
-                }
-            }
-        })
-        .detach();
-
-        self.cell_list.reset(self.cell_order.len());
-        cx.notify();
+        self.insert_cell(new_cell_id, Cell::Code(code_cell), cx);
     }
 
     fn cell_count(&self) -> usize {
@@ -962,6 +993,21 @@ impl NotebookEditor {
                                 .on_click(|_, window, cx| {
                                     window.dispatch_action(Box::new(MoveCellDown), cx);
                                 }),
+                            )
+                            .child(
+                                Self::render_notebook_control(
+                                    "delete-cell",
+                                    IconName::Trash,
+                                    window,
+                                    cx,
+                                )
+                                .disabled(self.cell_order.is_empty())
+                                .tooltip(move |window, cx| {
+                                    Tooltip::for_action("Delete cell", &DeleteCell, cx)
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(Box::new(DeleteCell), cx);
+                                }),
                             ),
                     )
                     .child(
@@ -1225,6 +1271,19 @@ impl Render for NotebookEditor {
             .on_action(
                 cx.listener(|this, &AddCodeBlock, window, cx| this.add_code_block(window, cx)),
             )
+            .on_action(cx.listener(|this, &DeleteCell, window, cx| this.delete_cell(window, cx)))
+            .on_action(cx.listener(|this, &ConvertToCodeCell, window, cx| {
+                this.convert_cell(CellType::Code, window, cx)
+            }))
+            .on_action(cx.listener(|this, &ConvertToMarkdownCell, window, cx| {
+                this.convert_cell(CellType::Markdown, window, cx)
+            }))
+            .on_action(cx.listener(|this, &ConvertToRawCell, window, cx| {
+                this.convert_cell(CellType::Raw, window, cx)
+            }))
+            .on_action(
+                cx.listener(|this, &UndoDeleteCell, window, cx| this.undo_delete_cell(window, cx)),
+            )
             .on_action(cx.listener(|this, _: &MoveUp, window, cx| {
                 this.select_previous(&menu::SelectPrevious, window, cx);
                 if let Some(cell_id) = this.cell_order.get(this.selected_cell_index) {
@@ -1659,12 +1718,16 @@ impl Item for NotebookEditor {
                     cell_order.push(cell_id.clone());
                     let cell_entity =
                         Cell::load(cell, &languages, notebook_language.clone(), window, cx);
+                    Self::subscribe_to_cell(cell_id.clone(), &cell_entity, cx);
                     cell_map.insert(cell_id.clone(), cell_entity);
                 }
 
+                this.abort_executions(cx);
                 this.cell_order = cell_order.clone();
                 this.original_cell_order = cell_order;
+                this.cell_types_changed = false;
                 this.cell_map = cell_map;
+                this.deleted_cells.clear();
                 this.cell_list =
                     ListState::new(this.cell_order.len(), gpui::ListAlignment::Top, px(1000.));
                 cx.notify();
@@ -1729,6 +1792,315 @@ impl KernelSession for NotebookEditor {
 
     fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
         self.kernel = Kernel::ErroredLaunch(error_message);
+        self.abort_executions(cx);
         cx.notify();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use runtimelib::{ExecuteInput, Stdio, StreamContent};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::{path, rel_path::rel_path};
+
+    async fn open_notebook(
+        cells: serde_json::Value,
+        cx: &mut TestAppContext,
+    ) -> (Entity<NotebookEditor>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ "notebook.ipynb": "" }))
+            .await;
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let (worktree_id, languages) = project.read_with(cx, |project, cx| {
+            let worktree = project.worktrees(cx).next().unwrap();
+            (worktree.read(cx).id(), project.languages().clone())
+        });
+        let notebook_item = cx.new(|_| NotebookItem {
+            path: PathBuf::from(path!("/project/notebook.ipynb")),
+            project_path: ProjectPath {
+                worktree_id,
+                path: rel_path("notebook.ipynb").into(),
+            },
+            languages,
+            notebook: nbformat::v4::Notebook {
+                metadata: serde_json::from_str("{}").unwrap(),
+                nbformat: 4,
+                nbformat_minor: 5,
+                cells: serde_json::from_value(cells).unwrap(),
+            },
+            id: ProjectEntryId::from_proto(1),
+        });
+
+        let (editor, cx) = cx.add_window_view(|window, cx| {
+            let mut editor = NotebookEditor::new(project, notebook_item, window, cx);
+            // Drop the kernel before it's launched, so no process is spawned.
+            editor.kernel = Kernel::Shutdown;
+            editor
+        });
+        cx.run_until_parked();
+        (editor, cx)
+    }
+
+    fn cell(cell_type: &str, id: &str, source: &str) -> serde_json::Value {
+        let mut cell = json!({
+            "cell_type": cell_type,
+            "id": id,
+            "metadata": {},
+            "source": [source],
+        });
+        if cell_type == "code" {
+            cell["execution_count"] = json!(null);
+            cell["outputs"] = json!([]);
+        }
+        cell
+    }
+
+    fn cells(
+        editor: &Entity<NotebookEditor>,
+        cx: &mut VisualTestContext,
+    ) -> Vec<(&'static str, String)> {
+        editor.read_with(cx, |editor, cx| {
+            editor
+                .cell_order
+                .iter()
+                .map(|cell_id| {
+                    let cell = &editor.cell_map[cell_id];
+                    let cell_type = match cell {
+                        Cell::Code(_) => "code",
+                        Cell::Markdown(_) => "markdown",
+                        Cell::Raw(_) => "raw",
+                    };
+                    (cell_type, cell.current_source(cx))
+                })
+                .collect()
+        })
+    }
+
+    fn select(editor: &Entity<NotebookEditor>, index: usize, cx: &mut VisualTestContext) {
+        editor.update_in(cx, |editor, window, cx| {
+            editor.set_selected_index(index, false, window, cx)
+        });
+    }
+
+    #[gpui::test]
+    async fn test_move_cells(cx: &mut TestAppContext) {
+        let (editor, cx) = open_notebook(
+            json!([
+                cell("code", "a", "x = 1"),
+                cell("markdown", "b", "# Title"),
+                cell("code", "c", "print(x)"),
+            ]),
+            cx,
+        )
+        .await;
+
+        select(&editor, 0, cx);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.move_cell_up(window, cx);
+            editor.move_cell_down(window, cx);
+            editor.move_cell_down(window, cx);
+            editor.move_cell_down(window, cx);
+        });
+        assert_eq!(
+            cells(&editor, cx),
+            vec![
+                ("markdown", "# Title".into()),
+                ("code", "print(x)".into()),
+                ("code", "x = 1".into()),
+            ],
+            "Moving the first cell up and the last one down should do nothing"
+        );
+        editor.read_with(cx, |editor, cx| {
+            assert_eq!(editor.selected_cell_index, 2);
+            assert!(editor.is_dirty(cx));
+        });
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.move_cell_up(window, cx);
+            editor.move_cell_up(window, cx);
+        });
+        editor.read_with(cx, |editor, cx| {
+            assert_eq!(editor.selected_cell_index, 0);
+            assert!(
+                !editor.is_dirty(cx),
+                "Moving the cell back to where it was should undo the change"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_delete_and_undo_delete_cell(cx: &mut TestAppContext) {
+        let (editor, cx) = open_notebook(
+            json!([
+                cell("code", "a", "x = 1"),
+                cell("markdown", "b", "# Title"),
+                cell("code", "c", "print(x)"),
+            ]),
+            cx,
+        )
+        .await;
+
+        select(&editor, 1, cx);
+        editor.update_in(cx, |editor, window, cx| editor.delete_cell(window, cx));
+        assert_eq!(
+            cells(&editor, cx),
+            vec![("code", "x = 1".into()), ("code", "print(x)".into())]
+        );
+        editor.update_in(cx, |editor, window, cx| editor.delete_cell(window, cx));
+        assert_eq!(cells(&editor, cx), vec![("code", "x = 1".into())]);
+        editor.read_with(cx, |editor, cx| {
+            assert_eq!(editor.selected_cell_index, 0);
+            assert_eq!(editor.cell_count(), 1);
+            assert!(editor.is_dirty(cx));
+        });
+
+        editor.update_in(cx, |editor, window, cx| editor.undo_delete_cell(window, cx));
+        assert_eq!(
+            cells(&editor, cx),
+            vec![("code", "x = 1".into()), ("code", "print(x)".into())]
+        );
+        editor.read_with(cx, |editor, _| assert_eq!(editor.selected_cell_index, 1));
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.undo_delete_cell(window, cx);
+            // There's nothing left to restore.
+            editor.undo_delete_cell(window, cx);
+        });
+        assert_eq!(
+            cells(&editor, cx),
+            vec![
+                ("code", "x = 1".into()),
+                ("markdown", "# Title".into()),
+                ("code", "print(x)".into()),
+            ]
+        );
+        editor.read_with(cx, |editor, cx| {
+            assert_eq!(editor.selected_cell_index, 1);
+            assert!(!editor.is_dirty(cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_convert_cell(cx: &mut TestAppContext) {
+        let (editor, cx) = open_notebook(
+            json!([cell("code", "a", "x = 1"), cell("raw", "b", "raw")]),
+            cx,
+        )
+        .await;
+
+        select(&editor, 0, cx);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.convert_cell(CellType::Raw, window, cx)
+        });
+        assert_eq!(
+            cells(&editor, cx),
+            vec![("raw", "x = 1".into()), ("raw", "raw".into())]
+        );
+        editor.read_with(cx, |editor, cx| {
+            assert!(editor.is_dirty(cx));
+            let notebook = editor.to_notebook(cx);
+            assert!(matches!(
+                &notebook.cells[0],
+                nbformat::v4::Cell::Raw { id, .. } if *id == editor.cell_order[0]
+            ));
+        });
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.convert_cell(CellType::Markdown, window, cx);
+            editor.set_selected_index(1, false, window, cx);
+            editor.convert_cell(CellType::Code, window, cx);
+        });
+        assert_eq!(
+            cells(&editor, cx),
+            vec![("markdown", "x = 1".into()), ("code", "raw".into())]
+        );
+
+        editor.update(cx, |editor, cx| editor.mark_as_saved(cx));
+        editor.update_in(cx, |editor, window, cx| {
+            editor.convert_cell(CellType::Code, window, cx)
+        });
+        editor.read_with(cx, |editor, cx| {
+            assert!(
+                !editor.is_dirty(cx),
+                "Converting a cell to its own type should do nothing"
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_kernel_messages_update_saved_outputs(cx: &mut TestAppContext) {
+        let (editor, cx) = open_notebook(json!([cell("code", "a", "print(x)")]), cx).await;
+        let Some(Cell::Code(code_cell)) = editor.read_with(cx, |editor, _| {
+            editor.cell_map.get(&editor.cell_order[0]).cloned()
+        }) else {
+            panic!("expected a code cell");
+        };
+        let send = |content: JupyterMessageContent, cx: &mut VisualTestContext| {
+            code_cell.update_in(cx, |cell, window, cx| {
+                cell.handle_message(&JupyterMessage::new(content, None), window, cx)
+            });
+        };
+        let stream = |name: Stdio, text: &str| {
+            JupyterMessageContent::StreamContent(StreamContent {
+                name,
+                text: text.to_string(),
+            })
+        };
+
+        let execute_input: ExecuteInput =
+            serde_json::from_value(json!({ "code": "print(x)", "execution_count": 3 })).unwrap();
+        send(JupyterMessageContent::ExecuteInput(execute_input), cx);
+        code_cell.read_with(cx, |cell, cx| {
+            assert!(
+                cell.is_dirty(cx),
+                "A new execution count should be saved to the notebook"
+            );
+        });
+
+        send(stream(Stdio::Stdout, "hello "), cx);
+        send(stream(Stdio::Stdout, "world\n"), cx);
+        send(stream(Stdio::Stderr, "oops\n"), cx);
+        send(stream(Stdio::Stdout, "done\n"), cx);
+
+        code_cell.read_with(cx, |cell, cx| {
+            let nbformat::v4::Cell::Code {
+                execution_count,
+                outputs,
+                ..
+            } = cell.to_nbformat_cell(cx)
+            else {
+                panic!("expected a code cell");
+            };
+            assert_eq!(execution_count, Some(3));
+            let streams = outputs
+                .iter()
+                .map(|output| match output {
+                    nbformat::v4::Output::Stream { name, text } => {
+                        (serde_json::to_value(name).unwrap(), text.0.clone())
+                    }
+                    _ => panic!("expected only stream outputs"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                streams,
+                vec![
+                    (json!("stdout"), "hello world\n".to_string()),
+                    (json!("stderr"), "oops\n".to_string()),
+                    (json!("stdout"), "done\n".to_string()),
+                ],
+                "Consecutive chunks of the same stream should be merged"
+            );
+        });
+    }
+}
@@ -135,47 +135,6 @@ pub enum Output {
     ClearOutputWaitMarker,
 }
 
-impl Output {
-    pub fn to_nbformat(&self, cx: &App) -> Option<nbformat::v4::Output> {
-        match self {
-            Output::Stream { content } => {
-                let text = content.read(cx).full_text();
-                Some(nbformat::v4::Output::Stream {
-                    name: "stdout".to_string(),
-                    text: nbformat::v4::MultilineString(text),
-                })
-            }
-            Output::Plain { content, .. } => {
-                let text = content.read(cx).full_text();
-                let mut data = jupyter_protocol::media::Media::default();
-                data.content.push(jupyter_protocol::MediaType::Plain(text));
-                Some(nbformat::v4::Output::DisplayData(
-                    nbformat::v4::DisplayData {
-                        data,
-                        metadata: serde_json::Map::new(),
-                    },
-                ))
-            }
-            Output::ErrorOutput(error_view) => {
-                let traceback_text = error_view.traceback.read(cx).full_text();
-                let traceback_lines: Vec<String> =
-                    traceback_text.lines().map(|s| s.to_string()).collect();
-                Some(nbformat::v4::Output::Error(nbformat::v4::ErrorOutput {
-                    ename: error_view.ename.clone(),
-                    evalue: error_view.evalue.clone(),
-                    traceback: traceback_lines,
-                }))
-            }
-            Output::Image { .. }
-            | Output::Markdown { .. }
-            | Output::Table { .. }
-            | Output::Json { .. } => None,
-            Output::Message(_) => None,
-            Output::ClearOutputWaitMarker => None,
-        }
-    }
-}
-
 impl Output {
     fn render_output_controls<V: OutputContent + 'static>(
         v: Entity<V>,
@@ -1,8 +1,10 @@
 use crate::{
     BufferSearchBar, FocusSearch, HighlightKey, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll,
     ReplaceNext, SearchOption, SearchOptions, SearchSource, SelectNextMatch, SelectPreviousMatch,
-    ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex, ToggleReplace, ToggleWholeWord,
+    ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex, ToggleReplace, ToggleStructural,
+    ToggleWholeWord,
     buffer_search::Deploy,
+    saved_searches::{SavedSearch, SavedSearchesModal},
     search_bar::{
         ActionButtonState, alignment_element, input_base_styles, render_action_button,
         render_text_input,
@@ -67,7 +69,11 @@ actions!(
         /// Toggles the search filters panel.
         ToggleFilters,
         /// Toggles collapse/expand state of all search result excerpts.
-        ToggleAllSearchResults
+        ToggleAllSearchResults,
+        /// Opens the saved searches picker, to re-run a saved search or save the current one.
+        ToggleSavedSearches,
+        /// Copies the search results into a plain text buffer, formatted like `rg --vimgrep` output.
+        FreezeResults
     ]
 );
 
@@ -132,6 +138,12 @@ pub fn init(cx: &mut App) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, window, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, window, cx);
         });
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ToggleStructural, window, cx| {
+                search_bar.toggle_search_option(SearchOptions::STRUCTURAL, window, cx);
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, action: &ToggleReplace, window, cx| {
@@ -156,6 +168,20 @@ pub fn init(cx: &mut App) {
             ProjectSearchView::search_in_new(workspace, action, window, cx)
         });
 
+        register_workspace_action_for_present_search(
+            workspace,
+            |workspace, _: &FreezeResults, window, cx| {
+                if let Some(search_view) = workspace
+                    .active_item(cx)
+                    .and_then(|item| item.downcast::<ProjectSearchView>())
+                {
+                    search_view.update(cx, |search_view, cx| {
+                        search_view.freeze_results(window, cx);
+                    });
+                }
+            },
+        );
+
         register_workspace_action_for_present_search(
             workspace,
             |workspace, action: &ToggleAllSearchResults, window, cx| {
@@ -213,6 +239,9 @@ pub fn init(cx: &mut App) {
             ProjectSearchView::deploy_search(workspace, action, window, cx);
             cx.notify();
         });
+        workspace.register_action(move |workspace, _: &ToggleSavedSearches, window, cx| {
+            SavedSearchesModal::toggle(workspace, window, cx);
+        });
         workspace.register_action(move |workspace, action: &NewSearch, window, cx| {
             if workspace.has_active_modal(window, cx) && !workspace.hide_modal(window, cx) {
                 cx.propagate();
@@ -721,6 +750,12 @@ impl ProjectSearchView {
 
     fn toggle_search_option(&mut self, option: SearchOptions, cx: &mut Context<Self>) {
         self.search_options.toggle(option);
+        // Structural patterns are neither regexes nor plain text, so the two modes exclude each other.
+        if option.contains(SearchOptions::STRUCTURAL) {
+            self.search_options.remove(SearchOptions::REGEX);
+        } else if option.contains(SearchOptions::REGEX) {
+            self.search_options.remove(SearchOptions::STRUCTURAL);
+        }
         ActiveSettings::update_global(cx, |settings, cx| {
             settings.0.insert(
                 self.entity.read(cx).project.downgrade(),
@@ -1092,6 +1127,116 @@ impl ProjectSearchView {
         }
     }
 
+    /// Opens a new search tab with the inputs of the saved search, and runs it.
+    pub(crate) fn run_saved_search(
+        workspace: &mut Workspace,
+        saved_search: &SavedSearch,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let weak_workspace = cx.entity().downgrade();
+        let entity = cx.new(|cx| ProjectSearch::new(workspace.project().clone(), cx));
+        let search_view =
+            cx.new(|cx| ProjectSearchView::new(weak_workspace, entity, window, cx, None));
+        workspace.add_item_to_active_pane(Box::new(search_view.clone()), None, true, window, cx);
+        search_view.update(cx, |search_view, cx| {
+            search_view.search_options = saved_search.options;
+            search_view.adjust_query_regex_language(cx);
+            search_view.set_search_editor(SearchInputKind::Query, &saved_search.query, window, cx);
+            search_view.set_search_editor(
+                SearchInputKind::Include,
+                &saved_search.included_files,
+                window,
+                cx,
+            );
+            search_view.set_search_editor(
+                SearchInputKind::Exclude,
+                &saved_search.excluded_files,
+                window,
+                cx,
+            );
+            search_view.filters_enabled =
+                !saved_search.included_files.is_empty() || !saved_search.excluded_files.is_empty();
+            search_view.search(cx);
+        });
+    }
+
+    /// The inputs of this search, saved under its query until the user names it.
+    pub(crate) fn to_saved_search(&self, cx: &App) -> Option<SavedSearch> {
+        let query = self.search_query_text(cx);
+        if query.is_empty() {
+            return None;
+        }
+        let (included_files, excluded_files) = if self.filters_enabled {
+            (
+                self.included_files_editor.read(cx).text(cx),
+                self.excluded_files_editor.read(cx).text(cx),
+            )
+        } else {
+            Default::default()
+        };
+        Some(SavedSearch {
+            name: query.clone(),
+            query,
+            included_files,
+            excluded_files,
+            options: self.search_options - SearchOptions::BACKWARDS,
+        })
+    }
+
+    /// Opens the current results in a plain text buffer, one `path:line:column:text` line per match,
+    /// so that they stay put while the files change and can be saved like ripgrep's output.
+    fn freeze_results(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self.results_as_text(cx);
+        let project = self.entity.read(cx).project.clone();
+        let create_buffer =
+            project.update(cx, |project, cx| project.create_buffer(None, false, cx));
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let buffer = create_buffer.await?;
+            buffer.update(cx, |buffer, cx| buffer.set_text(text, cx));
+            workspace.update_in(cx, |workspace, window, cx| {
+                let editor = cx.new(|cx| Editor::for_buffer(buffer, Some(project), window, cx));
+                workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn results_as_text(&self, cx: &App) -> String {
+        let search = self.entity.read(cx);
+        let project = search.project.read(cx);
+        let path_style = project.path_style(cx);
+        let full_paths = project.visible_worktrees(cx).count() > 1;
+        let snapshot = search.excerpts.read(cx).snapshot(cx);
+        let mut text = String::new();
+        for range in &search.match_ranges {
+            for (buffer, buffer_range, _) in snapshot.range_to_buffer_ranges(range.clone()) {
+                let Some(file) = buffer.file() else {
+                    continue;
+                };
+                let path = if full_paths {
+                    file.full_path(cx).to_string_lossy().into_owned()
+                } else {
+                    file.path().display(path_style).into_owned()
+                };
+                let start = buffer.offset_to_point(buffer_range.start.0);
+                let line = buffer
+                    .text_for_range(
+                        language::Point::new(start.row, 0)
+                            ..language::Point::new(start.row, buffer.line_len(start.row)),
+                    )
+                    .collect::<String>();
+                text.push_str(&format!(
+                    "{path}:{}:{}:{line}\n",
+                    start.row + 1,
+                    start.column + 1
+                ));
+            }
+        }
+        text
+    }
+
     // Add another search tab to the workspace.
     fn new_search(
         workspace: &mut Workspace,
@@ -1323,8 +1468,17 @@ impl ProjectSearchView {
             .count()
             > 1;
 
-        let query = if self.search_options.contains(SearchOptions::REGEX) {
-            match SearchQuery::regex(
+        let query = if self.search_options.contains(SearchOptions::STRUCTURAL) {
+            SearchQuery::structural(
+                text,
+                self.search_options.contains(SearchOptions::INCLUDE_IGNORED),
+                included_files,
+                excluded_files,
+                match_full_paths,
+                open_buffers,
+            )
+        } else if self.search_options.contains(SearchOptions::REGEX) {
+            SearchQuery::regex(
                 text,
                 self.search_options.contains(SearchOptions::WHOLE_WORD),
                 self.search_options.contains(SearchOptions::CASE_SENSITIVE),
@@ -1335,28 +1489,9 @@ impl ProjectSearchView {
                 excluded_files,
                 match_full_paths,
                 open_buffers,
-            ) {
-                Ok(query) => {
-                    let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
-                    if should_unmark_error.is_some() {
-                        cx.notify();
-                    }
-
-                    Some(query)
-                }
-                Err(e) => {
-                    let should_mark_error = self
-                        .panels_with_errors
-                        .insert(InputPanel::Query, e.to_string());
-                    if should_mark_error.is_none() {
-                        cx.notify();
-                    }
-
-                    None
-                }
-            }
+            )
         } else {
-            match SearchQuery::text(
+            SearchQuery::text(
                 text,
                 self.search_options.contains(SearchOptions::WHOLE_WORD),
                 self.search_options.contains(SearchOptions::CASE_SENSITIVE),
@@ -1365,25 +1500,26 @@ impl ProjectSearchView {
                 excluded_files,
                 match_full_paths,
                 open_buffers,
-            ) {
-                Ok(query) => {
-                    let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
-                    if should_unmark_error.is_some() {
-                        cx.notify();
-                    }
-
-                    Some(query)
+            )
+        };
+        let query = match query {
+            Ok(query) => {
+                let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
+                if should_unmark_error.is_some() {
+                    cx.notify();
                 }
-                Err(e) => {
-                    let should_mark_error = self
-                        .panels_with_errors
-                        .insert(InputPanel::Query, e.to_string());
-                    if should_mark_error.is_none() {
-                        cx.notify();
-                    }
 
-                    None
+                Some(query)
+            }
+            Err(e) => {
+                let should_mark_error = self
+                    .panels_with_errors
+                    .insert(InputPanel::Query, e.to_string());
+                if should_mark_error.is_none() {
+                    cx.notify();
                 }
+
+                None
             }
         };
         if !self.panels_with_errors.is_empty() {
@@ -2119,6 +2255,11 @@ impl Render for ProjectSearchBar {
                         search.search_options,
                         SearchSource::Project(cx),
                         focus_handle.clone(),
+                    ))
+                    .child(SearchOption::Structural.as_button(
+                        search.search_options,
+                        SearchSource::Project(cx),
+                        focus_handle.clone(),
                     )),
             );
 
@@ -2530,6 +2671,7 @@ pub mod tests {
     };
 
     use super::*;
+    use crate::saved_searches;
     use editor::{DisplayPoint, display_map::DisplayRow};
     use gpui::{Action, TestAppContext, VisualTestContext, WindowHandle};
     use language::{FakeLspAdapter, rust_lang};
@@ -2825,6 +2967,118 @@ pub mod tests {
         assert!(!results_collapsed);
     }
 
+    #[perf]
+    #[gpui::test]
+    async fn test_freeze_and_save_results(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+                "three.rs": "const THREE: usize = one::ONE + two::TWO;",
+                "four.rs": "const FOUR: usize = one::ONE + three::THREE;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.new_window_entity(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(
+                Box::new(search_view.clone()),
+                None,
+                true,
+                window,
+                cx,
+            );
+        });
+
+        search_view.update_in(cx, |search_view, window, cx| {
+            search_view
+                .included_files_editor
+                .update(cx, |editor, cx| editor.set_text("*.rs", window, cx));
+            search_view.filters_enabled = true;
+            search_view.search_options = SearchOptions::CASE_SENSITIVE;
+            search_view
+                .query_editor
+                .update(cx, |editor, cx| editor.set_text("TWO", window, cx));
+            search_view.search(cx);
+        });
+        cx.run_until_parked();
+
+        let expected_results = "three.rs:1:33:const THREE: usize = one::ONE + two::TWO;\n\
+                                three.rs:1:38:const THREE: usize = one::ONE + two::TWO;\n\
+                                two.rs:1:7:const TWO: usize = one::ONE + one::ONE;\n";
+        search_view.update_in(cx, |search_view, window, cx| {
+            assert_eq!(search_view.results_as_text(cx), expected_results);
+            search_view.freeze_results(window, cx);
+        });
+        cx.run_until_parked();
+        let frozen_results = workspace.read_with(cx, |workspace, cx| {
+            workspace
+                .active_item(cx)
+                .and_then(|item| item.downcast::<Editor>())
+                .expect("the frozen results should be opened in an editor")
+        });
+        assert_eq!(
+            frozen_results.read_with(cx, |editor, cx| editor.text(cx)),
+            expected_results
+        );
+
+        // The saved search round-trips through the database and re-runs with the same inputs.
+        let saved_search = search_view
+            .read_with(cx, |search_view, cx| search_view.to_saved_search(cx))
+            .unwrap();
+        assert_eq!(
+            saved_search,
+            SavedSearch {
+                name: "TWO".to_string(),
+                query: "TWO".to_string(),
+                included_files: "*.rs".to_string(),
+                excluded_files: String::new(),
+                options: SearchOptions::CASE_SENSITIVE,
+            }
+        );
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        saved_searches::DB
+            .save_search(workspace_id, saved_search.clone())
+            .await
+            .unwrap();
+        let loaded_search = saved_searches::DB
+            .saved_searches(workspace_id)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(loaded_search, saved_search);
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            ProjectSearchView::run_saved_search(workspace, &loaded_search, window, cx);
+        });
+        cx.run_until_parked();
+        let rerun_search_view = workspace.read_with(cx, |workspace, cx| {
+            workspace
+                .active_item(cx)
+                .and_then(|item| item.downcast::<ProjectSearchView>())
+                .unwrap()
+        });
+        assert_ne!(rerun_search_view, search_view);
+        rerun_search_view.read_with(cx, |search_view, cx| {
+            assert_eq!(search_view.to_saved_search(cx), Some(saved_search));
+            assert_eq!(search_view.results_as_text(cx), expected_results);
+        });
+    }
+
     #[perf]
     #[gpui::test]
     async fn test_deploy_project_search_focus(cx: &mut TestAppContext) {
@@ -0,0 +1,495 @@
+//! Named project searches, stored in the database for each workspace so that recurring searches can be
+//! re-run from a picker.
+
+use anyhow::Result;
+use db::{
+    query,
+    sqlez::{
+        bindable::Column, domain::Domain, statement::Statement,
+        thread_safe_connection::ThreadSafeConnection,
+    },
+    sqlez_macros::sql,
+};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Task,
+    WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace, WorkspaceDb, WorkspaceId};
+
+use crate::{ProjectSearchView, SearchOptions};
+
+/// A project search that was saved under a name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    pub included_files: String,
+    pub excluded_files: String,
+    pub options: SearchOptions,
+}
+
+impl Column for SavedSearch {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (name, next_index): (String, i32) = Column::column(statement, start_index)?;
+        let (query, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (included_files, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (excluded_files, next_index): (String, i32) = Column::column(statement, next_index)?;
+        let (options, next_index): (u32, i32) = Column::column(statement, next_index)?;
+        let saved_search = Self {
+            name,
+            query,
+            included_files,
+            excluded_files,
+            options: SearchOptions::from_bits_truncate(options as u8),
+        };
+        Ok((saved_search, next_index))
+    }
+}
+
+pub struct SearchDb(ThreadSafeConnection);
+
+impl Domain for SearchDb {
+    const NAME: &str = stringify!(SearchDb);
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE saved_searches(
+            workspace_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            query TEXT NOT NULL,
+            included_files TEXT NOT NULL,
+            excluded_files TEXT NOT NULL,
+            options INTEGER NOT NULL,
+            PRIMARY KEY(workspace_id, name),
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        ) STRICT;
+    )];
+}
+
+db::static_connection!(DB, SearchDb, [WorkspaceDb]);
+
+impl SearchDb {
+    /// Saves the search in the workspace, replacing any saved search with the same name.
+    pub async fn save_search(
+        &self,
+        workspace_id: WorkspaceId,
+        saved_search: SavedSearch,
+    ) -> Result<()> {
+        self.save_search_internal(
+            workspace_id,
+            saved_search.name,
+            saved_search.query,
+            saved_search.included_files,
+            saved_search.excluded_files,
+            saved_search.options.bits() as u32,
+        )
+        .await
+    }
+
+    query! {
+        async fn save_search_internal(
+            workspace_id: WorkspaceId,
+            name: String,
+            query: String,
+            included_files: String,
+            excluded_files: String,
+            options: u32
+        ) -> Result<()> {
+            INSERT OR REPLACE INTO saved_searches(workspace_id, name, query, included_files, excluded_files, options)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        }
+    }
+
+    query! {
+        pub fn saved_searches(workspace_id: WorkspaceId) -> Result<Vec<SavedSearch>> {
+            SELECT name, query, included_files, excluded_files, options
+            FROM saved_searches
+            WHERE workspace_id = ?
+            ORDER BY name
+        }
+    }
+
+    query! {
+        pub async fn delete_saved_search(workspace_id: WorkspaceId, name: String) -> Result<()> {
+            DELETE FROM saved_searches WHERE workspace_id = ?1 AND name = ?2
+        }
+    }
+}
+
+pub struct SavedSearchesModal {
+    picker: Entity<Picker<SavedSearchesDelegate>>,
+}
+
+impl SavedSearchesModal {
+    pub(crate) fn toggle(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let workspace_id = workspace.database_id();
+        let current_search = workspace
+            .active_item(cx)
+            .and_then(|item| item.downcast::<ProjectSearchView>())
+            .and_then(|search_view| search_view.read(cx).to_saved_search(cx))
+            .filter(|_| workspace_id.is_some());
+        let saved_searches = workspace_id
+            .and_then(|workspace_id| DB.saved_searches(workspace_id).log_err())
+            .unwrap_or_default();
+        let weak_workspace = cx.entity().downgrade();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            let delegate = SavedSearchesDelegate {
+                modal: cx.entity().downgrade(),
+                workspace: weak_workspace,
+                workspace_id,
+                current_search,
+                saved_searches,
+                query: String::new(),
+                matches: Vec::new(),
+                selected_index: 0,
+            };
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            Self { picker }
+        });
+    }
+}
+
+impl Render for SavedSearchesModal {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("SavedSearchesModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for SavedSearchesModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for SavedSearchesModal {}
+impl ModalView for SavedSearchesModal {}
+
+pub struct SavedSearchesDelegate {
+    modal: WeakEntity<SavedSearchesModal>,
+    workspace: WeakEntity<Workspace>,
+    workspace_id: Option<WorkspaceId>,
+    /// The search in the active project search tab, which can be saved under the name typed into the picker.
+    current_search: Option<SavedSearch>,
+    saved_searches: Vec<SavedSearch>,
+    query: String,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl SavedSearchesDelegate {
+    fn name_to_save(&self) -> Option<&str> {
+        self.current_search.as_ref()?;
+        let name = self.query.trim();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// The index of the entry that saves the current search under the typed name. It's listed
+    /// first, unless the name is already taken by a saved search, which is then listed first.
+    fn save_entry_index(&self) -> Option<usize> {
+        let name = self.name_to_save()?;
+        let name_taken = self
+            .saved_searches
+            .iter()
+            .any(|saved_search| saved_search.name == name);
+        Some(if name_taken { self.matches.len() } else { 0 })
+    }
+
+    /// The saved search listed at `ix`, or `None` for the entry that saves the current search.
+    fn match_at(&self, ix: usize) -> Option<&StringMatch> {
+        match self.save_entry_index() {
+            Some(save_entry_index) if ix == save_entry_index => None,
+            Some(save_entry_index) if ix > save_entry_index => self.matches.get(ix - 1),
+            _ => self.matches.get(ix),
+        }
+    }
+
+    fn delete_saved_search(&mut self, ix: usize, cx: &mut Context<Picker<Self>>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        let Some(candidate_id) = self.match_at(ix).map(|mat| mat.candidate_id) else {
+            return;
+        };
+        let saved_search = self.saved_searches.remove(candidate_id);
+        db::write_and_log(cx, move || {
+            DB.delete_saved_search(workspace_id, saved_search.name)
+        });
+        // Candidate ids past the removed search are stale until the matches are refreshed.
+        self.matches.clear();
+    }
+}
+
+impl PickerDelegate for SavedSearchesDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        if self.current_search.is_some() {
+            "Select a saved search, or type a name to save the current one…".into()
+        } else {
+            "Select a saved search…".into()
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No saved searches. Save one from a project search tab.".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len() + self.name_to_save().is_some() as usize
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .saved_searches
+            .iter()
+            .enumerate()
+            .map(|(id, saved_search)| StringMatchCandidate::new(id, &saved_search.name))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            picker
+                .update(cx, |picker, cx| {
+                    let delegate = &mut picker.delegate;
+                    delegate.query = query;
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.match_count().saturating_sub(1));
+                    cx.notify();
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.match_at(self.selected_index) {
+            let saved_search = self.saved_searches[mat.candidate_id].clone();
+            self.workspace
+                .update(cx, |workspace, cx| {
+                    ProjectSearchView::run_saved_search(workspace, &saved_search, window, cx);
+                })
+                .log_err();
+        } else if let Some(name) = self.name_to_save()
+            && let Some(current_search) = self.current_search.clone()
+            && let Some(workspace_id) = self.workspace_id
+        {
+            let saved_search = SavedSearch {
+                name: name.to_string(),
+                ..current_search
+            };
+            db::write_and_log(cx, move || DB.save_search(workspace_id, saved_search));
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.modal
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let Some(mat) = self.match_at(ix) else {
+            let name = self.name_to_save()?;
+            return Some(
+                ListItem::new(ix)
+                    .inset(true)
+                    .spacing(ListItemSpacing::Sparse)
+                    .toggle_state(selected)
+                    .start_slot(Icon::new(IconName::Plus).color(Color::Muted))
+                    .child(Label::new(format!("Save current search as \"{name}\""))),
+            );
+        };
+        let saved_search = &self.saved_searches[mat.candidate_id];
+        let mut details = saved_search.query.clone();
+        if !saved_search.included_files.is_empty() {
+            details.push_str(&format!(" in {}", saved_search.included_files));
+        }
+        if !saved_search.excluded_files.is_empty() {
+            details.push_str(&format!(" excluding {}", saved_search.excluded_files));
+        }
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            mat.string.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .child(
+                            Label::new(details)
+                                .color(Color::Muted)
+                                .size(LabelSize::Small)
+                                .truncate(),
+                        ),
+                )
+                .end_hover_slot(
+                    IconButton::new(("delete-saved-search", ix), IconName::Trash)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Delete Saved Search"))
+                        .on_click(cx.listener(move |picker, _, window, cx| {
+                            cx.stop_propagation();
+                            picker.delegate.delete_saved_search(ix, cx);
+                            picker.refresh(window, cx);
+                        })),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_saved_searches_are_replaced_by_name() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let other_workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        assert_eq!(DB.saved_searches(workspace_id).unwrap(), Vec::new());
+
+        let todos = SavedSearch {
+            name: "TODOs".to_string(),
+            query: "TODO".to_string(),
+            included_files: "crates/**".to_string(),
+            excluded_files: String::new(),
+            options: SearchOptions::CASE_SENSITIVE | SearchOptions::WHOLE_WORD,
+        };
+        let unsafe_blocks = SavedSearch {
+            name: "Unsafe blocks".to_string(),
+            query: r"unsafe\s*\{".to_string(),
+            included_files: String::new(),
+            excluded_files: "vendor/**".to_string(),
+            options: SearchOptions::REGEX,
+        };
+        DB.save_search(workspace_id, unsafe_blocks.clone())
+            .await
+            .unwrap();
+        DB.save_search(workspace_id, todos.clone()).await.unwrap();
+        assert_eq!(
+            DB.saved_searches(workspace_id).unwrap(),
+            vec![todos.clone(), unsafe_blocks.clone()]
+        );
+        assert_eq!(
+            DB.saved_searches(other_workspace_id).unwrap(),
+            Vec::new(),
+            "Saved searches should belong to the workspace they were saved in"
+        );
+
+        let todos = SavedSearch {
+            query: "TODO|FIXME".to_string(),
+            options: SearchOptions::REGEX,
+            ..todos
+        };
+        DB.save_search(workspace_id, todos.clone()).await.unwrap();
+        assert_eq!(
+            DB.saved_searches(workspace_id).unwrap(),
+            vec![todos.clone(), unsafe_blocks]
+        );
+
+        DB.delete_saved_search(workspace_id, "Unsafe blocks".to_string())
+            .await
+            .unwrap();
+        assert_eq!(DB.saved_searches(workspace_id).unwrap(), vec![todos]);
+    }
+
+    #[test]
+    fn test_save_entry_comes_first_unless_the_name_is_taken() {
+        let saved_search = |name: &str| SavedSearch {
+            name: name.to_string(),
+            query: "TODO".to_string(),
+            included_files: String::new(),
+            excluded_files: String::new(),
+            options: SearchOptions::NONE,
+        };
+        let string_match = |candidate_id: usize, string: &str| StringMatch {
+            candidate_id,
+            string: string.to_string(),
+            positions: Vec::new(),
+            score: 0.0,
+        };
+        let mut delegate = SavedSearchesDelegate {
+            modal: WeakEntity::new_invalid(),
+            workspace: WeakEntity::new_invalid(),
+            workspace_id: Some(WorkspaceId::from_i64(1)),
+            current_search: Some(saved_search("")),
+            saved_searches: vec![saved_search("TODOs"), saved_search("TODOs in tests")],
+            query: "TODO".to_string(),
+            matches: vec![string_match(0, "TODOs"), string_match(1, "TODOs in tests")],
+            selected_index: 0,
+        };
+        assert_eq!(delegate.match_count(), 3);
+        assert_eq!(delegate.save_entry_index(), Some(0));
+        assert!(delegate.match_at(0).is_none());
+        assert_eq!(delegate.match_at(1).unwrap().string, "TODOs");
+        assert_eq!(delegate.match_at(2).unwrap().string, "TODOs in tests");
+
+        delegate.query = "TODOs".to_string();
+        assert_eq!(delegate.save_entry_index(), Some(2));
+        assert_eq!(delegate.match_at(0).unwrap().string, "TODOs");
+        assert_eq!(delegate.match_at(1).unwrap().string, "TODOs in tests");
+        assert!(delegate.match_at(2).is_none());
+    }
+}
@@ -422,6 +422,12 @@ impl SettingsStore {
             .set_global_value(Box::new(value))
     }
 
+    /// Returns the value of a setting as configured in the settings files, ignoring any
+    /// value set with [`Self::override_global`].
+    pub fn configured_value<T: Settings>(&self) -> T {
+        T::from_settings(&self.merged_settings)
+    }
+
     /// Get the user's settings content.
     ///
     /// For user-facing functionality use the typed setting interface.
@@ -195,7 +195,9 @@ impl VsCodeSettings {
             image_viewer: None,
             journal: None,
             language_models: None,
+            large_file_viewer: None,
             line_indicator_format: None,
+            local_history: None,
             log: None,
             message_editor: None,
             node: self.node_binary_settings(),
@@ -209,6 +211,7 @@ impl VsCodeSettings {
             repl: None,
             server_url: None,
             session: None,
+            spell_checker: None,
             status_bar: self.status_bar_settings_content(),
             tab_bar: self.tab_bar_settings_content(),
             tabs: self.item_settings_content(),
@@ -327,6 +330,7 @@ impl VsCodeSettings {
             min_line_number_digits: None,
             runnables: None,
             breakpoints: None,
+            bookmarks: None,
             folds: self.read_enum("editor.showFoldingControls", |s| match s {
                 "always" | "mouseover" => Some(true),
                 "never" => Some(false),
@@ -610,6 +614,7 @@ impl VsCodeSettings {
                         .collect()
                 }),
             word_diff_enabled: None,
+            spell_check: None,
         }
     }
 
@@ -868,6 +873,10 @@ impl VsCodeSettings {
             minimum_contrast: None,
             option_as_meta: self.read_bool("terminal.integrated.macOptionIsMeta"),
             project: self.project_terminal_settings_content(),
+            restore_scrollback: skip_default(TerminalRestoreScrollbackContent {
+                enabled: self.read_bool("terminal.integrated.enablePersistentSessions"),
+                max_lines: self.read_usize("terminal.integrated.persistentSessionScrollback"),
+            }),
             scrollbar: None,
             scroll_multiplier: None,
             toolbar: None,
@@ -553,6 +553,10 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub word_diff_enabled: Option<bool>,
+    /// Whether to check the spelling of comments, strings and prose.
+    ///
+    /// Default: false
+    pub spell_check: Option<bool>,
     /// Whether to use tree-sitter bracket queries to detect and colorize the brackets in the editor.
     ///
     /// Default: false
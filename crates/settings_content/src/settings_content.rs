@@ -142,6 +142,15 @@ pub struct SettingsContent {
     /// The settings for the image viewer.
     pub image_viewer: Option<ImageViewerSettingsContent>,
 
+    /// The settings for the large file viewer.
+    pub large_file_viewer: Option<LargeFileViewerSettingsContent>,
+
+    /// The settings for the local history of files.
+    pub local_history: Option<LocalHistorySettingsContent>,
+
+    /// The settings for spell checking.
+    pub spell_checker: Option<SpellCheckerSettingsContent>,
+
     pub repl: Option<ReplSettingsContent>,
 
     /// Whether or not to enable Helix mode.
@@ -1031,6 +1040,49 @@ pub struct ImageViewerSettingsContent {
     pub unit: Option<ImageFileSizeUnit>,
 }
 
+/// The settings for the large file viewer.
+#[with_fallible_options]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
+pub struct LargeFileViewerSettingsContent {
+    /// Files of at least this many megabytes are opened read-only in the large file viewer,
+    /// which pages them in from disk instead of loading them into an editor.
+    ///
+    /// Default: 256
+    pub threshold_mb: Option<u64>,
+}
+
+/// The settings for the local history of files, which keeps a snapshot of a file each time it's
+/// saved or changed on disk.
+#[with_fallible_options]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to keep snapshots of the files in local projects.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The most disk space, in megabytes, that snapshots take up. The oldest snapshots are
+    /// deleted when there are more.
+    ///
+    /// Default: 256
+    pub max_size_mb: Option<u64>,
+}
+
+/// The settings for spell checking, which is enabled per language with `spell_check`.
+#[with_fallible_options]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
+pub struct SpellCheckerSettingsContent {
+    /// The Hunspell dictionaries to check words against, by name. Each needs a `<name>.dic`
+    /// and `<name>.aff` file in the dictionary directory.
+    ///
+    /// Default: ["en_US"]
+    pub dictionaries: Option<Vec<String>>,
+    /// The directory to load dictionaries from. When unset, they are loaded from
+    /// the `dictionaries` directory in Zed's config directory.
+    ///
+    /// Default: null
+    pub dictionary_directory: Option<String>,
+}
+
 #[with_fallible_options]
 #[derive(
     Clone,
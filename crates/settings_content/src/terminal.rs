@@ -155,6 +155,8 @@ pub struct TerminalSettingsContent {
     pub toolbar: Option<TerminalToolbarContent>,
     /// Scrollbar-related settings
     pub scrollbar: Option<ScrollbarSettingsContent>,
+    /// Settings for saving terminal scrollback and restoring it when the workspace reopens.
+    pub restore_scrollback: Option<TerminalRestoreScrollbackContent>,
     /// The minimum APCA perceptual contrast between foreground and background colors.
     ///
     /// APCA (Accessible Perceptual Contrast Algorithm) is more accurate than WCAG 2.x,
@@ -372,6 +374,22 @@ pub enum AlternateScroll {
     Off,
 }
 
+#[with_fallible_options]
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq,
+)]
+pub struct TerminalRestoreScrollbackContent {
+    /// Whether to save the scrollback of terminals along with the workspace and replay it into
+    /// them when the workspace reopens.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The maximum number of lines saved per terminal, counted from the bottom.
+    ///
+    /// Default: 5000
+    pub max_lines: Option<usize>,
+}
+
 // Toolbar related settings
 #[with_fallible_options]
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
@@ -0,0 +1,457 @@
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+
+/// At most this many suggestions are offered for a misspelled word.
+pub(crate) const MAX_SUGGESTIONS: usize = 5;
+
+type Flag = u32;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FlagFormat {
+    /// Each character is a flag, which is the default.
+    Char,
+    /// Each pair of characters is a flag.
+    Long,
+    /// Flags are comma-separated numbers.
+    Numeric,
+}
+
+struct Affix {
+    flag: Flag,
+    cross_product: bool,
+    strip: String,
+    condition: Condition,
+}
+
+/// A Hunspell affix condition, which the start of a stem (for prefixes)
+/// or its end (for suffixes) has to match, one character at a time.
+struct Condition(Vec<ConditionPart>);
+
+enum ConditionPart {
+    Any,
+    Char(char),
+    Class { negated: bool, chars: Vec<char> },
+}
+
+impl Condition {
+    fn parse(condition: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut chars = condition.chars();
+        while let Some(c) = chars.next() {
+            parts.push(match c {
+                '.' => ConditionPart::Any,
+                '[' => {
+                    let mut class = chars.by_ref().take_while(|c| *c != ']').peekable();
+                    let negated = class.next_if_eq(&'^').is_some();
+                    ConditionPart::Class {
+                        negated,
+                        chars: class.collect(),
+                    }
+                }
+                c => ConditionPart::Char(c),
+            });
+        }
+        Self(parts)
+    }
+
+    fn matches_start(&self, stem: &str) -> bool {
+        let mut chars = stem.chars();
+        self.0
+            .iter()
+            .all(|part| chars.next().is_some_and(|c| part.matches(c)))
+    }
+
+    fn matches_end(&self, stem: &str) -> bool {
+        let mut chars = stem.chars().rev();
+        self.0
+            .iter()
+            .rev()
+            .all(|part| chars.next().is_some_and(|c| part.matches(c)))
+    }
+}
+
+impl ConditionPart {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Char(expected) => c == *expected,
+            Self::Class { negated, chars } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// A dictionary in the Hunspell format, made of a `.dic` file listing stems with the affixes
+/// they take, and an `.aff` file defining those affixes.
+///
+/// This supports single prefixes and suffixes (combined when both allow it), replacement tables
+/// for suggestions, and the `FORBIDDENWORD`, `NEEDAFFIX` and `NOSUGGEST` flags. Compounding,
+/// continuation classes and encodings other than UTF-8 are not supported.
+pub struct Dictionary {
+    words: HashMap<String, Vec<Flag>>,
+    /// Prefixes by the text they add to the start of a stem.
+    prefixes: HashMap<String, Vec<Affix>>,
+    /// Suffixes by the text they add to the end of a stem.
+    suffixes: HashMap<String, Vec<Affix>>,
+    max_prefix_len: usize,
+    max_suffix_len: usize,
+    try_chars: Vec<char>,
+    replacements: Vec<(String, String)>,
+    forbidden_flag: Option<Flag>,
+    need_affix_flag: Option<Flag>,
+    no_suggest_flag: Option<Flag>,
+}
+
+impl Dictionary {
+    /// Parses a dictionary from the contents of its `.aff` and `.dic` files.
+    pub fn parse(aff: &str, dic: &str) -> Result<Self> {
+        let mut dictionary = Self {
+            words: HashMap::default(),
+            prefixes: HashMap::default(),
+            suffixes: HashMap::default(),
+            max_prefix_len: 0,
+            max_suffix_len: 0,
+            try_chars: Vec::new(),
+            replacements: Vec::new(),
+            forbidden_flag: None,
+            need_affix_flag: None,
+            no_suggest_flag: None,
+        };
+        let mut flag_format = FlagFormat::Char;
+        let mut cross_products = HashMap::<(bool, Flag), bool>::default();
+
+        for (row, line) in aff.lines().enumerate() {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let parse_flag = |field: &str| parse_flags(field, flag_format).into_iter().next();
+            match fields.as_slice() {
+                ["FLAG", format] => {
+                    flag_format = match *format {
+                        "long" => FlagFormat::Long,
+                        "num" => FlagFormat::Numeric,
+                        _ => FlagFormat::Char,
+                    }
+                }
+                ["TRY", chars] => dictionary.try_chars = chars.chars().collect(),
+                ["REP", from, to, ..] => dictionary
+                    .replacements
+                    .push((from.replace('_', " "), to.replace('_', " "))),
+                ["FORBIDDENWORD", flag] => dictionary.forbidden_flag = parse_flag(flag),
+                ["NEEDAFFIX", flag] => dictionary.need_affix_flag = parse_flag(flag),
+                ["NOSUGGEST", flag] => dictionary.no_suggest_flag = parse_flag(flag),
+                [kind @ ("PFX" | "SFX"), flag, cross_product, _count] => {
+                    let flag = parse_flag(flag)
+                        .with_context(|| format!("invalid flag on line {}", row + 1))?;
+                    cross_products.insert((*kind == "PFX", flag), *cross_product == "Y");
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, condition, ..] => {
+                    let is_prefix = *kind == "PFX";
+                    let flag = parse_flag(flag)
+                        .with_context(|| format!("invalid flag on line {}", row + 1))?;
+                    // Continuation classes after the slash are not supported.
+                    let add = add.split('/').next().unwrap_or_default();
+                    let add = if add == "0" { "" } else { add };
+                    let affix = Affix {
+                        flag,
+                        cross_product: cross_products
+                            .get(&(is_prefix, flag))
+                            .copied()
+                            .unwrap_or(false),
+                        strip: if *strip == "0" {
+                            String::new()
+                        } else {
+                            strip.to_string()
+                        },
+                        condition: Condition::parse(condition),
+                    };
+                    let add_len = add.chars().count();
+                    let (affixes, max_len) = if is_prefix {
+                        (&mut dictionary.prefixes, &mut dictionary.max_prefix_len)
+                    } else {
+                        (&mut dictionary.suffixes, &mut dictionary.max_suffix_len)
+                    };
+                    *max_len = (*max_len).max(add_len);
+                    affixes.entry(add.to_string()).or_default().push(affix);
+                }
+                _ => {}
+            }
+        }
+
+        let mut lines = dic.lines();
+        // The first line holds the approximate number of words.
+        lines.next().context("empty .dic file")?;
+        for line in lines {
+            // Morphological fields follow the word after whitespace.
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+            dictionary
+                .words
+                .entry(word.to_string())
+                .or_default()
+                .extend(parse_flags(flags, flag_format));
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Whether `word` is spelled correctly. Words are also accepted when capitalized, as at the
+    /// start of a sentence, or in all caps.
+    pub fn check(&self, word: &str) -> bool {
+        if self.check_exact(word) {
+            return true;
+        }
+        let lowercase = word.to_lowercase();
+        if lowercase == word {
+            return false;
+        }
+        let is_all_caps = word.chars().all(|c| !c.is_lowercase());
+        if (is_all_caps || capitalize(&lowercase) == word) && self.check_exact(&lowercase) {
+            return true;
+        }
+        is_all_caps && self.check_exact(&capitalize(&lowercase))
+    }
+
+    /// Suggests correctly spelled words that are a single edit, or a replacement from the
+    /// dictionary's table of common mistakes, away from `word`.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        let mut push = |candidate: String| {
+            if candidate != word
+                && !suggestions.contains(&candidate)
+                && self.can_suggest(&candidate)
+            {
+                suggestions.push(candidate);
+            }
+        };
+
+        for (from, to) in &self.replacements {
+            for (ix, _) in word.match_indices(from.as_str()) {
+                push(format!("{}{to}{}", &word[..ix], &word[ix + from.len()..]));
+            }
+        }
+
+        let chars = word.chars().collect::<Vec<_>>();
+        let try_chars = if self.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_chars.clone()
+        };
+        for ix in 0..chars.len().saturating_sub(1) {
+            let mut swapped = chars.clone();
+            swapped.swap(ix, ix + 1);
+            push(swapped.into_iter().collect());
+        }
+        for ix in 0..chars.len() {
+            for c in &try_chars {
+                let mut replaced = chars.clone();
+                replaced[ix] = *c;
+                push(replaced.into_iter().collect());
+            }
+        }
+        for ix in 0..chars.len() {
+            let mut removed = chars.clone();
+            removed.remove(ix);
+            push(removed.into_iter().collect());
+        }
+        for ix in 0..=chars.len() {
+            for c in &try_chars {
+                let mut inserted = chars.clone();
+                inserted.insert(ix, *c);
+                push(inserted.into_iter().collect());
+            }
+        }
+        for ix in 1..chars.len() {
+            let first = chars[..ix].iter().collect::<String>();
+            let second = chars[ix..].iter().collect::<String>();
+            if self.check(&first) && self.check(&second) {
+                push(format!("{first} {second}"));
+            }
+        }
+
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+
+    fn can_suggest(&self, candidate: &str) -> bool {
+        candidate.split(' ').all(|word| self.check(word))
+            && self
+                .words
+                .get(candidate)
+                .is_none_or(|flags| !has_flag(flags, self.no_suggest_flag))
+    }
+
+    fn check_exact(&self, word: &str) -> bool {
+        if let Some(flags) = self.words.get(word) {
+            if has_flag(flags, self.forbidden_flag) {
+                return false;
+            }
+            if !has_flag(flags, self.need_affix_flag) {
+                return true;
+            }
+        }
+        self.check_suffixed(word, None) || self.check_prefixed(word)
+    }
+
+    /// Whether `word` is a stem with one of its suffixes, and the given prefix if any.
+    fn check_suffixed(&self, word: &str, prefix: Option<&Affix>) -> bool {
+        let boundaries = word
+            .char_indices()
+            .map(|(ix, _)| ix)
+            .skip(1)
+            .chain([word.len()])
+            .collect::<Vec<_>>();
+        boundaries
+            .into_iter()
+            .rev()
+            .take(self.max_suffix_len + 1)
+            .any(|ix| {
+                let Some(affixes) = self.suffixes.get(&word[ix..]) else {
+                    return false;
+                };
+                affixes.iter().any(|affix| {
+                    if prefix.is_some() && !affix.cross_product {
+                        return false;
+                    }
+                    let stem = format!("{}{}", &word[..ix], affix.strip);
+                    affix.condition.matches_end(&stem)
+                        && self.words.get(&stem).is_some_and(|flags| {
+                            flags.contains(&affix.flag)
+                                && prefix.is_none_or(|prefix| flags.contains(&prefix.flag))
+                                && !has_flag(flags, self.forbidden_flag)
+                        })
+                })
+            })
+    }
+
+    /// Whether `word` is a stem with one of its prefixes, and possibly also a suffix.
+    fn check_prefixed(&self, word: &str) -> bool {
+        word.char_indices()
+            .map(|(ix, _)| ix)
+            .chain([word.len()])
+            .take(self.max_prefix_len + 1)
+            .filter(|ix| *ix < word.len())
+            .any(|ix| {
+                let Some(affixes) = self.prefixes.get(&word[..ix]) else {
+                    return false;
+                };
+                affixes.iter().any(|affix| {
+                    let stem = format!("{}{}", affix.strip, &word[ix..]);
+                    if !affix.condition.matches_start(&stem) {
+                        return false;
+                    }
+                    let has_prefix = self.words.get(&stem).is_some_and(|flags| {
+                        flags.contains(&affix.flag) && !has_flag(flags, self.forbidden_flag)
+                    });
+                    has_prefix || (affix.cross_product && self.check_suffixed(&stem, Some(affix)))
+                })
+            })
+    }
+}
+
+fn parse_flags(flags: &str, format: FlagFormat) -> Vec<Flag> {
+    match format {
+        FlagFormat::Char => flags.chars().map(|c| c as Flag).collect(),
+        FlagFormat::Long => flags
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().fold(0, |flag, c| (flag << 16) | *c as Flag))
+            .collect(),
+        FlagFormat::Numeric => flags
+            .split(',')
+            .filter_map(|flag| flag.trim().parse().ok())
+            .collect(),
+    }
+}
+
+fn has_flag(flags: &[Flag], flag: Option<Flag>) -> bool {
+    flag.is_some_and(|flag| flags.contains(&flag))
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn test_dictionary() -> Dictionary {
+        let aff = indoc! {"
+            SET UTF-8
+            TRY esianrtolcdugmphbyfvkwz
+            FORBIDDENWORD !
+
+            REP 1
+            REP f ph
+
+            PFX U Y 1
+            PFX U 0 un .
+
+            SFX S Y 2
+            SFX S 0 s [^y]
+            SFX S y ies [^aeiou]y
+
+            SFX D Y 2
+            SFX D 0 ed [^e]
+            SFX D 0 d e
+        "};
+        let dic = indoc! {"
+            6
+            check/SDU
+            city/S
+            phone/SD
+            Paris
+            irregardless/!
+            lock/U
+        "};
+        Dictionary::parse(aff, dic).unwrap()
+    }
+
+    #[test]
+    fn test_check() {
+        let dictionary = test_dictionary();
+        for word in [
+            "check",
+            "checks",
+            "checked",
+            "unchecked",
+            "unchecks",
+            "cities",
+            "phoned",
+            "Paris",
+            "Check",
+            "CHECKS",
+            "PARIS",
+            "unlock",
+        ] {
+            assert!(dictionary.check(word), "{word} should be spelled correctly");
+        }
+        for word in [
+            "chek",
+            "citys",
+            "paris",
+            "irregardless",
+            "unphone",
+            "unlocks",
+            "cHECK",
+        ] {
+            assert!(!dictionary.check(word), "{word} should be misspelled");
+        }
+    }
+
+    #[test]
+    fn test_suggest() {
+        let dictionary = test_dictionary();
+        assert_eq!(dictionary.suggest("fones"), ["phones"]);
+        assert_eq!(dictionary.suggest("chekc"), ["check"]);
+        assert!(dictionary.suggest("cheks").contains(&"checks".to_string()));
+        assert_eq!(dictionary.suggest("checkcity"), ["check city"]);
+        assert!(dictionary.suggest("irregardles").is_empty());
+    }
+}
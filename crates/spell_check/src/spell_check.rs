@@ -0,0 +1,655 @@
+mod dictionary;
+mod spell_check_settings;
+mod spell_checker;
+mod words;
+
+use std::{any::Any, ops::Range, path::Path, rc::Rc, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use collections::HashMap;
+use editor::{Addon, CodeActionProvider, Editor, EditorEvent, ExcerptId, HighlightKey};
+use fs::Fs;
+use gpui::{
+    App, AppContext as _, Context, Entity, HighlightStyle, Subscription, Task, UnderlineStyle,
+    WeakEntity, Window, px,
+};
+use language::{Anchor, Buffer, BufferSnapshot, LanguageServerId, Point, ToOffset as _};
+use parking_lot::Mutex;
+use project::{CodeAction, LspAction, ProjectTransaction};
+use serde::{Deserialize, Serialize};
+use settings::SettingsStore;
+use theme::ActiveTheme as _;
+
+pub use crate::dictionary::Dictionary;
+pub use crate::spell_check_settings::SpellCheckerSettings;
+pub use crate::spell_checker::{DictionaryScope, SpellChecker, WordChecker};
+pub use crate::words::words;
+
+/// How long to wait after an edit before checking the buffer again.
+const CHECK_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How many lines above and below the visible ones are checked as well, so that scrolling a
+/// little doesn't reveal unchecked text.
+const CHECK_MARGIN_LINES: u32 = 50;
+
+/// At most this many lines of prose have their misspellings remembered per editor.
+const MAX_CACHED_LINES: usize = 10_000;
+
+const CODE_ACTION_PROVIDER_ID: &str = "spell_check";
+
+/// The server id of the code actions offered for misspellings, which don't come from a language
+/// server.
+pub const SPELL_CHECK_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX - 2);
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+    SpellChecker::init(fs, cx);
+    cx.observe_new(|editor: &mut Editor, window, cx| {
+        if let Some(window) = window {
+            register_editor(editor, window, cx);
+        }
+    })
+    .detach();
+}
+
+/// Finds the misspelled words in the parts of `range` that hold prose: comments and strings in
+/// code, and the text of markup languages like Markdown.
+///
+/// The misspellings are looked up in `cache` by the text of each line of prose, so only the lines
+/// that aren't in it yet are checked, and then added to it.
+pub fn find_misspellings(
+    snapshot: &BufferSnapshot,
+    range: Range<usize>,
+    checker: &WordChecker,
+    cache: &mut HashMap<String, Vec<Range<usize>>>,
+) -> Vec<Range<usize>> {
+    let mut misspellings = Vec::new();
+    for prose_range in snapshot.prose_ranges(range) {
+        let text = snapshot
+            .text_for_range(prose_range.clone())
+            .collect::<String>();
+        let mut line_start = prose_range.start;
+        for line in text.split('\n') {
+            if !cache.contains_key(line) {
+                let line_misspellings = words(line)
+                    .into_iter()
+                    .filter(|word| !checker.check(&line[word.clone()]))
+                    .collect();
+                cache.insert(line.to_string(), line_misspellings);
+            }
+            misspellings.extend(
+                cache[line]
+                    .iter()
+                    .map(|word| line_start + word.start..line_start + word.end),
+            );
+            line_start += line.len() + 1;
+        }
+    }
+    misspellings
+}
+
+struct SpellCheckAddon {
+    /// The misspellings found around the visible part of the buffer.
+    misspellings: Vec<Range<Anchor>>,
+    /// The misspellings in each line of prose checked so far, which stay valid until the
+    /// dictionaries or settings change.
+    cache: Arc<Mutex<HashMap<String, Vec<Range<usize>>>>>,
+    check_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl Addon for SpellCheckAddon {
+    fn to_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn to_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+fn register_editor(editor: &mut Editor, window: &mut Window, cx: &mut Context<Editor>) {
+    if !editor.mode().is_full() || editor.project().is_none() {
+        return;
+    }
+    let Some(spell_checker) = SpellChecker::global(cx) else {
+        return;
+    };
+
+    let subscriptions = vec![
+        cx.subscribe(&cx.entity(), |editor, _, event, cx| match event {
+            EditorEvent::BufferEdited
+            | EditorEvent::Reparsed(_)
+            | EditorEvent::ScrollPositionChanged { .. } => check_buffer(editor, true, cx),
+            _ => {}
+        }),
+        cx.observe(&spell_checker, |editor, _, cx| {
+            clear_cache(editor);
+            check_buffer(editor, false, cx)
+        }),
+        cx.observe_global::<SettingsStore>(|editor, cx| {
+            clear_cache(editor);
+            check_buffer(editor, false, cx)
+        }),
+    ];
+    editor.register_addon(SpellCheckAddon {
+        misspellings: Vec::new(),
+        cache: Arc::default(),
+        check_task: Task::ready(()),
+        _subscriptions: subscriptions,
+    });
+    editor.add_code_action_provider(
+        Rc::new(SpellCheckCodeActionProvider {
+            editor: cx.entity().downgrade(),
+        }),
+        window,
+        cx,
+    );
+    check_buffer(editor, false, cx);
+}
+
+/// Forgets the misspellings found so far, as they may have changed along with the dictionaries.
+fn clear_cache(editor: &mut Editor) {
+    // A new cache is used rather than clearing the current one, so that a check that's still
+    // running can't fill it with outdated results.
+    if let Some(addon) = editor.addon_mut::<SpellCheckAddon>() {
+        addon.cache = Arc::default();
+    }
+}
+
+/// Checks the spelling around the visible part of the editor's buffer when spell checking is
+/// enabled for its language, replacing the misspellings found before.
+fn check_buffer(editor: &mut Editor, debounce: bool, cx: &mut Context<Editor>) {
+    let Some(spell_checker) = SpellChecker::global(cx) else {
+        return;
+    };
+    let buffer = editor.buffer().read(cx).as_singleton();
+    let snapshot = buffer
+        .as_ref()
+        .map(|buffer| buffer.read(cx).snapshot())
+        .filter(|snapshot| snapshot.settings_at(0, cx).spell_check);
+    let Some((buffer, snapshot)) = buffer.zip(snapshot) else {
+        set_misspellings(editor, Vec::new(), cx);
+        if let Some(addon) = editor.addon_mut::<SpellCheckAddon>() {
+            addon.check_task = Task::ready(());
+        }
+        return;
+    };
+
+    let Some(cache) = editor
+        .addon::<SpellCheckAddon>()
+        .map(|addon| addon.cache.clone())
+    else {
+        return;
+    };
+    let range = range_to_check(editor, &snapshot, cx);
+    let worktree_path = worktree_path(editor, buffer.read(cx), cx);
+    let checker = spell_checker.update(cx, |spell_checker, cx| {
+        spell_checker.word_checker(worktree_path, cx)
+    });
+    let task = cx.spawn(async move |editor, cx| {
+        if debounce {
+            cx.background_executor().timer(CHECK_DEBOUNCE).await;
+        }
+        let misspellings = cx
+            .background_spawn(async move {
+                let mut cache = cache.lock();
+                if cache.len() > MAX_CACHED_LINES {
+                    cache.clear();
+                }
+                find_misspellings(&snapshot, range, &checker, &mut cache)
+                    .into_iter()
+                    .map(|range| {
+                        snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end)
+                    })
+                    .collect()
+            })
+            .await;
+        editor
+            .update(cx, |editor, cx| set_misspellings(editor, misspellings, cx))
+            .ok();
+    });
+    if let Some(addon) = editor.addon_mut::<SpellCheckAddon>() {
+        addon.check_task = task;
+    }
+}
+
+/// The lines of the buffer that are shown in the editor, along with [`CHECK_MARGIN_LINES`] lines
+/// around them.
+fn range_to_check(
+    editor: &mut Editor,
+    snapshot: &BufferSnapshot,
+    cx: &mut Context<Editor>,
+) -> Range<usize> {
+    // Before the editor is first laid out, no lines are visible yet, and its top lines are the
+    // ones about to be shown.
+    let visible_rows = editor
+        .visible_excerpts(false, cx)
+        .into_values()
+        .next()
+        .map(|(_, _, range)| {
+            snapshot.offset_to_point(range.start).row..snapshot.offset_to_point(range.end).row
+        })
+        .unwrap_or(0..0);
+    let start_row = visible_rows.start.saturating_sub(CHECK_MARGIN_LINES);
+    let end_row = visible_rows
+        .end
+        .saturating_add(CHECK_MARGIN_LINES)
+        .min(snapshot.max_point().row);
+    Point::new(start_row, 0).to_offset(snapshot)
+        ..Point::new(end_row, snapshot.line_len(end_row)).to_offset(snapshot)
+}
+
+fn set_misspellings(
+    editor: &mut Editor,
+    misspellings: Vec<Range<Anchor>>,
+    cx: &mut Context<Editor>,
+) {
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let ranges = misspellings
+        .iter()
+        .filter_map(|range| {
+            Some(
+                snapshot.as_singleton_anchor(range.start)?
+                    ..snapshot.as_singleton_anchor(range.end)?,
+            )
+        })
+        .collect::<Vec<_>>();
+    let Some(addon) = editor.addon_mut::<SpellCheckAddon>() else {
+        return;
+    };
+    if addon.misspellings.is_empty() && misspellings.is_empty() {
+        return;
+    }
+    addon.misspellings = misspellings;
+
+    if ranges.is_empty() {
+        editor.clear_highlights(HighlightKey::SpellCheck, cx);
+    } else {
+        let style = HighlightStyle {
+            underline: Some(UnderlineStyle {
+                thickness: px(1.),
+                color: Some(cx.theme().status().info),
+                wavy: true,
+            }),
+            ..Default::default()
+        };
+        editor.highlight_text(HighlightKey::SpellCheck, ranges, style, cx);
+    }
+}
+
+/// The absolute path of the worktree containing the buffer, if it's in a local project.
+fn worktree_path(editor: &Editor, buffer: &Buffer, cx: &App) -> Option<Arc<Path>> {
+    let project = editor.project()?.read(cx);
+    if !project.is_local() {
+        return None;
+    }
+    let worktree_id = buffer.file()?.worktree_id(cx);
+    Some(
+        project
+            .worktree_for_id(worktree_id, cx)?
+            .read(cx)
+            .abs_path(),
+    )
+}
+
+/// What a spell checking code action does, which is stored in the action's data.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum SpellCheckAction {
+    Replace {
+        replacement: String,
+    },
+    AddToDictionary {
+        word: String,
+        scope: DictionaryScope,
+    },
+}
+
+/// Offers suggestions for the misspelled words in the requested range, and to add them to
+/// the workspace or user dictionary.
+struct SpellCheckCodeActionProvider {
+    editor: WeakEntity<Editor>,
+}
+
+impl CodeActionProvider for SpellCheckCodeActionProvider {
+    fn id(&self) -> Arc<str> {
+        CODE_ACTION_PROVIDER_ID.into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<Anchor>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        let Some(editor) = self.editor.upgrade() else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let Some(spell_checker) = SpellChecker::global(cx) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+        let editor = editor.read(cx);
+        let snapshot = buffer.read(cx).snapshot();
+        let (start, end) = (
+            range.start.to_offset(&snapshot),
+            range.end.to_offset(&snapshot),
+        );
+        let misspellings = editor
+            .addon::<SpellCheckAddon>()
+            .into_iter()
+            .flat_map(|addon| &addon.misspellings)
+            .filter(|misspelling| {
+                misspelling.start.to_offset(&snapshot) <= end
+                    && misspelling.end.to_offset(&snapshot) >= start
+            })
+            .map(|misspelling| {
+                let word = snapshot
+                    .text_for_range(misspelling.clone())
+                    .collect::<String>();
+                (misspelling.clone(), word)
+            })
+            .collect::<Vec<_>>();
+        if misspellings.is_empty() {
+            return Task::ready(Ok(Vec::new()));
+        }
+
+        let worktree_path = worktree_path(editor, buffer.read(cx), cx);
+        let has_workspace = worktree_path.is_some();
+        let checker = spell_checker.update(cx, |spell_checker, cx| {
+            spell_checker.word_checker(worktree_path, cx)
+        });
+        cx.background_spawn(async move {
+            let mut actions = Vec::new();
+            for (range, word) in misspellings {
+                for replacement in checker.suggest(&word) {
+                    actions.push(code_action(
+                        format!("Change to '{replacement}'"),
+                        range.clone(),
+                        SpellCheckAction::Replace { replacement },
+                    ));
+                }
+                if has_workspace {
+                    actions.push(code_action(
+                        format!("Add '{word}' to workspace dictionary"),
+                        range.clone(),
+                        SpellCheckAction::AddToDictionary {
+                            word: word.clone(),
+                            scope: DictionaryScope::Workspace,
+                        },
+                    ));
+                }
+                actions.push(code_action(
+                    format!("Add '{word}' to user dictionary"),
+                    range,
+                    SpellCheckAction::AddToDictionary {
+                        word,
+                        scope: DictionaryScope::User,
+                    },
+                ));
+            }
+            Ok(actions)
+        })
+    }
+
+    fn apply_code_action(
+        &self,
+        buffer: Entity<Buffer>,
+        action: CodeAction,
+        _excerpt_id: ExcerptId,
+        _push_to_history: bool,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let LspAction::Action(lsp_action) = &action.lsp_action else {
+            return Task::ready(Ok(ProjectTransaction::default()));
+        };
+        let Some(spell_check_action) = lsp_action
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<SpellCheckAction>(data).ok())
+        else {
+            return Task::ready(Ok(ProjectTransaction::default()));
+        };
+
+        match spell_check_action {
+            SpellCheckAction::Replace { replacement } => {
+                buffer.update(cx, |buffer, cx| {
+                    buffer.edit([(action.range, replacement)], None, cx);
+                });
+                Task::ready(Ok(ProjectTransaction::default()))
+            }
+            SpellCheckAction::AddToDictionary { word, scope } => {
+                let Some(spell_checker) = SpellChecker::global(cx) else {
+                    return Task::ready(Ok(ProjectTransaction::default()));
+                };
+                let worktree_path = self
+                    .editor
+                    .upgrade()
+                    .and_then(|editor| worktree_path(editor.read(cx), buffer.read(cx), cx));
+                let add_word = spell_checker.update(cx, |spell_checker, cx| {
+                    spell_checker.add_word(word, scope, worktree_path, cx)
+                });
+                cx.background_spawn(async move {
+                    add_word.await?;
+                    Ok(ProjectTransaction::default())
+                })
+            }
+        }
+    }
+}
+
+fn code_action(title: String, range: Range<Anchor>, action: SpellCheckAction) -> CodeAction {
+    CodeAction {
+        server_id: SPELL_CHECK_SERVER_ID,
+        range,
+        lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+            title,
+            kind: Some(lsp::CodeActionKind::QUICKFIX),
+            data: serde_json::to_value(action).ok(),
+            ..Default::default()
+        })),
+        resolved: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::{TestAppContext, VisualTestContext};
+    use indoc::indoc;
+    use project::Project;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    const AFF: &str = "SET UTF-8\nTRY esianrtolcdugmphbyfvkwz\n";
+    const DIC: &str = indoc! {"
+        6
+        check
+        spelling
+        the
+        of
+        this
+        line
+    "};
+
+    fn word_range(text: &str, word: &str) -> Range<usize> {
+        let start = text.find(word).unwrap();
+        start..start + word.len()
+    }
+
+    #[gpui::test]
+    fn test_find_misspellings_rechecks_edited_lines(cx: &mut TestAppContext) {
+        let checker = WordChecker::new(vec![Dictionary::parse(AFF, DIC).unwrap()]);
+        let text = "check this line\ncheck the speling\ncheck ths line";
+        let buffer = cx.new(|cx| Buffer::local(text, cx));
+        let mut cache = HashMap::default();
+
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let misspellings = find_misspellings(&snapshot, 0..text.len(), &checker, &mut cache);
+        assert_eq!(
+            misspellings,
+            [word_range(text, "speling"), word_range(text, "ths")]
+        );
+        assert_eq!(cache.len(), 3);
+
+        buffer.update(cx, |buffer, cx| {
+            let start = text.find("check the").unwrap();
+            buffer.edit([(start..start, "now ")], None, cx);
+        });
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let text = snapshot.text();
+        // A line that's already cached isn't checked again, so this bogus result is returned.
+        cache.insert("check this line".to_string(), vec![0..5]);
+        let misspellings = find_misspellings(&snapshot, 0..text.len(), &checker, &mut cache);
+        assert_eq!(
+            misspellings,
+            [
+                0..5,
+                word_range(&text, "now"),
+                word_range(&text, "speling"),
+                word_range(&text, "ths")
+            ]
+        );
+        assert_eq!(
+            cache.len(),
+            4,
+            "Only the edited line should have been checked"
+        );
+        assert_eq!(cache["now check the speling"], [0..3, 14..21]);
+    }
+
+    #[gpui::test]
+    async fn test_code_actions(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dictionaries"),
+            json!({ "en_US.aff": AFF, "en_US.dic": DIC }),
+        )
+        .await;
+        fs.insert_tree(
+            path!("/project"),
+            json!({ "notes.txt": "check the speling of this line" }),
+        )
+        .await;
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.project.all_languages.defaults.spell_check = Some(true);
+                    settings
+                        .spell_checker
+                        .get_or_insert_default()
+                        .dictionary_directory = Some(path!("/dictionaries").to_string());
+                });
+            });
+            init(fs.clone(), cx);
+        });
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/notes.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let (editor, cx) = cx.add_window_view(|window, cx| {
+            Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx)
+        });
+        cx.run_until_parked();
+
+        let misspelled_words = |cx: &mut VisualTestContext| {
+            editor.read_with(cx, |editor, cx| {
+                let snapshot = buffer.read(cx).snapshot();
+                editor
+                    .addon::<SpellCheckAddon>()
+                    .unwrap()
+                    .misspellings
+                    .iter()
+                    .map(|range| snapshot.text_for_range(range.clone()).collect::<String>())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(misspelled_words(cx), ["speling"]);
+
+        let provider = SpellCheckCodeActionProvider {
+            editor: editor.downgrade(),
+        };
+        let (start, end) = buffer.read_with(cx, |buffer, _| {
+            let offset = buffer.text().find("speling").unwrap();
+            (
+                buffer.anchor_before(offset),
+                buffer.anchor_before(offset + 1),
+            )
+        });
+        let actions = cx
+            .update(|window, cx| provider.code_actions(&buffer, start..end, window, cx))
+            .await
+            .unwrap();
+        let titles = actions
+            .iter()
+            .map(|action| action.lsp_action.title().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            [
+                "Change to 'spelling'",
+                "Add 'speling' to workspace dictionary",
+                "Add 'speling' to user dictionary",
+            ]
+        );
+        assert!(
+            actions
+                .iter()
+                .all(|action| action.server_id == SPELL_CHECK_SERVER_ID)
+        );
+
+        let add_to_workspace_dictionary = actions[1].clone();
+        cx.update(|window, cx| {
+            provider.apply_code_action(
+                buffer.clone(),
+                add_to_workspace_dictionary,
+                ExcerptId::min(),
+                true,
+                window,
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(path!("/project/.zed/dictionary.txt").as_ref())
+                .await
+                .unwrap(),
+            "speling\n"
+        );
+        assert!(
+            misspelled_words(cx).is_empty(),
+            "The added word should no longer be underlined"
+        );
+
+        let fix_spelling = actions[0].clone();
+        cx.update(|window, cx| {
+            provider.apply_code_action(
+                buffer.clone(),
+                fix_spelling,
+                ExcerptId::min(),
+                true,
+                window,
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+        buffer.read_with(cx, |buffer, _| {
+            assert_eq!(buffer.text(), "check the spelling of this line")
+        });
+    }
+}
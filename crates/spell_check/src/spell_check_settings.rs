@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use settings::{RegisterSetting, Settings};
+
+/// The settings for spell checking.
+#[derive(Clone, Debug, Default, RegisterSetting)]
+pub struct SpellCheckerSettings {
+    /// The Hunspell dictionaries to check words against, by name.
+    ///
+    /// Default: ["en_US"]
+    pub dictionaries: Vec<String>,
+    /// The directory to load dictionaries from, instead of the `dictionaries`
+    /// directory in Zed's config directory.
+    ///
+    /// Default: null
+    pub dictionary_directory: Option<PathBuf>,
+}
+
+impl SpellCheckerSettings {
+    pub fn dictionary_directory(&self) -> PathBuf {
+        self.dictionary_directory
+            .clone()
+            .unwrap_or_else(|| paths::dictionaries_dir().clone())
+    }
+}
+
+impl Settings for SpellCheckerSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let spell_checker = content.spell_checker.clone().unwrap();
+        Self {
+            dictionaries: spell_checker.dictionaries.unwrap(),
+            dictionary_directory: spell_checker.dictionary_directory.map(
+                |directory| match directory.strip_prefix("~/") {
+                    Some(relative) => util::paths::home_dir().join(relative),
+                    None => PathBuf::from(directory),
+                },
+            ),
+        }
+    }
+}
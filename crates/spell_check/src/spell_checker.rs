@@ -0,0 +1,302 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context as _, Result};
+use collections::{HashMap, HashSet};
+use fs::Fs;
+use futures::StreamExt as _;
+use gpui::{App, AppContext as _, Context, Entity, Global, Task};
+use serde::{Deserialize, Serialize};
+use settings::{Settings as _, SettingsStore};
+
+use crate::{Dictionary, SpellCheckerSettings, dictionary::MAX_SUGGESTIONS};
+
+/// Where a word added to a dictionary is saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DictionaryScope {
+    /// The `.zed/dictionary.txt` file of the worktree the word was found in.
+    Workspace,
+    /// The `dictionary.txt` file in Zed's config directory.
+    User,
+}
+
+/// The dictionaries and lists of custom words that spell checking uses, shared by all editors.
+pub struct SpellChecker {
+    fs: Arc<dyn Fs>,
+    dictionaries: Arc<Vec<Dictionary>>,
+    /// The dictionary names and directory that `dictionaries` were loaded for.
+    loaded_settings: Option<(Vec<String>, PathBuf)>,
+    user_words: Arc<HashSet<String>>,
+    /// Words listed in the `.zed/dictionary.txt` file of each worktree, by its absolute path.
+    workspace_words: HashMap<Arc<Path>, WorkspaceWords>,
+    /// Held while a word is appended to a dictionary file, so that words added in quick
+    /// succession don't overwrite each other.
+    dictionary_write_lock: Arc<futures::lock::Mutex<()>>,
+    _load_dictionaries: Task<()>,
+    _watch_user_dictionary: Task<()>,
+}
+
+struct WorkspaceWords {
+    words: Arc<HashSet<String>>,
+    _watch: Task<()>,
+}
+
+struct GlobalSpellChecker(Entity<SpellChecker>);
+
+impl Global for GlobalSpellChecker {}
+
+impl SpellChecker {
+    pub(crate) fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+        let spell_checker = cx.new(|cx| {
+            let mut this = Self::new(fs, cx);
+            this.load_dictionaries(cx);
+            this
+        });
+        cx.observe_global::<SettingsStore>({
+            let spell_checker = spell_checker.downgrade();
+            move |cx| {
+                spell_checker
+                    .update(cx, |spell_checker, cx| spell_checker.load_dictionaries(cx))
+                    .ok();
+            }
+        })
+        .detach();
+        cx.set_global(GlobalSpellChecker(spell_checker));
+    }
+
+    pub fn global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalSpellChecker>()
+            .map(|spell_checker| spell_checker.0.clone())
+    }
+
+    fn new(fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
+        let watch_user_dictionary = Self::watch_words(
+            fs.clone(),
+            paths::user_dictionary_file().clone(),
+            cx,
+            |this, words| this.user_words = words,
+        );
+        Self {
+            fs,
+            dictionaries: Arc::default(),
+            loaded_settings: None,
+            user_words: Arc::default(),
+            workspace_words: HashMap::default(),
+            dictionary_write_lock: Arc::default(),
+            _load_dictionaries: Task::ready(()),
+            _watch_user_dictionary: watch_user_dictionary,
+        }
+    }
+
+    /// (Re)loads the dictionaries when the settings naming them have changed.
+    fn load_dictionaries(&mut self, cx: &mut Context<Self>) {
+        let settings = SpellCheckerSettings::get_global(cx);
+        let loaded_settings = (
+            settings.dictionaries.clone(),
+            settings.dictionary_directory(),
+        );
+        if self.loaded_settings.as_ref() == Some(&loaded_settings) {
+            return;
+        }
+        self.loaded_settings = Some(loaded_settings.clone());
+
+        let fs = self.fs.clone();
+        let (names, directory) = loaded_settings;
+        self._load_dictionaries = cx.spawn(async move |this, cx| {
+            let mut dictionaries = Vec::new();
+            for name in names {
+                let dictionary = async {
+                    let aff = fs.load(&directory.join(format!("{name}.aff"))).await?;
+                    let dic = fs.load(&directory.join(format!("{name}.dic"))).await?;
+                    cx.background_spawn(async move { Dictionary::parse(&aff, &dic) })
+                        .await
+                }
+                .await
+                .with_context(|| {
+                    format!("loading dictionary {name:?} from {}", directory.display())
+                });
+                match dictionary {
+                    Ok(dictionary) => dictionaries.push(dictionary),
+                    Err(error) => log::error!("{error:#}"),
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.dictionaries = Arc::new(dictionaries);
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// Returns a snapshot of the dictionaries and custom words that apply to files in the
+    /// worktree at `worktree_path`, watching its dictionary file from now on.
+    pub fn word_checker(
+        &mut self,
+        worktree_path: Option<Arc<Path>>,
+        cx: &mut Context<Self>,
+    ) -> WordChecker {
+        let workspace_words = worktree_path.map(|worktree_path| {
+            if !self.workspace_words.contains_key(&worktree_path) {
+                let dictionary_path =
+                    worktree_path.join(paths::local_dictionary_file_relative_path().as_std_path());
+                let key = worktree_path.clone();
+                let watch =
+                    Self::watch_words(self.fs.clone(), dictionary_path, cx, move |this, words| {
+                        if let Some(workspace_words) = this.workspace_words.get_mut(&key) {
+                            workspace_words.words = words;
+                        }
+                    });
+                self.workspace_words.insert(
+                    worktree_path.clone(),
+                    WorkspaceWords {
+                        words: Arc::default(),
+                        _watch: watch,
+                    },
+                );
+            }
+            self.workspace_words[&worktree_path].words.clone()
+        });
+        WordChecker {
+            dictionaries: self.dictionaries.clone(),
+            user_words: self.user_words.clone(),
+            workspace_words,
+        }
+    }
+
+    /// Appends `word` to the user's dictionary file, or to the `.zed/dictionary.txt` file
+    /// of the worktree at `worktree_path`.
+    pub fn add_word(
+        &mut self,
+        word: String,
+        scope: DictionaryScope,
+        worktree_path: Option<Arc<Path>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let path = match scope {
+            DictionaryScope::User => {
+                Arc::make_mut(&mut self.user_words).insert(word.to_lowercase());
+                paths::user_dictionary_file().clone()
+            }
+            DictionaryScope::Workspace => {
+                let Some(worktree_path) = worktree_path else {
+                    return Task::ready(Err(anyhow::anyhow!(
+                        "cannot add a word to the dictionary of a file outside of a project"
+                    )));
+                };
+                if let Some(workspace_words) = self.workspace_words.get_mut(&worktree_path) {
+                    Arc::make_mut(&mut workspace_words.words).insert(word.to_lowercase());
+                }
+                worktree_path.join(paths::local_dictionary_file_relative_path().as_std_path())
+            }
+        };
+        cx.notify();
+
+        let fs = self.fs.clone();
+        let write_lock = self.dictionary_write_lock.clone();
+        cx.background_spawn(async move {
+            let _write_guard = write_lock.lock().await;
+            let mut contents = fs.load(&path).await.unwrap_or_default();
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                contents.push('\n');
+            }
+            contents.push_str(&word);
+            contents.push('\n');
+            if let Some(parent) = path.parent() {
+                fs.create_dir(parent).await?;
+            }
+            fs.atomic_write(path, contents).await
+        })
+    }
+
+    /// Keeps the words listed in the file at `path` up to date as it changes.
+    fn watch_words(
+        fs: Arc<dyn Fs>,
+        path: PathBuf,
+        cx: &mut Context<Self>,
+        set_words: impl Fn(&mut Self, Arc<HashSet<String>>) + 'static,
+    ) -> Task<()> {
+        let (mut contents, watcher) =
+            settings::watch_config_file(cx.background_executor(), fs, path);
+        cx.spawn(async move |this, cx| {
+            let _watcher = watcher;
+            while let Some(contents) = contents.next().await {
+                let words = parse_words(&contents);
+                if this
+                    .update(cx, |this, cx| {
+                        set_words(this, Arc::new(words));
+                        cx.notify();
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+/// Parses a list of custom words, one per line. Words are compared case-insensitively.
+fn parse_words(contents: &str) -> HashSet<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty() && !word.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// A snapshot of the dictionaries and custom words that apply to a file, which can be used on a
+/// background thread.
+#[derive(Clone)]
+pub struct WordChecker {
+    dictionaries: Arc<Vec<Dictionary>>,
+    user_words: Arc<HashSet<String>>,
+    workspace_words: Option<Arc<HashSet<String>>>,
+}
+
+impl WordChecker {
+    #[cfg(test)]
+    pub(crate) fn new(dictionaries: Vec<Dictionary>) -> Self {
+        Self {
+            dictionaries: Arc::new(dictionaries),
+            user_words: Arc::default(),
+            workspace_words: None,
+        }
+    }
+
+    /// Whether `word` is spelled correctly. Every word is accepted when no dictionaries are
+    /// loaded, so that a missing dictionary doesn't flag every word in a file.
+    pub fn check(&self, word: &str) -> bool {
+        if self.dictionaries.is_empty() {
+            return true;
+        }
+        let lowercase = word.to_lowercase();
+        self.user_words.contains(&lowercase)
+            || self
+                .workspace_words
+                .as_ref()
+                .is_some_and(|words| words.contains(&lowercase))
+            || self
+                .dictionaries
+                .iter()
+                .any(|dictionary| dictionary.check(word))
+    }
+
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        for suggestion in self
+            .dictionaries
+            .iter()
+            .flat_map(|dictionary| dictionary.suggest(word))
+        {
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+}
@@ -0,0 +1,107 @@
+use std::ops::Range;
+
+/// Splits `text` into the words to spell check, with their byte ranges.
+///
+/// Identifiers are split into words at `snake_case` and `camelCase` boundaries. Parts that
+/// contain digits, are in all caps or are a single letter are skipped, as those are usually
+/// abbreviations or codes rather than words, and so are URLs and email addresses.
+pub fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    for (chunk_start, chunk) in split_with_offsets(text, char::is_whitespace) {
+        if chunk.contains("://") || chunk.contains('@') || chunk.starts_with("www.") {
+            continue;
+        }
+        for (token_start, token) in split_with_offsets(chunk, |c| !is_word_char(c)) {
+            if token.chars().any(|c| c.is_numeric()) {
+                continue;
+            }
+            let token_start = chunk_start + token_start;
+            for (part_start, part) in split_with_offsets(token, |c| c == '_') {
+                for range in camel_case_parts(part) {
+                    let part = &part[range.clone()];
+                    let word = part.trim_matches(is_apostrophe);
+                    let leading = part.len() - part.trim_start_matches(is_apostrophe).len();
+                    let start = token_start + part_start + range.start + leading;
+                    if word.chars().count() > 1 && word.chars().any(char::is_lowercase) {
+                        words.push(start..start + word.len());
+                    }
+                }
+            }
+        }
+    }
+    words
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || is_apostrophe(c)
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '’'
+}
+
+/// Splits `text` at the characters matching `is_separator`, returning the non-empty pieces with
+/// their byte offsets.
+fn split_with_offsets(
+    text: &str,
+    is_separator: impl Fn(char) -> bool,
+) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = 0;
+    text.char_indices()
+        .chain([(text.len(), ' ')])
+        .filter_map(move |(ix, c)| {
+            if ix == text.len() || is_separator(c) {
+                let piece = (start, &text[start..ix]);
+                start = ix + c.len_utf8();
+                Some(piece)
+            } else {
+                None
+            }
+        })
+        .filter(|(_, piece)| !piece.is_empty())
+}
+
+/// Splits an identifier like `parseHTTPHeader` into `parse`, `HTTP` and `Header`.
+fn camel_case_parts(identifier: &str) -> Vec<Range<usize>> {
+    let chars = identifier.char_indices().collect::<Vec<_>>();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for (i, (ix, c)) in chars.iter().enumerate().skip(1) {
+        let previous = chars[i - 1].1;
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        let is_boundary = c.is_uppercase()
+            && (previous.is_lowercase()
+                || (previous.is_uppercase() && next.is_some_and(char::is_lowercase)));
+        if is_boundary {
+            parts.push(start..*ix);
+            start = *ix;
+        }
+    }
+    parts.push(start..identifier.len());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(text: &str) -> Vec<&str> {
+        words(text).into_iter().map(|range| &text[range]).collect()
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            split("// Don't parse the 'quoted' header, see https://example.com/fooBar"),
+            ["Don't", "parse", "the", "quoted", "header", "see"]
+        );
+        assert_eq!(
+            split("parseHTTPHeader user_name_v2 max_retries XMLHttpRequest"),
+            ["parse", "Header", "max", "retries", "Http", "Request"]
+        );
+        assert_eq!(
+            split("a TODO for naïve café owners, mail me@example.com"),
+            ["for", "naïve", "café", "owners", "mail"]
+        );
+    }
+}
@@ -280,10 +280,48 @@ pub struct DebugScenario {
     pub tcp_connection: Option<TcpArgumentsTemplate>,
 }
 
+/// A user created group of debug tasks that are launched together, e.g. a server and its client.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct DebugCompound {
+    /// Name of the compound debug task
+    pub label: SharedString,
+    /// Labels of the debug tasks to launch, in the order they should be started
+    pub compound: Vec<SharedString>,
+    /// Whether stopping one of the debug sessions should stop all the others
+    #[serde(default)]
+    pub stop_all: bool,
+}
+
+/// A single entry of a debug tasks file. Entries with a `compound` field are compounds.
+#[derive(Serialize, PartialEq, Eq, Clone, Debug, JsonSchema)]
+#[serde(untagged)]
+pub enum DebugTaskFileEntry {
+    Compound(DebugCompound),
+    Scenario(DebugScenario),
+}
+
+impl<'de> Deserialize<'de> for DebugTaskFileEntry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Picking the variant up front, rather than trying each in turn, keeps the error of the
+        // variant that was meant, e.g. which of its fields is missing.
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let entry = if value.get("compound").is_some() {
+            DebugCompound::deserialize(value).map(DebugTaskFileEntry::Compound)
+        } else {
+            DebugScenario::deserialize(value).map(DebugTaskFileEntry::Scenario)
+        };
+        entry.map_err(serde::de::Error::custom)
+    }
+}
+
 /// A group of Debug Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
-pub struct DebugTaskFile(pub Vec<DebugScenario>);
+pub struct DebugTaskFile(pub Vec<DebugTaskFileEntry>);
 
 impl DebugTaskFile {
     pub fn generate_json_schema(schemas: &AdapterSchemas) -> serde_json::Value {
@@ -346,6 +384,49 @@ impl DebugTaskFile {
             build_task_value,
         );
 
+        let scenario_schema = serde_json::json!({
+            "type": "object",
+            "required": ["adapter", "label"],
+            // TODO: Uncommenting this will cause json-language-server to provide warnings for
+            // unrecognized properties. It should be enabled if/when there's an adapter JSON
+            // schema that's comprehensive. In order to not get warnings for the other schemas,
+            // `additionalProperties` or `unevaluatedProperties` (to handle "allOf" etc style
+            // schema combinations) could be set to `true` for that schema.
+            //
+            // "unevaluatedProperties": false,
+            "properties": {
+                "adapter": {
+                    "type": "string",
+                    "description": "The name of the debug adapter"
+                },
+                "label": {
+                    "type": "string",
+                    "description": "The name of the debug configuration"
+                },
+                "build": build_task_definition_ref,
+                "tcp_connection": {
+                    "type": "object",
+                    "description": "Optional TCP connection information for connecting to an already running debug adapter",
+                    "properties": {
+                        "port": {
+                            "type": "integer",
+                            "description": "The port that the debug adapter is listening on (default: auto-find open port)"
+                        },
+                        "host": {
+                            "type": "string",
+                            "pattern": "^((25[0-5]|(2[0-4]|1\\d|[1-9]|)\\d)\\.?\\b){4}$",
+                            "description": "The host that the debug adapter is listening to (default: 127.0.0.1)"
+                        },
+                        "timeout": {
+                            "type": "integer",
+                            "description": "The max amount of time in milliseconds to connect to a tcp DAP before returning an error (default: 2000ms)"
+                        }
+                    }
+                }
+            },
+            "allOf": adapter_conditions
+        });
+
         let meta_schema = generator
             .settings()
             .meta_schema
@@ -360,46 +441,30 @@ impl DebugTaskFile {
             "allowTrailingCommas": true,
             "type": "array",
             "items": {
-                "type": "object",
-                "required": ["adapter", "label"],
-                // TODO: Uncommenting this will cause json-language-server to provide warnings for
-                // unrecognized properties. It should be enabled if/when there's an adapter JSON
-                // schema that's comprehensive. In order to not get warnings for the other schemas,
-                // `additionalProperties` or `unevaluatedProperties` (to handle "allOf" etc style
-                // schema combinations) could be set to `true` for that schema.
-                //
-                // "unevaluatedProperties": false,
-                "properties": {
-                    "adapter": {
-                        "type": "string",
-                        "description": "The name of the debug adapter"
-                    },
-                    "label": {
-                        "type": "string",
-                        "description": "The name of the debug configuration"
-                    },
-                    "build": build_task_definition_ref,
-                    "tcp_connection": {
+                "anyOf": [
+                    {
                         "type": "object",
-                        "description": "Optional TCP connection information for connecting to an already running debug adapter",
+                        "required": ["label", "compound"],
+                        "additionalProperties": false,
                         "properties": {
-                            "port": {
-                                "type": "integer",
-                                "description": "The port that the debug adapter is listening on (default: auto-find open port)"
-                            },
-                            "host": {
+                            "label": {
                                 "type": "string",
-                                "pattern": "^((25[0-5]|(2[0-4]|1\\d|[1-9]|)\\d)\\.?\\b){4}$",
-                                "description": "The host that the debug adapter is listening to (default: 127.0.0.1)"
+                                "description": "The name of the compound debug configuration"
+                            },
+                            "compound": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Labels of the debug configurations to launch together"
                             },
-                            "timeout": {
-                                "type": "integer",
-                                "description": "The max amount of time in milliseconds to connect to a tcp DAP before returning an error (default: 2000ms)"
+                            "stop_all": {
+                                "type": "boolean",
+                                "default": false,
+                                "description": "Whether stopping one of the debug sessions should stop all the others"
                             }
                         }
-                    }
-                },
-                "allOf": adapter_conditions
+                    },
+                    scenario_schema
+                ]
             },
             "$defs": generator.take_definitions(true),
         })
@@ -531,4 +596,80 @@ mod tests {
             _ => panic!("Expected Template variant"),
         }
     }
+
+    #[test]
+    fn test_debug_task_file_entries() {
+        use crate::{DebugTaskFile, DebugTaskFileEntry};
+
+        let json = r#"[
+            {
+                "label": "Backend",
+                "adapter": "CodeLLDB",
+                "request": "launch",
+                "program": "target/debug/server"
+            },
+            {
+                "label": "Frontend and backend",
+                "compound": ["Backend", "Frontend"],
+                "stop_all": true
+            }
+        ]"#;
+
+        let DebugTaskFile(entries) = serde_json::from_str(json).unwrap();
+        assert_eq!(2, entries.len());
+        match &entries[0] {
+            DebugTaskFileEntry::Scenario(scenario) => {
+                assert_eq!("Backend", scenario.label.as_ref());
+                assert_eq!("CodeLLDB", scenario.adapter.as_ref());
+            }
+            _ => panic!("Expected Scenario variant"),
+        }
+        match &entries[1] {
+            DebugTaskFileEntry::Compound(compound) => {
+                assert_eq!("Frontend and backend", compound.label.as_ref());
+                assert_eq!(
+                    vec!["Backend", "Frontend"],
+                    compound
+                        .compound
+                        .iter()
+                        .map(|label| label.as_ref())
+                        .collect::<Vec<_>>()
+                );
+                assert!(compound.stop_all);
+            }
+            _ => panic!("Expected Compound variant"),
+        }
+    }
+
+    #[test]
+    fn test_debug_task_file_entry_errors() {
+        use crate::DebugTaskFileEntry;
+
+        let error = serde_json::from_str::<DebugTaskFileEntry>(
+            r#"{ "label": "Backend", "request": "launch", "program": "server" }"#,
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("missing field `adapter`"),
+            "Unexpected error: {error}"
+        );
+
+        let error = serde_json::from_str::<DebugTaskFileEntry>(
+            r#"{ "label": "Both", "compound": ["Backend"], "stop_all": "yes" }"#,
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("expected a boolean"),
+            "Unexpected error: {error}"
+        );
+
+        let error = serde_json::from_str::<DebugTaskFileEntry>(
+            r#"{ "label": "Both", "compound": ["Backend"], "adapter": "CodeLLDB" }"#,
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `adapter`"),
+            "Unexpected error: {error}"
+        );
+    }
 }
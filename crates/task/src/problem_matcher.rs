@@ -0,0 +1,553 @@
+//! Problem matchers find the errors and warnings that tools like compilers print, so that a task's
+//! output can be turned into diagnostics.
+
+use std::path::PathBuf;
+
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How to find problems in the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcher {
+    /// One of the built-in matchers for common tools.
+    Preset(ProblemMatcherPreset),
+    /// A matcher defined by regular expressions.
+    Custom(CustomProblemMatcher),
+}
+
+/// Built-in matchers for the output of common tools.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemMatcherPreset {
+    /// Errors and warnings of `rustc` and `cargo`.
+    Rustc,
+    /// Errors of the TypeScript compiler, with or without `--pretty`.
+    Tsc,
+    /// Errors, warnings and notes of `gcc` and `clang`.
+    Gcc,
+    /// Problems reported by `eslint` with its default (stylish) or compact formatter.
+    Eslint,
+    /// Errors of the `go` tool and `go vet`.
+    Go,
+}
+
+/// A problem matcher defined by regular expressions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CustomProblemMatcher {
+    /// Patterns matching consecutive lines of the output, which together describe a problem.
+    /// Each part of the problem can be captured by any of the patterns.
+    pub patterns: Vec<ProblemPattern>,
+    /// The severity of problems whose patterns don't capture one.
+    #[serde(default)]
+    pub severity: ProblemSeverity,
+}
+
+/// A regular expression matching one line of a problem, along with the indices of the capture
+/// groups holding the parts of the problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ProblemPattern {
+    /// The regular expression to match a line of the output with.
+    pub regexp: String,
+    /// The capture group of the file's path, relative to the task's working directory or absolute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<usize>,
+    /// The capture group of the 1-based line number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// The capture group of the 1-based column number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// The capture group of the 1-based line number the problem ends at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    /// The capture group of the 1-based column number the problem ends at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    /// The capture group of the severity, like `error` or `warning`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<usize>,
+    /// The capture group of the problem's code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<usize>,
+    /// The capture group of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<usize>,
+    /// Whether this pattern, which has to be the last one, keeps matching the following lines,
+    /// reporting a problem for each of them.
+    #[serde(default, rename = "loop", skip_serializing_if = "std::ops::Not::not")]
+    pub repeat: bool,
+}
+
+/// How severe a problem is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+}
+
+impl ProblemSeverity {
+    fn parse(severity: &str) -> Option<Self> {
+        let severity = severity.to_ascii_lowercase();
+        if severity.starts_with("err") || severity.starts_with("fatal") {
+            Some(Self::Error)
+        } else if severity.starts_with("warn") {
+            Some(Self::Warning)
+        } else if ["info", "note", "hint"]
+            .iter()
+            .any(|prefix| severity.starts_with(prefix))
+        {
+            Some(Self::Info)
+        } else {
+            None
+        }
+    }
+}
+
+/// A problem found in the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// The path of the file, as printed by the task.
+    pub path: PathBuf,
+    /// The 1-based line of the problem.
+    pub line: u32,
+    /// The 1-based column of the problem, if the task printed one.
+    pub column: Option<u32>,
+    /// The 1-based line the problem ends at, if the task printed one.
+    pub end_line: Option<u32>,
+    /// The 1-based column the problem ends at, if the task printed one.
+    pub end_column: Option<u32>,
+    pub severity: ProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl ProblemMatcherPreset {
+    fn matchers(self) -> Vec<CustomProblemMatcher> {
+        fn pattern(regexp: &str) -> ProblemPattern {
+            ProblemPattern {
+                regexp: regexp.to_string(),
+                ..ProblemPattern::default()
+            }
+        }
+
+        match self {
+            Self::Rustc => vec![CustomProblemMatcher {
+                patterns: vec![
+                    ProblemPattern {
+                        severity: Some(1),
+                        code: Some(2),
+                        message: Some(3),
+                        ..pattern(r"^(warning|error)(?:\[(\w+)\])?: (.*)$")
+                    },
+                    ProblemPattern {
+                        file: Some(1),
+                        line: Some(2),
+                        column: Some(3),
+                        ..pattern(r"^\s*--> (.+?):(\d+):(\d+)$")
+                    },
+                ],
+                severity: ProblemSeverity::Error,
+            }],
+            Self::Tsc => vec![
+                CustomProblemMatcher {
+                    patterns: vec![ProblemPattern {
+                        file: Some(1),
+                        line: Some(2),
+                        column: Some(3),
+                        severity: Some(4),
+                        code: Some(5),
+                        message: Some(6),
+                        ..pattern(r"^(.+?)\((\d+),(\d+)\): (error|warning|info) (TS\d+)\s*: (.*)$")
+                    }],
+                    severity: ProblemSeverity::Error,
+                },
+                CustomProblemMatcher {
+                    patterns: vec![ProblemPattern {
+                        file: Some(1),
+                        line: Some(2),
+                        column: Some(3),
+                        severity: Some(4),
+                        code: Some(5),
+                        message: Some(6),
+                        ..pattern(r"^(.+?):(\d+):(\d+) - (error|warning|info) (TS\d+): (.*)$")
+                    }],
+                    severity: ProblemSeverity::Error,
+                },
+            ],
+            Self::Gcc => vec![CustomProblemMatcher {
+                patterns: vec![ProblemPattern {
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    severity: Some(4),
+                    message: Some(5),
+                    ..pattern(r"^(.+?):(\d+):(\d+):\s+(?:fatal\s+)?(warning|error|note):\s+(.*)$")
+                }],
+                severity: ProblemSeverity::Error,
+            }],
+            Self::Eslint => vec![
+                CustomProblemMatcher {
+                    patterns: vec![
+                        ProblemPattern {
+                            file: Some(1),
+                            ..pattern(r"^(\S.*)$")
+                        },
+                        ProblemPattern {
+                            line: Some(1),
+                            column: Some(2),
+                            severity: Some(3),
+                            message: Some(4),
+                            code: Some(5),
+                            repeat: true,
+                            ..pattern(
+                                r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.*?)(?:\s\s+(\S+))?$",
+                            )
+                        },
+                    ],
+                    severity: ProblemSeverity::Error,
+                },
+                CustomProblemMatcher {
+                    patterns: vec![ProblemPattern {
+                        file: Some(1),
+                        line: Some(2),
+                        column: Some(3),
+                        severity: Some(4),
+                        message: Some(5),
+                        code: Some(6),
+                        ..pattern(
+                            r"^(.+?): line (\d+), col (\d+), (Error|Warning|Info) - (.+?)(?: \((.+)\))?$",
+                        )
+                    }],
+                    severity: ProblemSeverity::Error,
+                },
+            ],
+            Self::Go => vec![CustomProblemMatcher {
+                patterns: vec![ProblemPattern {
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    message: Some(4),
+                    ..pattern(r"^\s*(?:vet: )?(.+?\.go):(\d+)(?::(\d+))?:\s+(.*)$")
+                }],
+                severity: ProblemSeverity::Error,
+            }],
+        }
+    }
+}
+
+/// Finds the problems in `output` with all of the given matchers.
+pub fn find_problems(matchers: &[ProblemMatcher], output: &str) -> Vec<Problem> {
+    let lines = output.lines().collect::<Vec<_>>();
+    let mut problems = Vec::new();
+    for matcher in matchers {
+        let custom_matchers = match matcher {
+            ProblemMatcher::Preset(preset) => preset.matchers(),
+            ProblemMatcher::Custom(custom) => vec![custom.clone()],
+        };
+        for matcher in custom_matchers {
+            let patterns = matcher
+                .patterns
+                .iter()
+                .map(|pattern| Regex::new(&pattern.regexp).map(|regex| (regex, pattern)))
+                .collect::<Result<Vec<_>, _>>();
+            match patterns {
+                Ok(patterns) if !patterns.is_empty() => {
+                    find_matches(&patterns, matcher.severity, &lines, &mut problems)
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("invalid problem matcher pattern: {e}"),
+            }
+        }
+    }
+    problems
+}
+
+fn find_matches(
+    patterns: &[(Regex, &ProblemPattern)],
+    default_severity: ProblemSeverity,
+    lines: &[&str],
+    problems: &mut Vec<Problem>,
+) {
+    let (last_regex, last_pattern) = &patterns[patterns.len() - 1];
+    let mut ix = 0;
+    'lines: while ix + patterns.len() <= lines.len() {
+        let mut fields = ProblemFields::default();
+        for (offset, (regex, pattern)) in patterns.iter().enumerate() {
+            let Some(captures) = regex.captures(lines[ix + offset]) else {
+                ix += 1;
+                continue 'lines;
+            };
+            fields.capture(pattern, &captures);
+        }
+        problems.extend(fields.to_problem(default_severity));
+        ix += patterns.len();
+
+        if last_pattern.repeat {
+            while let Some(captures) = lines.get(ix).and_then(|line| last_regex.captures(line)) {
+                let mut fields = fields.clone();
+                fields.capture(last_pattern, &captures);
+                problems.extend(fields.to_problem(default_severity));
+                ix += 1;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct ProblemFields {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<ProblemSeverity>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl ProblemFields {
+    fn capture(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let text = |group: Option<usize>| {
+            captures
+                .get(group?)
+                .map(|capture| capture.as_str().trim())
+                .filter(|text| !text.is_empty())
+        };
+        let number = |group: Option<usize>| text(group)?.parse::<u32>().ok();
+
+        if let Some(file) = text(pattern.file) {
+            self.file = Some(file.to_string());
+        }
+        if let Some(line) = number(pattern.line) {
+            self.line = Some(line);
+        }
+        if let Some(column) = number(pattern.column) {
+            self.column = Some(column);
+        }
+        if let Some(end_line) = number(pattern.end_line) {
+            self.end_line = Some(end_line);
+        }
+        if let Some(end_column) = number(pattern.end_column) {
+            self.end_column = Some(end_column);
+        }
+        if let Some(severity) = text(pattern.severity).and_then(ProblemSeverity::parse) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = text(pattern.code) {
+            self.code = Some(code.to_string());
+        }
+        if let Some(message) = text(pattern.message) {
+            self.message = Some(message.to_string());
+        }
+    }
+
+    fn to_problem(&self, default_severity: ProblemSeverity) -> Option<Problem> {
+        Some(Problem {
+            path: PathBuf::from(self.file.as_ref()?),
+            line: self.line?,
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            severity: self.severity.unwrap_or(default_severity),
+            code: self.code.clone(),
+            message: self.message.clone()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(path: &str, line: u32, column: Option<u32>, message: &str) -> Problem {
+        Problem {
+            path: PathBuf::from(path),
+            line,
+            column,
+            end_line: None,
+            end_column: None,
+            severity: ProblemSeverity::Error,
+            code: None,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rustc_preset() {
+        let output = "\
+   Compiling zed v0.1.0
+error[E0425]: cannot find value `x` in this scope
+ --> src/main.rs:2:5
+  |
+2 |     x
+  |     ^ not found in this scope
+
+warning: unused variable: `y`
+  --> crates/zed/src/zed.rs:10:9
+warning: `zed` (bin \"zed\") generated 1 warning
+error: could not compile `zed`";
+
+        assert_eq!(
+            find_problems(
+                &[ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)],
+                output
+            ),
+            vec![
+                Problem {
+                    code: Some("E0425".to_string()),
+                    ..problem(
+                        "src/main.rs",
+                        2,
+                        Some(5),
+                        "cannot find value `x` in this scope"
+                    )
+                },
+                Problem {
+                    severity: ProblemSeverity::Warning,
+                    ..problem("crates/zed/src/zed.rs", 10, Some(9), "unused variable: `y`")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_presets_for_single_line_formats() {
+        let cases = [
+            (
+                ProblemMatcherPreset::Tsc,
+                "src/app.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.",
+                Problem {
+                    code: Some("TS2322".to_string()),
+                    ..problem(
+                        "src/app.ts",
+                        3,
+                        Some(7),
+                        "Type 'string' is not assignable to type 'number'.",
+                    )
+                },
+            ),
+            (
+                ProblemMatcherPreset::Tsc,
+                "src/app.ts:3:7 - error TS2322: Type 'string' is not assignable to type 'number'.",
+                Problem {
+                    code: Some("TS2322".to_string()),
+                    ..problem(
+                        "src/app.ts",
+                        3,
+                        Some(7),
+                        "Type 'string' is not assignable to type 'number'.",
+                    )
+                },
+            ),
+            (
+                ProblemMatcherPreset::Gcc,
+                "main.c:4:12: warning: unused variable 'x' [-Wunused-variable]",
+                Problem {
+                    severity: ProblemSeverity::Warning,
+                    ..problem(
+                        "main.c",
+                        4,
+                        Some(12),
+                        "unused variable 'x' [-Wunused-variable]",
+                    )
+                },
+            ),
+            (
+                ProblemMatcherPreset::Eslint,
+                "/repo/src/index.js: line 1, col 10, Error - 'foo' is defined but never used. (no-unused-vars)",
+                Problem {
+                    code: Some("no-unused-vars".to_string()),
+                    ..problem(
+                        "/repo/src/index.js",
+                        1,
+                        Some(10),
+                        "'foo' is defined but never used.",
+                    )
+                },
+            ),
+            (
+                ProblemMatcherPreset::Go,
+                "./main.go:8:2: undefined: fmt.Printn",
+                problem("./main.go", 8, Some(2), "undefined: fmt.Printn"),
+            ),
+        ];
+
+        for (preset, output, expected) in cases {
+            assert_eq!(
+                find_problems(&[ProblemMatcher::Preset(preset)], output),
+                vec![expected],
+                "{preset:?} should match {output:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_looping_pattern() {
+        let output = "\
+/repo/src/index.js
+  1:10  error    'foo' is defined but never used  no-unused-vars
+  3:1   warning  Unexpected console statement     no-console
+
+✖ 2 problems (1 error, 1 warning)";
+
+        assert_eq!(
+            find_problems(
+                &[ProblemMatcher::Preset(ProblemMatcherPreset::Eslint)],
+                output
+            ),
+            vec![
+                Problem {
+                    code: Some("no-unused-vars".to_string()),
+                    ..problem(
+                        "/repo/src/index.js",
+                        1,
+                        Some(10),
+                        "'foo' is defined but never used"
+                    )
+                },
+                Problem {
+                    severity: ProblemSeverity::Warning,
+                    code: Some("no-console".to_string()),
+                    ..problem(
+                        "/repo/src/index.js",
+                        3,
+                        Some(1),
+                        "Unexpected console statement"
+                    )
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_matcher() {
+        let matchers: Vec<ProblemMatcher> = serde_json_lenient::from_str(
+            r#"[
+                "go",
+                {
+                    "patterns": [{ "regexp": "^(.*):(\\d+) (.*)$", "file": 1, "line": 2, "message": 3 }],
+                    "severity": "warning"
+                },
+                { "patterns": [{ "regexp": "(unclosed", "file": 1 }] }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            matchers[0],
+            ProblemMatcher::Preset(ProblemMatcherPreset::Go)
+        );
+
+        assert_eq!(
+            find_problems(&matchers[1..], "lib/a.py:12 something is off"),
+            vec![Problem {
+                severity: ProblemSeverity::Warning,
+                ..problem("lib/a.py", 12, None, "something is off")
+            }]
+        );
+    }
+}
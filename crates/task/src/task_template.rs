@@ -9,8 +9,8 @@ use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
+    AttachRequest, ProblemMatcher, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext,
+    TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
 };
 
 /// A template definition of a Zed task to run.
@@ -25,6 +25,7 @@ pub struct TaskTemplate {
     /// Human readable name of the task to display in the UI.
     pub label: String,
     /// Executable command to spawn.
+    #[serde(default)]
     pub command: String,
     /// Arguments to the command.
     #[serde(default)]
@@ -72,6 +73,21 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks to run before this one, each in its own terminal.
+    /// If any of them fails, this task is not started.
+    /// A task with dependencies may omit its `command` to only run the tasks it depends on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// How to run the tasks listed in `depends_on`:
+    /// * `parallel` — start all of them at once (default)
+    /// * `sequence` — start each one after the previous one succeeded
+    #[serde(default, skip_serializing_if = "DependsOrder::is_parallel")]
+    pub depends_order: DependsOrder,
+    /// How to find errors and warnings in the task's output, to show them as diagnostics until the task runs again.
+    /// Either the name of a built-in matcher (`rustc`, `tsc`, `gcc`, `eslint` or `go`),
+    /// or `{ "patterns": [{ "regexp": "...", "file": 1, "line": 2, "message": 3 }] }` with the capture groups of each part.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_matchers: Vec<ProblemMatcher>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -109,6 +125,23 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// How to run the tasks a task depends on.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Start all dependencies at once, and wait for all of them to finish.
+    #[default]
+    Parallel,
+    /// Start each dependency after the previous one succeeded.
+    Sequence,
+}
+
+impl DependsOrder {
+    fn is_parallel(&self) -> bool {
+        *self == Self::Parallel
+    }
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -132,7 +165,9 @@ impl TaskTemplate {
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty()
+            || (self.command.trim().is_empty() && self.depends_on.is_empty())
+        {
             return None;
         }
 
@@ -270,6 +305,7 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers: self.problem_matchers.clone(),
             },
         })
     }
@@ -523,6 +559,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_templates_with_only_dependencies() {
+        let task = TaskTemplate {
+            label: "build and serve".to_string(),
+            depends_on: vec!["build".to_string(), "serve".to_string()],
+            depends_order: DependsOrder::Sequence,
+            ..TaskTemplate::default()
+        };
+        let resolved_task = task
+            .resolve_task(TEST_ID_BASE, &TaskContext::default())
+            .expect("a task with dependencies should resolve without a command");
+        assert_eq!(resolved_task.original_task().depends_on, task.depends_on);
+
+        let without_dependencies = TaskTemplate {
+            depends_on: Vec::new(),
+            ..task
+        };
+        assert_eq!(
+            without_dependencies.resolve_task(TEST_ID_BASE, &TaskContext::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dependencies_are_parsed() {
+        let templates: TaskTemplates = serde_json_lenient::from_str(
+            r#"[
+                { "label": "migrate", "command": "just migrate", "depends_on": ["build"] },
+                { "label": "dev", "depends_on": ["migrate", "serve"], "depends_order": "sequence" }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(templates.0[0].depends_on, vec!["build".to_string()]);
+        assert_eq!(templates.0[0].depends_order, DependsOrder::Parallel);
+        assert_eq!(templates.0[1].command, "");
+        assert_eq!(templates.0[1].depends_order, DependsOrder::Sequence);
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -3,7 +3,10 @@ use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    CustomProblemMatcher, DependsOrder, EnvVariableReplacer, ProblemMatcher, ProblemMatcherPreset,
+    ProblemPattern, ProblemSeverity, TaskTemplate, TaskTemplates, VariableName,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -41,30 +44,64 @@ enum Command {
     },
 }
 
-impl VsCodeTaskDefinition {
-    fn into_zed_format(
-        self,
-        replacer: &EnvVariableReplacer,
-    ) -> anyhow::Result<Option<TaskTemplate>> {
-        if self.other_attributes.contains_key("dependsOn") {
-            log::warn!(
-                "Skipping deserializing of a task `{}` with the unsupported `dependsOn` key",
-                self.label
-            );
-            return Ok(None);
+/// A pattern of a problem matcher defined in a `problemMatcher` object.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemPattern {
+    regexp: String,
+    file: Option<usize>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    severity: Option<usize>,
+    code: Option<usize>,
+    message: Option<usize>,
+    #[serde(default, rename = "loop")]
+    repeat: bool,
+}
+
+impl From<VsCodeProblemPattern> for ProblemPattern {
+    fn from(pattern: VsCodeProblemPattern) -> Self {
+        Self {
+            regexp: pattern.regexp,
+            file: pattern.file,
+            line: pattern.line,
+            column: pattern.column,
+            end_line: pattern.end_line,
+            end_column: pattern.end_column,
+            severity: pattern.severity,
+            code: pattern.code,
+            message: pattern.message,
+            repeat: pattern.repeat,
         }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
-        // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
-        let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
-        };
+    }
+}
 
-        let (command, args) = match command {
-            Command::Npm { script } => ("npm".to_owned(), vec!["run".to_string(), script]),
-            Command::Shell { command, args } => (command, args),
-            Command::Gulp { task } => ("gulp".to_owned(), vec![task]),
+impl VsCodeTaskDefinition {
+    fn into_zed_format(self, replacer: &EnvVariableReplacer) -> anyhow::Result<TaskTemplate> {
+        let depends_on = self.depends_on();
+        let problem_matchers = self
+            .other_attributes
+            .get("problemMatcher")
+            .map(|problem_matcher| self.problem_matchers(problem_matcher))
+            .unwrap_or_default();
+        let depends_order = match self
+            .other_attributes
+            .get("dependsOrder")
+            .and_then(|order| order.as_str())
+        {
+            Some("sequence") => DependsOrder::Sequence,
+            _ => DependsOrder::Parallel,
+        };
+        // `type` is not set in tasks that only run their `dependsOn` tasks; we still want to deserialize the whole object though (hence command is an Option),
+        // as that way we can provide more specific description of why deserialization failed.
+        let (command, args) = match self.command {
+            Some(Command::Npm { script }) => ("npm".to_owned(), vec!["run".to_string(), script]),
+            Some(Command::Shell { command, args }) => (command, args),
+            Some(Command::Gulp { task }) => ("gulp".to_owned(), vec![task]),
+            None if !depends_on.is_empty() => (String::new(), Vec::new()),
+            None => bail!("Missing `type` field in task"),
         };
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
@@ -73,16 +110,109 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order,
+            problem_matchers,
             ..TaskTemplate::default()
         };
         if let Some(options) = self.options {
             template.cwd = options.cwd.map(|cwd| replacer.replace(&cwd));
             template.env = options.env;
         }
-        Ok(Some(template))
+        Ok(template)
+    }
+
+    /// Labels of the tasks listed in `dependsOn`, which is either a single label or a list of them.
+    fn depends_on(&self) -> Vec<String> {
+        let labels = match self.other_attributes.get("dependsOn") {
+            Some(serde_json_lenient::Value::String(label)) => return vec![label.clone()],
+            Some(serde_json_lenient::Value::Array(labels)) => labels,
+            _ => return Vec::new(),
+        };
+        labels
+            .iter()
+            .filter_map(|label| match label.as_str() {
+                Some(label) => Some(label.to_owned()),
+                None => {
+                    // Detected tasks can be referenced with an object like `{ "type": "npm", "script": "build" }`,
+                    // but there are no such tasks to refer to in Zed.
+                    log::warn!(
+                        "Skipping a `dependsOn` entry of task `{}` that is not a task label",
+                        self.label
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Problem matchers of a `problemMatcher` value, which is either the name of a built-in matcher (like `$tsc`),
+    /// an object defining a matcher, or a list of those.
+    /// Matchers without a counterpart in Zed are skipped.
+    fn problem_matchers(&self, value: &serde_json_lenient::Value) -> Vec<ProblemMatcher> {
+        let matcher = match value {
+            serde_json_lenient::Value::Array(values) => {
+                return values
+                    .iter()
+                    .flat_map(|value| self.problem_matchers(value))
+                    .collect();
+            }
+            serde_json_lenient::Value::String(name) => problem_matcher_preset(name),
+            serde_json_lenient::Value::Object(object) => match object.get("pattern") {
+                Some(pattern) => self.custom_problem_matcher(pattern, object.get("severity")),
+                None => object
+                    .get("base")
+                    .and_then(|base| base.as_str())
+                    .and_then(problem_matcher_preset),
+            },
+            _ => None,
+        };
+        if matcher.is_none() {
+            log::warn!(
+                "Skipping an unsupported `problemMatcher` of task `{}`: {value}",
+                self.label
+            );
+        }
+        matcher.into_iter().collect()
+    }
+
+    fn custom_problem_matcher(
+        &self,
+        pattern: &serde_json_lenient::Value,
+        severity: Option<&serde_json_lenient::Value>,
+    ) -> Option<ProblemMatcher> {
+        let patterns = match pattern {
+            serde_json_lenient::Value::Array(_) => {
+                serde_json_lenient::from_value::<Vec<VsCodeProblemPattern>>(pattern.clone())
+            }
+            _ => serde_json_lenient::from_value::<VsCodeProblemPattern>(pattern.clone())
+                .map(|pattern| vec![pattern]),
+        }
+        .log_err()?;
+        let severity = match severity.and_then(|severity| severity.as_str()) {
+            Some("warning") => ProblemSeverity::Warning,
+            Some("info") => ProblemSeverity::Info,
+            _ => ProblemSeverity::Error,
+        };
+        Some(ProblemMatcher::Custom(CustomProblemMatcher {
+            patterns: patterns.into_iter().map(ProblemPattern::from).collect(),
+            severity,
+        }))
     }
 }
 
+fn problem_matcher_preset(name: &str) -> Option<ProblemMatcher> {
+    let preset = match name {
+        "$rustc" | "$rustc-watch" => ProblemMatcherPreset::Rustc,
+        "$tsc" | "$tsc-watch" => ProblemMatcherPreset::Tsc,
+        "$gcc" => ProblemMatcherPreset::Gcc,
+        "$eslint-stylish" | "$eslint-compact" => ProblemMatcherPreset::Eslint,
+        "$go" => ProblemMatcherPreset::Go,
+        _ => return None,
+    };
+    Some(ProblemMatcher::Preset(preset))
+}
+
 /// [`VsCodeTaskFile`] is a superset of Code's task definition format.
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct VsCodeTaskFile {
@@ -108,12 +238,7 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
         let templates = value
             .tasks
             .into_iter()
-            .filter_map(|vscode_definition| {
-                vscode_definition
-                    .into_zed_format(&replacer)
-                    .log_err()
-                    .flatten()
-            })
+            .filter_map(|vscode_definition| vscode_definition.into_zed_format(&replacer).log_err())
             .collect();
         Ok(Self(templates))
     }
@@ -124,7 +249,8 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        TaskTemplate, TaskTemplates, VsCodeTaskFile,
+        CustomProblemMatcher, DependsOrder, ProblemMatcher, ProblemMatcherPreset, ProblemPattern,
+        ProblemSeverity, TaskTemplate, TaskTemplates, VsCodeTaskFile,
         vscode_format::{Command, VsCodeTaskDefinition},
     };
 
@@ -227,6 +353,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
@@ -238,18 +365,21 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
         ];
@@ -330,32 +460,155 @@ mod tests {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                problem_matchers: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)],
                 ..Default::default()
             },
         ];
         let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
         assert_eq!(tasks.0, expected);
     }
+
+    #[test]
+    fn can_deserialize_depends_on() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "version": "2.0.0",
+                "tasks": [
+                    { "label": "build", "type": "shell", "command": "make" },
+                    { "label": "migrate", "type": "shell", "command": "make migrate", "dependsOn": "build" },
+                    {
+                        "label": "dev",
+                        "dependsOn": ["migrate", { "type": "npm", "script": "serve" }, "serve"],
+                        "dependsOrder": "sequence"
+                    },
+                    { "label": "broken" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "build".to_string(),
+                    command: "make".to_string(),
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "migrate".to_string(),
+                    command: "make migrate".to_string(),
+                    depends_on: vec!["build".to_string()],
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "dev".to_string(),
+                    depends_on: vec!["migrate".to_string(), "serve".to_string()],
+                    depends_order: DependsOrder::Sequence,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_deserialize_problem_matchers() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "version": "2.0.0",
+                "tasks": [
+                    {
+                        "label": "build",
+                        "type": "shell",
+                        "command": "make",
+                        "problemMatcher": ["$gcc", "$msCompile", { "base": "$eslint-stylish" }]
+                    },
+                    {
+                        "label": "lint",
+                        "type": "shell",
+                        "command": "lint",
+                        "problemMatcher": {
+                            "owner": "lint",
+                            "severity": "warning",
+                            "pattern": { "regexp": "^(.*):(\\d+): (.*)$", "file": 1, "line": 2, "message": 3 }
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "build".to_string(),
+                    command: "make".to_string(),
+                    problem_matchers: vec![
+                        ProblemMatcher::Preset(ProblemMatcherPreset::Gcc),
+                        ProblemMatcher::Preset(ProblemMatcherPreset::Eslint),
+                    ],
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "lint".to_string(),
+                    command: "lint".to_string(),
+                    problem_matchers: vec![ProblemMatcher::Custom(CustomProblemMatcher {
+                        patterns: vec![ProblemPattern {
+                            regexp: r"^(.*):(\d+): (.*)$".to_string(),
+                            file: Some(1),
+                            line: Some(2),
+                            message: Some(3),
+                            ..Default::default()
+                        }],
+                        severity: ProblemSeverity::Warning,
+                    })],
+                    ..Default::default()
+                },
+            ]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::TaskContexts;
+use crate::{TaskContexts, schedule_task_with_dependencies};
 use editor::Editor;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{
@@ -422,11 +422,14 @@ impl PickerDelegate for TasksModalDelegate {
             task.resolved.reveal_target = *reveal_target;
         }
 
+        let task_contexts = self.task_contexts.clone();
         self.workspace
             .update(cx, |workspace, cx| {
-                workspace.schedule_resolved_task(
+                schedule_task_with_dependencies(
+                    workspace,
                     task_source_kind,
                     task,
+                    &task_contexts,
                     omit_history_entry,
                     window,
                     cx,
@@ -0,0 +1,343 @@
+//! Runs the tasks a task depends on (its `depends_on` list) before the task itself.
+
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{self, Shared},
+};
+use gpui::{AsyncWindowContext, Context, Task, WeakEntity, Window};
+use project::{TaskContexts, TaskDependency, TaskSourceKind};
+use task::{DependsOrder, ResolvedTask, TaskContext, TaskId, TaskTemplate};
+use workspace::{Toast, Workspace, notifications::NotificationId};
+
+/// The runs of the dependencies started for one task by their resolved id, so that a task
+/// several others depend on runs once and all of them wait for the same run.
+type DependencyRuns = Rc<RefCell<HashMap<TaskId, Shared<Task<Result<bool, Arc<anyhow::Error>>>>>>>;
+
+/// Schedules `resolved_task` after the tasks it depends on, which run in their own terminals.
+/// If any of the dependencies fails, the task is not started.
+pub fn schedule_task_with_dependencies(
+    workspace: &mut Workspace,
+    task_source_kind: TaskSourceKind,
+    resolved_task: ResolvedTask,
+    task_contexts: &TaskContexts,
+    omit_history: bool,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let task = resolved_task.original_task();
+    if task.depends_on.is_empty() {
+        workspace.schedule_resolved_task(task_source_kind, resolved_task, omit_history, window, cx);
+        return;
+    }
+
+    let worktree_id = match &task_source_kind {
+        TaskSourceKind::Worktree { id, .. } => Some(*id),
+        _ => task_contexts.worktree(),
+    };
+    let Some(task_inventory) = workspace
+        .project()
+        .read(cx)
+        .task_store()
+        .read(cx)
+        .task_inventory()
+        .cloned()
+    else {
+        return;
+    };
+    let dependencies = match task_inventory.read(cx).task_dependencies(task, worktree_id) {
+        Ok(dependencies) => dependencies,
+        Err(e) => {
+            show_error_toast(
+                workspace,
+                format!("Cannot run task `{}`: {e}", task.label),
+                cx,
+            );
+            return;
+        }
+    };
+
+    let task_context = worktree_id
+        .and_then(|worktree_id| task_contexts.task_context_for_worktree_id(worktree_id))
+        .or_else(|| task_contexts.active_context())
+        .cloned()
+        .unwrap_or_default();
+    let depends_order = task.depends_order;
+    cx.spawn_in(window, async move |workspace, cx| {
+        let runs = DependencyRuns::default();
+        if !run_dependencies(
+            dependencies,
+            depends_order,
+            &task_context,
+            &runs,
+            &workspace,
+            cx,
+        )
+        .await?
+        {
+            return Ok(());
+        }
+        workspace.update_in(cx, |workspace, window, cx| {
+            if resolved_task.original_task().command.trim().is_empty() {
+                // The task only groups its dependencies, there's nothing left to spawn.
+                if !omit_history {
+                    task_inventory.update(cx, |inventory, _| {
+                        inventory.task_scheduled(task_source_kind, resolved_task);
+                    });
+                }
+            } else {
+                workspace.schedule_resolved_task(
+                    task_source_kind,
+                    resolved_task,
+                    omit_history,
+                    window,
+                    cx,
+                );
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+/// Runs `dependencies` in the given order, returning whether all of them succeeded.
+async fn run_dependencies(
+    dependencies: Vec<TaskDependency>,
+    depends_order: DependsOrder,
+    task_context: &TaskContext,
+    runs: &DependencyRuns,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> Result<bool> {
+    match depends_order {
+        DependsOrder::Sequence => {
+            for dependency in dependencies {
+                let run = workspace.update_in(cx, |_, window, cx| {
+                    run_dependency(dependency, task_context.clone(), runs, window, cx)
+                })?;
+                if !run.await? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        DependsOrder::Parallel => {
+            let runs = workspace.update_in(cx, |_, window, cx| {
+                dependencies
+                    .into_iter()
+                    .map(|dependency| {
+                        run_dependency(dependency, task_context.clone(), runs, window, cx)
+                    })
+                    .collect::<Vec<_>>()
+            })?;
+            let mut succeeded = true;
+            for result in future::join_all(runs).await {
+                succeeded &= result?;
+            }
+            Ok(succeeded)
+        }
+    }
+}
+
+/// Runs the dependencies of `dependency` and then the dependency itself in a terminal,
+/// returning whether it succeeded. A dependency that already ran or is running for the same
+/// task isn't started again, its run is awaited instead.
+fn run_dependency(
+    dependency: TaskDependency,
+    task_context: TaskContext,
+    runs: &DependencyRuns,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) -> Task<Result<bool>> {
+    let TaskDependency {
+        source_kind,
+        template,
+        dependencies,
+    } = dependency;
+    let resolved_task = match template
+        .resolve_task(&source_kind.to_id_base(), &task_context)
+        .with_context(|| format!("resolving task {:?}", template.label))
+    {
+        Ok(resolved_task) => resolved_task,
+        Err(e) => return Task::ready(Err(e)),
+    };
+
+    let run = runs
+        .borrow_mut()
+        .entry(resolved_task.id.clone())
+        .or_insert_with(|| {
+            let runs = runs.clone();
+            cx.spawn_in(window, async move |workspace, cx| {
+                if !run_dependencies(
+                    dependencies,
+                    template.depends_order,
+                    &task_context,
+                    &runs,
+                    &workspace,
+                    cx,
+                )
+                .await
+                .map_err(Arc::new)?
+                {
+                    return Ok(false);
+                }
+                run_in_terminal(resolved_task, &template, &workspace, cx)
+                    .await
+                    .map_err(Arc::new)
+            })
+            .shared()
+        })
+        .clone();
+    cx.background_spawn(async move { run.await.map_err(|e| anyhow::anyhow!("{e:#}")) })
+}
+
+/// Spawns the resolved dependency in a terminal, returning whether it succeeded.
+async fn run_in_terminal(
+    resolved_task: ResolvedTask,
+    template: &TaskTemplate,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut AsyncWindowContext,
+) -> Result<bool> {
+    if template.command.trim().is_empty() {
+        return Ok(true);
+    }
+    let status = workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(resolved_task.resolved, window, cx)
+        })?
+        .await;
+    let succeeded = match status {
+        Some(status) => status?.success(),
+        // The terminal was closed before the task finished.
+        None => false,
+    };
+    if !succeeded {
+        workspace.update(cx, |workspace, cx| {
+            show_error_toast(
+                workspace,
+                format!(
+                    "Task `{}` failed, the tasks depending on it were not started",
+                    template.label
+                ),
+                cx,
+            );
+        })?;
+    }
+    Ok(succeeded)
+}
+
+fn show_error_toast(workspace: &mut Workspace, message: String, cx: &mut Context<Workspace>) {
+    let id = NotificationId::unique::<TaskDependency>();
+    workspace.show_toast(Toast::new(id, message), cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, process::ExitStatus};
+
+    use gpui::{App, TestAppContext};
+    use parking_lot::Mutex;
+    use project::{FakeFs, Project, task_store::TaskSettingsLocation};
+    use serde_json::json;
+    use task::SpawnInTerminal;
+    use util::path;
+    use workspace::{MultiWorkspace, TerminalProvider};
+
+    use crate::tests::init_test;
+
+    use super::*;
+
+    /// Records the labels of the tasks spawned, which all succeed right away.
+    struct FakeTerminalProvider(Arc<Mutex<Vec<String>>>);
+
+    impl TerminalProvider for FakeTerminalProvider {
+        fn spawn(
+            &self,
+            task: SpawnInTerminal,
+            _: &mut Window,
+            _: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            self.0.lock().push(task.label);
+            Task::ready(Some(Ok(ExitStatus::default())))
+        }
+    }
+
+    #[gpui::test]
+    async fn test_shared_dependency_runs_once(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let inventory = project.read_with(cx, |project, cx| {
+            project
+                .task_store()
+                .read(cx)
+                .task_inventory()
+                .cloned()
+                .unwrap()
+        });
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Global(Path::new(path!("/config/tasks.json"))),
+                    Some(
+                        &json!([
+                            { "label": "build", "command": "make" },
+                            { "label": "lint", "command": "make lint", "depends_on": ["build"] },
+                            { "label": "test", "command": "make test", "depends_on": ["build"] },
+                            {
+                                "label": "check",
+                                "depends_on": ["lint", "test"],
+                                "depends_order": "parallel"
+                            },
+                        ])
+                        .to_string(),
+                    ),
+                )
+                .unwrap();
+        });
+        let check = inventory
+            .update(cx, |inventory, cx| {
+                inventory.list_tasks(None, None, None, cx)
+            })
+            .await
+            .into_iter()
+            .find(|(_, template)| template.label == "check")
+            .unwrap();
+
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project, window, cx));
+        let workspace = multi_workspace.read_with(cx, |multi, _| multi.workspace().clone());
+        let spawned = Arc::new(Mutex::new(Vec::new()));
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(FakeTerminalProvider(spawned.clone()));
+        });
+
+        let (source_kind, template) = check;
+        let resolved_task = template
+            .resolve_task(&source_kind.to_id_base(), &TaskContext::default())
+            .unwrap();
+        workspace.update_in(cx, |workspace, window, cx| {
+            schedule_task_with_dependencies(
+                workspace,
+                source_kind,
+                resolved_task,
+                &TaskContexts::default(),
+                false,
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+
+        let spawned = spawned.lock().clone();
+        assert_eq!(
+            spawned.iter().filter(|label| *label == "build").count(),
+            1,
+            "The dependency of both tasks should run once, got {spawned:?}"
+        );
+        assert_eq!(spawned.first().map(String::as_str), Some("build"));
+        assert_eq!(spawned.len(), 3, "{spawned:?}");
+    }
+}
@@ -8,8 +8,10 @@ use task::{RevealTarget, TaskContext, TaskId, TaskTemplate, TaskVariables, Varia
 use workspace::Workspace;
 
 mod modal;
+mod task_dependencies;
 
 pub use modal::{Rerun, ShowAttachModal, Spawn, TaskOverrides, TasksModal};
+pub use task_dependencies::schedule_task_with_dependencies;
 
 pub fn init(cx: &mut App) {
     cx.observe_new(
@@ -47,12 +49,33 @@ pub fn init(cx: &mut App) {
                                 let default_context = TaskContext::default();
                                 workspace
                                     .update_in(cx, |workspace, window, cx| {
-                                        workspace.schedule_task(
+                                        schedule_task(
+                                            workspace,
                                             task_source_kind,
                                             &original_task,
+                                            &task_contexts,
                                             task_contexts
                                                 .active_context()
                                                 .unwrap_or(&default_context),
+                                            window,
+                                            cx,
+                                        )
+                                    })
+                                    .ok()
+                            })
+                            .detach()
+                        } else if !last_scheduled_task.original_task().depends_on.is_empty() {
+                            // The dependencies are resolved anew, and need the current contexts for that.
+                            let task_contexts = task_contexts(workspace, window, cx);
+                            cx.spawn_in(window, async move |workspace, cx| {
+                                let task_contexts = task_contexts.await;
+                                workspace
+                                    .update_in(cx, |workspace, window, cx| {
+                                        schedule_task_with_dependencies(
+                                            workspace,
+                                            task_source_kind,
+                                            last_scheduled_task,
+                                            &task_contexts,
                                             false,
                                             window,
                                             cx,
@@ -232,11 +255,12 @@ where
                         {
                             target_task.reveal_target = target_override;
                         }
-                        workspace.schedule_task(
+                        schedule_task(
+                            workspace,
                             task_source_kind.clone(),
                             target_task,
+                            &task_contexts,
                             active_context,
-                            false,
                             window,
                             cx,
                         );
@@ -268,6 +292,33 @@ where
     })
 }
 
+/// Resolves and schedules `task`, along with the tasks it depends on.
+fn schedule_task(
+    workspace: &mut Workspace,
+    task_source_kind: TaskSourceKind,
+    task: &TaskTemplate,
+    task_contexts: &TaskContexts,
+    task_context: &TaskContext,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    if task.depends_on.is_empty() {
+        workspace.schedule_task(task_source_kind, task, task_context, false, window, cx);
+    } else if let Some(resolved_task) =
+        task.resolve_task(&task_source_kind.to_id_base(), task_context)
+    {
+        schedule_task_with_dependencies(
+            workspace,
+            task_source_kind,
+            resolved_task,
+            task_contexts,
+            false,
+            window,
+            cx,
+        );
+    }
+}
+
 pub fn task_contexts(
     workspace: &Workspace,
     window: &mut Window,
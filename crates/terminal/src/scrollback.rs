@@ -0,0 +1,150 @@
+//! Serializes terminal lines into text with the escape sequences that reproduce their colors
+//! and styles, so that they can be replayed into another terminal later.
+
+use std::fmt::Write as _;
+
+use alacritty_terminal::{
+    Term,
+    grid::Dimensions as _,
+    index::{Column, Line},
+    term::cell::{Cell, Flags},
+    vte::ansi::{Color, NamedColor},
+};
+
+/// The flags that are reproduced through SGR parameters, along with their parameter.
+const STYLE_FLAGS: [(Flags, u8); 7] = [
+    (Flags::BOLD, 1),
+    (Flags::DIM, 2),
+    (Flags::ITALIC, 3),
+    (Flags::ALL_UNDERLINES, 4),
+    (Flags::INVERSE, 7),
+    (Flags::HIDDEN, 8),
+    (Flags::STRIKEOUT, 9),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+struct Style {
+    fg: Color,
+    bg: Color,
+    flags: Flags,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fg: Color::Named(NamedColor::Foreground),
+            bg: Color::Named(NamedColor::Background),
+            flags: Flags::empty(),
+        }
+    }
+}
+
+impl Style {
+    fn of(cell: &Cell) -> Self {
+        let style_flags = STYLE_FLAGS
+            .iter()
+            .fold(Flags::empty(), |flags, (flag, _)| flags | *flag);
+        Self {
+            fg: cell.fg,
+            bg: cell.bg,
+            flags: cell.flags & style_flags,
+        }
+    }
+
+    fn write_sgr(&self, output: &mut String) {
+        output.push_str("\x1b[0");
+        for (flag, parameter) in STYLE_FLAGS {
+            if self.flags.intersects(flag) {
+                write!(output, ";{parameter}").ok();
+            }
+        }
+        write_color(output, self.fg, 30, 90, 38);
+        write_color(output, self.bg, 40, 100, 48);
+        output.push('m');
+    }
+}
+
+fn write_color(output: &mut String, color: Color, base: usize, bright_base: usize, extended: u8) {
+    match color {
+        Color::Named(named) => {
+            // Only the 16 palette colors have SGR parameters, the others are the defaults.
+            let index = named as usize;
+            if index < 8 {
+                write!(output, ";{}", base + index).ok();
+            } else if index < 16 {
+                write!(output, ";{}", bright_base + index - 8).ok();
+            }
+        }
+        Color::Indexed(index) => {
+            write!(output, ";{extended};5;{index}").ok();
+        }
+        Color::Spec(rgb) => {
+            write!(output, ";{extended};2;{};{};{}", rgb.r, rgb.g, rgb.b).ok();
+        }
+    }
+}
+
+fn is_blank(cell: &Cell) -> bool {
+    cell.c == ' '
+        && cell.bg == Color::Named(NamedColor::Background)
+        && !cell
+            .flags
+            .intersects(Flags::INVERSE | Flags::ALL_UNDERLINES | Flags::STRIKEOUT)
+}
+
+/// Serializes `lines` of the terminal's grid. Every line that doesn't wrap onto the next one
+/// ends with `\r\n`, and trailing blank cells are dropped so that the text re-wraps at any width.
+pub(crate) fn serialize_lines<T>(term: &Term<T>, lines: impl IntoIterator<Item = Line>) -> String {
+    let grid = term.grid();
+    let columns = grid.columns();
+    let mut output = String::new();
+    let mut style = Style::default();
+
+    for line in lines {
+        let row = &grid[line];
+        let wraps = columns
+            .checked_sub(1)
+            .is_some_and(|last_column| row[Column(last_column)].flags.contains(Flags::WRAPLINE));
+        let length = if wraps {
+            columns
+        } else {
+            (0..columns)
+                .rev()
+                .find(|&column| !is_blank(&row[Column(column)]))
+                .map_or(0, |column| column + 1)
+        };
+
+        for column in 0..length {
+            let cell = &row[Column(column)];
+            if cell
+                .flags
+                .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+            {
+                continue;
+            }
+
+            let cell_style = Style::of(cell);
+            if cell_style != style {
+                cell_style.write_sgr(&mut output);
+                style = cell_style;
+            }
+            output.push(cell.c);
+            if let Some(zero_width) = cell.zerowidth() {
+                output.extend(zero_width);
+            }
+        }
+
+        if !wraps {
+            if style != Style::default() {
+                output.push_str("\x1b[0m");
+                style = Style::default();
+            }
+            output.push_str("\r\n");
+        }
+    }
+
+    if style != Style::default() {
+        output.push_str("\x1b[0m");
+    }
+    output
+}
@@ -0,0 +1,379 @@
+//! Semantic prompt tracking through the `OSC 133` shell integration sequences.
+//!
+//! Shells that support them (fish, and bash/zsh through integration scripts or prompts like
+//! starship) wrap every prompt and command with:
+//!
+//! - `OSC 133 ; A ST` before drawing the prompt,
+//! - `OSC 133 ; B ST` once the prompt is drawn and the user starts typing,
+//! - `OSC 133 ; C ST` after the command was submitted, before its output,
+//! - `OSC 133 ; D [; <exit code>] ST` once the command finished.
+//!
+//! Alacritty drops these sequences, so the PTY output is teed through a
+//! [`ShellIntegrationParser`] before alacritty sees it. Since that happens before the output is
+//! drawn, each mark records how many line feeds preceded it, which places it relative to the
+//! cursor once alacritty caught up. Output read while the terminal is locked for rendering is
+//! counted before alacritty parses it, so marks in it may land a few lines too high.
+
+use std::{
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use alacritty_terminal::{
+    event::{OnResize, WindowSize},
+    tty::{self, ChildEvent, EventedPty, EventedReadWrite},
+};
+use futures::channel::mpsc::UnboundedSender;
+use polling::{Event, PollMode, Poller};
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+
+/// Payloads longer than this can't be a shell integration mark, so the parser gives up on them
+/// instead of buffering arbitrarily large OSC sequences (like clipboard writes).
+const MAX_PAYLOAD_LEN: usize = 128;
+
+/// A semantic prompt mark emitted by the shell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShellMark {
+    /// `OSC 133 ; A`: the shell is about to draw its prompt.
+    PromptStart,
+    /// `OSC 133 ; B`: the prompt is drawn and the user is typing a command.
+    CommandStart,
+    /// `OSC 133 ; C`: the command was submitted and its output follows.
+    OutputStart,
+    /// `OSC 133 ; D`: the command finished, optionally reporting its exit code.
+    CommandFinished { exit_code: Option<i32> },
+}
+
+/// A [`ShellMark`] along with its position in the PTY output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PositionedShellMark {
+    pub mark: ShellMark,
+    /// How many line feeds the shell wrote before the mark.
+    pub line_feeds: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Ground,
+    Escape,
+    Osc,
+    OscEscape,
+}
+
+/// Finds `OSC 133` marks in a stream of PTY output.
+///
+/// Sequences may be split across any number of chunks; everything that is not a shell
+/// integration mark is skipped.
+pub struct ShellIntegrationParser {
+    state: State,
+    payload: Vec<u8>,
+    line_feeds: usize,
+}
+
+impl Default for ShellIntegrationParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShellIntegrationParser {
+    pub fn new() -> Self {
+        Self {
+            state: State::Ground,
+            payload: Vec::with_capacity(MAX_PAYLOAD_LEN),
+            line_feeds: 0,
+        }
+    }
+
+    /// How many line feeds were fed to the parser so far.
+    pub fn line_feeds(&self) -> usize {
+        self.line_feeds
+    }
+
+    /// Feeds the next chunk of PTY output, returning the marks completed within it.
+    pub fn advance(&mut self, bytes: &[u8]) -> Vec<PositionedShellMark> {
+        let mut marks = Vec::new();
+        for &byte in bytes {
+            // Like alacritty, treat vertical tabs and form feeds as line feeds, which are
+            // ignored within OSC strings.
+            if matches!(byte, b'\n' | 0x0b | 0x0c)
+                && matches!(self.state, State::Ground | State::Escape)
+            {
+                self.line_feeds += 1;
+            }
+            let line_feeds = self.line_feeds;
+            let mut complete = |payload: &[u8]| {
+                marks.extend(
+                    parse_payload(payload).map(|mark| PositionedShellMark { mark, line_feeds }),
+                )
+            };
+            self.state = match (self.state, byte) {
+                (State::Ground, ESC) => State::Escape,
+                (State::Ground, _) => State::Ground,
+                (State::Escape, b']') => {
+                    self.payload.clear();
+                    State::Osc
+                }
+                (State::Escape, ESC) => State::Escape,
+                (State::Escape, _) => State::Ground,
+                (State::Osc, BEL) => {
+                    complete(&self.payload);
+                    State::Ground
+                }
+                (State::Osc, ESC) => State::OscEscape,
+                (State::Osc, _) => {
+                    if self.payload.len() < MAX_PAYLOAD_LEN {
+                        self.payload.push(byte);
+                    }
+                    State::Osc
+                }
+                (State::OscEscape, b'\\') => {
+                    complete(&self.payload);
+                    State::Ground
+                }
+                // An escape inside an OSC aborts it and may start the next sequence.
+                (State::OscEscape, ESC) => State::Escape,
+                (State::OscEscape, b']') => {
+                    self.payload.clear();
+                    State::Osc
+                }
+                (State::OscEscape, _) => State::Ground,
+            };
+        }
+        marks
+    }
+}
+
+fn parse_payload(payload: &[u8]) -> Option<ShellMark> {
+    if payload.len() >= MAX_PAYLOAD_LEN {
+        return None;
+    }
+    let payload = std::str::from_utf8(payload).ok()?;
+    let mut params = payload.strip_prefix("133;")?.split(';');
+    match params.next()? {
+        "A" => Some(ShellMark::PromptStart),
+        "B" => Some(ShellMark::CommandStart),
+        "C" => Some(ShellMark::OutputStart),
+        "D" => Some(ShellMark::CommandFinished {
+            // Extra `key=value` options may follow, but the exit code always comes first.
+            exit_code: params.next().and_then(|code| code.parse().ok()),
+        }),
+        _ => None,
+    }
+}
+
+/// The PTY handed to alacritty's event loop, which reports the shell integration marks found in
+/// everything read from the shell, along with the number of line feeds read so far.
+pub(crate) struct ShellIntegrationPty {
+    pty: tty::Pty,
+    parser: ShellIntegrationParser,
+    marks_tx: UnboundedSender<PositionedShellMark>,
+    line_feeds: Arc<AtomicUsize>,
+}
+
+impl ShellIntegrationPty {
+    pub(crate) fn new(
+        pty: tty::Pty,
+        marks_tx: UnboundedSender<PositionedShellMark>,
+        line_feeds: Arc<AtomicUsize>,
+    ) -> Self {
+        Self {
+            pty,
+            parser: ShellIntegrationParser::new(),
+            marks_tx,
+            line_feeds,
+        }
+    }
+}
+
+impl io::Read for ShellIntegrationPty {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.pty.reader().read(buf)?;
+        for mark in self.parser.advance(&buf[..read]) {
+            self.marks_tx.unbounded_send(mark).ok();
+        }
+        self.line_feeds
+            .store(self.parser.line_feeds(), Ordering::Release);
+        Ok(read)
+    }
+}
+
+impl EventedReadWrite for ShellIntegrationPty {
+    type Reader = Self;
+    type Writer = <tty::Pty as EventedReadWrite>::Writer;
+
+    unsafe fn register(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        unsafe { self.pty.register(poll, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.pty.reregister(poll, interest, mode)
+    }
+
+    fn deregister(&mut self, poll: &Arc<Poller>) -> io::Result<()> {
+        self.pty.deregister(poll)
+    }
+
+    fn reader(&mut self) -> &mut Self::Reader {
+        self
+    }
+
+    fn writer(&mut self) -> &mut Self::Writer {
+        self.pty.writer()
+    }
+}
+
+impl EventedPty for ShellIntegrationPty {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.pty.next_child_event()
+    }
+}
+
+impl OnResize for ShellIntegrationPty {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.pty.on_resize(window_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_chunks(chunks: &[&[u8]]) -> Vec<ShellMark> {
+        let mut parser = ShellIntegrationParser::new();
+        chunks
+            .iter()
+            .flat_map(|chunk| parser.advance(chunk))
+            .map(|mark| mark.mark)
+            .collect()
+    }
+
+    #[test]
+    fn test_records_line_feeds_before_marks() {
+        let mut parser = ShellIntegrationParser::new();
+        let marks = parser.advance(
+            b"\x1b]133;C\x07one\r\ntwo\r\n\x1b]0;multi\nline title\x07\x1b]133;D;0\x07\r\n\x1b]133;A\x07$ ",
+        );
+        assert_eq!(
+            marks,
+            vec![
+                PositionedShellMark {
+                    mark: ShellMark::OutputStart,
+                    line_feeds: 0,
+                },
+                PositionedShellMark {
+                    mark: ShellMark::CommandFinished { exit_code: Some(0) },
+                    line_feeds: 2,
+                },
+                PositionedShellMark {
+                    mark: ShellMark::PromptStart,
+                    line_feeds: 3,
+                },
+            ]
+        );
+        assert_eq!(parser.line_feeds(), 3);
+    }
+
+    #[test]
+    fn test_parses_a_full_command_cycle() {
+        let stream = b"\x1b]133;A\x07~/zed $ \x1b]133;B\x07ls\r\n\x1b]133;C\x07Cargo.toml\r\n\x1b]133;D;0\x07\x1b]133;A\x07~/zed $ \x1b]133;B\x07";
+
+        assert_eq!(
+            parse_chunks(&[stream]),
+            vec![
+                ShellMark::PromptStart,
+                ShellMark::CommandStart,
+                ShellMark::OutputStart,
+                ShellMark::CommandFinished { exit_code: Some(0) },
+                ShellMark::PromptStart,
+                ShellMark::CommandStart,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_accepts_both_terminators() {
+        assert_eq!(
+            parse_chunks(&[b"\x1b]133;A\x1b\\prompt\x1b]133;D;2\x1b\\"]),
+            vec![
+                ShellMark::PromptStart,
+                ShellMark::CommandFinished { exit_code: Some(2) },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sequences_split_across_chunks() {
+        let stream = b"output\x1b]133;D;127\x1b\\\x1b]133;A\x07";
+        let expected = vec![
+            ShellMark::CommandFinished {
+                exit_code: Some(127),
+            },
+            ShellMark::PromptStart,
+        ];
+
+        for split in 0..stream.len() {
+            let (first, second) = stream.split_at(split);
+            assert_eq!(parse_chunks(&[first, second]), expected, "split at {split}");
+        }
+        let bytes = stream.iter().map(std::slice::from_ref).collect::<Vec<_>>();
+        assert_eq!(parse_chunks(&bytes), expected);
+    }
+
+    #[test]
+    fn test_exit_code_and_options() {
+        assert_eq!(
+            parse_chunks(&[
+                b"\x1b]133;D\x07",
+                b"\x1b]133;D;1;aid=42\x07",
+                b"\x1b]133;D;garbage\x07",
+                b"\x1b]133;A;cl=m;aid=42\x07",
+            ]),
+            vec![
+                ShellMark::CommandFinished { exit_code: None },
+                ShellMark::CommandFinished { exit_code: Some(1) },
+                ShellMark::CommandFinished { exit_code: None },
+                ShellMark::PromptStart,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_unrelated_sequences() {
+        let mut long_osc = b"\x1b]52;c;".to_vec();
+        long_osc.extend(std::iter::repeat_n(b'A', MAX_PAYLOAD_LEN * 4));
+        long_osc.extend(b"\x07");
+
+        assert_eq!(
+            parse_chunks(&[
+                b"\x1b]0;title\x07\x1b[31mred\x1b[0m]133;A\x07",
+                &long_osc,
+                b"\x1b]1337;A\x07\x1b]133;Z\x07\x1b]133A\x07",
+            ]),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn test_escape_inside_osc_restarts_parsing() {
+        assert_eq!(
+            parse_chunks(&[b"\x1b]0;unterminated title\x1b]133;B\x07"]),
+            vec![ShellMark::CommandStart]
+        );
+    }
+}
@@ -3,6 +3,8 @@ pub mod mappings;
 pub use alacritty_terminal;
 
 mod pty_info;
+mod scrollback;
+pub mod shell_integration;
 mod terminal_hyperlinks;
 pub mod terminal_settings;
 
@@ -44,6 +46,7 @@ use futures::StreamExt;
 use pty_info::{ProcessIdGetter, PtyProcessInfo};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
+use shell_integration::{PositionedShellMark, ShellIntegrationPty, ShellMark};
 use smol::channel::{Receiver, Sender};
 use task::{HideStrategy, Shell, SpawnInTerminal};
 use terminal_hyperlinks::RegexSearches;
@@ -61,7 +64,10 @@ use std::{
     ops::{Deref, RangeInclusive},
     path::PathBuf,
     process::ExitStatus,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{self, AtomicUsize},
+    },
     time::{Duration, Instant},
 };
 use thiserror::Error;
@@ -107,6 +113,16 @@ actions!(
         ToggleViMode,
         /// Selects all text in the terminal.
         SelectAll,
+        /// Scrolls to the previous shell prompt.
+        ScrollToPreviousPrompt,
+        /// Scrolls to the next shell prompt.
+        ScrollToNextPrompt,
+        /// Selects the output of the last command the shell finished.
+        SelectLastCommandOutput,
+        /// Copies the output of the last command the shell finished.
+        CopyLastCommandOutput,
+        /// Runs the last command the shell finished again.
+        RerunLastCommand,
     ]
 );
 
@@ -140,6 +156,10 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
+    /// The shell reported through its integration marks that a command finished.
+    CommandFinished {
+        exit_code: Option<i32>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -412,6 +432,10 @@ impl TerminalBuilder {
                 window_id,
             },
             child_exited: None,
+            shell_marks_rx: None,
+            shell_line_feeds: Arc::default(),
+            shell_lines_synced: None,
+            command_blocks: Vec::new(),
             event_loop_task: Task::ready(Ok(())),
             background_executor: background_executor.clone(),
             path_style,
@@ -586,6 +610,10 @@ impl TerminalBuilder {
 
             let pty_info = PtyProcessInfo::new(&pty);
 
+            let (shell_marks_tx, shell_marks_rx) = unbounded();
+            let shell_line_feeds = Arc::new(AtomicUsize::new(0));
+            let pty = ShellIntegrationPty::new(pty, shell_marks_tx, shell_line_feeds.clone());
+
             //And connect them together
             let event_loop = EventLoop::new(
                 term.clone(),
@@ -643,6 +671,10 @@ impl TerminalBuilder {
                     window_id,
                 },
                 child_exited: None,
+                shell_marks_rx: Some(shell_marks_rx),
+                shell_line_feeds,
+                shell_lines_synced: None,
+                command_blocks: Vec::new(),
                 event_loop_task: Task::ready(Ok(())),
                 background_executor,
                 path_style,
@@ -867,6 +899,13 @@ pub struct Terminal {
     template: CopyTemplate,
     activation_script: Vec<String>,
     child_exited: Option<ExitStatus>,
+    shell_marks_rx: Option<UnboundedReceiver<PositionedShellMark>>,
+    /// How many line feeds the shell wrote, as counted by the PTY reader.
+    shell_line_feeds: Arc<AtomicUsize>,
+    /// The line feed count and the cursor line when the marks were last processed, to notice
+    /// lines dropped from the top of a full scrollback.
+    shell_lines_synced: Option<(usize, usize)>,
+    command_blocks: Vec<CommandBlock>,
     event_loop_task: Task<Result<(), anyhow::Error>>,
     background_executor: BackgroundExecutor,
     path_style: PathStyle,
@@ -916,6 +955,55 @@ impl TaskStatus {
     }
 }
 
+/// A command the shell announced through its `OSC 133` integration marks.
+///
+/// Lines are counted from the top of the scrollback, and move up as lines are dropped from the
+/// top of a full scrollback.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandBlock {
+    /// The line the prompt was drawn on.
+    pub prompt_line: usize,
+    /// The line and column where the user started typing the command.
+    pub command_start: Option<(usize, usize)>,
+    /// The first line of the command's output.
+    pub output_start: Option<usize>,
+    /// The line after the last line of the command's output.
+    pub output_end: Option<usize>,
+    pub status: CommandStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandStatus {
+    /// The prompt is shown and the user may be typing a command.
+    Prompt,
+    /// The command was submitted and is running.
+    Running,
+    /// The command finished, the shell may not report an exit code.
+    Finished { exit_code: Option<i32> },
+}
+
+impl CommandBlock {
+    fn new(prompt_line: usize) -> Self {
+        Self {
+            prompt_line,
+            command_start: None,
+            output_start: None,
+            output_end: None,
+            status: CommandStatus::Prompt,
+        }
+    }
+
+    pub fn exit_code(&self) -> Option<i32> {
+        match self.status {
+            CommandStatus::Finished { exit_code } => exit_code,
+            CommandStatus::Prompt | CommandStatus::Running => None,
+        }
+    }
+}
+
+/// How many commands are remembered per terminal; older ones are likely out of the scrollback.
+const MAX_COMMAND_BLOCKS: usize = 1000;
+
 const FIND_HYPERLINK_THROTTLE_PX: Pixels = px(5.0);
 
 impl Terminal {
@@ -973,6 +1061,7 @@ impl Terminal {
                 //NOOP, Handled in render
             }
             AlacTermEvent::Wakeup => {
+                self.process_shell_marks(cx);
                 cx.emit(Event::Wakeup);
 
                 if let TerminalType::Pty { info, .. } = &self.terminal_type {
@@ -1061,6 +1150,19 @@ impl Terminal {
                     term.grid_mut().reset_region((new_cursor.line + 1)..);
                 }
 
+                // Only the prompt on the cursor line survives, and it is now the first line.
+                let current_prompt = self
+                    .command_blocks
+                    .pop()
+                    .filter(|block| block.status == CommandStatus::Prompt);
+                self.command_blocks.clear();
+                self.shell_lines_synced = None;
+                if let Some(mut block) = current_prompt {
+                    block.prompt_line = 0;
+                    block.command_start = block.command_start.map(|(_, column)| (0, column));
+                    self.command_blocks.push(block);
+                }
+
                 cx.emit(Event::Wakeup);
             }
             InternalEvent::Scroll(scroll) => {
@@ -1318,6 +1420,62 @@ impl Terminal {
         self.term.lock_unfair().total_lines()
     }
 
+    /// Serializes up to `max_lines` of the lines above the cursor, including their colors, so
+    /// that they can be replayed with [`Self::restore_scrollback`] after a restart.
+    ///
+    /// The cursor line is left out as it usually holds the prompt the next shell prints again.
+    pub fn scrollback_snapshot(&self, max_lines: usize) -> Option<String> {
+        let term = self.term.lock_unfair();
+        if term.mode().contains(TermMode::ALT_SCREEN) {
+            return None;
+        }
+        let cursor_line = term.grid().cursor.point.line;
+        let max_lines = i32::try_from(max_lines).unwrap_or(i32::MAX);
+        let start = cmp::max(
+            term.topmost_line().0,
+            cursor_line.0.saturating_sub(max_lines),
+        );
+        let snapshot = scrollback::serialize_lines(&term, (start..cursor_line.0).map(Line));
+        (!snapshot.trim().is_empty()).then_some(snapshot)
+    }
+
+    /// Replays a snapshot taken by [`Self::scrollback_snapshot`] at the top of the terminal,
+    /// followed by anything the shell printed in the meantime.
+    pub fn restore_scrollback(&mut self, snapshot: &str, cx: &mut Context<Self>) {
+        let term = self.term.clone();
+        let mut term = term.lock();
+        let cursor = term.grid().cursor.point;
+        let cursor_line = term.history_size() + cursor.line.0.max(0) as usize;
+        let printed =
+            scrollback::serialize_lines(&term, (term.topmost_line().0..=cursor.line.0).map(Line));
+        let printed = printed.strip_suffix("\r\n").unwrap_or(&printed);
+
+        term.clear_screen(ClearMode::Saved);
+        term.grid_mut().reset_region(..);
+        term.grid_mut().cursor.point = AlacPoint::new(Line(0), Column(0));
+
+        let mut processor = alacritty_terminal::vte::ansi::Processor::<
+            alacritty_terminal::vte::ansi::StdSyncHandler,
+        >::new();
+        processor.advance(&mut *term, snapshot.as_bytes());
+        processor.advance(&mut *term, printed.as_bytes());
+        term.grid_mut().cursor.point.column = cursor.column;
+
+        // Prompts the shell already announced moved down along with their lines.
+        let restored_lines = (term.history_size()
+            + term.grid().cursor.point.line.0.max(0) as usize)
+            .saturating_sub(cursor_line);
+        for block in &mut self.command_blocks {
+            block.prompt_line += restored_lines;
+            if let Some((line, _)) = block.command_start.as_mut() {
+                *line += restored_lines;
+            }
+        }
+        drop(term);
+
+        cx.emit(Event::Wakeup);
+    }
+
     pub fn viewport_lines(&self) -> usize {
         self.term.lock_unfair().screen_lines()
     }
@@ -1423,6 +1581,265 @@ impl Terminal {
         self.last_content.scrolled_to_bottom
     }
 
+    /// Applies the shell integration marks read from the PTY since the last wakeup.
+    ///
+    /// The output that followed a mark may already be drawn, so each mark is placed as many lines
+    /// above the cursor as the shell wrote line feeds after it.
+    fn process_shell_marks(&mut self, cx: &mut Context<Self>) {
+        let Some(marks_rx) = self.shell_marks_rx.as_mut() else {
+            return;
+        };
+        let mut marks = Vec::new();
+        while let Ok(Some(mark)) = marks_rx.try_next() {
+            marks.push(mark);
+        }
+
+        let term = self.term.clone();
+        let term = term.lock();
+        let line_feeds = self.shell_line_feeds.load(atomic::Ordering::Acquire);
+        let cursor = term.grid().cursor.point;
+        let cursor_line = term.history_size() + cursor.line.0.max(0) as usize;
+
+        // Once the scrollback is full, every line scrolled into it drops one from its top.
+        if let Some((synced_line_feeds, synced_line)) = self.shell_lines_synced
+            && term.history_size() >= self.term_config.scrolling_history
+        {
+            let scrolled_lines = line_feeds.saturating_sub(synced_line_feeds);
+            let dropped_lines =
+                scrolled_lines.saturating_sub(cursor_line.saturating_sub(synced_line));
+            self.drop_command_block_lines(dropped_lines);
+        }
+        self.shell_lines_synced = Some((line_feeds, cursor_line));
+
+        for PositionedShellMark {
+            mark,
+            line_feeds: mark_line_feeds,
+        } in marks
+        {
+            let lines_below = line_feeds.saturating_sub(mark_line_feeds);
+            let mark_line = cursor_line.saturating_sub(lines_below);
+            match mark {
+                ShellMark::PromptStart => {
+                    match self.command_blocks.last_mut() {
+                        // Nothing ran since the previous prompt, e.g. it was submitted empty.
+                        Some(block) if block.status == CommandStatus::Prompt => {
+                            self.command_blocks.pop();
+                        }
+                        // The shell doesn't report when commands finish.
+                        Some(block) if block.status == CommandStatus::Running => {
+                            block.output_end = Some(mark_line);
+                            block.status = CommandStatus::Finished { exit_code: None };
+                        }
+                        _ => {}
+                    }
+                    self.command_blocks.push(CommandBlock::new(mark_line));
+                }
+                ShellMark::CommandStart => {
+                    if !self
+                        .command_blocks
+                        .last()
+                        .is_some_and(|block| block.status == CommandStatus::Prompt)
+                    {
+                        self.command_blocks.push(CommandBlock::new(mark_line));
+                    }
+                    // The column is only known while the cursor is still on the mark's line.
+                    if let Some(block) = self.command_blocks.last_mut() {
+                        block.command_start =
+                            (lines_below == 0).then_some((mark_line, cursor.column.0));
+                    }
+                }
+                ShellMark::OutputStart => {
+                    if let Some(block) = self.command_blocks.last_mut()
+                        && block.status == CommandStatus::Prompt
+                    {
+                        // The output starts right after the (possibly wrapped) line the command
+                        // was typed on.
+                        let command_line = block
+                            .command_start
+                            .map_or(block.prompt_line, |(line, _)| line);
+                        let command_start = grid_line(&term, command_line);
+                        let wrapped_lines =
+                            logical_line_end(term.grid(), command_start).0 - command_start.0;
+                        block.output_start = Some(command_line + 1 + wrapped_lines as usize);
+                        block.status = CommandStatus::Running;
+                    }
+                }
+                ShellMark::CommandFinished { exit_code } => {
+                    if let Some(block) = self.command_blocks.last_mut()
+                        && block.status == CommandStatus::Running
+                    {
+                        block.output_end = Some(mark_line.max(block.output_start.unwrap_or(0)));
+                        block.status = CommandStatus::Finished { exit_code };
+                        cx.emit(Event::CommandFinished { exit_code });
+                    }
+                }
+            }
+        }
+
+        if self.command_blocks.len() > MAX_COMMAND_BLOCKS {
+            let excess = self.command_blocks.len() - MAX_COMMAND_BLOCKS;
+            self.command_blocks.drain(..excess);
+        }
+    }
+
+    /// Moves the commands up by the number of lines dropped from the top of the scrollback,
+    /// forgetting those whose prompt was dropped.
+    fn drop_command_block_lines(&mut self, lines: usize) {
+        if lines == 0 {
+            return;
+        }
+        self.command_blocks.retain_mut(|block| {
+            let Some(prompt_line) = block.prompt_line.checked_sub(lines) else {
+                return false;
+            };
+            block.prompt_line = prompt_line;
+            if let Some((line, _)) = block.command_start.as_mut() {
+                *line = line.saturating_sub(lines);
+            }
+            for line in [&mut block.output_start, &mut block.output_end]
+                .into_iter()
+                .flatten()
+            {
+                *line = line.saturating_sub(lines);
+            }
+            true
+        });
+    }
+
+    /// The commands the shell reported through its integration marks, oldest first.
+    pub fn command_blocks(&self) -> &[CommandBlock] {
+        &self.command_blocks
+    }
+
+    pub fn last_finished_command(&self) -> Option<&CommandBlock> {
+        self.command_blocks
+            .iter()
+            .rev()
+            .find(|block| matches!(block.status, CommandStatus::Finished { .. }))
+    }
+
+    /// The prompts within the viewport, as lines relative to its top.
+    pub fn visible_prompts(&self) -> Vec<(usize, CommandStatus)> {
+        let term = self.term.lock_unfair();
+        let top_line = term.history_size() - term.grid().display_offset();
+        let screen_lines = term.screen_lines();
+        self.command_blocks
+            .iter()
+            .filter_map(|block| {
+                let line = block.prompt_line.checked_sub(top_line)?;
+                (line < screen_lines).then_some((line, block.status))
+            })
+            .collect()
+    }
+
+    /// The command line the user submitted for `block`.
+    pub fn command_text(&self, block: &CommandBlock) -> Option<String> {
+        let (line, column) = block.command_start?;
+        let term = self.term.lock_unfair();
+        let start = AlacPoint::new(grid_line(&term, line), Column(column));
+        if start.line < term.topmost_line() {
+            return None;
+        }
+        let end_line = match block.output_start {
+            Some(output_start) => Line(grid_line(&term, output_start).0 - 1),
+            None => logical_line_end(term.grid(), start.line),
+        }
+        .min(term.bottommost_line());
+        if end_line < start.line {
+            return None;
+        }
+
+        let text = term.bounds_to_string(start, AlacPoint::new(end_line, term.last_column()));
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// The output `block` printed, or `None` if it printed nothing or has left the scrollback.
+    pub fn command_output(&self, block: &CommandBlock) -> Option<String> {
+        let range = self.command_output_range(block)?;
+        Some(
+            self.term
+                .lock_unfair()
+                .bounds_to_string(*range.start(), *range.end()),
+        )
+    }
+
+    fn command_output_range(&self, block: &CommandBlock) -> Option<RangeInclusive<AlacPoint>> {
+        let term = self.term.lock_unfair();
+        let start = grid_line(&term, block.output_start?).max(term.topmost_line());
+        let end = Line(grid_line(&term, block.output_end?).0 - 1).min(term.bottommost_line());
+        (start <= end)
+            .then(|| AlacPoint::new(start, Column(0))..=AlacPoint::new(end, term.last_column()))
+    }
+
+    pub fn select_command_output(&mut self, block: &CommandBlock) {
+        if let Some(range) = self.command_output_range(block) {
+            self.set_selection(Some((make_selection(&range), *range.end())));
+            self.events
+                .push_back(InternalEvent::ScrollToAlacPoint(*range.start()));
+        }
+    }
+
+    /// Types the last finished command at the prompt and submits it again.
+    pub fn rerun_last_command(&mut self) {
+        let at_prompt = self
+            .command_blocks
+            .last()
+            .is_some_and(|block| block.status == CommandStatus::Prompt);
+        if !at_prompt {
+            return;
+        }
+        if let Some(command) = self
+            .last_finished_command()
+            .and_then(|block| self.command_text(block))
+        {
+            self.input(format!("{command}\r").into_bytes());
+        }
+    }
+
+    /// Scrolls the closest prompt above the top of the viewport to the top.
+    pub fn scroll_to_previous_prompt(&mut self) {
+        let (history_size, display_offset) = self.history_size_and_display_offset();
+        let top_line = history_size - display_offset;
+        if let Some(prompt_line) = self
+            .command_blocks
+            .iter()
+            .rev()
+            .map(|block| block.prompt_line)
+            .find(|&line| line < top_line)
+        {
+            self.scroll_line_to_top(prompt_line, history_size, display_offset);
+        }
+    }
+
+    /// Scrolls the closest prompt below the top of the viewport to the top, or to the bottom
+    /// when there is none.
+    pub fn scroll_to_next_prompt(&mut self) {
+        let (history_size, display_offset) = self.history_size_and_display_offset();
+        let top_line = history_size - display_offset;
+        match self
+            .command_blocks
+            .iter()
+            .map(|block| block.prompt_line)
+            .find(|&line| line > top_line)
+        {
+            Some(prompt_line) => self.scroll_line_to_top(prompt_line, history_size, display_offset),
+            None => self.scroll_to_bottom(),
+        }
+    }
+
+    fn history_size_and_display_offset(&self) -> (usize, usize) {
+        let term = self.term.lock_unfair();
+        (term.history_size(), term.grid().display_offset())
+    }
+
+    fn scroll_line_to_top(&mut self, line: usize, history_size: usize, display_offset: usize) {
+        let target_offset = history_size.saturating_sub(line);
+        let delta = target_offset as i32 - display_offset as i32;
+        self.events
+            .push_back(InternalEvent::Scroll(AlacScroll::Delta(delta)));
+    }
+
     ///Resize the terminal and the PTY.
     pub fn set_size(&mut self, new_bounds: TerminalBounds) {
         if self.last_content.terminal_bounds != new_bounds {
@@ -2404,6 +2821,24 @@ impl Drop for Terminal {
 
 impl EventEmitter<Event> for Terminal {}
 
+/// Converts a line counted from the top of the scrollback into a grid line.
+fn grid_line(term: &Term<ZedListener>, line: usize) -> Line {
+    Line(line as i32 - term.history_size() as i32)
+}
+
+/// The last line `line` wraps onto.
+fn logical_line_end(grid: &Grid<Cell>, line: Line) -> Line {
+    if line < grid.topmost_line() {
+        return line;
+    }
+    let last_column = Column(grid.columns() - 1);
+    let mut end = line;
+    while end < grid.bottommost_line() && grid[end][last_column].flags.contains(Flags::WRAPLINE) {
+        end = Line(end.0 + 1);
+    }
+    end
+}
+
 fn make_selection(range: &RangeInclusive<AlacPoint>) -> Selection {
     let mut selection = Selection::new(SelectionType::Simple, *range.start(), AlacDirection::Left);
     selection.update(*range.end(), AlacDirection::Right);
@@ -2519,16 +2954,17 @@ pub fn rgba_color(r: u8, g: u8, b: u8) -> Hsla {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::{cell::RefCell, time::Duration};
 
     use super::*;
     use crate::{
         IndexedCell, TerminalBounds, TerminalBuilder, TerminalContent, content_index_for_mouse,
-        rgb_for_index,
+        rgb_for_index, shell_integration::ShellIntegrationParser,
     };
     use alacritty_terminal::{
         index::{Column, Line, Point as AlacPoint},
         term::cell::Cell,
+        vte::ansi::{Color as AnsiColor, NamedColor},
     };
     use collections::HashMap;
     use gpui::{
@@ -3114,6 +3550,208 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_shell_integration_command_blocks(cx: &mut TestAppContext) {
+        let (terminal, write) = shell_integration_terminal(None, cx);
+
+        write(b"\x1b]133;A\x07$ \x1b]133;B\x07", cx);
+        write(b"echo hi", cx);
+        write(b"\n\x1b]133;C\x07hi\nthere\n", cx);
+        write(b"\x1b]133;D;3\x07\x1b]133;A\x07$ \x1b]133;B\x07", cx);
+
+        terminal.read_with(cx, |terminal, _| {
+            assert_eq!(
+                terminal.command_blocks(),
+                &[
+                    CommandBlock {
+                        prompt_line: 0,
+                        command_start: Some((0, 2)),
+                        output_start: Some(1),
+                        output_end: Some(3),
+                        status: CommandStatus::Finished { exit_code: Some(3) },
+                    },
+                    CommandBlock {
+                        prompt_line: 3,
+                        command_start: Some((3, 2)),
+                        output_start: None,
+                        output_end: None,
+                        status: CommandStatus::Prompt,
+                    },
+                ]
+            );
+
+            let finished = terminal.last_finished_command().unwrap();
+            assert_eq!(finished.exit_code(), Some(3));
+            assert_eq!(terminal.command_text(finished).as_deref(), Some("echo hi"));
+            assert_eq!(
+                terminal.command_output(finished).as_deref(),
+                Some("hi\nthere")
+            );
+            assert_eq!(
+                terminal.visible_prompts(),
+                vec![
+                    (0, CommandStatus::Finished { exit_code: Some(3) }),
+                    (3, CommandStatus::Prompt),
+                ]
+            );
+        });
+
+        // A whole command read in a single batch is placed by the line feeds before each mark,
+        // not by where the cursor ended up.
+        write(
+            b"ls\n\x1b]133;C\x07a\nb\nc\n\x1b]133;D;0\x07\x1b]133;A\x07$ \x1b]133;B\x07",
+            cx,
+        );
+        terminal.read_with(cx, |terminal, _| {
+            assert_eq!(
+                &terminal.command_blocks()[1..],
+                &[
+                    CommandBlock {
+                        prompt_line: 3,
+                        command_start: Some((3, 2)),
+                        output_start: Some(4),
+                        output_end: Some(7),
+                        status: CommandStatus::Finished { exit_code: Some(0) },
+                    },
+                    CommandBlock {
+                        prompt_line: 7,
+                        command_start: Some((7, 2)),
+                        output_start: None,
+                        output_end: None,
+                        status: CommandStatus::Prompt,
+                    },
+                ]
+            );
+            let finished = terminal.last_finished_command().unwrap();
+            assert_eq!(
+                terminal.command_output(finished).as_deref(),
+                Some("a\nb\nc")
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_shell_integration_drops_commands_leaving_scrollback(cx: &mut TestAppContext) {
+        let (terminal, write) = shell_integration_terminal(Some(10), cx);
+        let rows = terminal.read_with(cx, |terminal, _| terminal.term.lock().screen_lines());
+
+        write(b"\x1b]133;A\x07$ \x1b]133;B\x07true\n\x1b]133;C\x07", cx);
+        write(b"\x1b]133;D;0\x07\x1b]133;A\x07$ \x1b]133;B\x07", cx);
+        // Fill the scrollback, so the first prompt gets dropped from its top.
+        write(&b"\n".repeat(rows + 10), cx);
+        write(b"\x1b]133;A\x07$ \x1b]133;B\x07", cx);
+
+        terminal.read_with(cx, |terminal, _| {
+            let history_size = terminal.term.lock().history_size();
+            assert_eq!(history_size, 10);
+            assert_eq!(
+                terminal
+                    .command_blocks()
+                    .iter()
+                    .map(|block| block.prompt_line)
+                    .collect::<Vec<_>>(),
+                vec![history_size + rows - 1]
+            );
+        });
+    }
+
+    /// A display-only terminal, with a function writing output to it as if it was read from a PTY
+    /// with shell integration.
+    fn shell_integration_terminal(
+        max_scroll_history_lines: Option<usize>,
+        cx: &mut TestAppContext,
+    ) -> (Entity<Terminal>, impl Fn(&[u8], &mut TestAppContext)) {
+        let (marks_tx, marks_rx) = unbounded();
+        let terminal = cx.new(|cx| {
+            let mut terminal = TerminalBuilder::new_display_only(
+                CursorShape::default(),
+                AlternateScroll::On,
+                max_scroll_history_lines,
+                0,
+                cx.background_executor(),
+                PathStyle::local(),
+            )
+            .unwrap()
+            .subscribe(cx);
+            terminal.shell_marks_rx = Some(marks_rx);
+            terminal
+        });
+        let parser = RefCell::new(ShellIntegrationParser::new());
+        let write = {
+            let terminal = terminal.clone();
+            move |bytes: &[u8], cx: &mut TestAppContext| {
+                let mut parser = parser.borrow_mut();
+                for mark in parser.advance(bytes) {
+                    marks_tx.unbounded_send(mark).unwrap();
+                }
+                terminal.update(cx, |terminal, cx| {
+                    terminal
+                        .shell_line_feeds
+                        .store(parser.line_feeds(), atomic::Ordering::Release);
+                    terminal.write_output(bytes, cx);
+                    terminal.process_shell_marks(cx);
+                });
+            }
+        };
+        (terminal, write)
+    }
+
+    #[gpui::test]
+    async fn test_scrollback_snapshot_round_trip(cx: &mut TestAppContext) {
+        let new_terminal = |cx: &mut TestAppContext| {
+            cx.new(|cx| {
+                TerminalBuilder::new_display_only(
+                    CursorShape::default(),
+                    AlternateScroll::On,
+                    None,
+                    0,
+                    cx.background_executor(),
+                    PathStyle::local(),
+                )
+                .unwrap()
+                .subscribe(cx)
+            })
+        };
+
+        let original = new_terminal(cx);
+        let snapshot = original.update(cx, |terminal, cx| {
+            terminal.write_output(
+                b"\x1b[1;31merror\x1b[0m: \x1b[38;2;1;2;3mrgb\x1b[0m \x1b[44mbg\x1b[0m\nplain\n$ ",
+                cx,
+            );
+            terminal.scrollback_snapshot(100).unwrap()
+        });
+        assert_eq!(
+            snapshot,
+            "\x1b[0;1;31merror\x1b[0m: \x1b[0;38;2;1;2;3mrgb\x1b[0m \x1b[0;44mbg\x1b[0m\r\nplain\r\n"
+        );
+
+        let restored = new_terminal(cx);
+        restored.update(cx, |terminal, cx| {
+            terminal.write_output(b"$ ", cx);
+            terminal.restore_scrollback(&snapshot, cx);
+        });
+        restored.read_with(cx, |terminal, _| {
+            assert_eq!(terminal.get_content().trim_end(), "error: rgb bg\nplain\n$");
+            assert_eq!(
+                terminal.scrollback_snapshot(100).as_deref(),
+                Some(snapshot.as_str())
+            );
+
+            let term = terminal.term.lock_unfair();
+            let error_cell = &term.grid()[Line(0)][Column(0)];
+            assert_eq!(error_cell.fg, AnsiColor::Named(NamedColor::Red));
+            assert!(error_cell.flags.contains(Flags::BOLD));
+            assert_eq!(term.grid().cursor.point, AlacPoint::new(Line(2), Column(2)));
+        });
+
+        let last_line = original.read_with(cx, |terminal, _| terminal.scrollback_snapshot(1));
+        assert_eq!(last_line.as_deref(), Some("plain\r\n"));
+        let everything =
+            original.read_with(cx, |terminal, _| terminal.scrollback_snapshot(usize::MAX));
+        assert_eq!(everything, Some(snapshot));
+    }
+
     #[gpui::test]
     async fn test_hyperlink_ctrl_click_same_position(cx: &mut TestAppContext) {
         let terminal = init_ctrl_click_hyperlink_test(cx, b"Visit https://zed.dev/ for more\r\n");
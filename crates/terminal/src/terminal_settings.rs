@@ -47,6 +47,7 @@ pub struct TerminalSettings {
     pub scroll_multiplier: f32,
     pub toolbar: Toolbar,
     pub scrollbar: ScrollbarSettings,
+    pub restore_scrollback: RestoreScrollbackSettings,
     pub minimum_contrast: f32,
     pub path_hyperlink_regexes: Vec<String>,
     pub path_hyperlink_timeout_ms: u64,
@@ -60,6 +61,14 @@ pub struct ScrollbarSettings {
     pub show: Option<ShowScrollbar>,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct RestoreScrollbackSettings {
+    /// Whether to save terminal scrollback with the workspace and restore it when it reopens.
+    pub enabled: bool,
+    /// The maximum number of lines saved per terminal.
+    pub max_lines: usize,
+}
+
 fn settings_shell_to_task_shell(shell: settings::Shell) -> Shell {
     match shell {
         settings::Shell::System => Shell::System,
@@ -118,6 +127,13 @@ impl settings::Settings for TerminalSettings {
             scrollbar: ScrollbarSettings {
                 show: user_content.scrollbar.unwrap().show,
             },
+            restore_scrollback: {
+                let restore_scrollback = user_content.restore_scrollback.unwrap();
+                RestoreScrollbackSettings {
+                    enabled: restore_scrollback.enabled.unwrap(),
+                    max_lines: restore_scrollback.max_lines.unwrap(),
+                }
+            },
             minimum_contrast: user_content.minimum_contrast.unwrap(),
             path_hyperlink_regexes: project_content
                 .path_hyperlink_regexes
@@ -13,7 +13,7 @@ use language::CursorShape;
 use settings::Settings;
 use std::time::Instant;
 use terminal::{
-    IndexedCell, Terminal, TerminalBounds, TerminalContent,
+    CommandStatus, IndexedCell, Terminal, TerminalBounds, TerminalContent,
     alacritty_terminal::{
         grid::Dimensions,
         index::Point as AlacPoint,
@@ -50,6 +50,8 @@ pub struct LayoutState {
     display_offset: usize,
     hyperlink_tooltip: Option<AnyElement>,
     gutter: Pixels,
+    /// Shell prompts in the viewport, marked in the gutter with their command's exit status.
+    prompt_marks: Vec<(usize, Hsla)>,
     block_below_cursor_element: Option<AnyElement>,
     base_text_style: TextStyle,
     content_mode: ContentMode,
@@ -983,6 +985,26 @@ impl Element for TerminalElement {
                 };
 
                 let search_matches = self.terminal.read(cx).matches.clone();
+                let prompt_marks = self
+                    .terminal
+                    .read(cx)
+                    .visible_prompts()
+                    .into_iter()
+                    .filter_map(|(line, status)| {
+                        let color = match status {
+                            CommandStatus::Prompt => return None,
+                            CommandStatus::Running => theme.status().info,
+                            CommandStatus::Finished { exit_code: Some(0) } => {
+                                theme.status().success
+                            }
+                            CommandStatus::Finished { exit_code: Some(_) } => theme.status().error,
+                            CommandStatus::Finished { exit_code: None } => {
+                                theme.colors().text_muted
+                            }
+                        };
+                        Some((line, color))
+                    })
+                    .collect();
 
                 let background_color = theme.colors().terminal_background;
 
@@ -1219,6 +1241,7 @@ impl Element for TerminalElement {
                     display_offset,
                     hyperlink_tooltip,
                     gutter,
+                    prompt_marks,
                     block_below_cursor_element,
                     base_text_style: text_style,
                     content_mode,
@@ -1302,6 +1325,21 @@ impl Element for TerminalElement {
                         rect.paint(origin, &layout.dimensions, window);
                     }
 
+                    for (line, color) in &layout.prompt_marks {
+                        let mark_width = px(3.);
+                        let mark_origin = point(
+                            bounds.origin.x + (layout.gutter - mark_width) / 2.,
+                            origin.y + *line as f32 * layout.dimensions.line_height,
+                        );
+                        window.paint_quad(fill(
+                            Bounds::new(
+                                mark_origin,
+                                size(mark_width, layout.dimensions.line_height),
+                            ),
+                            *color,
+                        ));
+                    }
+
                     for (relative_highlighted_range, color) in &layout.relative_highlighted_ranges {
                         if let Some((start_y, highlighted_range_lines)) =
                             to_highlighted_range_lines(relative_highlighted_range, layout, origin)
@@ -41,6 +41,7 @@ use anyhow::{Result, anyhow};
 use zed_actions::assistant::InlineAssist;
 
 const TERMINAL_PANEL_KEY: &str = "TerminalPanel";
+const PROBLEM_SCAN_DEBOUNCE: Duration = Duration::from_millis(500);
 
 actions!(
     terminal_panel,
@@ -1799,6 +1800,15 @@ impl workspace::TerminalProvider for TerminalProvider {
         cx: &mut App,
     ) -> Task<Option<Result<ExitStatus>>> {
         let terminal_panel = self.0.clone();
+        let project = terminal_panel
+            .read(cx)
+            .workspace
+            .upgrade()
+            .map(|workspace| workspace.read(cx).project().clone())
+            .filter(|project| !task.problem_matchers.is_empty() && project.read(cx).is_local());
+        if let Some(project) = &project {
+            report_task_problems(project, &task, Vec::new(), cx);
+        }
         window.spawn(cx, async move |cx| {
             let terminal = terminal_panel
                 .update_in(cx, |terminal_panel, window, cx| {
@@ -1808,10 +1818,40 @@ impl workspace::TerminalProvider for TerminalProvider {
                 .await;
             match terminal {
                 Ok(terminal) => {
+                    // Rescan the output whenever it settles, so that tasks that keep running,
+                    // such as watchers, report their problems before they exit.
+                    let output_subscription = project.as_ref().and_then(|project| {
+                        let project = project.clone();
+                        let task = task.clone();
+                        let terminal = terminal.upgrade()?;
+                        cx.update(|_, cx| {
+                            let mut pending_scan: Option<Task<()>> = None;
+                            cx.subscribe(&terminal, move |terminal, event, cx| {
+                                if !matches!(event, terminal::Event::Wakeup) {
+                                    return;
+                                }
+                                let project = project.clone();
+                                let task = task.clone();
+                                let terminal = terminal.downgrade();
+                                pending_scan = Some(cx.spawn(async move |cx| {
+                                    cx.background_executor().timer(PROBLEM_SCAN_DEBOUNCE).await;
+                                    cx.update(|cx| {
+                                        scan_task_output(&project, &task, &terminal, cx)
+                                    });
+                                }));
+                            })
+                        })
+                        .ok()
+                    });
                     let exit_status = terminal
                         .read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))
                         .ok()?
                         .await?;
+                    drop(output_subscription);
+                    if let Some(project) = &project {
+                        cx.update(|_, cx| scan_task_output(project, &task, &terminal, cx))
+                            .ok();
+                    }
                     Some(Ok(exit_status))
                 }
                 Err(e) => Some(Err(e)),
@@ -1820,6 +1860,38 @@ impl workspace::TerminalProvider for TerminalProvider {
     }
 }
 
+/// Runs the problem matchers of `task` over everything its terminal has printed so far.
+fn scan_task_output(
+    project: &Entity<Project>,
+    task: &SpawnInTerminal,
+    terminal: &WeakEntity<Terminal>,
+    cx: &mut App,
+) {
+    let Ok(output) = terminal.read_with(cx, |terminal, _| terminal.get_content()) else {
+        return;
+    };
+    let problems = task::find_problems(&task.problem_matchers, &output);
+    report_task_problems(project, task, problems, cx);
+}
+
+/// Shows the problems found in the output of `task` as diagnostics,
+/// replacing the ones reported by its previous run.
+fn report_task_problems(
+    project: &Entity<Project>,
+    task: &SpawnInTerminal,
+    problems: Vec<task::Problem>,
+    cx: &mut App,
+) {
+    let cwd = task.cwd.clone();
+    project.update(cx, |project, cx| {
+        project.lsp_store().update(cx, |lsp_store, cx| {
+            lsp_store
+                .update_task_diagnostics(&task.label, cwd, problems, cx)
+                .log_err();
+        })
+    });
+}
+
 struct InlineAssistTabBarButton {
     focus_handle: FocusHandle,
 }
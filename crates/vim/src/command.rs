@@ -2,7 +2,8 @@ use anyhow::{Result, anyhow};
 use collections::{HashMap, HashSet};
 use command_palette_hooks::{CommandInterceptItem, CommandInterceptResult};
 use editor::{
-    Bias, Editor, EditorSettings, SelectionEffects, ToPoint,
+    Bias, CurrentLineHighlight, Editor, EditorSettings, HighlightKey, SelectionEffects, SoftWrap,
+    ToPoint,
     actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive},
     display_map::ToDisplayPoint,
 };
@@ -11,7 +12,12 @@ use gpui::{
     Action, App, AppContext as _, Context, Global, Keystroke, Task, WeakEntity, Window, actions,
 };
 use itertools::Itertools;
-use language::Point;
+use language::{
+    Point,
+    language_settings::{
+        LanguageSettingsOverride, LanguageSettingsOverrides, ShowWhitespaceSetting,
+    },
+};
 use multi_buffer::MultiBufferRow;
 use project::ProjectPath;
 use regex::Regex;
@@ -21,6 +27,7 @@ use serde::Deserialize;
 use settings::{Settings, SettingsStore};
 use std::{
     iter::Peekable,
+    num::NonZeroU32,
     ops::{Deref, Range},
     path::{Path, PathBuf},
     process::Stdio,
@@ -47,7 +54,7 @@ use crate::{
         search::{FindCommand, ReplaceCommand, Replacement},
     },
     object::Object,
-    state::{Mark, Mode},
+    state::{Mark, Mode, VimGlobals},
     visual::VisualDeleteLine,
 };
 
@@ -65,6 +72,15 @@ pub struct YankCommand {
     range: CommandRange,
 }
 
+/// Copies (`:t`) or moves (`:m`) the lines in a range to below the given line.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub struct CopyOrMoveLines {
+    range: CommandRange,
+    destination: Position,
+    copy: bool,
+}
+
 /// Executes a command with the specified range.
 #[derive(Clone, Debug, PartialEq, Action)]
 #[action(namespace = vim, no_json, no_register)]
@@ -82,17 +98,323 @@ pub struct WithCount {
     action: WrappedAction,
 }
 
-#[derive(Clone, Deserialize, JsonSchema, PartialEq)]
+/// An option that can be changed with `:set`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimOptionName {
+    Wrap,
+    Number,
+    RelativeNumber,
+    IgnoreCase,
+    GDefault,
+    TabStop,
+    ShiftWidth,
+    ExpandTab,
+    List,
+    CursorLine,
+    ScrollOff,
+    TextWidth,
+    SmartCase,
+    HlSearch,
+    Spell,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimOptionValue {
+    Bool(bool),
+    Number(u32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimOptionAdjustment {
+    Add,
+    Subtract,
+    Multiply,
+}
+
+/// A single argument to `:set`.
+#[derive(Clone, Debug, PartialEq)]
 pub enum VimOption {
-    Wrap(bool),
-    Number(bool),
-    RelativeNumber(bool),
-    IgnoreCase(bool),
-    GDefault(bool),
+    /// `:set {option}`, `:set no{option}` and `:set {option}={value}`.
+    Set(VimOptionName, VimOptionValue),
+    /// `:set inv{option}` and `:set {option}!`.
+    Toggle(VimOptionName),
+    /// `:set {option}?`, or `:set {option}` for a number option.
+    Query(VimOptionName),
+    /// `:set {option}&`.
+    Reset(VimOptionName),
+    /// `:set {option}+={value}`, `:set {option}-={value}` and `:set {option}^={value}`.
+    Adjust(VimOptionName, VimOptionAdjustment, u32),
+}
+
+impl VimOptionName {
+    const ALL: [Self; 15] = [
+        Self::Wrap,
+        Self::Number,
+        Self::RelativeNumber,
+        Self::IgnoreCase,
+        Self::GDefault,
+        Self::TabStop,
+        Self::ShiftWidth,
+        Self::ExpandTab,
+        Self::List,
+        Self::CursorLine,
+        Self::ScrollOff,
+        Self::TextWidth,
+        Self::SmartCase,
+        Self::HlSearch,
+        Self::Spell,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Wrap => "wrap",
+            Self::Number => "number",
+            Self::RelativeNumber => "relativenumber",
+            Self::IgnoreCase => "ignorecase",
+            Self::GDefault => "gdefault",
+            Self::TabStop => "tabstop",
+            Self::ShiftWidth => "shiftwidth",
+            Self::ExpandTab => "expandtab",
+            Self::List => "list",
+            Self::CursorLine => "cursorline",
+            Self::ScrollOff => "scrolloff",
+            Self::TextWidth => "textwidth",
+            Self::SmartCase => "smartcase",
+            Self::HlSearch => "hlsearch",
+            Self::Spell => "spell",
+        }
+    }
+
+    fn abbreviation(self) -> Option<&'static str> {
+        match self {
+            Self::Number => Some("nu"),
+            Self::RelativeNumber => Some("rnu"),
+            Self::IgnoreCase => Some("ic"),
+            Self::GDefault => Some("gd"),
+            Self::TabStop => Some("ts"),
+            Self::ShiftWidth => Some("sw"),
+            Self::ExpandTab => Some("et"),
+            Self::CursorLine => Some("cul"),
+            Self::ScrollOff => Some("so"),
+            Self::TextWidth => Some("tw"),
+            Self::SmartCase => Some("scs"),
+            Self::HlSearch => Some("hls"),
+            Self::Wrap | Self::List | Self::Spell => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|option| option.name() == name || option.abbreviation() == Some(name))
+    }
+
+    fn is_boolean(self) -> bool {
+        !matches!(
+            self,
+            Self::TabStop | Self::ShiftWidth | Self::ScrollOff | Self::TextWidth
+        )
+    }
+
+    /// Buffer-local options apply to the file rather than to the editor showing it.
+    fn is_buffer_local(self) -> bool {
+        matches!(
+            self,
+            Self::TabStop | Self::ShiftWidth | Self::ExpandTab | Self::List | Self::Spell
+        )
+    }
+
+    fn value(self, editor: &Editor, cx: &App) -> VimOptionValue {
+        let language_settings = editor.buffer().read(cx).language_settings(cx);
+        let editor_settings = EditorSettings::get_global(cx);
+        match self {
+            Self::Wrap => VimOptionValue::Bool(!matches!(
+                editor.soft_wrap_mode(cx),
+                SoftWrap::None | SoftWrap::GitDiff
+            )),
+            Self::Number => VimOptionValue::Bool(editor.line_numbers_enabled(cx)),
+            Self::RelativeNumber => {
+                VimOptionValue::Bool(editor.relative_line_numbers(cx).enabled())
+            }
+            Self::IgnoreCase => VimOptionValue::Bool(!editor_settings.search.case_sensitive),
+            Self::GDefault => VimOptionValue::Bool(VimSettings::get_global(cx).gdefault),
+            Self::TabStop | Self::ShiftWidth => {
+                VimOptionValue::Number(language_settings.tab_size.get())
+            }
+            Self::ExpandTab => VimOptionValue::Bool(!language_settings.hard_tabs),
+            Self::List => VimOptionValue::Bool(
+                language_settings.show_whitespaces == ShowWhitespaceSetting::All,
+            ),
+            Self::CursorLine => VimOptionValue::Bool(
+                editor.current_line_highlight(cx) != CurrentLineHighlight::None,
+            ),
+            Self::ScrollOff => {
+                VimOptionValue::Number(editor_settings.vertical_scroll_margin as u32)
+            }
+            Self::TextWidth => {
+                VimOptionValue::Number(editor.hard_wrap().unwrap_or_default() as u32)
+            }
+            Self::SmartCase => VimOptionValue::Bool(editor_settings.use_smartcase_search),
+            Self::HlSearch => VimOptionValue::Bool(!cx.global::<VimGlobals>().no_hlsearch),
+            Self::Spell => VimOptionValue::Bool(language_settings.spell_check),
+        }
+    }
+
+    /// Sets the option to the given value, or resets it to its configured value if `None`.
+    fn set_value(
+        self,
+        value: Option<VimOptionValue>,
+        local: bool,
+        editor: &mut Editor,
+        cx: &mut Context<Editor>,
+    ) -> Result<()> {
+        let enabled = match value {
+            Some(VimOptionValue::Bool(enabled)) => Some(enabled),
+            _ => None,
+        };
+        let number = match value {
+            Some(VimOptionValue::Number(number)) => Some(number),
+            _ => None,
+        };
+
+        // Zed indents by the tab size, so the shiftwidth can only be set to the tabstop, or to
+        // zero, which means "use the tabstop". Resetting it leaves the tabstop alone.
+        if self == Self::ShiftWidth {
+            let tab_size = editor
+                .buffer()
+                .read(cx)
+                .language_settings(cx)
+                .tab_size
+                .get();
+            if let Some(number) = number
+                && number != 0
+                && number != tab_size
+            {
+                anyhow::bail!(
+                    "shiftwidth must equal tabstop ({tab_size}), Zed has one indent width"
+                );
+            }
+            return Ok(());
+        }
+
+        if self.is_buffer_local() {
+            let tab_size = match number {
+                Some(0) => anyhow::bail!("tabstop must be positive"),
+                Some(number) => NonZeroU32::new(number),
+                None => None,
+            };
+            let update = |overrides: &mut LanguageSettingsOverride| match self {
+                Self::TabStop => overrides.tab_size = tab_size,
+                Self::ExpandTab => overrides.hard_tabs = enabled.map(|enabled| !enabled),
+                Self::List => {
+                    overrides.show_whitespaces = enabled.map(|enabled| {
+                        if enabled {
+                            ShowWhitespaceSetting::All
+                        } else {
+                            ShowWhitespaceSetting::None
+                        }
+                    })
+                }
+                Self::Spell => overrides.spell_check = enabled,
+                _ => {}
+            };
+            let file = editor
+                .active_excerpt(cx)
+                .and_then(|(_, buffer, _)| buffer.read(cx).file().cloned());
+            match file {
+                Some(file) => LanguageSettingsOverrides::update_for_file(&file, cx, update),
+                None if local => {
+                    anyhow::bail!("{} can't be set for an unsaved buffer", self.name())
+                }
+                None => {}
+            }
+            if !local {
+                LanguageSettingsOverrides::update_global(cx, update);
+            }
+            return Ok(());
+        }
+
+        match self {
+            Self::Wrap => {
+                let mode = match enabled {
+                    Some(true) => language::language_settings::SoftWrap::EditorWidth,
+                    Some(false) => language::language_settings::SoftWrap::None,
+                    None => editor.buffer().read(cx).language_settings(cx).soft_wrap,
+                };
+                editor.set_soft_wrap_mode(mode, cx);
+            }
+            Self::Number => {
+                let enabled =
+                    enabled.unwrap_or_else(|| EditorSettings::get_global(cx).gutter.line_numbers);
+                editor.set_show_line_numbers(enabled, cx);
+            }
+            Self::RelativeNumber => editor.set_relative_line_number(enabled, cx),
+            Self::CursorLine => {
+                editor.set_current_line_highlight(enabled.map(|enabled| {
+                    if enabled {
+                        CurrentLineHighlight::All
+                    } else {
+                        CurrentLineHighlight::None
+                    }
+                }));
+                cx.notify();
+            }
+            Self::TextWidth => editor.set_hard_wrap(
+                number
+                    .filter(|width| *width > 0)
+                    .map(|width| width as usize),
+                cx,
+            ),
+            Self::HlSearch => {
+                let no_hlsearch = enabled.is_some_and(|enabled| !enabled);
+                Vim::globals(cx).no_hlsearch = no_hlsearch;
+                if no_hlsearch {
+                    editor.clear_background_highlights(HighlightKey::BufferSearchHighlights, cx);
+                }
+            }
+            Self::IgnoreCase | Self::SmartCase | Self::ScrollOff => {
+                let configured = cx
+                    .global::<SettingsStore>()
+                    .configured_value::<EditorSettings>();
+                let mut settings = EditorSettings::get_global(cx).clone();
+                match self {
+                    Self::IgnoreCase => {
+                        settings.search.case_sensitive =
+                            enabled.map_or(configured.search.case_sensitive, |enabled| !enabled)
+                    }
+                    Self::SmartCase => {
+                        settings.use_smartcase_search =
+                            enabled.unwrap_or(configured.use_smartcase_search)
+                    }
+                    _ => {
+                        settings.vertical_scroll_margin =
+                            number.map_or(configured.vertical_scroll_margin, |lines| lines as f64)
+                    }
+                }
+                SettingsStore::update(cx, |store, _| {
+                    store.override_global(settings);
+                });
+            }
+            Self::GDefault => {
+                let configured = cx
+                    .global::<SettingsStore>()
+                    .configured_value::<VimSettings>();
+                let mut settings = VimSettings::get_global(cx).clone();
+                settings.gdefault = enabled.unwrap_or(configured.gdefault);
+                SettingsStore::update(cx, |store, _| {
+                    store.override_global(settings);
+                })
+            }
+            Self::TabStop | Self::ShiftWidth | Self::ExpandTab | Self::List | Self::Spell => {
+                unreachable!()
+            }
+        }
+        Ok(())
+    }
 }
 
 impl VimOption {
-    fn possible_commands(query: &str) -> Vec<CommandInterceptItem> {
+    fn possible_commands(command: &str, query: &str) -> Vec<CommandInterceptItem> {
         let mut prefix_of_options = Vec::new();
         let mut options = query.split(" ").collect::<Vec<_>>();
         let prefix = options.pop().unwrap_or_default();
@@ -104,88 +426,168 @@ impl VimOption {
             }
         }
 
+        let local = command.starts_with("setl");
+        let command = if local { "setlocal" } else { "set" };
         Self::possibilities(prefix)
+            .into_iter()
             .map(|possible| {
                 let mut options = prefix_of_options.clone();
                 options.push(possible);
 
                 CommandInterceptItem {
-                    string: format!(
-                        ":set {}",
-                        options.iter().map(|opt| opt.to_string()).join(" ")
-                    ),
-                    action: VimSet { options }.boxed_clone(),
+                    string: format!(":{command} {}", options.iter().join(" ")),
+                    action: VimSet { options, local }.boxed_clone(),
                     positions: vec![],
                 }
             })
             .collect()
     }
 
-    fn possibilities(query: &str) -> impl Iterator<Item = Self> + '_ {
-        [
-            (None, VimOption::Wrap(true)),
-            (None, VimOption::Wrap(false)),
-            (None, VimOption::Number(true)),
-            (None, VimOption::Number(false)),
-            (None, VimOption::RelativeNumber(true)),
-            (None, VimOption::RelativeNumber(false)),
-            (Some("rnu"), VimOption::RelativeNumber(true)),
-            (Some("nornu"), VimOption::RelativeNumber(false)),
-            (None, VimOption::IgnoreCase(true)),
-            (None, VimOption::IgnoreCase(false)),
-            (Some("ic"), VimOption::IgnoreCase(true)),
-            (Some("noic"), VimOption::IgnoreCase(false)),
-            (None, VimOption::GDefault(true)),
-            (Some("gd"), VimOption::GDefault(true)),
-            (None, VimOption::GDefault(false)),
-            (Some("nogd"), VimOption::GDefault(false)),
-        ]
-        .into_iter()
-        .filter(move |(prefix, option)| prefix.unwrap_or(option.to_string()).starts_with(query))
-        .map(|(_, option)| option)
+    fn possibilities(query: &str) -> Vec<Self> {
+        if query.contains(['=', ':', '?', '&', '!']) {
+            return Self::from(query).into_iter().collect();
+        }
+
+        // An exact match (including `inv{option}`) comes first, followed by prefix matches.
+        let mut possibilities = Self::from(query).into_iter().collect::<Vec<_>>();
+        for name in VimOptionName::ALL {
+            let keys = [Some(name.name()), name.abbreviation()];
+            for key in keys.into_iter().flatten() {
+                let candidates = if name.is_boolean() {
+                    vec![
+                        (key.to_string(), Self::Set(name, VimOptionValue::Bool(true))),
+                        (
+                            format!("no{key}"),
+                            Self::Set(name, VimOptionValue::Bool(false)),
+                        ),
+                    ]
+                } else {
+                    vec![(key.to_string(), Self::Query(name))]
+                };
+                for (key, option) in candidates {
+                    if key.starts_with(query) && !possibilities.contains(&option) {
+                        possibilities.push(option);
+                    }
+                }
+            }
+        }
+        possibilities
     }
 
     fn from(option: &str) -> Option<Self> {
-        match option {
-            "wrap" => Some(Self::Wrap(true)),
-            "nowrap" => Some(Self::Wrap(false)),
-
-            "number" => Some(Self::Number(true)),
-            "nu" => Some(Self::Number(true)),
-            "nonumber" => Some(Self::Number(false)),
-            "nonu" => Some(Self::Number(false)),
-
-            "relativenumber" => Some(Self::RelativeNumber(true)),
-            "rnu" => Some(Self::RelativeNumber(true)),
-            "norelativenumber" => Some(Self::RelativeNumber(false)),
-            "nornu" => Some(Self::RelativeNumber(false)),
-
-            "ignorecase" => Some(Self::IgnoreCase(true)),
-            "ic" => Some(Self::IgnoreCase(true)),
-            "noignorecase" => Some(Self::IgnoreCase(false)),
-            "noic" => Some(Self::IgnoreCase(false)),
-
-            "gdefault" => Some(Self::GDefault(true)),
-            "gd" => Some(Self::GDefault(true)),
-            "nogdefault" => Some(Self::GDefault(false)),
-            "nogd" => Some(Self::GDefault(false)),
+        if let Some(name) = option.strip_suffix('?') {
+            return VimOptionName::from_name(name).map(Self::Query);
+        }
+        if let Some(name) = option.strip_suffix('&') {
+            return VimOptionName::from_name(name).map(Self::Reset);
+        }
+        if let Some(name) = option.strip_suffix('!') {
+            return VimOptionName::from_name(name)
+                .filter(|name| name.is_boolean())
+                .map(Self::Toggle);
+        }
+        if let Some((name, value)) = option.split_once(['=', ':']) {
+            let (name, adjustment) = if let Some(name) = name.strip_suffix('+') {
+                (name, Some(VimOptionAdjustment::Add))
+            } else if let Some(name) = name.strip_suffix('-') {
+                (name, Some(VimOptionAdjustment::Subtract))
+            } else if let Some(name) = name.strip_suffix('^') {
+                (name, Some(VimOptionAdjustment::Multiply))
+            } else {
+                (name, None)
+            };
+            let name = VimOptionName::from_name(name).filter(|name| !name.is_boolean())?;
+            let value = value.parse().ok()?;
+            return Some(match adjustment {
+                Some(adjustment) => Self::Adjust(name, adjustment, value),
+                None => Self::Set(name, VimOptionValue::Number(value)),
+            });
+        }
 
-            _ => None,
+        if let Some(name) = VimOptionName::from_name(option) {
+            return Some(if name.is_boolean() {
+                Self::Set(name, VimOptionValue::Bool(true))
+            } else {
+                Self::Query(name)
+            });
         }
+        if let Some(name) = option.strip_prefix("no").and_then(VimOptionName::from_name) {
+            return name
+                .is_boolean()
+                .then_some(Self::Set(name, VimOptionValue::Bool(false)));
+        }
+        option
+            .strip_prefix("inv")
+            .and_then(VimOptionName::from_name)
+            .filter(|name| name.is_boolean())
+            .map(Self::Toggle)
+    }
+
+    fn name(&self) -> VimOptionName {
+        match self {
+            Self::Set(name, _)
+            | Self::Toggle(name)
+            | Self::Query(name)
+            | Self::Reset(name)
+            | Self::Adjust(name, _, _) => *name,
+        }
+    }
+
+    /// Applies the option to the editor, returning the message to show for queries.
+    fn apply(
+        &self,
+        local: bool,
+        editor: &mut Editor,
+        cx: &mut Context<Editor>,
+    ) -> Result<Option<String>> {
+        let name = self.name();
+        let value = match self {
+            Self::Query(_) => {
+                let message = match name.value(editor, cx) {
+                    VimOptionValue::Bool(true) => name.name().to_string(),
+                    VimOptionValue::Bool(false) => format!("no{}", name.name()),
+                    VimOptionValue::Number(number) => format!("{}={number}", name.name()),
+                };
+                return Ok(Some(message));
+            }
+            Self::Set(_, value) => Some(*value),
+            Self::Reset(_) => None,
+            Self::Toggle(_) => match name.value(editor, cx) {
+                VimOptionValue::Bool(enabled) => Some(VimOptionValue::Bool(!enabled)),
+                VimOptionValue::Number(_) => anyhow::bail!("{} is not a toggle", name.name()),
+            },
+            Self::Adjust(_, adjustment, amount) => match name.value(editor, cx) {
+                VimOptionValue::Number(number) => Some(VimOptionValue::Number(match adjustment {
+                    VimOptionAdjustment::Add => number.saturating_add(*amount),
+                    VimOptionAdjustment::Subtract => number.saturating_sub(*amount),
+                    VimOptionAdjustment::Multiply => number.saturating_mul(*amount),
+                })),
+                VimOptionValue::Bool(_) => anyhow::bail!("{} is not a number", name.name()),
+            },
+        };
+        name.set_value(value, local, editor, cx)?;
+        Ok(None)
     }
+}
 
-    fn to_string(&self) -> &'static str {
+impl std::fmt::Display for VimOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.name().name();
         match self {
-            VimOption::Wrap(true) => "wrap",
-            VimOption::Wrap(false) => "nowrap",
-            VimOption::Number(true) => "number",
-            VimOption::Number(false) => "nonumber",
-            VimOption::RelativeNumber(true) => "relativenumber",
-            VimOption::RelativeNumber(false) => "norelativenumber",
-            VimOption::IgnoreCase(true) => "ignorecase",
-            VimOption::IgnoreCase(false) => "noignorecase",
-            VimOption::GDefault(true) => "gdefault",
-            VimOption::GDefault(false) => "nogdefault",
+            Self::Set(_, VimOptionValue::Bool(true)) => write!(f, "{name}"),
+            Self::Set(_, VimOptionValue::Bool(false)) => write!(f, "no{name}"),
+            Self::Set(_, VimOptionValue::Number(value)) => write!(f, "{name}={value}"),
+            Self::Toggle(_) => write!(f, "inv{name}"),
+            Self::Query(_) => write!(f, "{name}?"),
+            Self::Reset(_) => write!(f, "{name}&"),
+            Self::Adjust(_, adjustment, value) => {
+                let operator = match adjustment {
+                    VimOptionAdjustment::Add => "+",
+                    VimOptionAdjustment::Subtract => "-",
+                    VimOptionAdjustment::Multiply => "^",
+                };
+                write!(f, "{name}{operator}={value}")
+            }
         }
     }
 }
@@ -195,6 +597,8 @@ impl VimOption {
 #[action(namespace = vim, no_json, no_register)]
 pub struct VimSet {
     options: Vec<VimOption>,
+    /// Whether this is `:setlocal`, which leaves the global value of buffer-local options alone.
+    local: bool,
 }
 
 /// Saves the current file with optional save intent.
@@ -283,38 +687,28 @@ impl Deref for WrappedAction {
 }
 
 pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
-    Vim::action(editor, cx, |vim, action: &VimSet, _, cx| {
+    Vim::action(editor, cx, |vim, action: &VimSet, window, cx| {
+        let mut messages = Vec::new();
         for option in action.options.iter() {
-            vim.update_editor(cx, |_, editor, cx| match option {
-                VimOption::Wrap(true) => {
-                    editor
-                        .set_soft_wrap_mode(language::language_settings::SoftWrap::EditorWidth, cx);
-                }
-                VimOption::Wrap(false) => {
-                    editor.set_soft_wrap_mode(language::language_settings::SoftWrap::None, cx);
-                }
-                VimOption::Number(enabled) => {
-                    editor.set_show_line_numbers(*enabled, cx);
-                }
-                VimOption::RelativeNumber(enabled) => {
-                    editor.set_relative_line_number(Some(*enabled), cx);
-                }
-                VimOption::IgnoreCase(enabled) => {
-                    let mut settings = EditorSettings::get_global(cx).clone();
-                    settings.search.case_sensitive = !*enabled;
-                    SettingsStore::update(cx, |store, _| {
-                        store.override_global(settings);
+            let result =
+                vim.update_editor(cx, |_, editor, cx| option.apply(action.local, editor, cx));
+            match result {
+                Some(Ok(Some(message))) => messages.push(message),
+                Some(Ok(None)) | None => {}
+                Some(Err(error)) => {
+                    let Some(workspace) = vim.workspace(window, cx) else {
+                        return;
+                    };
+                    workspace.update(cx, |workspace, cx| {
+                        Err::<(), _>(error).notify_err(workspace, cx);
                     });
+                    return;
                 }
-                VimOption::GDefault(enabled) => {
-                    let mut settings = VimSettings::get_global(cx).clone();
-                    settings.gdefault = *enabled;
-
-                    SettingsStore::update(cx, |store, _| {
-                        store.override_global(settings);
-                    })
-                }
-            });
+            }
+        }
+        if !messages.is_empty() {
+            vim.status_label = Some(messages.join("  ").into());
+            cx.notify();
         }
     });
     Vim::action(editor, cx, |vim, _: &VisualCommand, window, cx| {
@@ -918,6 +1312,10 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
         });
     });
 
+    Vim::action(editor, cx, |vim, action: &CopyOrMoveLines, window, cx| {
+        action.run(vim, window, cx)
+    });
+
     Vim::action(editor, cx, |_, action: &WithCount, window, cx| {
         for _ in 0..action.count {
             window.dispatch_action(action.action.boxed_clone(), cx)
@@ -1848,6 +2246,15 @@ pub fn command_interceptor(
         })
         .flatten();
 
+    // The command run on each matching line has its own range, relative to that line.
+    let range = if on_matching_lines.is_some() {
+        let (inner_range, inner_query) = VimCommand::parse_range(query);
+        query = query[query.len() - inner_query.len()..].trim_start();
+        inner_range
+    } else {
+        range
+    };
+
     let mut action = if range.is_some() && query.is_empty() {
         Some(
             GoToLine {
@@ -1863,9 +2270,10 @@ pub fn command_interceptor(
             }
             .boxed_clone(),
         )
-    } else if query.starts_with("se ") || query.starts_with("set ") {
-        let (prefix, option) = query.split_once(' ').unwrap();
-        let mut commands = VimOption::possible_commands(option);
+    } else if let Some((prefix, option)) = query.split_once(' ')
+        && matches!(prefix, "se" | "set" | "setl" | "setlocal")
+    {
+        let mut commands = VimOption::possible_commands(prefix, option);
         if !commands.is_empty() {
             let query = prefix.to_string() + " " + option;
             for command in &mut commands {
@@ -1897,10 +2305,12 @@ pub fn command_interceptor(
         }
     } else if query.contains('!') {
         ShellExec::parse(query, range.clone())
+    } else if let Some(action) = CopyOrMoveLines::parse(query, range.clone()) {
+        Some(action)
     } else if on_matching_lines.is_some() {
         commands(cx)
             .iter()
-            .find_map(|command| command.parse(query, &None, cx))
+            .find_map(|command| command.parse(query, &range, cx))
     } else {
         None
     };
@@ -2190,87 +2600,292 @@ impl OnMatchingLines {
             });
         };
 
-        vim.update_editor(cx, |_, editor, cx| {
-            let snapshot = editor.snapshot(window, cx);
-            let mut row = range.start.0;
+        let Some(editor) = vim.editor() else {
+            return;
+        };
+        let workspace = vim.workspace(window, cx);
+        let snapshot = editor.update(cx, |editor, cx| editor.buffer().read(cx).snapshot(cx));
+        let point_range = Point::new(range.start.0, 0)
+            ..snapshot.clip_point(Point::new(range.end.0 + 1, 0), Bias::Left);
+
+        // Substitutions are applied directly rather than through the buffer search bar,
+        // so that each one sees the lines as left by the previous command.
+        let substitution = action
+            .as_any()
+            .downcast_ref::<ReplaceCommand>()
+            .map(|command| {
+                let replacement = Vim::update_globals(cx, |globals, _| {
+                    let replacement = command
+                        .replacement
+                        .resolve(globals.last_replacement.as_ref());
+                    globals.last_replacement = Some(replacement.clone());
+                    replacement
+                });
+                (command.range.clone(), replacement)
+            });
 
-            let point_range = Point::new(range.start.0, 0)
-                ..snapshot
-                    .buffer_snapshot()
-                    .clip_point(Point::new(range.end.0 + 1, 0), Bias::Left);
-            cx.spawn_in(window, async move |editor, cx| {
-                let new_selections = cx
-                    .background_spawn(async move {
-                        let mut line = String::new();
-                        let mut new_selections = Vec::new();
-                        let chunks = snapshot
-                            .buffer_snapshot()
-                            .text_for_range(point_range)
-                            .chain(["\n"]);
-
-                        for chunk in chunks {
-                            for (newline_ix, text) in chunk.split('\n').enumerate() {
-                                if newline_ix > 0 {
-                                    if regexes.iter().all(|(regex, should_match)| {
-                                        regex.is_match(&line) == *should_match
-                                    }) {
-                                        new_selections
-                                            .push(Point::new(row, 0).to_display_point(&snapshot))
-                                    }
-                                    row += 1;
-                                    line.clear();
+        cx.spawn_in(window, async move |vim, cx| {
+            let mut row = range.start.0;
+            let matching_lines = cx
+                .background_spawn(async move {
+                    let mut line = String::new();
+                    let mut matching_lines = Vec::new();
+                    let chunks = snapshot.text_for_range(point_range).chain(["\n"]);
+
+                    for chunk in chunks {
+                        for (newline_ix, text) in chunk.split('\n').enumerate() {
+                            if newline_ix > 0 {
+                                if regexes.iter().all(|(regex, should_match)| {
+                                    regex.is_match(&line) == *should_match
+                                }) {
+                                    matching_lines.push(snapshot.anchor_after(Point::new(row, 0)))
                                 }
-                                line.push_str(text)
+                                row += 1;
+                                line.clear();
                             }
+                            line.push_str(text)
                         }
+                    }
 
-                        new_selections
-                    })
-                    .await;
-
-                if new_selections.is_empty() {
-                    return;
-                }
+                    matching_lines
+                })
+                .await;
 
-                if let Some(vim_norm) = action.as_any().downcast_ref::<VimNorm>() {
-                    let mut vim_norm = vim_norm.clone();
-                    vim_norm.override_rows =
-                        Some(new_selections.iter().map(|point| point.row().0).collect());
-                    editor
-                        .update_in(cx, |_, window, cx| {
-                            window.dispatch_action(vim_norm.boxed_clone(), cx);
-                        })
-                        .log_err();
-                    return;
-                }
+            if matching_lines.is_empty() {
+                return anyhow::Ok(());
+            }
 
-                editor
-                    .update_in(cx, |editor, window, cx| {
-                        editor.start_transaction_at(Instant::now(), window, cx);
-                        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
-                            s.replace_cursors_with(|_| new_selections);
-                        });
-                        window.dispatch_action(action, cx);
+            if let Some(vim_norm) = action.as_any().downcast_ref::<VimNorm>() {
+                let mut vim_norm = vim_norm.clone();
+                editor.update_in(cx, |editor, window, cx| {
+                    let snapshot = editor.buffer().read(cx).snapshot(cx);
+                    vim_norm.override_rows = Some(
+                        matching_lines
+                            .iter()
+                            .map(|anchor| anchor.to_point(&snapshot).row)
+                            .collect(),
+                    );
+                    window.dispatch_action(vim_norm.boxed_clone(), cx);
+                })?;
+                return anyhow::Ok(());
+            }
 
-                        cx.defer_in(window, move |editor, window, cx| {
-                            let newest = editor
-                                .selections
-                                .newest::<Point>(&editor.display_snapshot(cx));
+            editor.update_in(cx, |editor, window, cx| {
+                editor.start_transaction_at(Instant::now(), window, cx);
+            })?;
+
+            let result = async {
+                let mut confirm = substitution
+                    .as_ref()
+                    .is_some_and(|(_, replacement)| replacement.flag_c);
+                for line in matching_lines {
+                    let pending_edits = vim.update_in(cx, |vim, window, cx| {
+                        vim.update_editor(cx, |vim, editor, cx| {
+                            let snapshot = editor.buffer().read(cx).snapshot(cx);
+                            // Skip lines that an earlier command deleted or joined into another.
+                            let point = line.to_point(&snapshot);
+                            if !line.is_valid(&snapshot) || point.column != 0 {
+                                return anyhow::Ok(Vec::new());
+                            }
                             editor.change_selections(
                                 SelectionEffects::no_scroll(),
                                 window,
                                 cx,
-                                |s| {
-                                    s.select(vec![newest]);
-                                },
+                                |s| s.select_ranges([point..point]),
                             );
-                            editor.end_transaction_at(Instant::now(), cx);
+
+                            let Some((range, replacement)) = &substitution else {
+                                window.dispatch_action(action.boxed_clone(), cx);
+                                return anyhow::Ok(Vec::new());
+                            };
+                            let range = range.buffer_range(vim, editor, window, cx)?;
+                            let search = if replacement.search().is_empty() {
+                                &last_pattern
+                            } else {
+                                replacement.search()
+                            };
+                            let edits = replacement.edits_in_range(
+                                search,
+                                Point::new(range.start.0, 0)
+                                    ..Point::new(range.end.0, snapshot.line_len(range.end)),
+                                &snapshot,
+                                cx,
+                            )?;
+                            if replacement.flag_n {
+                                return anyhow::Ok(Vec::new());
+                            }
+                            if confirm {
+                                return anyhow::Ok(
+                                    edits
+                                        .into_iter()
+                                        .map(|(range, text)| {
+                                            (
+                                                snapshot.anchor_before(range.start)
+                                                    ..snapshot.anchor_after(range.end),
+                                                text,
+                                            )
+                                        })
+                                        .collect(),
+                                );
+                            }
+                            editor.edit(edits, cx);
+                            anyhow::Ok(Vec::new())
                         })
-                    })
-                    .log_err();
-            })
-            .detach();
+                    })?;
+
+                    for (range, text) in pending_edits.transpose()?.unwrap_or_default() {
+                        if !confirm {
+                            editor.update(cx, |editor, cx| editor.edit([(range, text)], cx))?;
+                            continue;
+                        }
+                        let answer = editor.update_in(cx, |editor, window, cx| {
+                            editor.change_selections(Default::default(), window, cx, |s| {
+                                s.select_anchor_ranges([range.clone()])
+                            });
+                            window.prompt(
+                                gpui::PromptLevel::Info,
+                                &format!("Replace with \"{text}\"?"),
+                                None,
+                                &["Yes", "No", "All", "Quit"],
+                                cx,
+                            )
+                        })?;
+                        match answer.await? {
+                            0 => {}
+                            1 => continue,
+                            2 => confirm = false,
+                            _ => return anyhow::Ok(()),
+                        }
+                        editor.update(cx, |editor, cx| editor.edit([(range, text)], cx))?;
+                    }
+                }
+                anyhow::Ok(())
+            }
+            .await;
+
+            editor.update_in(cx, |editor, window, cx| {
+                let newest = editor
+                    .selections
+                    .newest::<Point>(&editor.display_snapshot(cx));
+                let cursor = newest.head();
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_ranges([cursor..cursor]);
+                });
+                editor.end_transaction_at(Instant::now(), cx);
+            })?;
+
+            if let Err(error) = result
+                && let Some(workspace) = workspace
+            {
+                workspace.update(cx, |workspace, cx| {
+                    Err::<(), _>(error).notify_err(workspace, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl CopyOrMoveLines {
+    pub(crate) fn parse(query: &str, range: Option<CommandRange>) -> Option<Box<dyn Action>> {
+        let query = query.trim();
+        let command_len = query
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(query.len());
+        let (command, destination) = query.split_at(command_len);
+        let copy = match command {
+            "t" | "co" | "cop" | "copy" => true,
+            "m" | "mo" | "mov" | "move" => false,
+            _ => return None,
+        };
+        let mut chars = destination.trim_start().chars().peekable();
+        let destination = VimCommand::parse_position(&mut chars)?;
+        if chars.next().is_some() {
+            return None;
+        }
+        let range = range.unwrap_or(CommandRange {
+            start: Position::CurrentLine { offset: 0 },
+            end: None,
         });
+        Some(
+            CopyOrMoveLines {
+                range,
+                destination,
+                copy,
+            }
+            .boxed_clone(),
+        )
+    }
+
+    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        vim.switch_mode(Mode::Normal, false, window, cx);
+        let result = vim.update_editor(cx, |vim, editor, cx| {
+            let range = self.range.buffer_range(vim, editor, window, cx)?;
+            // Line 0 is the position above the first line.
+            let destination = match &self.destination {
+                Position::Line { row: 0, offset: 0 } => None,
+                destination => Some(destination.buffer_row(vim, editor, window, cx)?),
+            };
+            let line_count = range.end.0 - range.start.0 + 1;
+            if !self.copy {
+                if destination.is_some_and(|row| row >= range.start && row < range.end) {
+                    anyhow::bail!("cannot move a range of lines into itself");
+                }
+                let above_start = range.start.0.checked_sub(1).map(MultiBufferRow);
+                if destination == Some(range.end) || destination == above_start {
+                    return anyhow::Ok(());
+                }
+            }
+
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let start = Point::new(range.start.0, 0);
+            let end = Point::new(range.end.0, snapshot.line_len(range.end));
+            let text = snapshot.text_for_range(start..end).collect::<String>();
+
+            let mut edits = Vec::new();
+            match destination {
+                Some(row) => {
+                    let point = Point::new(row.0, snapshot.line_len(row));
+                    edits.push((point..point, format!("\n{text}")));
+                }
+                None => edits.push((Point::zero()..Point::zero(), format!("{text}\n"))),
+            }
+            if !self.copy {
+                let deleted = if range.end < snapshot.max_row() {
+                    start..Point::new(range.end.0 + 1, 0)
+                } else {
+                    let previous = MultiBufferRow(range.start.0 - 1);
+                    Point::new(previous.0, snapshot.line_len(previous))..end
+                };
+                edits.push((deleted, String::new()));
+            }
+            edits.sort_by_key(|(range, _)| range.start);
+
+            let last_row = match destination {
+                Some(row) if !self.copy && row > range.end => row.0,
+                Some(row) => row.0 + line_count,
+                None => line_count - 1,
+            };
+            editor.transact(window, cx, |editor, window, cx| {
+                editor.edit(edits, cx);
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                let indent = snapshot.indent_size_for_line(MultiBufferRow(last_row)).len;
+                let point = Point::new(last_row, indent);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges([point..point]);
+                });
+            });
+            anyhow::Ok(())
+        });
+        if let Some(e @ Err(_)) = result {
+            let Some(workspace) = vim.workspace(window, cx) else {
+                return;
+            };
+            workspace.update(cx, |workspace, cx| {
+                e.notify_err(workspace, cx);
+            });
+        }
     }
 }
 
@@ -2479,6 +3094,7 @@ impl ShellExec {
                     show_summary: false,
                     show_command: false,
                     show_rerun: false,
+                    problem_matchers: Vec::new(),
                 };
 
                 let task_status = workspace.spawn_in_terminal(spawn_in_terminal, window, cx);
@@ -2632,7 +3248,7 @@ mod test {
 
     use crate::{
         VimAddon,
-        state::Mode,
+        state::{Mode, VimGlobals},
         test::{NeovimBackedTestContext, VimTestContext},
     };
     use editor::{Editor, EditorSettings};
@@ -3275,6 +3891,51 @@ mod test {
         "});
     }
 
+    #[gpui::test]
+    async fn test_command_g_move_and_copy(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone\ntwo\nthree", Mode::Normal);
+        cx.simulate_keystrokes(": g / ^ / m 0 enter");
+        cx.run_until_parked();
+        cx.assert_state("ˇthree\ntwo\none", Mode::Normal);
+
+        // The whole command is undone at once.
+        cx.simulate_keystrokes("u");
+        cx.assert_state("ˇone\ntwo\nthree", Mode::Normal);
+
+        cx.set_state("ˇx1\ny\nx2", Mode::Normal);
+        cx.simulate_keystrokes(": g / x / t . enter");
+        cx.run_until_parked();
+        cx.assert_state("x1\nx1\ny\nx2\nˇx2", Mode::Normal);
+
+        cx.set_state("ˇa\nb\nc", Mode::Normal);
+        cx.simulate_keystrokes(": 1 , 2 m $ enter");
+        cx.assert_state("c\na\nˇb", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_command_g_substitute_and_join(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇab b\nbb\na b", Mode::Normal);
+        cx.simulate_keystrokes(": g / a / s / b / c / g enter");
+        cx.run_until_parked();
+        cx.assert_state("ac c\nbb\nˇa c", Mode::Normal);
+
+        // An empty pattern reuses the :g pattern.
+        cx.set_state("ˇfoo bar\nbaz", Mode::Normal);
+        cx.simulate_keystrokes(": g / b a / s / / x / enter");
+        cx.run_until_parked();
+        cx.assert_state("foo xr\nˇxz", Mode::Normal);
+
+        // Lines joined into a previous match are no longer visited.
+        cx.set_state("ˇa\na\na\nb", Mode::Normal);
+        cx.simulate_keystrokes(": g / a / j enter");
+        cx.run_until_parked();
+        cx.assert_state("a a\naˇ b", Mode::Normal);
+    }
+
     #[gpui::test]
     async fn test_command_tabnew(cx: &mut TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
@@ -3429,6 +4090,103 @@ mod test {
         });
     }
 
+    #[gpui::test]
+    async fn test_set_number_options(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let tab_size = |cx: &mut VimTestContext| {
+            cx.update_editor(|editor, _, cx| {
+                editor
+                    .buffer()
+                    .read(cx)
+                    .language_settings(cx)
+                    .tab_size
+                    .get()
+            })
+        };
+        let status_label = |cx: &mut VimTestContext| {
+            cx.update_editor(|editor, _, cx| {
+                let vim = editor.addon::<VimAddon>().unwrap().entity.read(cx);
+                vim.status_label.as_ref().map(|label| label.to_string())
+            })
+        };
+        let default_tab_size = tab_size(&mut cx);
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set ts=2");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(tab_size(&mut cx), 2);
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set ts+=1");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(tab_size(&mut cx), 3);
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set ts? et?");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(
+            status_label(&mut cx).as_deref(),
+            Some("tabstop=3  expandtab")
+        );
+
+        // Zed has a single indent width, so a shiftwidth other than the tabstop is rejected.
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set sw=3 sw? sw=0");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(status_label(&mut cx).as_deref(), Some("shiftwidth=3"));
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set sw=2");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(tab_size(&mut cx), 3);
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set ts&");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(tab_size(&mut cx), default_tab_size);
+
+        let default_scroll_margin =
+            cx.read(|cx| EditorSettings::get_global(cx).vertical_scroll_margin);
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set so=5");
+        cx.simulate_keystrokes("enter");
+        cx.read(|cx| assert_eq!(EditorSettings::get_global(cx).vertical_scroll_margin, 5.));
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set so&");
+        cx.simulate_keystrokes("enter");
+        cx.read(|cx| {
+            assert_eq!(
+                EditorSettings::get_global(cx).vertical_scroll_margin,
+                default_scroll_margin
+            )
+        });
+    }
+
+    #[gpui::test]
+    async fn test_set_toggle_options(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set invnumber");
+        cx.simulate_keystrokes("enter");
+        cx.update_editor(|editor, _, cx| assert!(!editor.line_numbers_enabled(cx)));
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set nu!");
+        cx.simulate_keystrokes("enter");
+        cx.update_editor(|editor, _, cx| assert!(editor.line_numbers_enabled(cx)));
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set nohls");
+        cx.simulate_keystrokes("enter");
+        cx.read(|cx| assert!(cx.global::<VimGlobals>().no_hlsearch));
+
+        cx.simulate_keystrokes(":");
+        cx.simulate_input("set hls");
+        cx.simulate_keystrokes("enter");
+        cx.read(|cx| assert!(!cx.global::<VimGlobals>().no_hlsearch));
+    }
+
     #[gpui::test]
     async fn test_sort_commands(cx: &mut TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
@@ -1,5 +1,6 @@
 use editor::{
-    Anchor, Bias, BufferOffset, DisplayPoint, Editor, MultiBufferOffset, RowExt, ToOffset,
+    Anchor, Bias, BufferOffset, DisplayPoint, Editor, HighlightKey, MultiBufferOffset, RowExt,
+    ToOffset,
     display_map::{DisplayRow, DisplaySnapshot, FoldPoint, ToDisplayPoint},
     movement::{
         self, FindRange, TextLayoutDetails, find_boundary, find_preceding_boundary_display_point,
@@ -689,6 +690,11 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
 
 impl Vim {
     pub(crate) fn search_motion(&mut self, m: Motion, window: &mut Window, cx: &mut Context<Self>) {
+        if Vim::globals(cx).no_hlsearch {
+            self.update_editor(cx, |_, editor, cx| {
+                editor.clear_background_highlights(HighlightKey::BufferSearchHighlights, cx);
+            });
+        }
         if let Motion::ZedSearchResult {
             prior_selections, ..
         } = &m
@@ -1,11 +1,14 @@
 use editor::{Editor, EditorSettings};
-use gpui::{Action, Context, Window, actions};
+use gpui::{Action, App, Context, Window, actions};
+use itertools::Itertools;
 use language::Point;
+use multi_buffer::{MultiBufferRow, MultiBufferSnapshot};
+use regex::RegexBuilder;
 use schemars::JsonSchema;
 use search::{BufferSearchBar, SearchOptions, buffer_search};
 use serde::Deserialize;
 use settings::Settings;
-use std::{iter::Peekable, str::Chars};
+use std::{iter::Peekable, ops::Range, str::Chars};
 use util::serde::default_true;
 use workspace::{notifications::NotifyResultExt, searchable::Direction};
 
@@ -101,9 +104,15 @@ pub struct Replacement {
     search: String,
     replacement: String,
     case_sensitive: Option<bool>,
-    flag_n: bool,
+    pub(crate) flag_n: bool,
     flag_g: bool,
-    flag_c: bool,
+    pub(crate) flag_c: bool,
+    /// The `&` flag: reuse the flags of the previous substitution.
+    flag_keep: bool,
+    /// Byte offsets of unescaped `~` in `search`, which stand for the previous replacement string.
+    search_tildes: Vec<usize>,
+    /// Byte offsets of unescaped `~` in `replacement`, which stand for the previous replacement string.
+    replacement_tildes: Vec<usize>,
 }
 
 actions!(
@@ -552,7 +561,13 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let replacement = action.replacement.clone();
+        let replacement = Vim::update_globals(cx, |globals, _| {
+            let replacement = action
+                .replacement
+                .resolve(globals.last_replacement.as_ref());
+            globals.last_replacement = Some(replacement.clone());
+            replacement
+        });
         let Some(((pane, workspace), editor)) = self
             .pane(window, cx)
             .zip(self.workspace(window, cx))
@@ -560,20 +575,27 @@ impl Vim {
         else {
             return;
         };
-        if let Some(result) = self.update_editor(cx, |vim, editor, cx| {
+        let Some(result) = self.update_editor(cx, |vim, editor, cx| {
             let range = action.range.buffer_range(vim, editor, window, cx)?;
             let snapshot = editor.snapshot(window, cx);
             let snapshot = snapshot.buffer_snapshot();
             let end_point = Point::new(range.end.0, snapshot.line_len(range.end));
-            let range = snapshot.anchor_before(Point::new(range.start.0, 0))
-                ..snapshot.anchor_after(end_point);
+            let point_range = Point::new(range.start.0, 0)..end_point;
+            let range = snapshot.anchor_before(point_range.start)..snapshot.anchor_after(end_point);
             editor.set_search_within_ranges(&[range], cx);
-            anyhow::Ok(())
-        }) {
-            workspace.update(cx, |workspace, cx| {
-                result.notify_err(workspace, cx);
-            })
-        }
+            anyhow::Ok(point_range)
+        }) else {
+            return;
+        };
+        let point_range = match result {
+            Ok(point_range) => point_range,
+            Err(error) => {
+                workspace.update(cx, |workspace, cx| {
+                    Err::<(), _>(error).notify_err(workspace, cx);
+                });
+                return;
+            }
+        };
         let Some(search_bar) = pane.update(cx, |pane, cx| {
             pane.toolbar().read(cx).item_of_type::<BufferSearchBar>()
         }) else {
@@ -588,27 +610,16 @@ impl Vim {
             let search = if replacement.search.is_empty() {
                 search_bar.query(cx)
             } else {
-                replacement.search
+                replacement.search.clone()
             };
 
-            if let Some(case) = replacement.case_sensitive {
-                options.set(SearchOptions::CASE_SENSITIVE, case)
-            } else if search_bar.should_use_smartcase_search(cx) {
-                options.set(
-                    SearchOptions::CASE_SENSITIVE,
-                    search_bar.is_contains_uppercase(&search),
-                );
-            } else {
-                // Fallback: no explicit i/I flags and smartcase disabled;
-                // use global editor.search.case_sensitive.
-                options.set(
-                    SearchOptions::CASE_SENSITIVE,
-                    EditorSettings::get_global(cx).search.case_sensitive,
-                )
-            }
+            options.set(
+                SearchOptions::CASE_SENSITIVE,
+                replacement.is_case_sensitive(&search, cx),
+            );
 
             // gdefault inverts the behavior of the 'g' flag.
-            let replace_all = VimSettings::get_global(cx).gdefault != replacement.flag_g;
+            let replace_all = replacement.replace_all(cx);
             if !replace_all {
                 options.set(SearchOptions::ONE_MATCH_PER_LINE, true);
             }
@@ -617,7 +628,8 @@ impl Vim {
             if replacement.flag_c {
                 search_bar.focus_replace(window, cx);
             }
-            Some(search_bar.search(&search, Some(options), true, window, cx))
+            let task = search_bar.search(&search, Some(options), true, window, cx);
+            Some((search, task))
         });
         if replacement.flag_n {
             self.move_cursor(
@@ -628,9 +640,38 @@ impl Vim {
                 window,
                 cx,
             );
+            // Like vim, report the number of matches rather than replacing them.
+            let Some((search, _)) = search else { return };
+            let edits = editor.update(cx, |editor, cx| {
+                let snapshot = editor.buffer().read(cx).snapshot(cx);
+                replacement.edits_in_range(&search, point_range, &snapshot, cx)
+            });
+            match edits {
+                Ok(edits) => {
+                    let lines = edits
+                        .iter()
+                        .map(|(range, _)| range.start.row)
+                        .dedup()
+                        .count();
+                    self.status_label = Some(
+                        format!(
+                            "{} match{} on {} line{}",
+                            edits.len(),
+                            if edits.len() == 1 { "" } else { "es" },
+                            lines,
+                            if lines == 1 { "" } else { "s" },
+                        )
+                        .into(),
+                    );
+                    cx.notify();
+                }
+                Err(error) => workspace.update(cx, |workspace, cx| {
+                    Err::<(), _>(error).notify_err(workspace, cx);
+                }),
+            }
             return;
         }
-        let Some(search) = search else { return };
+        let Some((_, search)) = search else { return };
         let search_bar = search_bar.downgrade();
         cx.spawn_in(window, async move |vim, cx| {
             search.await?;
@@ -680,6 +721,8 @@ impl Replacement {
         let mut search = String::new();
         let mut replacement = String::new();
         let mut flags = String::new();
+        let mut search_tildes = Vec::new();
+        let mut replacement_tildes = Vec::new();
 
         let mut buffer = &mut search;
 
@@ -696,7 +739,7 @@ impl Replacement {
                     buffer.push('$')
                 // unescape escaped parens
                 } else if phase == 0 && (c == '(' || c == ')') {
-                } else if c != delimiter {
+                } else if c != delimiter && !(phase < 2 && c == '~') {
                     buffer.push('\\')
                 }
                 buffer.push(c)
@@ -712,6 +755,13 @@ impl Replacement {
                 } else {
                     break;
                 }
+            } else if c == '~' && phase < 2 {
+                // an unescaped ~ stands for the previous replacement string
+                if phase == 0 {
+                    search_tildes.push(buffer.len());
+                } else {
+                    replacement_tildes.push(buffer.len());
+                }
             } else {
                 // escape unescaped parens
                 if phase == 0 && (c == '(' || c == ')') {
@@ -728,10 +778,14 @@ impl Replacement {
             flag_g: false,
             flag_n: false,
             flag_c: false,
+            flag_keep: false,
+            search_tildes,
+            replacement_tildes,
         };
 
-        for c in flags.chars() {
+        for (ix, c) in flags.chars().enumerate() {
             match c {
+                '&' if ix == 0 => replacement.flag_keep = true,
                 'g' => replacement.flag_g = !replacement.flag_g,
                 'n' => replacement.flag_n = true,
                 'c' => replacement.flag_c = true,
@@ -743,6 +797,129 @@ impl Replacement {
 
         Some(replacement)
     }
+
+    /// Fills in the parts of this substitution that refer to the previous one:
+    /// `~` in the pattern or replacement, and the flags kept by `&`.
+    pub(crate) fn resolve(&self, previous: Option<&Replacement>) -> Replacement {
+        let previous_replacement = previous.map_or("", |previous| previous.replacement.as_str());
+        let mut resolved = self.clone();
+        resolved.search = insert_at_offsets(
+            &self.search,
+            &self.search_tildes,
+            &regex::escape(previous_replacement),
+        );
+        resolved.replacement = insert_at_offsets(
+            &self.replacement,
+            &self.replacement_tildes,
+            previous_replacement,
+        );
+        resolved.search_tildes.clear();
+        resolved.replacement_tildes.clear();
+        resolved.flag_keep = false;
+
+        if self.flag_keep
+            && let Some(previous) = previous
+        {
+            resolved.flag_g ^= previous.flag_g;
+            resolved.flag_n |= previous.flag_n;
+            resolved.flag_c |= previous.flag_c;
+            resolved.case_sensitive = self.case_sensitive.or(previous.case_sensitive);
+        }
+        resolved
+    }
+
+    pub(crate) fn search(&self) -> &str {
+        &self.search
+    }
+
+    pub(crate) fn is_case_sensitive(&self, search: &str, cx: &App) -> bool {
+        let settings = EditorSettings::get_global(cx);
+        if let Some(case) = self.case_sensitive {
+            case
+        } else if settings.use_smartcase_search {
+            search.chars().any(|c| c.is_uppercase())
+        } else {
+            // Fallback: no explicit i/I flags and smartcase disabled;
+            // use global editor.search.case_sensitive.
+            settings.search.case_sensitive
+        }
+    }
+
+    pub(crate) fn replace_all(&self, cx: &App) -> bool {
+        VimSettings::get_global(cx).gdefault != self.flag_g
+    }
+
+    /// Computes the edits this substitution makes to the lines in `range`, matching
+    /// each line against `search` the same way the buffer search bar would.
+    pub(crate) fn edits_in_range(
+        &self,
+        search: &str,
+        range: Range<Point>,
+        snapshot: &MultiBufferSnapshot,
+        cx: &App,
+    ) -> anyhow::Result<Vec<(Range<Point>, String)>> {
+        let regex = RegexBuilder::new(search)
+            .case_insensitive(!self.is_case_sensitive(search, cx))
+            .build()?;
+        let replacement = expand_replacement_escapes(&self.replacement);
+        let replace_all = self.replace_all(cx);
+
+        let mut edits = Vec::new();
+        for row in range.start.row..=range.end.row {
+            let line_len = snapshot.line_len(MultiBufferRow(row));
+            let line = snapshot
+                .text_for_range(Point::new(row, 0)..Point::new(row, line_len))
+                .collect::<String>();
+            for captures in regex.captures_iter(&line) {
+                let matched = captures.get(0).unwrap();
+                let mut new_text = String::new();
+                captures.expand(&replacement, &mut new_text);
+                edits.push((
+                    Point::new(row, matched.start() as u32)..Point::new(row, matched.end() as u32),
+                    new_text,
+                ));
+                if !replace_all {
+                    break;
+                }
+            }
+        }
+        Ok(edits)
+    }
+}
+
+fn insert_at_offsets(text: &str, offsets: &[usize], insertion: &str) -> String {
+    let mut result = String::with_capacity(text.len() + offsets.len() * insertion.len());
+    let mut last_offset = 0;
+    for &offset in offsets {
+        result.push_str(&text[last_offset..offset]);
+        result.push_str(insertion);
+        last_offset = offset;
+    }
+    result.push_str(&text[last_offset..]);
+    result
+}
+
+// Mirrors the escapes the buffer search bar understands in replacement text.
+fn expand_replacement_escapes(replacement: &str) -> String {
+    let mut result = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
 }
 
 #[cfg(test)]
@@ -750,6 +927,7 @@ mod test {
     use std::time::Duration;
 
     use crate::{
+        VimAddon,
         state::Mode,
         test::{NeovimBackedTestContext, VimTestContext},
     };
@@ -1405,4 +1583,38 @@ mod test {
         // The cursor should be at the match location on line 3 (row 2).
         cx.assert_state("hello world\nfoo bar\nhello ˇagain\n", Mode::Normal);
     }
+
+    #[gpui::test]
+    async fn test_replace_previous_substitution(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇaa aa\naa aa\naa aa", Mode::Normal);
+
+        cx.simulate_keystrokes(": s / a a / b / g enter");
+        cx.run_until_parked();
+        cx.assert_state("ˇb b\naa aa\naa aa", Mode::Normal);
+
+        // `&` keeps the `g` flag, `~` in the replacement is the previous replacement.
+        cx.simulate_keystrokes("j : s / a a / ~ c / & enter");
+        cx.run_until_parked();
+        cx.assert_state("b b\nˇbc bc\naa aa", Mode::Normal);
+
+        // `~` in the pattern matches the previous replacement.
+        cx.simulate_keystrokes(": s / ~ / d enter");
+        cx.run_until_parked();
+        cx.assert_state("b b\nˇd bc\naa aa", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_replace_n_counts_matches(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇaa aa\nbb\naa", Mode::Normal);
+
+        cx.simulate_keystrokes(": % s / a a / b / g n enter");
+        cx.run_until_parked();
+        cx.assert_state("ˇaa aa\nbb\naa", Mode::Normal);
+        cx.update_editor(|editor, _, cx| {
+            let vim = editor.addon::<VimAddon>().unwrap().entity.read(cx);
+            assert_eq!(vim.status_label.as_deref(), Some("3 matches on 2 lines"));
+        });
+    }
 }
@@ -1,6 +1,7 @@
 use crate::command::command_interceptor;
 use crate::motion::MotionKind;
 use crate::normal::repeat::Replayer;
+use crate::normal::search::Replacement;
 use crate::surrounds::SurroundsType;
 use crate::{ToggleMarksView, ToggleRegistersView, UseSystemClipboard, Vim, VimAddon, VimSettings};
 use crate::{motion::Motion, object::Object};
@@ -211,6 +212,7 @@ impl From<String> for Register {
 #[derive(Default)]
 pub struct VimGlobals {
     pub last_find: Option<Motion>,
+    pub last_replacement: Option<Replacement>,
 
     pub dot_recording: bool,
     pub dot_replaying: bool,
@@ -240,6 +242,9 @@ pub struct VimGlobals {
     pub focused_vim: Option<WeakEntity<Vim>>,
 
     pub marks: HashMap<EntityId, Entity<MarksState>>,
+
+    /// Set by `:set nohlsearch` to stop search matches from being highlighted.
+    pub no_hlsearch: bool,
 }
 
 pub struct MarksState {
@@ -84,6 +84,7 @@ use postage::stream::Stream;
 use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
     WorktreeSettings,
+    bookmark_store::BookmarkStoreEvent,
     debugger::{breakpoint_store::BreakpointStoreEvent, session::ThreadStatus},
     project_settings::ProjectSettings,
     toolchain_store::ToolchainStoreEvent,
@@ -711,10 +712,27 @@ type BuildProjectItemForPathFn =
 struct ProjectItemRegistry {
     build_project_item_fns_by_type: HashMap<TypeId, BuildProjectItemFn>,
     build_project_item_for_path_fns: Vec<BuildProjectItemForPathFn>,
+    build_fallback_project_item_for_path_fns: Vec<BuildProjectItemForPathFn>,
 }
 
 impl ProjectItemRegistry {
     fn register<T: ProjectItem>(&mut self) {
+        self.register_build_item_fn::<T>();
+        self.build_project_item_for_path_fns
+            .push(|project, project_path, window, cx| {
+                open_project_item_for_path::<T>(project, project_path, true, window, cx)
+            });
+    }
+
+    fn register_fallback<T: ProjectItem>(&mut self) {
+        self.register_build_item_fn::<T>();
+        self.build_fallback_project_item_for_path_fns
+            .push(|project, project_path, window, cx| {
+                open_project_item_for_path::<T>(project, project_path, false, window, cx)
+            });
+    }
+
+    fn register_build_item_fn<T: ProjectItem>(&mut self) {
         self.build_project_item_fns_by_type.insert(
             TypeId::of::<T::Item>(),
             |item, project, pane, window, cx| {
@@ -723,72 +741,6 @@ impl ProjectItemRegistry {
                     as Box<dyn ItemHandle>
             },
         );
-        self.build_project_item_for_path_fns
-            .push(|project, project_path, window, cx| {
-                let project_path = project_path.clone();
-                let is_file = project
-                    .read(cx)
-                    .entry_for_path(&project_path, cx)
-                    .is_some_and(|entry| entry.is_file());
-                let entry_abs_path = project.read(cx).absolute_path(&project_path, cx);
-                let is_local = project.read(cx).is_local();
-                let project_item =
-                    <T::Item as project::ProjectItem>::try_open(project, &project_path, cx)?;
-                let project = project.clone();
-                Some(window.spawn(cx, async move |cx| {
-                    match project_item.await.with_context(|| {
-                        format!(
-                            "opening project path {:?}",
-                            entry_abs_path.as_deref().unwrap_or(&project_path.path.as_std_path())
-                        )
-                    }) {
-                        Ok(project_item) => {
-                            let project_item = project_item;
-                            let project_entry_id: Option<ProjectEntryId> =
-                                project_item.read_with(cx, project::ProjectItem::entry_id);
-                            let build_workspace_item = Box::new(
-                                |pane: &mut Pane, window: &mut Window, cx: &mut Context<Pane>| {
-                                    Box::new(cx.new(|cx| {
-                                        T::for_project_item(
-                                            project,
-                                            Some(pane),
-                                            project_item,
-                                            window,
-                                            cx,
-                                        )
-                                    })) as Box<dyn ItemHandle>
-                                },
-                            ) as Box<_>;
-                            Ok((project_entry_id, build_workspace_item))
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to open a project item: {e:#}");
-                            if e.error_code() == ErrorCode::Internal {
-                                if let Some(abs_path) =
-                                    entry_abs_path.as_deref().filter(|_| is_file)
-                                {
-                                    if let Some(broken_project_item_view) =
-                                        cx.update(|window, cx| {
-                                            T::for_broken_project_item(
-                                                abs_path, is_local, &e, window, cx,
-                                            )
-                                        })?
-                                    {
-                                        let build_workspace_item = Box::new(
-                                            move |_: &mut Pane, _: &mut Window, cx: &mut Context<Pane>| {
-                                                cx.new(|_| broken_project_item_view).boxed_clone()
-                                            },
-                                        )
-                                        as Box<_>;
-                                        return Ok((None, build_workspace_item));
-                                    }
-                                }
-                            }
-                            Err(e)
-                        }
-                    }
-                }))
-            });
     }
 
     fn open_path(
@@ -809,6 +761,20 @@ impl ProjectItemRegistry {
         open_project_item
     }
 
+    /// Opens the path with the first fallback item that supports it, if any.
+    fn open_fallback(
+        &self,
+        project: &Entity<Project>,
+        path: &ProjectPath,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<Task<Result<(Option<ProjectEntryId>, WorkspaceItemBuilder)>>> {
+        self.build_fallback_project_item_for_path_fns
+            .iter()
+            .rev()
+            .find_map(|open_project_item| open_project_item(project, path, window, cx))
+    }
+
     fn build_item<T: project::ProjectItem>(
         &self,
         item: Entity<T>,
@@ -829,6 +795,80 @@ type WorkspaceItemBuilder =
 
 impl Global for ProjectItemRegistry {}
 
+/// Opens the path as a `T`. When that fails, the fallback items get to open the file if
+/// `open_fallback` is set, before `T`'s view for broken items is shown.
+fn open_project_item_for_path<T: ProjectItem>(
+    project: &Entity<Project>,
+    project_path: &ProjectPath,
+    open_fallback: bool,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<Task<Result<(Option<ProjectEntryId>, WorkspaceItemBuilder)>>> {
+    let project_path = project_path.clone();
+    let is_file = project
+        .read(cx)
+        .entry_for_path(&project_path, cx)
+        .is_some_and(|entry| entry.is_file());
+    let entry_abs_path = project.read(cx).absolute_path(&project_path, cx);
+    let is_local = project.read(cx).is_local();
+    let project_item = <T::Item as project::ProjectItem>::try_open(project, &project_path, cx)?;
+    let project = project.clone();
+    Some(window.spawn(cx, async move |cx| {
+        match project_item.await.with_context(|| {
+            format!(
+                "opening project path {:?}",
+                entry_abs_path
+                    .as_deref()
+                    .unwrap_or(&project_path.path.as_std_path())
+            )
+        }) {
+            Ok(project_item) => {
+                let project_item = project_item;
+                let project_entry_id: Option<ProjectEntryId> =
+                    project_item.read_with(cx, project::ProjectItem::entry_id);
+                let build_workspace_item = Box::new(
+                    |pane: &mut Pane, window: &mut Window, cx: &mut Context<Pane>| {
+                        Box::new(cx.new(|cx| {
+                            T::for_project_item(project, Some(pane), project_item, window, cx)
+                        })) as Box<dyn ItemHandle>
+                    },
+                ) as Box<_>;
+                Ok((project_entry_id, build_workspace_item))
+            }
+            Err(e) => {
+                log::warn!("Failed to open a project item: {e:#}");
+                if e.error_code() == ErrorCode::Internal {
+                    if let Some(abs_path) = entry_abs_path.as_deref().filter(|_| is_file) {
+                        if open_fallback {
+                            let fallback = cx.update(|window, cx| {
+                                cx.default_global::<ProjectItemRegistry>()
+                                    .clone()
+                                    .open_fallback(&project, &project_path, window, cx)
+                            })?;
+                            if let Some(fallback) = fallback
+                                && let Some(opened) = fallback.await.log_err()
+                            {
+                                return Ok(opened);
+                            }
+                        }
+                        if let Some(broken_project_item_view) = cx.update(|window, cx| {
+                            T::for_broken_project_item(abs_path, is_local, &e, window, cx)
+                        })? {
+                            let build_workspace_item = Box::new(
+                                move |_: &mut Pane, _: &mut Window, cx: &mut Context<Pane>| {
+                                    cx.new(|_| broken_project_item_view).boxed_clone()
+                                },
+                            ) as Box<_>;
+                            return Ok((None, build_workspace_item));
+                        }
+                    }
+                }
+                Err(e)
+            }
+        }
+    }))
+}
+
 /// Registers a [ProjectItem] for the app. When opening a file, all the registered
 /// items will get a chance to open the file, starting from the project item that
 /// was added last.
@@ -836,6 +876,13 @@ pub fn register_project_item<I: ProjectItem>(cx: &mut App) {
     cx.default_global::<ProjectItemRegistry>().register::<I>();
 }
 
+/// Registers an item that opens the files which the other items failed to open, such as a viewer
+/// for files that can't be loaded as text.
+pub fn register_fallback_project_item<I: ProjectItem>(cx: &mut App) {
+    cx.default_global::<ProjectItemRegistry>()
+        .register_fallback::<I>();
+}
+
 #[derive(Default)]
 pub struct FollowableViewRegistry(HashMap<TypeId, FollowableViewDescriptor>);
 
@@ -1448,6 +1495,17 @@ impl Workspace {
             },
         )
         .detach();
+        cx.subscribe_in(
+            &project.read(cx).bookmark_store(),
+            window,
+            |workspace, _, event, window, cx| match event {
+                BookmarkStoreEvent::BookmarksUpdated(_)
+                | BookmarkStoreEvent::BookmarksCleared(_) => {
+                    workspace.serialize_workspace(window, cx);
+                }
+            },
+        )
+        .detach();
         if let Some(toolchain_store) = project.read(cx).toolchain_store() {
             cx.subscribe_in(
                 &toolchain_store,
@@ -6017,6 +6075,12 @@ impl Workspace {
                         .read(cx)
                         .all_source_breakpoints(cx)
                 });
+                let bookmarks = self
+                    .project
+                    .read(cx)
+                    .bookmark_store()
+                    .read(cx)
+                    .all_serialized_bookmarks(cx);
                 let user_toolchains = self
                     .project
                     .read(cx)
@@ -6038,6 +6102,7 @@ impl Workspace {
                     centered_layout: self.centered_layout,
                     session_id: self.session_id.clone(),
                     breakpoints,
+                    bookmarks,
                     window_id: Some(window.window_handle().window_id().as_u64()),
                     user_toolchains,
                 };
@@ -6252,6 +6317,14 @@ impl Workspace {
                         })
                 })
                 .await;
+            project
+                .update(cx, |project, cx| {
+                    project.bookmark_store().update(cx, |bookmark_store, cx| {
+                        bookmark_store.with_serialized_bookmarks(serialized_workspace.bookmarks, cx)
+                    })
+                })
+                .await
+                .log_err();
 
             // Clean up all the items that have _not_ been loaded. Our ItemIds aren't stable. That means
             // after loading the items, we might have different items and in order to avoid
@@ -643,6 +643,11 @@ fn main() {
 
         editor::init(cx);
         image_viewer::init(cx);
+        large_file_viewer::init(cx);
+        hex_viewer::init(cx);
+        spell_check::init(app_state.fs.clone(), cx);
+        bookmarks::init(cx);
+        local_history::init(app_state.fs.clone(), cx);
         repl::notebook::init(cx);
         diagnostics::init(cx);
 
@@ -654,6 +659,7 @@ fn main() {
         file_finder::init(cx);
         tab_switcher::init(cx);
         outline::init(cx);
+        hierarchy_view::init(cx);
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
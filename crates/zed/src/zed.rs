@@ -3127,6 +3127,65 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_open_binary_files(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(path!("/root"), json!({ "notes.txt": "not binary" }))
+            .await;
+        app_state
+            .fs
+            .as_fake()
+            .insert_file(
+                path!("/root/firmware"),
+                b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00".to_vec(),
+            )
+            .await;
+        app_state
+            .fs
+            .as_fake()
+            .insert_file(
+                path!("/root/logo.png"),
+                b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x00\x01".to_vec(),
+            )
+            .await;
+
+        let project = Project::test(app_state.fs.clone(), [path!("/root").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| MultiWorkspace::test_new(project, window, cx));
+        let workspace = window
+            .read_with(cx, |mw, _| mw.workspace().clone())
+            .unwrap();
+        let worktree_id = cx.read(|cx| workspace.file_project_paths(cx)[0].worktree_id);
+        let mut open = async |name: &str| {
+            window
+                .update(cx, |_, window, cx| {
+                    workspace.update(cx, |workspace, cx| {
+                        workspace.open_path((worktree_id, rel_path(name)), None, true, window, cx)
+                    })
+                })
+                .unwrap()
+                .await
+                .unwrap()
+        };
+
+        // The hex viewer only opens the files that can't be opened otherwise.
+        assert!(open("notes.txt").await.downcast::<Editor>().is_some());
+        assert!(
+            open("logo.png")
+                .await
+                .downcast::<image_viewer::ImageView>()
+                .is_some()
+        );
+        assert!(
+            open("firmware")
+                .await
+                .downcast::<hex_viewer::HexView>()
+                .is_some()
+        );
+    }
+
     #[gpui::test]
     async fn test_open_paths(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
@@ -4859,6 +4918,7 @@ mod tests {
                 "git_panel",
                 "git_picker",
                 "go_to_line",
+                "hierarchy_view",
                 "highlights_tree_view",
                 "icon_theme_selector",
                 "image_viewer",
@@ -5052,6 +5112,7 @@ mod tests {
                 cx,
             );
             image_viewer::init(cx);
+            hex_viewer::init(cx);
             language_model::init(app_state.client.clone(), cx);
             language_models::init(app_state.user_store.clone(), app_state.client.clone(), cx);
             web_search::init(cx);
@@ -307,6 +307,18 @@ pub mod project_panel {
         ]
     );
 }
+
+pub mod hex_viewer {
+    use gpui::actions;
+
+    actions!(
+        hex_viewer,
+        [
+            /// Reopens the active file as a hex dump of its bytes.
+            OpenAsHex
+        ]
+    );
+}
 pub mod feedback {
     use gpui::actions;
 
@@ -623,6 +635,24 @@ pub mod outline {
     pub static TOGGLE_OUTLINE: OnceLock<fn(AnyView, &mut Window, &mut App)> = OnceLock::new();
 }
 
+pub mod hierarchy_view {
+    use gpui::actions;
+
+    actions!(
+        hierarchy_view,
+        [
+            /// Shows the callers of the symbol under the cursor.
+            ShowIncomingCalls,
+            /// Shows the functions called by the symbol under the cursor.
+            ShowOutgoingCalls,
+            /// Shows the types the type under the cursor inherits from or implements.
+            ShowSupertypes,
+            /// Shows the types inheriting from or implementing the type under the cursor.
+            ShowSubtypes,
+        ]
+    );
+}
+
 actions!(
     zed_predict_onboarding,
     [